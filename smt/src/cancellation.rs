@@ -0,0 +1,142 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A function that interrupts a running solver from another thread.
+pub type Interrupter = Box<dyn Fn() + Send + Sync>;
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    /// The token counts as cancelled from this instant on.
+    deadline: Option<Instant>,
+    next_handler_id: AtomicUsize,
+    handlers: Mutex<Vec<(usize, Interrupter)>>,
+}
+
+/// A token for cancelling a verification run from another thread.
+///
+/// Clones share the same state, so a clone can be handed to the thread that
+/// decides when to cancel.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Creates a token that is cancelled once `timeout` has elapsed. A
+    /// background thread interrupts the registered solvers at the deadline
+    /// unless all clones of the token have been dropped by then.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        let token = Self {
+            inner: Arc::new(Inner {
+                deadline: Some(Instant::now() + timeout),
+                ..Inner::default()
+            }),
+        };
+        let inner = Arc::downgrade(&token.inner);
+        std::thread::spawn(move || {
+            std::thread::sleep(timeout);
+            if let Some(inner) = inner.upgrade() {
+                CancellationToken { inner }.cancel();
+            }
+        });
+        token
+    }
+    /// Marks the token as cancelled and interrupts all registered solvers.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        for (_, interrupt) in self.inner.handlers.lock().unwrap().iter() {
+            interrupt();
+        }
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+            || matches!(self.inner.deadline, Some(deadline) if Instant::now() >= deadline)
+    }
+    /// Calls `interrupt` when the token is cancelled, or immediately if it
+    /// already is. The registration lasts until the returned guard is
+    /// dropped.
+    pub fn on_cancel(&self, interrupt: Interrupter) -> CancellationGuard {
+        let id = self.inner.next_handler_id.fetch_add(1, Ordering::SeqCst);
+        let mut handlers = self.inner.handlers.lock().unwrap();
+        if self.is_cancelled() {
+            interrupt();
+        }
+        handlers.push((id, interrupt));
+        CancellationGuard {
+            token: self.clone(),
+            id,
+        }
+    }
+}
+
+/// Unregisters an interrupter when dropped.
+pub struct CancellationGuard {
+    token: CancellationToken,
+    id: usize,
+}
+
+impl Drop for CancellationGuard {
+    fn drop(&mut self) {
+        self.token
+            .inner
+            .handlers
+            .lock()
+            .unwrap()
+            .retain(|(id, _)| *id != self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_interrupters() {
+        let token = CancellationToken::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let guard = token.on_cancel(Box::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        let counter = calls.clone();
+        drop(token.on_cancel(Box::new(move || {
+            counter.fetch_add(10, Ordering::SeqCst);
+        })));
+        token.clone().cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        drop(guard);
+        // Registering on a cancelled token interrupts immediately.
+        let counter = calls.clone();
+        let _guard = token.on_cancel(Box::new(move || {
+            counter.fetch_add(100, Ordering::SeqCst);
+        }));
+        assert_eq!(calls.load(Ordering::SeqCst), 101);
+    }
+
+    #[test]
+    fn check_timeout() {
+        let token = CancellationToken::with_timeout(Duration::from_millis(50));
+        assert!(!token.is_cancelled());
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sender = Mutex::new(sender);
+        let _guard = token.on_cancel(Box::new(move || {
+            let _ = sender.lock().unwrap().send(());
+        }));
+        receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(token.is_cancelled());
+    }
+}
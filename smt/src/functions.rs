@@ -0,0 +1,458 @@
+//! Ordering and encoding of function definitions.
+//!
+//! Functions with a body are sent to the solver with `define-fun`, and
+//! groups of mutually recursive functions with `define-funs-rec`. As an
+//! alternative to `define-funs-rec`, [`unfold_recursive_functions`] replaces
+//! recursive definitions with axioms that the solver unfolds only a bounded
+//! number of times.
+
+use std::collections::HashMap;
+use svirpti_vir::common::expression::ExpressionIterator;
+use svirpti_vir::smt as ast;
+use svirpti_vir::smt::{
+    BinaryOperationHelpers, FunctionApplicationHelpers, QuantifierHelpers, VariableHelpers,
+};
+
+/// Functions that have to be defined together.
+#[derive(Debug)]
+pub struct FunctionGroup<'a> {
+    pub functions: Vec<&'a ast::FunctionDeclaration>,
+    /// Whether the functions call themselves or each other, so that they
+    /// have to be defined with `define-funs-rec`.
+    pub recursive: bool,
+}
+
+/// Splits `functions` into groups of mutually recursive functions. The groups
+/// are ordered so that every function comes after the functions it calls.
+pub fn function_groups(functions: &[ast::FunctionDeclaration]) -> Vec<FunctionGroup<'_>> {
+    let indices: HashMap<_, _> = functions
+        .iter()
+        .enumerate()
+        .map(|(index, function)| (&function.name, index))
+        .collect();
+    let callees: Vec<Vec<usize>> = functions
+        .iter()
+        .map(|function| {
+            let mut callees = Vec::new();
+            if let Some(body) = &function.body {
+                rename_functions(body, &mut |callee| {
+                    if let Some(&index) = indices.get(callee) {
+                        callees.push(index);
+                    }
+                    callee.clone()
+                });
+            }
+            callees
+        })
+        .collect();
+    let mut components = StronglyConnectedComponents {
+        callees: &callees,
+        indices: vec![None; functions.len()],
+        lowlinks: vec![0; functions.len()],
+        stack: Vec::new(),
+        on_stack: vec![false; functions.len()],
+        next_index: 0,
+        components: Vec::new(),
+    };
+    for function in 0..functions.len() {
+        if components.indices[function].is_none() {
+            components.visit(function);
+        }
+    }
+    components
+        .components
+        .into_iter()
+        .map(|component| {
+            let recursive = component.len() > 1 || callees[component[0]].contains(&component[0]);
+            FunctionGroup {
+                functions: component
+                    .into_iter()
+                    .map(|index| &functions[index])
+                    .collect(),
+                recursive,
+            }
+        })
+        .collect()
+}
+
+/// Tarjan's algorithm on the call graph. A component is completed only after
+/// all components reachable from it, so the callees come first.
+struct StronglyConnectedComponents<'a> {
+    callees: &'a [Vec<usize>],
+    indices: Vec<Option<usize>>,
+    lowlinks: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl StronglyConnectedComponents<'_> {
+    fn visit(&mut self, node: usize) {
+        self.indices[node] = Some(self.next_index);
+        self.lowlinks[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+        let callees = self.callees;
+        for &callee in &callees[node] {
+            match self.indices[callee] {
+                None => {
+                    self.visit(callee);
+                    self.lowlinks[node] = self.lowlinks[node].min(self.lowlinks[callee]);
+                }
+                Some(index) if self.on_stack[callee] => {
+                    self.lowlinks[node] = self.lowlinks[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+        if self.indices[node] == Some(self.lowlinks[node]) {
+            let mut component = Vec::new();
+            loop {
+                let member = self.stack.pop().unwrap();
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            // Keep the declaration order within a group.
+            component.sort_unstable();
+            self.components.push(component);
+        }
+    }
+}
+
+/// Replaces the definitions of recursive functions with axioms that unfold
+/// every function at most `fuel` times, and returns the axioms. The
+/// definitions of non-recursive functions are kept.
+///
+/// A recursive function `f` is split into the uninterpreted layers `f`,
+/// `f%1`, …, `f%fuel`. The axiom of layer `i < fuel` equates `f%i` with the
+/// body of `f`, in which the calls to the functions of the group go to layer
+/// `i + 1`, and is guarded by the preconditions of `f`. The axioms are
+/// triggered by the applications of their layer, so a proof that needs more
+/// than `fuel` unfoldings fails instead of making the solver diverge.
+pub fn unfold_recursive_functions(
+    declarations: &mut ast::Declarations,
+    fuel: u32,
+) -> Vec<ast::Expression> {
+    let mut functions = Vec::new();
+    let mut axioms = Vec::new();
+    for group in function_groups(&declarations.functions) {
+        if !group.recursive {
+            functions.extend(group.functions.into_iter().cloned());
+            continue;
+        }
+        for function in &group.functions {
+            for layer in 0..=fuel {
+                let name = layer_name(&function.name, layer);
+                if layer < fuel {
+                    let mut next_layer = |callee: &ast::FunctionSymbol| {
+                        if group.functions.iter().any(|member| &member.name == callee) {
+                            layer_name(callee, layer + 1)
+                        } else {
+                            callee.clone()
+                        }
+                    };
+                    let preconditions = function
+                        .preconditions
+                        .iter()
+                        .map(|precondition| rename_functions(precondition, &mut next_layer))
+                        .collect();
+                    let body = rename_functions(
+                        function
+                            .body
+                            .as_ref()
+                            .expect("recursive functions have a body"),
+                        &mut next_layer,
+                    );
+                    axioms.push(definition_axiom(
+                        function,
+                        name.clone(),
+                        preconditions,
+                        body,
+                    ));
+                }
+                functions.push(ast::FunctionDeclaration {
+                    name,
+                    parameters: function.parameters.clone(),
+                    return_sort: function.return_sort.clone(),
+                    preconditions: Vec::new(),
+                    body: None,
+                });
+            }
+        }
+    }
+    declarations.functions = functions;
+    axioms
+}
+
+fn layer_name(name: &ast::FunctionSymbol, layer: u32) -> ast::FunctionSymbol {
+    if layer == 0 {
+        name.clone()
+    } else {
+        format!("{}%{}", name, layer).into()
+    }
+}
+
+/// `forall parameters :: preconditions ==> name(parameters) == body`.
+fn definition_axiom(
+    function: &ast::FunctionDeclaration,
+    name: ast::FunctionSymbol,
+    preconditions: Vec<ast::Expression>,
+    body: ast::Expression,
+) -> ast::Expression {
+    let application = ast::Expression::call(
+        name,
+        function
+            .parameters
+            .iter()
+            .map(|parameter| ast::Expression::variable(parameter.name.clone()))
+            .collect(),
+    );
+    let mut definition = ast::Expression::equals(application.clone(), body);
+    if !preconditions.is_empty() {
+        definition = ast::Expression::implies(preconditions.into_iter().conjoin(), definition);
+    }
+    if function.parameters.is_empty() {
+        return definition;
+    }
+    ast::Expression::forall(
+        function
+            .parameters
+            .iter()
+            .map(|parameter| ast::BoundedVariableDecl {
+                name: parameter.name.clone(),
+                sort: parameter.sort.clone(),
+            })
+            .collect(),
+        vec![ast::Trigger {
+            parts: vec![application],
+        }],
+        definition,
+    )
+}
+
+/// Replaces the function of every function application in `expression` with
+/// the result of `rename`.
+pub fn rename_functions(
+    expression: &ast::Expression,
+    rename: &mut impl FnMut(&ast::FunctionSymbol) -> ast::FunctionSymbol,
+) -> ast::Expression {
+    let mut rename_all = |expressions: &[ast::Expression]| -> Vec<ast::Expression> {
+        expressions
+            .iter()
+            .map(|expression| rename_functions(expression, rename))
+            .collect()
+    };
+    match expression {
+        ast::Expression::Variable(_)
+        | ast::Expression::Constant(_)
+        | ast::Expression::EmptySeq(_)
+        | ast::Expression::EmptySet(_) => expression.clone(),
+        ast::Expression::UnaryOperation(operation) => {
+            ast::Expression::UnaryOperation(ast::UnaryOperation {
+                kind: operation.kind,
+                arg: Box::new(rename_functions(&operation.arg, rename)),
+            })
+        }
+        ast::Expression::BinaryOperation(operation) => {
+            ast::Expression::BinaryOperation(ast::BinaryOperation {
+                kind: operation.kind,
+                left: Box::new(rename_functions(&operation.left, rename)),
+                right: Box::new(rename_functions(&operation.right, rename)),
+            })
+        }
+        ast::Expression::BitVectorConversion(conversion) => {
+            ast::Expression::BitVectorConversion(ast::BitVectorConversion {
+                kind: conversion.kind,
+                arg: Box::new(rename_functions(&conversion.arg, rename)),
+            })
+        }
+        ast::Expression::FloatOperation(operation) => {
+            ast::Expression::FloatOperation(ast::FloatOperation {
+                kind: operation.kind,
+                args: rename_all(&operation.args),
+            })
+        }
+        ast::Expression::FloatConversion(conversion) => {
+            ast::Expression::FloatConversion(ast::FloatConversion {
+                kind: conversion.kind,
+                arg: Box::new(rename_functions(&conversion.arg, rename)),
+            })
+        }
+        ast::Expression::ArraySelect(select) => ast::Expression::ArraySelect(ast::ArraySelect {
+            array: Box::new(rename_functions(&select.array, rename)),
+            index: Box::new(rename_functions(&select.index, rename)),
+        }),
+        ast::Expression::ArrayStore(store) => ast::Expression::ArrayStore(ast::ArrayStore {
+            array: Box::new(rename_functions(&store.array, rename)),
+            index: Box::new(rename_functions(&store.index, rename)),
+            value: Box::new(rename_functions(&store.value, rename)),
+        }),
+        ast::Expression::ConstantArray(array) => {
+            ast::Expression::ConstantArray(ast::ConstantArray {
+                index_sort: array.index_sort.clone(),
+                value: Box::new(rename_functions(&array.value, rename)),
+            })
+        }
+        ast::Expression::SeqOperation(operation) => {
+            ast::Expression::SeqOperation(ast::SeqOperation {
+                kind: operation.kind,
+                args: rename_all(&operation.args),
+            })
+        }
+        ast::Expression::SetOperation(operation) => {
+            ast::Expression::SetOperation(ast::SetOperation {
+                kind: operation.kind,
+                args: rename_all(&operation.args),
+            })
+        }
+        ast::Expression::AdtConstructor(constructor) => {
+            ast::Expression::AdtConstructor(ast::AdtConstructor {
+                adt: constructor.adt.clone(),
+                constructor: constructor.constructor.clone(),
+                args: rename_all(&constructor.args),
+            })
+        }
+        ast::Expression::AdtFieldAccess(access) => {
+            ast::Expression::AdtFieldAccess(ast::AdtFieldAccess {
+                base: Box::new(rename_functions(&access.base, rename)),
+                field: access.field.clone(),
+            })
+        }
+        ast::Expression::AdtTester(tester) => ast::Expression::AdtTester(ast::AdtTester {
+            base: Box::new(rename_functions(&tester.base, rename)),
+            constructor: tester.constructor.clone(),
+        }),
+        ast::Expression::AdtMatch(adt_match) => ast::Expression::AdtMatch(ast::AdtMatch {
+            adt: adt_match.adt.clone(),
+            base: Box::new(rename_functions(&adt_match.base, rename)),
+            cases: adt_match
+                .cases
+                .iter()
+                .map(|case| ast::AdtMatchCase {
+                    constructor: case.constructor.clone(),
+                    binders: case.binders.clone(),
+                    body: Box::new(rename_functions(&case.body, rename)),
+                })
+                .collect(),
+        }),
+        ast::Expression::Conditional(conditional) => {
+            ast::Expression::Conditional(ast::Conditional {
+                guard: Box::new(rename_functions(&conditional.guard, rename)),
+                then_expr: Box::new(rename_functions(&conditional.then_expr, rename)),
+                else_expr: Box::new(rename_functions(&conditional.else_expr, rename)),
+            })
+        }
+        ast::Expression::Quantifier(quantifier) => ast::Expression::Quantifier(ast::Quantifier {
+            kind: quantifier.kind.clone(),
+            variables: quantifier.variables.clone(),
+            triggers: quantifier
+                .triggers
+                .iter()
+                .map(|trigger| ast::Trigger {
+                    parts: rename_all(&trigger.parts),
+                })
+                .collect(),
+            body: Box::new(rename_functions(&quantifier.body, rename)),
+        }),
+        ast::Expression::Let(binding) => ast::Expression::Let(ast::Let {
+            variable: binding.variable.clone(),
+            value: Box::new(rename_functions(&binding.value, rename)),
+            body: Box::new(rename_functions(&binding.body, rename)),
+        }),
+        ast::Expression::FunctionApplication(application) => {
+            let args = rename_all(&application.args);
+            ast::Expression::call(rename(&application.function), args)
+        }
+        ast::Expression::LabelledExpression(labelled) => {
+            ast::Expression::LabelledExpression(ast::LabelledExpression {
+                name: labelled.name.clone(),
+                positivity: labelled.positivity.clone(),
+                expression: Box::new(rename_functions(&labelled.expression, rename)),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{function_groups, unfold_recursive_functions};
+    use crate::parser::{parse_expression, parse_query};
+
+    const EVEN_ODD: &str = "
+        (declare-fun g (Int) Int)
+        (define-funs-rec
+            ((is_even ((n Int)) Bool) (is_odd ((n Int)) Bool))
+            ((ite (= n 0) true (is_odd (- n 1))) (ite (= n 0) false (is_even (- n 1)))))
+        (define-fun twice ((n Int)) Int (+ (g n) (g n)))
+        (define-fun check ((n Int)) Bool (and (is_even (twice n)) (not (is_odd n))))
+        (define-fun-rec sum ((n Int)) Int (ite (<= n 0) 0 (+ n (sum (- n 1)))))
+    ";
+
+    #[test]
+    fn check_function_groups() {
+        let query = parse_query(EVEN_ODD).unwrap();
+        let groups: Vec<_> = function_groups(&query.declarations.functions)
+            .into_iter()
+            .map(|group| {
+                let names: Vec<_> = group
+                    .functions
+                    .iter()
+                    .map(|function| function.name.to_string())
+                    .collect();
+                (names.join(" "), group.recursive)
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("g".to_string(), false),
+                ("is_even is_odd".to_string(), true),
+                ("twice".to_string(), false),
+                ("check".to_string(), false),
+                ("sum".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_unfold_recursive_functions() {
+        let mut query = parse_query(EVEN_ODD).unwrap();
+        let axioms = unfold_recursive_functions(&mut query.declarations, 2);
+        let functions: Vec<_> = query
+            .declarations
+            .functions
+            .iter()
+            .map(|function| (function.name.to_string(), function.body.is_some()))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![
+                ("g".to_string(), false),
+                ("is_even".to_string(), false),
+                ("is_even%1".to_string(), false),
+                ("is_even%2".to_string(), false),
+                ("is_odd".to_string(), false),
+                ("is_odd%1".to_string(), false),
+                ("is_odd%2".to_string(), false),
+                ("twice".to_string(), true),
+                ("check".to_string(), true),
+                ("sum".to_string(), false),
+                ("sum%1".to_string(), false),
+                ("sum%2".to_string(), false),
+            ]
+        );
+        assert_eq!(axioms.len(), 6);
+        let expected = parse_expression(
+            "(forall ((n Int)) (! (= (is_even%1 n) (ite (= n 0) true (is_odd%2 (- n 1)))) \
+             :pattern ((is_even%1 n))))",
+            &query.declarations,
+        )
+        .unwrap();
+        assert_eq!(axioms[1], expected);
+    }
+}
@@ -1,4 +1,11 @@
+pub mod cancellation;
+pub mod functions;
+pub mod parser;
 pub mod solvers;
 mod verify;
 
-pub use verify::{verify, VerificationFailure, VerificationResult, VerifierError};
+pub use cancellation::CancellationToken;
+pub use verify::{
+    verify, verify_cancellable, verify_with_pool, verify_with_solver, QueryConfiguration,
+    QueryStatistics, VerificationFailure, VerificationResult, VerifierError,
+};
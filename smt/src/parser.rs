@@ -0,0 +1,2790 @@
+//! A parser for SMT-LIB2 scripts that produces an [`ast::Query`].
+//!
+//! Only the fragment that can be expressed in the SMT IR is supported:
+//! `declare-sort`, `declare-datatypes`, `declare-fun`, `declare-const`,
+//! `define-fun`, `define-fun-rec`, `define-funs-rec`, and `assert` commands
+//! over Booleans, integers, bit-vectors, floating-point numbers, arrays,
+//! algebraic datatypes, sequences, and uninterpreted sorts. Sets are encoded
+//! as arrays and are therefore parsed back as arrays, and floating-point
+//! arithmetic is parsed back as [`ast::FloatOperation`]. Commands that do not
+//! affect the query (`set-info`, `check-sat`, `get-model`, …) are skipped.
+//!
+//! Labelled expressions are recognized in the form produced by the printer,
+//! `(! (and L e) :lblpos L)` and `(! (or L e) :lblneg L)`, and the constants
+//! used as labels are moved from the declared variables to the declared
+//! labels.
+
+use std::collections::{HashMap, HashSet};
+use svirpti_vir::common::float::{self, RoundingMode};
+use svirpti_vir::common::integer::BigInt;
+use svirpti_vir::common::real;
+use svirpti_vir::smt as ast;
+use svirpti_vir::smt::{
+    AdtHelpers, ArrayHelpers, BinaryOperationHelpers, BitVectorConversionHelpers, FloatHelpers,
+    FunctionApplicationHelpers, LabelledExpressionHelpers, LetHelpers, QuantifierHelpers,
+    SeqHelpers, UnaryOperationHelpers, VariableHelpers,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("line {line}: {message}")]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Parses an SMT-LIB2 script.
+pub fn parse_query(input: &str) -> ParseResult<ast::Query> {
+    let mut parser = Parser::default();
+    for command in read_sexprs(input)? {
+        parser.parse_command(&command)?;
+    }
+    Ok(parser.finish())
+}
+
+/// Parses a single expression. All free symbols must be declared in
+/// `declarations`.
+pub fn parse_expression(
+    input: &str,
+    declarations: &ast::Declarations,
+) -> ParseResult<ast::Expression> {
+    let mut parser = Parser::default();
+    for sort in &declarations.sorts {
+        parser.sorts.insert(sort.name.as_string());
+    }
+    for adt in &declarations.adts {
+        parser.register_adt(adt);
+    }
+    for variable in &declarations.variables {
+        parser.constants.insert(variable.name.as_string());
+    }
+    for label in &declarations.labels {
+        parser.constants.insert(label.name.as_string());
+    }
+    for function in &declarations.functions {
+        parser.functions.insert(function.name.as_string());
+    }
+    parser.parse_expression(&read_single_sexpr(input)?)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SExprKind {
+    Symbol(String),
+    Keyword(String),
+    Numeral(String),
+    List(Vec<SExpr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SExpr {
+    kind: SExprKind,
+    line: usize,
+}
+
+impl SExpr {
+    fn error<T>(&self, message: impl Into<String>) -> ParseResult<T> {
+        Err(ParseError {
+            line: self.line,
+            message: message.into(),
+        })
+    }
+    fn as_symbol(&self) -> ParseResult<&str> {
+        match &self.kind {
+            SExprKind::Symbol(symbol) => Ok(symbol),
+            _ => self.error(format!("expected a symbol, found {}", self)),
+        }
+    }
+    fn as_list(&self) -> ParseResult<&[SExpr]> {
+        match &self.kind {
+            SExprKind::List(items) => Ok(items),
+            _ => self.error(format!("expected a list, found {}", self)),
+        }
+    }
+}
+
+impl std::fmt::Display for SExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            SExprKind::Symbol(value) | SExprKind::Keyword(value) | SExprKind::Numeral(value) => {
+                write!(f, "{}", value)
+            }
+            SExprKind::List(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "~!@$%^&*_-+=<>.?/".contains(c)
+}
+
+/// Splits `input` into top-level s-expressions.
+fn read_sexprs(input: &str) -> ParseResult<Vec<SExpr>> {
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    // The items of the currently open lists together with their start lines.
+    let mut stack: Vec<(Vec<SExpr>, usize)> = vec![(Vec::new(), 0)];
+    while let Some(c) = chars.next() {
+        let kind = match c {
+            '\n' => {
+                line += 1;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            ';' => {
+                while chars.peek().filter(|&&c| c != '\n').is_some() {
+                    chars.next();
+                }
+                continue;
+            }
+            '(' => {
+                stack.push((Vec::new(), line));
+                continue;
+            }
+            ')' => {
+                if stack.len() == 1 {
+                    return Err(ParseError {
+                        line,
+                        message: "unexpected `)`".into(),
+                    });
+                }
+                let (items, start) = stack.pop().unwrap();
+                stack.last_mut().unwrap().0.push(SExpr {
+                    kind: SExprKind::List(items),
+                    line: start,
+                });
+                continue;
+            }
+            '|' => {
+                let mut symbol = String::new();
+                loop {
+                    match chars.next() {
+                        Some('|') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            symbol.push(c);
+                        }
+                        None => {
+                            return Err(ParseError {
+                                line,
+                                message: "unterminated quoted symbol".into(),
+                            })
+                        }
+                    }
+                }
+                SExprKind::Symbol(symbol)
+            }
+            '#' => {
+                let mut literal = String::from("#");
+                while let Some(&c) = chars.peek().filter(|&&c| c.is_ascii_alphanumeric()) {
+                    literal.push(c);
+                    chars.next();
+                }
+                SExprKind::Symbol(literal)
+            }
+            ':' => {
+                let mut keyword = String::from(":");
+                while let Some(&c) = chars.peek().filter(|&&c| is_symbol_char(c)) {
+                    keyword.push(c);
+                    chars.next();
+                }
+                SExprKind::Keyword(keyword)
+            }
+            c if is_symbol_char(c) => {
+                let mut token = c.to_string();
+                while let Some(&c) = chars.peek().filter(|&&c| is_symbol_char(c)) {
+                    token.push(c);
+                    chars.next();
+                }
+                let digits = token.strip_prefix('-').unwrap_or(&token);
+                if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+                    SExprKind::Numeral(token)
+                } else {
+                    SExprKind::Symbol(token)
+                }
+            }
+            c => {
+                return Err(ParseError {
+                    line,
+                    message: format!("unexpected character `{}`", c),
+                })
+            }
+        };
+        stack.last_mut().unwrap().0.push(SExpr { kind, line });
+    }
+    if stack.len() > 1 {
+        return Err(ParseError {
+            line: stack.last().unwrap().1,
+            message: "unclosed `(`".into(),
+        });
+    }
+    Ok(stack.pop().unwrap().0)
+}
+
+#[derive(Default)]
+struct Parser {
+    declarations: Vec<Declaration>,
+    sorts: HashSet<String>,
+    /// Declared algebraic datatypes.
+    adts: HashSet<String>,
+    /// The datatype and the arity of each declared constructor.
+    constructors: HashMap<String, (String, usize)>,
+    /// The field selectors of the declared datatypes.
+    selectors: HashSet<String>,
+    /// Declared constants, which are represented as variables.
+    constants: HashSet<String>,
+    /// Declared functions with at least one parameter.
+    functions: HashSet<String>,
+    /// The variables bound by the enclosing quantifiers, matches, and lets.
+    bound: Vec<String>,
+    labels: HashSet<ast::LabelSymbol>,
+    assertions: Vec<ast::Expression>,
+}
+
+enum Declaration {
+    Sort(ast::UninterpretedSortDeclaration),
+    Adts(Vec<ast::AdtDeclaration>),
+    Variable(ast::VariableDeclaration),
+    Function(ast::FunctionDeclaration),
+}
+
+impl Parser {
+    fn finish(self) -> ast::Query {
+        let mut declarations = ast::Declarations {
+            sorts: Vec::new(),
+            adts: Vec::new(),
+            variables: Vec::new(),
+            functions: Vec::new(),
+            labels: Vec::new(),
+        };
+        for declaration in self.declarations {
+            match declaration {
+                Declaration::Sort(sort) => declarations.sorts.push(sort),
+                Declaration::Adts(adts) => declarations.adts.extend(adts),
+                Declaration::Variable(variable) => {
+                    if self.labels.contains(&variable.name) && variable.sort == ast::Sort::Bool {
+                        declarations.labels.push(ast::LabelDeclaration {
+                            name: variable.name,
+                        });
+                    } else {
+                        declarations.variables.push(variable);
+                    }
+                }
+                Declaration::Function(function) => declarations.functions.push(function),
+            }
+        }
+        ast::Query {
+            declarations,
+            assertions: self.assertions,
+        }
+    }
+    fn parse_command(&mut self, command: &SExpr) -> ParseResult<()> {
+        let items = command.as_list()?;
+        let (head, args) = match items.split_first() {
+            Some(split) => split,
+            None => return command.error("empty command"),
+        };
+        match head.as_symbol()? {
+            "declare-sort" => {
+                if args.is_empty() || args.len() > 2 {
+                    return command.error("expected a sort name and an optional arity");
+                }
+                let name = self.parse_fresh_symbol(&args[0])?;
+                if args.len() == 2 && args[1].kind != SExprKind::Numeral("0".into()) {
+                    return command.error("only sorts with arity 0 are supported");
+                }
+                self.sorts.insert(name.clone());
+                self.declarations
+                    .push(Declaration::Sort(ast::UninterpretedSortDeclaration {
+                        name: name.into(),
+                    }));
+            }
+            "declare-datatypes" => {
+                expect_arity(command, args, 2)?;
+                let adts = self.parse_datatypes(command, &args[0], &args[1])?;
+                self.declarations.push(Declaration::Adts(adts));
+            }
+            "declare-fun" => {
+                expect_arity(command, args, 3)?;
+                let name = self.parse_fresh_symbol(&args[0])?;
+                let parameter_sorts = args[1]
+                    .as_list()?
+                    .iter()
+                    .map(|sort| self.parse_sort(sort))
+                    .collect::<ParseResult<Vec<_>>>()?;
+                let sort = self.parse_sort(&args[2])?;
+                self.declare(name, parameter_sorts, sort);
+            }
+            "define-fun" | "define-fun-rec" => {
+                expect_arity(command, args, 4)?;
+                let mut function = self.parse_function_signature(&args[0], &args[1], &args[2])?;
+                let name = function.name.as_string();
+                // Only recursive definitions may refer to themselves.
+                let recursive = head.as_symbol()? == "define-fun-rec";
+                if recursive {
+                    self.functions.insert(name.clone());
+                }
+                function.body = Some(self.parse_function_body(&function, &args[3])?);
+                self.functions.insert(name);
+                self.declarations.push(Declaration::Function(function));
+            }
+            "define-funs-rec" => {
+                expect_arity(command, args, 2)?;
+                let signatures = args[0].as_list()?;
+                let bodies = args[1].as_list()?;
+                if signatures.len() != bodies.len() {
+                    return command.error("expected one body per function");
+                }
+                let mut functions = Vec::new();
+                for signature in signatures {
+                    let parts = signature.as_list()?;
+                    expect_arity(signature, parts, 3)?;
+                    let function =
+                        self.parse_function_signature(&parts[0], &parts[1], &parts[2])?;
+                    self.functions.insert(function.name.as_string());
+                    functions.push(function);
+                }
+                for (mut function, body) in functions.into_iter().zip(bodies) {
+                    function.body = Some(self.parse_function_body(&function, body)?);
+                    self.declarations.push(Declaration::Function(function));
+                }
+            }
+            "declare-const" => {
+                expect_arity(command, args, 2)?;
+                let name = self.parse_fresh_symbol(&args[0])?;
+                let sort = self.parse_sort(&args[1])?;
+                self.declare(name, Vec::new(), sort);
+            }
+            "assert" => {
+                expect_arity(command, args, 1)?;
+                let assertion = self.parse_expression(&args[0])?;
+                self.assertions.push(assertion);
+            }
+            "set-info" | "set-option" | "set-logic" | "check-sat" | "get-model" | "get-info"
+            | "get-value" | "labels" | "exit" => {}
+            other => return head.error(format!("unsupported command `{}`", other)),
+        }
+        Ok(())
+    }
+    fn declare(&mut self, name: String, parameter_sorts: Vec<ast::Sort>, sort: ast::Sort) {
+        let declaration = if parameter_sorts.is_empty() {
+            self.constants.insert(name.clone());
+            Declaration::Variable(ast::VariableDeclaration {
+                name: name.into(),
+                sort,
+            })
+        } else {
+            self.functions.insert(name.clone());
+            Declaration::Function(ast::FunctionDeclaration {
+                parameters: parameter_sorts
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, sort)| ast::VariableDeclaration {
+                        name: format!("arg{}", i).into(),
+                        sort,
+                    })
+                    .collect(),
+                name: name.into(),
+                return_sort: sort,
+                preconditions: Vec::new(),
+                body: None,
+            })
+        };
+        self.declarations.push(declaration);
+    }
+    /// Parses the name, the parameters, and the return sort of a function
+    /// definition. The body is parsed separately because recursive
+    /// definitions must be in scope first.
+    fn parse_function_signature(
+        &self,
+        name: &SExpr,
+        parameters: &SExpr,
+        return_sort: &SExpr,
+    ) -> ParseResult<ast::FunctionDeclaration> {
+        let name = self.parse_fresh_symbol(name)?;
+        let parameter_list = parameters.as_list()?;
+        if parameter_list.is_empty() {
+            return parameters.error("definitions of constants are not supported");
+        }
+        let mut parameters = Vec::new();
+        for parameter in parameter_list {
+            let parts = parameter.as_list()?;
+            expect_arity(parameter, parts, 2)?;
+            parameters.push(ast::VariableDeclaration {
+                name: parts[0].as_symbol()?.into(),
+                sort: self.parse_sort(&parts[1])?,
+            });
+        }
+        Ok(ast::FunctionDeclaration {
+            name: name.into(),
+            parameters,
+            return_sort: self.parse_sort(return_sort)?,
+            preconditions: Vec::new(),
+            body: None,
+        })
+    }
+    fn parse_function_body(
+        &mut self,
+        function: &ast::FunctionDeclaration,
+        body: &SExpr,
+    ) -> ParseResult<ast::Expression> {
+        let scope = self.bound.len();
+        self.bound.extend(
+            function
+                .parameters
+                .iter()
+                .map(|parameter| parameter.name.as_string()),
+        );
+        let body = self.parse_expression(body);
+        self.bound.truncate(scope);
+        body
+    }
+    /// Parses the sort declarations and the constructor lists of
+    /// `declare-datatypes`. All datatypes are in scope in all constructors.
+    fn parse_datatypes(
+        &mut self,
+        command: &SExpr,
+        sort_declarations: &SExpr,
+        constructor_lists: &SExpr,
+    ) -> ParseResult<Vec<ast::AdtDeclaration>> {
+        let sort_declarations = sort_declarations.as_list()?;
+        let constructor_lists = constructor_lists.as_list()?;
+        if sort_declarations.len() != constructor_lists.len() {
+            return command.error("expected one constructor list per datatype");
+        }
+        let mut names = Vec::new();
+        for sort_declaration in sort_declarations {
+            let parts = sort_declaration.as_list()?;
+            expect_arity(sort_declaration, parts, 2)?;
+            let name = self.parse_fresh_symbol(&parts[0])?;
+            if parts[1].kind != SExprKind::Numeral("0".into()) {
+                return parts[1].error("only datatypes without parameters are supported");
+            }
+            self.adts.insert(name.clone());
+            names.push(name);
+        }
+        let mut adts = Vec::new();
+        for (name, constructor_list) in names.into_iter().zip(constructor_lists) {
+            let mut constructors = Vec::new();
+            for constructor in constructor_list.as_list()? {
+                let parts = constructor.as_list()?;
+                let (constructor_name, fields) = match parts.split_first() {
+                    Some(split) => split,
+                    None => return constructor.error("empty constructor"),
+                };
+                let constructor_name = self.parse_fresh_symbol(constructor_name)?;
+                let mut field_declarations = Vec::new();
+                for field in fields {
+                    let field_parts = field.as_list()?;
+                    expect_arity(field, field_parts, 2)?;
+                    let field_name = self.parse_fresh_symbol(&field_parts[0])?;
+                    let sort = self.parse_sort(&field_parts[1])?;
+                    self.selectors.insert(field_name.clone());
+                    field_declarations.push(ast::AdtFieldDeclaration {
+                        name: field_name.into(),
+                        sort,
+                    });
+                }
+                self.constructors.insert(
+                    constructor_name.clone(),
+                    (name.clone(), field_declarations.len()),
+                );
+                constructors.push(ast::AdtConstructorDeclaration {
+                    name: constructor_name.into(),
+                    fields: field_declarations,
+                });
+            }
+            if constructors.is_empty() {
+                return constructor_list.error(format!("datatype `{}` has no constructors", name));
+            }
+            adts.push(ast::AdtDeclaration {
+                name: name.into(),
+                constructors,
+            });
+        }
+        Ok(adts)
+    }
+    fn register_adt(&mut self, adt: &ast::AdtDeclaration) {
+        let name = adt.name.as_string();
+        for constructor in &adt.constructors {
+            self.constructors.insert(
+                constructor.name.as_string(),
+                (name.clone(), constructor.fields.len()),
+            );
+            for field in &constructor.fields {
+                self.selectors.insert(field.name.as_string());
+            }
+        }
+        self.adts.insert(name);
+    }
+    fn parse_fresh_symbol(&self, sexpr: &SExpr) -> ParseResult<String> {
+        let name = sexpr.as_symbol()?;
+        if self.sorts.contains(name)
+            || self.adts.contains(name)
+            || self.constructors.contains_key(name)
+            || self.selectors.contains(name)
+            || self.constants.contains(name)
+            || self.functions.contains(name)
+        {
+            return sexpr.error(format!("`{}` is already declared", name));
+        }
+        Ok(name.to_string())
+    }
+    fn parse_sort(&self, sexpr: &SExpr) -> ParseResult<ast::Sort> {
+        parse_sort_with(sexpr, &|name| {
+            if self.sorts.contains(name) {
+                Some(ast::Sort::Uninterpreted { name: name.into() })
+            } else if self.adts.contains(name) {
+                Some(ast::Sort::Adt { name: name.into() })
+            } else {
+                None
+            }
+        })
+    }
+    fn parse_expression(&mut self, sexpr: &SExpr) -> ParseResult<ast::Expression> {
+        match &sexpr.kind {
+            SExprKind::Numeral(value) => Ok(ast::Constant::Int(parse_numeral(value)).into()),
+            SExprKind::Keyword(keyword) => sexpr.error(format!("unexpected keyword `{}`", keyword)),
+            SExprKind::Symbol(symbol) => self.parse_symbol(sexpr, symbol),
+            SExprKind::List(items) => {
+                let (head, args) = match items.split_first() {
+                    Some(split) => split,
+                    None => return sexpr.error("empty application"),
+                };
+                if is_constant_array_head(head) {
+                    expect_arity(sexpr, args, 1)?;
+                    let sort = self.parse_sort(&head.as_list()?[2])?;
+                    let index_sort = match sort {
+                        ast::Sort::Array { index, .. } => *index,
+                        _ => return head.error(format!("`{}` is not an array sort", sort)),
+                    };
+                    let value = self.parse_expression(&args[0])?;
+                    return Ok(ast::Expression::constant_array(index_sort, value));
+                }
+                if let Some(element_sort) = empty_seq_sort(sexpr) {
+                    let element_sort = self.parse_sort(element_sort)?;
+                    return Ok(ast::Expression::empty_seq(element_sort));
+                }
+                if let Some(constructor) = tester_constructor(head) {
+                    expect_arity(sexpr, args, 1)?;
+                    if !self.constructors.contains_key(constructor) {
+                        return head.error(format!("undeclared constructor `{}`", constructor));
+                    }
+                    let base = self.parse_expression(&args[0])?;
+                    return Ok(ast::Expression::is_constructor(base, constructor.into()));
+                }
+                if let SExprKind::List(indexed) = &head.kind {
+                    if is_float_conversion(indexed) {
+                        return self.parse_float_conversion(sexpr, head, indexed, args);
+                    }
+                    let kind = parse_bit_vector_conversion(head, indexed)?;
+                    expect_arity(sexpr, args, 1)?;
+                    let arg = self.parse_expression(&args[0])?;
+                    return Ok(ast::Expression::bit_vector_conversion(kind, arg));
+                }
+                match head.as_symbol()? {
+                    "_" => match parse_special_float(sexpr, items)? {
+                        Some((bits, exponent, significand)) => Ok(ast::Constant::Float {
+                            bits,
+                            exponent,
+                            significand,
+                        }
+                        .into()),
+                        None => parse_bit_vector_constant(sexpr, items),
+                    },
+                    "fp" => {
+                        let (bits, exponent, significand) = parse_float_literal(sexpr, items)?;
+                        Ok(ast::Constant::Float {
+                            bits,
+                            exponent,
+                            significand,
+                        }
+                        .into())
+                    }
+                    operator
+                        if rounded_float_operation(operator, RoundingMode::NearestTiesToEven)
+                            .is_some() =>
+                    {
+                        let (rounding_mode, args) = match args.split_first() {
+                            Some((rounding_mode, args)) => {
+                                (parse_rounding_mode(rounding_mode)?, args)
+                            }
+                            None => return sexpr.error("expected a rounding mode"),
+                        };
+                        let (kind, arity) =
+                            rounded_float_operation(operator, rounding_mode).unwrap();
+                        expect_arity(sexpr, args, arity)?;
+                        let args = args
+                            .iter()
+                            .map(|arg| self.parse_expression(arg))
+                            .collect::<ParseResult<Vec<_>>>()?;
+                        Ok(ast::Expression::float_operation(kind, args))
+                    }
+                    "forall" => self.parse_quantifier(sexpr, args, ast::QuantifierKind::ForAll),
+                    "exists" => self.parse_quantifier(sexpr, args, ast::QuantifierKind::Exists),
+                    "!" => self.parse_annotation(sexpr, args),
+                    "match" => self.parse_match(sexpr, args),
+                    "let" => self.parse_let(sexpr, args),
+                    operator => {
+                        let args = args
+                            .iter()
+                            .map(|arg| self.parse_expression(arg))
+                            .collect::<ParseResult<Vec<_>>>()?;
+                        self.parse_application(sexpr, operator, args)
+                    }
+                }
+            }
+        }
+    }
+    fn parse_symbol(&self, sexpr: &SExpr, symbol: &str) -> ParseResult<ast::Expression> {
+        match symbol {
+            "true" => Ok(true.into()),
+            "false" => Ok(false.into()),
+            _ if symbol.starts_with(|c: char| c.is_ascii_digit()) => {
+                match real::parse_decimal(symbol) {
+                    Some((numerator, denominator)) => Ok(ast::Constant::Real {
+                        numerator,
+                        denominator,
+                    }
+                    .into()),
+                    None => sexpr.error(format!("invalid decimal `{}`", symbol)),
+                }
+            }
+            _ if symbol.starts_with('#') => match parse_bit_vector_literal(symbol) {
+                Some((value, width)) => Ok(ast::Constant::BitVector { value, width }.into()),
+                None => sexpr.error(format!("invalid bit-vector literal `{}`", symbol)),
+            },
+            _ if self.bound.iter().any(|name| name == symbol)
+                || self.constants.contains(symbol) =>
+            {
+                Ok(ast::Expression::variable(symbol.into()))
+            }
+            _ if self.constructors.contains_key(symbol) => {
+                let (adt, arity) = &self.constructors[symbol];
+                if *arity != 0 {
+                    return sexpr.error(format!(
+                        "constructor `{}` is applied to no arguments",
+                        symbol
+                    ));
+                }
+                Ok(ast::Expression::construct(
+                    adt.as_str().into(),
+                    symbol.into(),
+                    Vec::new(),
+                ))
+            }
+            _ if self.functions.contains(symbol) => {
+                sexpr.error(format!("function `{}` is applied to no arguments", symbol))
+            }
+            _ => sexpr.error(format!("undeclared symbol `{}`", symbol)),
+        }
+    }
+    fn parse_application(
+        &self,
+        sexpr: &SExpr,
+        operator: &str,
+        mut args: Vec<ast::Expression>,
+    ) -> ParseResult<ast::Expression> {
+        use ast::BinaryOperationKind as Kind;
+        let expression = match (operator, args.len()) {
+            ("not", 1) | ("bvnot", 1) => ast::Expression::not(args.pop().unwrap()),
+            ("-", 1) => match args.pop().unwrap() {
+                // The printer writes negative rationals as `(- 1.5)`.
+                ast::Expression::Constant(ast::Constant::Real {
+                    numerator,
+                    denominator,
+                }) if numerator > 0.into() => ast::Constant::Real {
+                    numerator: -numerator,
+                    denominator,
+                }
+                .into(),
+                arg => ast::Expression::minus(arg),
+            },
+            ("bvneg", 1) | ("fp.neg", 1) => ast::Expression::minus(args.pop().unwrap()),
+            ("fp.to_real", 1) => ast::Expression::float_to_real(args.pop().unwrap()),
+            (operator, arity) if float_operation(operator).map(|(_, n)| n) == Some(arity) => {
+                let (kind, _) = float_operation(operator).unwrap();
+                ast::Expression::float_operation(kind, args)
+            }
+            ("fp.lt", 2) | ("fp.leq", 2) | ("fp.gt", 2) | ("fp.geq", 2) => {
+                let kind = match operator {
+                    "fp.lt" => Kind::LtCmp,
+                    "fp.leq" => Kind::LeCmp,
+                    "fp.gt" => Kind::GtCmp,
+                    _ => Kind::GeCmp,
+                };
+                let right = args.pop().unwrap();
+                let left = args.pop().unwrap();
+                ast::Expression::binary_operation(kind, left, right)
+            }
+            ("to_real", 1) => ast::Expression::to_real(args.pop().unwrap()),
+            ("to_int", 1) => ast::Expression::to_int(args.pop().unwrap()),
+            ("is_int", 1) => ast::Expression::is_int(args.pop().unwrap()),
+            ("/", 2) if rational_operands(&args).is_some() => {
+                // The printer writes inexact rationals as `(/ 1.0 3.0)`.
+                let (numerator, denominator) = rational_operands(&args).unwrap();
+                ast::Constant::Real {
+                    numerator,
+                    denominator,
+                }
+                .into()
+            }
+            ("bv2nat", 1) => ast::Expression::bit_vector_to_int(args.pop().unwrap()),
+            ("select", 2) => {
+                let index = args.pop().unwrap();
+                let array = args.pop().unwrap();
+                ast::Expression::select(array, index)
+            }
+            ("store", 3) => {
+                let value = args.pop().unwrap();
+                let index = args.pop().unwrap();
+                let array = args.pop().unwrap();
+                ast::Expression::store(array, index, value)
+            }
+            ("seq.unit", 1) => ast::Expression::seq_unit(args.pop().unwrap()),
+            ("seq.len", 1) => ast::Expression::seq_length(args.pop().unwrap()),
+            ("seq.nth", 2) => {
+                let index = args.pop().unwrap();
+                let seq = args.pop().unwrap();
+                ast::Expression::seq_index(seq, index)
+            }
+            ("seq.++", _) if args.len() >= 2 => {
+                let mut args = args.into_iter();
+                let first = args.next().unwrap();
+                args.fold(first, ast::Expression::seq_concat)
+            }
+            ("seq.extract", 3) => {
+                let length = args.pop().unwrap();
+                let offset = args.pop().unwrap();
+                let seq = args.pop().unwrap();
+                ast::Expression::seq_subsequence(seq, offset, length)
+            }
+            ("seq.update", 3) => {
+                // The printer writes updates of single elements as
+                // `(seq.update s i (seq.unit v))`.
+                let value = match args.pop().unwrap() {
+                    ast::Expression::SeqOperation(ast::SeqOperation {
+                        kind: ast::SeqOperationKind::Unit,
+                        mut args,
+                    }) => args.pop().unwrap(),
+                    _ => return sexpr.error("only updates with `seq.unit` are supported"),
+                };
+                let index = args.pop().unwrap();
+                let seq = args.pop().unwrap();
+                ast::Expression::seq_update(seq, index, value)
+            }
+            ("ite", 3) => {
+                let else_expr = args.pop().unwrap();
+                let then_expr = args.pop().unwrap();
+                let guard = args.pop().unwrap();
+                ast::Expression::Conditional(ast::Conditional {
+                    guard: Box::new(guard),
+                    then_expr: Box::new(then_expr),
+                    else_expr: Box::new(else_expr),
+                })
+            }
+            ("and", 0) => true.into(),
+            ("or", 0) => false.into(),
+            ("and", _) | ("or", _) | ("+", _) | ("*", _) if !args.is_empty() => {
+                let kind = match operator {
+                    "and" => Kind::And,
+                    "or" => Kind::Or,
+                    "+" => Kind::Add,
+                    _ => Kind::Mul,
+                };
+                left_fold(kind, args)
+            }
+            ("-", _) | ("div", _) | ("/", _) | ("mod", 2) if args.len() >= 2 => {
+                let kind = match operator {
+                    "-" => Kind::Sub,
+                    "mod" => Kind::Mod,
+                    _ => Kind::Div,
+                };
+                left_fold(kind, args)
+            }
+            ("bvand", _)
+            | ("bvor", _)
+            | ("bvxor", _)
+            | ("bvadd", _)
+            | ("bvmul", _)
+            | ("concat", _)
+                if args.len() >= 2 =>
+            {
+                let kind = match operator {
+                    "bvand" => Kind::BitAnd,
+                    "bvor" => Kind::BitOr,
+                    "bvxor" => Kind::BitXor,
+                    "bvadd" => Kind::Add,
+                    "bvmul" => Kind::Mul,
+                    _ => Kind::Concat,
+                };
+                left_fold(kind, args)
+            }
+            (operator, 2) if bit_vector_operation(operator).is_some() => {
+                let right = args.pop().unwrap();
+                let left = args.pop().unwrap();
+                let kind = bit_vector_operation(operator).unwrap();
+                ast::Expression::binary_operation(kind, left, right)
+            }
+            ("=>", _) if args.len() >= 2 => {
+                // Implication is right-associative.
+                let mut result = args.pop().unwrap();
+                while let Some(arg) = args.pop() {
+                    result = ast::Expression::implies(arg, result);
+                }
+                result
+            }
+            ("=", _) | ("<", _) | ("<=", _) | (">", _) | (">=", _) if args.len() >= 2 => {
+                let kind = match operator {
+                    "=" => Kind::EqCmp,
+                    "<" => Kind::LtCmp,
+                    "<=" => Kind::LeCmp,
+                    ">" => Kind::GtCmp,
+                    _ => Kind::GeCmp,
+                };
+                // Chained comparisons hold for each adjacent pair.
+                let pairs = args
+                    .windows(2)
+                    .map(|pair| {
+                        ast::Expression::binary_operation(kind, pair[0].clone(), pair[1].clone())
+                    })
+                    .collect();
+                left_fold(Kind::And, pairs)
+            }
+            ("distinct", 2) => {
+                let right = args.pop().unwrap();
+                let left = args.pop().unwrap();
+                ast::Expression::not_equals(left, right)
+            }
+            (constructor, arity) if self.constructors.contains_key(constructor) => {
+                let (adt, expected_arity) = &self.constructors[constructor];
+                if arity != *expected_arity {
+                    return sexpr.error(format!(
+                        "constructor `{}` expects {} arguments, found {}",
+                        constructor, expected_arity, arity
+                    ));
+                }
+                ast::Expression::construct(adt.as_str().into(), constructor.into(), args)
+            }
+            (field, 1) if self.selectors.contains(field) => {
+                ast::Expression::field(args.pop().unwrap(), field.into())
+            }
+            (function, _) if self.functions.contains(function) => {
+                ast::Expression::call(function.into(), args)
+            }
+            (operator, arity) => {
+                return sexpr.error(format!(
+                    "unsupported operator `{}` with {} arguments",
+                    operator, arity
+                ))
+            }
+        };
+        Ok(expression)
+    }
+    /// Parses an application of an indexed floating-point conversion such as
+    /// `((_ to_fp 8 24) RNE x)`. Without a rounding mode, `to_fp` converts
+    /// from the bits of `x`.
+    fn parse_float_conversion(
+        &mut self,
+        sexpr: &SExpr,
+        head: &SExpr,
+        indexed: &[SExpr],
+        args: &[SExpr],
+    ) -> ParseResult<ast::Expression> {
+        use ast::FloatConversionKind as Kind;
+        let (name, indices) = parse_indexed_identifier(head, indexed)?;
+        let (rounding_mode, arg) = match args {
+            [rounding_mode, arg] => (Some(parse_rounding_mode(rounding_mode)?), arg),
+            [arg] => (None, arg),
+            _ => return sexpr.error(format!("unsupported conversion `{}`", sexpr)),
+        };
+        if let ("to_fp" | "to_fp_unsigned", &[exponent, significand]) = (name, indices.as_slice()) {
+            if !float::is_supported_format(exponent, significand) {
+                return sexpr.error(format!("unsupported floating-point format `{}`", head));
+            }
+        }
+        let kind = match (name, indices.as_slice(), rounding_mode) {
+            ("to_fp", &[exponent, significand], Some(rounding_mode)) => Kind::ToFloat {
+                rounding_mode,
+                exponent,
+                significand,
+            },
+            ("to_fp", &[exponent, significand], None) => Kind::FromBits {
+                exponent,
+                significand,
+            },
+            ("to_fp_unsigned", &[exponent, significand], Some(rounding_mode)) => {
+                Kind::UnsignedToFloat {
+                    rounding_mode,
+                    exponent,
+                    significand,
+                }
+            }
+            ("fp.to_sbv", &[width], Some(rounding_mode)) => Kind::ToSigned {
+                rounding_mode,
+                width,
+            },
+            ("fp.to_ubv", &[width], Some(rounding_mode)) => Kind::ToUnsigned {
+                rounding_mode,
+                width,
+            },
+            _ => return sexpr.error(format!("unsupported conversion `{}`", sexpr)),
+        };
+        let arg = self.parse_expression(arg)?;
+        Ok(ast::Expression::float_conversion(kind, arg))
+    }
+    /// Parses `(match <term> ((<pattern> <term>)+))`. Only constructor
+    /// patterns are supported.
+    fn parse_match(&mut self, sexpr: &SExpr, args: &[SExpr]) -> ParseResult<ast::Expression> {
+        expect_arity(sexpr, args, 2)?;
+        let base = self.parse_expression(&args[0])?;
+        let mut adt: Option<String> = None;
+        let mut cases = Vec::new();
+        for case in args[1].as_list()? {
+            let parts = case.as_list()?;
+            expect_arity(case, parts, 2)?;
+            let (pattern, binders) = match &parts[0].kind {
+                SExprKind::Symbol(_) => (&parts[0], &[][..]),
+                SExprKind::List(items) if !items.is_empty() => (&items[0], &items[1..]),
+                _ => return parts[0].error(format!("unsupported pattern `{}`", parts[0])),
+            };
+            let constructor = pattern.as_symbol()?;
+            let (case_adt, arity) = match self.constructors.get(constructor) {
+                Some(entry) => entry.clone(),
+                None => {
+                    return pattern.error(format!(
+                        "only constructor patterns are supported, found `{}`",
+                        constructor
+                    ))
+                }
+            };
+            if arity != binders.len() {
+                return parts[0].error(format!(
+                    "constructor `{}` expects {} arguments, found {}",
+                    constructor,
+                    arity,
+                    binders.len()
+                ));
+            }
+            match &adt {
+                Some(adt) if *adt != case_adt => {
+                    return pattern.error(format!("expected a constructor of `{}`", adt))
+                }
+                _ => adt = Some(case_adt),
+            }
+            let binders = binders
+                .iter()
+                .map(|binder| binder.as_symbol().map(str::to_string))
+                .collect::<ParseResult<Vec<_>>>()?;
+            let scope = self.bound.len();
+            self.bound.extend(binders.iter().cloned());
+            let body = self.parse_expression(&parts[1]);
+            self.bound.truncate(scope);
+            cases.push(ast::AdtMatchCase {
+                constructor: constructor.into(),
+                binders: binders.into_iter().map(Into::into).collect(),
+                body: Box::new(body?),
+            });
+        }
+        let adt = match adt {
+            Some(adt) => adt,
+            None => return sexpr.error("a match must have at least one case"),
+        };
+        Ok(ast::Expression::AdtMatch(ast::AdtMatch {
+            adt: adt.into(),
+            base: Box::new(base),
+            cases,
+        }))
+    }
+    /// Parses `(let ((<symbol> <term>)+) <term>)` into nested let
+    /// expressions. The bindings are parallel, so a value must not refer to
+    /// the variable of an earlier binding.
+    fn parse_let(&mut self, sexpr: &SExpr, args: &[SExpr]) -> ParseResult<ast::Expression> {
+        expect_arity(sexpr, args, 2)?;
+        let mut bindings: Vec<(String, ast::Expression)> = Vec::new();
+        for binding in args[0].as_list()? {
+            let parts = binding.as_list()?;
+            expect_arity(binding, parts, 2)?;
+            let variable = parts[0].as_symbol()?;
+            if let Some((earlier, _)) = bindings
+                .iter()
+                .find(|(earlier, _)| mentions_symbol(&parts[1], earlier))
+            {
+                return parts[1].error(format!(
+                    "parallel bindings that refer to `{}` are not supported",
+                    earlier
+                ));
+            }
+            bindings.push((variable.to_string(), self.parse_expression(&parts[1])?));
+        }
+        if bindings.is_empty() {
+            return args[0].error("a let must have at least one binding");
+        }
+        let scope = self.bound.len();
+        self.bound
+            .extend(bindings.iter().map(|(variable, _)| variable.clone()));
+        let body = self.parse_expression(&args[1]);
+        self.bound.truncate(scope);
+        Ok(bindings
+            .into_iter()
+            .rev()
+            .fold(body?, |body, (variable, value)| {
+                ast::Expression::let_in(variable.into(), value, body)
+            }))
+    }
+    fn parse_quantifier(
+        &mut self,
+        sexpr: &SExpr,
+        args: &[SExpr],
+        kind: ast::QuantifierKind,
+    ) -> ParseResult<ast::Expression> {
+        expect_arity(sexpr, args, 2)?;
+        let mut variables = Vec::new();
+        for variable in args[0].as_list()? {
+            let parts = variable.as_list()?;
+            expect_arity(variable, parts, 2)?;
+            variables.push(ast::BoundedVariableDecl {
+                name: parts[0].as_symbol()?.into(),
+                sort: self.parse_sort(&parts[1])?,
+            });
+        }
+        let scope = self.bound.len();
+        self.bound
+            .extend(variables.iter().map(|variable| variable.name.as_string()));
+        let result = self.parse_quantifier_body(&args[1]);
+        self.bound.truncate(scope);
+        let (body, triggers) = result?;
+        Ok(ast::Expression::quantifier(kind, variables, triggers, body))
+    }
+    /// Parses the body of a quantifier together with its `:pattern`
+    /// annotations.
+    fn parse_quantifier_body(
+        &mut self,
+        sexpr: &SExpr,
+    ) -> ParseResult<(ast::Expression, Vec<ast::Trigger>)> {
+        if let SExprKind::List(items) = &sexpr.kind {
+            if items.first().map(|head| &head.kind) == Some(&SExprKind::Symbol("!".into())) {
+                let (body, attributes) = split_annotation(sexpr, &items[1..])?;
+                let mut triggers = Vec::new();
+                let mut other_attributes = Vec::new();
+                for (keyword, value) in attributes {
+                    if keyword == ":pattern" {
+                        let parts = value
+                            .as_list()?
+                            .iter()
+                            .map(|part| self.parse_expression(part))
+                            .collect::<ParseResult<_>>()?;
+                        triggers.push(ast::Trigger { parts });
+                    } else {
+                        other_attributes.push((keyword, value));
+                    }
+                }
+                let body = self.parse_annotated(body, other_attributes)?;
+                return Ok((body, triggers));
+            }
+        }
+        Ok((self.parse_expression(sexpr)?, Vec::new()))
+    }
+    fn parse_annotation(&mut self, sexpr: &SExpr, args: &[SExpr]) -> ParseResult<ast::Expression> {
+        let (body, attributes) = split_annotation(sexpr, args)?;
+        if let Some((_, pattern)) = attributes
+            .iter()
+            .find(|(keyword, _)| *keyword == ":pattern")
+        {
+            return pattern.error("`:pattern` is allowed only directly inside a quantifier");
+        }
+        self.parse_annotated(body, attributes)
+    }
+    /// Parses `body` and wraps it into the labels from `attributes`. Other
+    /// attributes are ignored.
+    fn parse_annotated(
+        &mut self,
+        body: &SExpr,
+        attributes: Vec<(&str, &SExpr)>,
+    ) -> ParseResult<ast::Expression> {
+        let mut expression = self.parse_expression(body)?;
+        for (keyword, value) in attributes {
+            let (positivity, connective) = match keyword {
+                ":lblpos" => (
+                    ast::LabelPositivity::Positive,
+                    ast::BinaryOperationKind::And,
+                ),
+                ":lblneg" => (ast::LabelPositivity::Negative, ast::BinaryOperationKind::Or),
+                _ => continue,
+            };
+            let name: ast::LabelSymbol = value.as_symbol()?.into();
+            // Strip the label constant that the printer adds to the body.
+            if let ast::Expression::BinaryOperation(operation) = &expression {
+                if operation.kind == connective
+                    && *operation.left == ast::Expression::variable(name.clone())
+                {
+                    expression = (*operation.right).clone();
+                }
+            }
+            self.labels.insert(name.clone());
+            expression = ast::Expression::labelled_expression(positivity, name, expression);
+        }
+        Ok(expression)
+    }
+}
+
+/// Parses a sort; the names of uninterpreted sorts and datatypes are
+/// resolved with `resolve`.
+fn parse_sort_with(
+    sexpr: &SExpr,
+    resolve: &dyn Fn(&str) -> Option<ast::Sort>,
+) -> ParseResult<ast::Sort> {
+    if let SExprKind::List(items) = &sexpr.kind {
+        if let [head, element] = items.as_slice() {
+            if head.kind == SExprKind::Symbol("Seq".into()) {
+                return Ok(ast::Sort::Seq {
+                    element: Box::new(parse_sort_with(element, resolve)?),
+                });
+            }
+        }
+        if let [head, index, element] = items.as_slice() {
+            if head.kind == SExprKind::Symbol("Array".into()) {
+                return Ok(ast::Sort::Array {
+                    index: Box::new(parse_sort_with(index, resolve)?),
+                    element: Box::new(parse_sort_with(element, resolve)?),
+                });
+            }
+        }
+        let (name, indices) = parse_indexed_identifier(sexpr, items)?;
+        return match (name, indices.as_slice()) {
+            ("BitVec", [width]) if *width > 0 => Ok(ast::Sort::BitVector { width: *width }),
+            ("FloatingPoint", &[exponent, significand])
+                if float::is_supported_format(exponent, significand) =>
+            {
+                Ok(ast::Sort::Float {
+                    exponent,
+                    significand,
+                })
+            }
+            _ => sexpr.error(format!("unsupported sort `{}`", sexpr)),
+        };
+    }
+    match sexpr.as_symbol()? {
+        "Bool" => Ok(ast::Sort::Bool),
+        "Int" => Ok(ast::Sort::Int),
+        "Real" => Ok(ast::Sort::Real),
+        name @ ("Float16" | "Float32" | "Float64" | "Float128") => {
+            let (exponent, significand) =
+                float::format_from_name(&format!("F{}", &name[5..])).unwrap();
+            Ok(ast::Sort::Float {
+                exponent,
+                significand,
+            })
+        }
+        name => match resolve(name) {
+            Some(sort) => Ok(sort),
+            None => sexpr.error(format!("undeclared sort `{}`", name)),
+        },
+    }
+}
+
+/// Parses a sort as printed by a solver in a model.
+pub(crate) fn parse_model_sort(
+    input: &str,
+    resolve: &dyn Fn(&str) -> Option<ast::Sort>,
+) -> ParseResult<ast::Sort> {
+    parse_sort_with(&read_single_sexpr(input)?, resolve)
+}
+
+/// Parses a value of sort `sort` as printed by a solver in a model. Array
+/// values must be built from constant arrays and stores, or refer to a function
+/// with `(_ as-array f)`, which is kept opaque until `resolve_as_array_values`
+/// replaces it. Sequence values must be built from `seq.empty`, `seq.unit`,
+/// and `seq.++`.
+pub(crate) fn parse_model_value(input: &str, sort: &ast::Sort) -> ParseResult<ast::Value> {
+    parse_value(&read_single_sexpr(input)?, sort)
+}
+
+fn parse_value(sexpr: &SExpr, sort: &ast::Sort) -> ParseResult<ast::Value> {
+    match (sort, &sexpr.kind) {
+        (ast::Sort::Bool, SExprKind::Symbol(symbol)) if symbol == "true" || symbol == "false" => {
+            Ok(ast::Value::Bool(symbol == "true"))
+        }
+        (ast::Sort::Int, SExprKind::Numeral(value)) => Ok(ast::Value::Int(parse_numeral(value))),
+        (ast::Sort::Int, SExprKind::List(items))
+            if items.len() == 2 && items[0].kind == SExprKind::Symbol("-".into()) =>
+        {
+            match parse_value(&items[1], sort)? {
+                ast::Value::Int(value) => Ok(ast::Value::Int(-value)),
+                _ => unreachable!(),
+            }
+        }
+        (ast::Sort::Real, SExprKind::Numeral(literal) | SExprKind::Symbol(literal)) => {
+            match real::parse_decimal(literal) {
+                Some((numerator, denominator)) => Ok(ast::Value::Real {
+                    numerator,
+                    denominator,
+                }),
+                None => sexpr.error(format!("invalid decimal `{}`", literal)),
+            }
+        }
+        (ast::Sort::Real, SExprKind::List(items)) => match items.as_slice() {
+            [minus, value] if minus.kind == SExprKind::Symbol("-".into()) => {
+                match parse_value(value, sort)? {
+                    ast::Value::Real {
+                        numerator,
+                        denominator,
+                    } => Ok(ast::Value::Real {
+                        numerator: -numerator,
+                        denominator,
+                    }),
+                    _ => unreachable!(),
+                }
+            }
+            [divide, numerator, denominator] if divide.kind == SExprKind::Symbol("/".into()) => {
+                let parse_integral = |sexpr: &SExpr| match parse_value(sexpr, sort)? {
+                    ast::Value::Real {
+                        numerator,
+                        denominator,
+                    } if denominator == 1.into() => Ok(numerator),
+                    _ => sexpr.error(format!("expected an integral real: {}", sexpr)),
+                };
+                let numerator = parse_integral(numerator)?;
+                let denominator = parse_integral(denominator)?;
+                match real::normalize(numerator, denominator) {
+                    Some((numerator, denominator)) => Ok(ast::Value::Real {
+                        numerator,
+                        denominator,
+                    }),
+                    None => sexpr.error(format!("invalid rational `{}`", sexpr)),
+                }
+            }
+            _ => sexpr.error(format!("unsupported real value `{}`", sexpr)),
+        },
+        (ast::Sort::BitVector { width }, SExprKind::Symbol(symbol)) => {
+            match parse_bit_vector_literal(symbol) {
+                Some((value, literal_width)) if literal_width == *width => {
+                    Ok(ast::Value::BitVector {
+                        value,
+                        width: *width,
+                    })
+                }
+                _ => sexpr.error(format!(
+                    "expected a bit-vector of width {}: {}",
+                    width, symbol
+                )),
+            }
+        }
+        (
+            &ast::Sort::Float {
+                exponent,
+                significand,
+            },
+            SExprKind::List(items),
+        ) => {
+            let value = match items.first() {
+                Some(head) if head.kind == SExprKind::Symbol("fp".into()) => {
+                    Some(parse_float_literal(sexpr, items)?)
+                }
+                Some(_) => parse_special_float(sexpr, items)?,
+                None => None,
+            };
+            match value {
+                Some((bits, value_exponent, value_significand))
+                    if (value_exponent, value_significand) == (exponent, significand) =>
+                {
+                    Ok(ast::Value::Float {
+                        bits,
+                        exponent,
+                        significand,
+                    })
+                }
+                _ => sexpr.error(format!("expected a value of sort {}: {}", sort, sexpr)),
+            }
+        }
+        (ast::Sort::Array { index, element }, SExprKind::List(items)) => match items.as_slice() {
+            [head, value] if is_constant_array_head(head) => Ok(ast::Value::Array {
+                entries: Vec::new(),
+                default: Box::new(parse_value(value, element)?),
+            }),
+            [head, array, index_value, value] if head.kind == SExprKind::Symbol("store".into()) => {
+                let (mut entries, default) = match parse_value(array, sort)? {
+                    ast::Value::Array { entries, default } => (entries, default),
+                    _ => unreachable!(),
+                };
+                let index_value = parse_value(index_value, index)?;
+                let value = parse_value(value, element)?;
+                // A later store overwrites an earlier one.
+                entries.retain(|(entry_index, _)| entry_index != &index_value);
+                entries.push((index_value, value));
+                Ok(ast::Value::Array { entries, default })
+            }
+            _ if as_array_function(sexpr).is_some() => Ok(ast::Value::Opaque(sexpr.to_string())),
+            _ => sexpr.error(format!("unsupported array value `{}`", sexpr)),
+        },
+        (ast::Sort::Set { element }, _) => parse_value(
+            sexpr,
+            &ast::Sort::Array {
+                index: element.clone(),
+                element: Box::new(ast::Sort::Bool),
+            },
+        ),
+        (ast::Sort::Seq { element }, SExprKind::List(items)) => match items.as_slice() {
+            _ if empty_seq_sort(sexpr).is_some() => Ok(ast::Value::Seq(Vec::new())),
+            [head, value] if head.kind == SExprKind::Symbol("seq.unit".into()) => {
+                Ok(ast::Value::Seq(vec![parse_value(value, element)?]))
+            }
+            [head, parts @ ..] if head.kind == SExprKind::Symbol("seq.++".into()) => {
+                let mut elements = Vec::new();
+                for part in parts {
+                    match parse_value(part, sort)? {
+                        ast::Value::Seq(part_elements) => elements.extend(part_elements),
+                        _ => unreachable!(),
+                    }
+                }
+                Ok(ast::Value::Seq(elements))
+            }
+            _ => sexpr.error(format!("unsupported sequence value `{}`", sexpr)),
+        },
+        (ast::Sort::Adt { .. }, _) => parse_adt_value(sexpr),
+        _ => sexpr.error(format!("unsupported value `{}` of sort {}", sexpr, sort)),
+    }
+}
+
+/// Replaces the array values `(_ as-array f)` in `model` by the interpretation
+/// of the function `f`, which is also part of the model. A value stays opaque
+/// if the body of `f` is not a chain of `ite`s that compare its parameter.
+pub(crate) fn resolve_as_array_values(model: &mut ast::Model) {
+    let mut resolved = Vec::new();
+    for (i, item) in model.items.iter().enumerate() {
+        if let (ast::Sort::Array { index, element }, ast::Value::Opaque(value)) =
+            (&item.sort, &item.value)
+        {
+            if let Some(array) = resolve_as_array(model, value, index, element) {
+                resolved.push((i, array));
+            }
+        }
+    }
+    for (i, array) in resolved {
+        model.items[i].value = array;
+    }
+}
+
+fn resolve_as_array(
+    model: &ast::Model,
+    value: &str,
+    index: &ast::Sort,
+    element: &ast::Sort,
+) -> Option<ast::Value> {
+    let value = read_single_sexpr(value).ok()?;
+    let function = as_array_function(&value)?;
+    let item = model
+        .items
+        .iter()
+        .find(|item| item.name.as_string() == function)?;
+    match (item.args.as_slice(), &item.value) {
+        ([parameter], ast::Value::Opaque(body)) => {
+            let body = read_single_sexpr(body).ok()?;
+            parse_function_entries(&body, &parameter.name.as_string(), index, element).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Parses the body of a function with the single parameter `parameter` as an
+/// array value. The body must have the form
+/// `(ite (= parameter index) value ... default)`.
+fn parse_function_entries(
+    mut body: &SExpr,
+    parameter: &str,
+    index: &ast::Sort,
+    element: &ast::Sort,
+) -> ParseResult<ast::Value> {
+    let is_parameter = |sexpr: &SExpr| sexpr.kind == SExprKind::Symbol(parameter.into());
+    let mut entries: Vec<(ast::Value, ast::Value)> = Vec::new();
+    loop {
+        match &body.kind {
+            SExprKind::List(items)
+                if items.len() == 4 && items[0].kind == SExprKind::Symbol("ite".into()) =>
+            {
+                let index_value = match items[1].as_list()? {
+                    [equals, left, right] if equals.kind == SExprKind::Symbol("=".into()) => {
+                        if is_parameter(left) {
+                            right
+                        } else if is_parameter(right) {
+                            left
+                        } else {
+                            return items[1].error(format!("unsupported condition `{}`", items[1]));
+                        }
+                    }
+                    _ => return items[1].error(format!("unsupported condition `{}`", items[1])),
+                };
+                let index_value = parse_value(index_value, index)?;
+                // An earlier condition takes precedence over a later one.
+                if !entries
+                    .iter()
+                    .any(|(entry_index, _)| entry_index == &index_value)
+                {
+                    entries.push((index_value, parse_value(&items[2], element)?));
+                }
+                body = &items[3];
+            }
+            _ => {
+                return Ok(ast::Value::Array {
+                    entries,
+                    default: Box::new(parse_value(body, element)?),
+                })
+            }
+        }
+    }
+}
+
+/// Returns `f` if `sexpr` is the array value `(_ as-array f)`.
+fn as_array_function(sexpr: &SExpr) -> Option<&str> {
+    match &sexpr.kind {
+        SExprKind::List(items) => match items.as_slice() {
+            [underscore, as_array, function]
+                if underscore.kind == SExprKind::Symbol("_".into())
+                    && as_array.kind == SExprKind::Symbol("as-array".into()) =>
+            {
+                function.as_symbol().ok()
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parses a value of an algebraic datatype. The sorts of the constructor
+/// arguments are not known here, so they are inferred from their shape.
+fn parse_adt_value(sexpr: &SExpr) -> ParseResult<ast::Value> {
+    match &sexpr.kind {
+        SExprKind::Numeral(_) => parse_value(sexpr, &ast::Sort::Int),
+        SExprKind::Symbol(symbol) if symbol == "true" || symbol == "false" => {
+            parse_value(sexpr, &ast::Sort::Bool)
+        }
+        SExprKind::Symbol(symbol) if symbol.starts_with(|c: char| c.is_ascii_digit()) => {
+            parse_value(sexpr, &ast::Sort::Real)
+        }
+        SExprKind::Symbol(symbol) if symbol.starts_with('#') => {
+            match parse_bit_vector_literal(symbol) {
+                Some((value, width)) => Ok(ast::Value::BitVector { value, width }),
+                None => sexpr.error(format!("invalid bit-vector literal `{}`", symbol)),
+            }
+        }
+        SExprKind::Symbol(symbol) => match symbol.split_once("!val!") {
+            // Z3 names the elements of uninterpreted sorts `<sort>!val!<n>`.
+            Some((sort, _)) => Ok(ast::Value::Uninterpreted {
+                sort: sort.into(),
+                value: symbol.clone(),
+            }),
+            None => Ok(ast::Value::Adt {
+                constructor: symbol.as_str().into(),
+                args: Vec::new(),
+            }),
+        },
+        SExprKind::List(items) => match items.as_slice() {
+            [minus, value] if minus.kind == SExprKind::Symbol("-".into()) => {
+                match parse_adt_value(value)? {
+                    ast::Value::Real { .. } => parse_value(sexpr, &ast::Sort::Real),
+                    _ => parse_value(sexpr, &ast::Sort::Int),
+                }
+            }
+            [divide, _, _] if divide.kind == SExprKind::Symbol("/".into()) => {
+                parse_value(sexpr, &ast::Sort::Real)
+            }
+            [head, ..] if head.kind == SExprKind::Symbol("fp".into()) => {
+                let (bits, exponent, significand) = parse_float_literal(sexpr, items)?;
+                Ok(ast::Value::Float {
+                    bits,
+                    exponent,
+                    significand,
+                })
+            }
+            [head, ..] if head.kind == SExprKind::Symbol("_".into()) => {
+                match parse_special_float(sexpr, items)? {
+                    Some((bits, exponent, significand)) => Ok(ast::Value::Float {
+                        bits,
+                        exponent,
+                        significand,
+                    }),
+                    None => sexpr.error(format!("unsupported datatype value `{}`", sexpr)),
+                }
+            }
+            _ if empty_seq_sort(sexpr).is_some() => Ok(ast::Value::Seq(Vec::new())),
+            [head, value] if head.kind == SExprKind::Symbol("seq.unit".into()) => {
+                Ok(ast::Value::Seq(vec![parse_adt_value(value)?]))
+            }
+            [head, parts @ ..] if head.kind == SExprKind::Symbol("seq.++".into()) => {
+                let mut elements = Vec::new();
+                for part in parts {
+                    match parse_adt_value(part)? {
+                        ast::Value::Seq(part_elements) => elements.extend(part_elements),
+                        _ => return part.error(format!("expected a sequence: {}", part)),
+                    }
+                }
+                Ok(ast::Value::Seq(elements))
+            }
+            [head, ..] if is_constant_array_head(head) || is_store_chain(sexpr) => {
+                let sort = array_value_sort(sexpr)?;
+                parse_value(sexpr, &sort)
+            }
+            [constructor, args @ ..] if !args.is_empty() => Ok(ast::Value::Adt {
+                constructor: constructor.as_symbol()?.into(),
+                args: args
+                    .iter()
+                    .map(parse_adt_value)
+                    .collect::<ParseResult<_>>()?,
+            }),
+            _ => sexpr.error(format!("unsupported datatype value `{}`", sexpr)),
+        },
+        SExprKind::Keyword(_) => sexpr.error(format!("unsupported datatype value `{}`", sexpr)),
+    }
+}
+
+/// Whether the symbol `symbol` occurs anywhere in `sexpr`.
+fn mentions_symbol(sexpr: &SExpr, symbol: &str) -> bool {
+    match &sexpr.kind {
+        SExprKind::Symbol(name) => name == symbol,
+        SExprKind::List(items) => items.iter().any(|item| mentions_symbol(item, symbol)),
+        SExprKind::Keyword(_) | SExprKind::Numeral(_) => false,
+    }
+}
+
+fn is_store_chain(sexpr: &SExpr) -> bool {
+    match &sexpr.kind {
+        SExprKind::List(items) => {
+            items.len() == 4 && items[0].kind == SExprKind::Symbol("store".into())
+        }
+        _ => false,
+    }
+}
+
+/// Finds the sort of an array value from the `(as const <sort>)` at the
+/// bottom of its store chain.
+fn array_value_sort(sexpr: &SExpr) -> ParseResult<ast::Sort> {
+    let items = sexpr.as_list()?;
+    if is_store_chain(sexpr) {
+        return array_value_sort(&items[1]);
+    }
+    match items.first() {
+        Some(head) if is_constant_array_head(head) => {
+            // Whether a sort name refers to a datatype or to an uninterpreted
+            // sort does not matter because both are parsed by shape.
+            parse_sort_with(&head.as_list()?[2], &|name| {
+                Some(ast::Sort::Adt { name: name.into() })
+            })
+        }
+        _ => sexpr.error(format!("unsupported array value `{}`", sexpr)),
+    }
+}
+
+/// Returns `C` if `sexpr` is the tester `(_ is C)`.
+fn tester_constructor(sexpr: &SExpr) -> Option<&str> {
+    match &sexpr.kind {
+        SExprKind::List(items) => match items.as_slice() {
+            [underscore, is, constructor]
+                if underscore.kind == SExprKind::Symbol("_".into())
+                    && is.kind == SExprKind::Symbol("is".into()) =>
+            {
+                constructor.as_symbol().ok()
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns `T` if `sexpr` is `(as seq.empty (Seq T))`.
+fn empty_seq_sort(sexpr: &SExpr) -> Option<&SExpr> {
+    match &sexpr.kind {
+        SExprKind::List(items) => match items.as_slice() {
+            [as_, empty, sort]
+                if as_.kind == SExprKind::Symbol("as".into())
+                    && empty.kind == SExprKind::Symbol("seq.empty".into()) =>
+            {
+                match &sort.kind {
+                    SExprKind::List(sort_items) => match sort_items.as_slice() {
+                        [seq, element] if seq.kind == SExprKind::Symbol("Seq".into()) => {
+                            Some(element)
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Checks whether `sexpr` is `(as const <sort>)`.
+fn is_constant_array_head(sexpr: &SExpr) -> bool {
+    match &sexpr.kind {
+        SExprKind::List(items) => {
+            items.len() == 3
+                && items[0].kind == SExprKind::Symbol("as".into())
+                && items[1].kind == SExprKind::Symbol("const".into())
+        }
+        _ => false,
+    }
+}
+
+fn read_single_sexpr(input: &str) -> ParseResult<SExpr> {
+    let mut sexprs = read_sexprs(input)?;
+    if sexprs.len() != 1 {
+        return Err(ParseError {
+            line: sexprs.get(1).map_or(1, |sexpr| sexpr.line),
+            message: format!("expected one expression, found {}", sexprs.len()),
+        });
+    }
+    Ok(sexprs.pop().unwrap())
+}
+
+/// Splits the arguments of `!` into the annotated term and its attributes.
+fn split_annotation<'a>(
+    sexpr: &SExpr,
+    args: &'a [SExpr],
+) -> ParseResult<(&'a SExpr, Vec<(&'a str, &'a SExpr)>)> {
+    let (body, rest) = match args.split_first() {
+        Some(split) => split,
+        None => return sexpr.error("`!` without a term"),
+    };
+    let mut attributes = Vec::new();
+    for pair in rest.chunks(2) {
+        match (&pair[0].kind, pair.get(1)) {
+            (SExprKind::Keyword(keyword), Some(value)) => {
+                attributes.push((keyword.as_str(), value))
+            }
+            _ => return pair[0].error("expected an attribute with a value"),
+        }
+    }
+    Ok((body, attributes))
+}
+
+/// The binary bit-vector operations that are not left-associative.
+fn bit_vector_operation(operator: &str) -> Option<ast::BinaryOperationKind> {
+    use ast::BinaryOperationKind as Kind;
+    let kind = match operator {
+        "bvsub" => Kind::Sub,
+        "bvudiv" => Kind::Div,
+        "bvurem" => Kind::Mod,
+        "bvsdiv" => Kind::SDiv,
+        "bvsrem" => Kind::SRem,
+        "bvshl" => Kind::Shl,
+        "bvlshr" => Kind::LShr,
+        "bvashr" => Kind::AShr,
+        "bvult" => Kind::LtCmp,
+        "bvule" => Kind::LeCmp,
+        "bvugt" => Kind::GtCmp,
+        "bvuge" => Kind::GeCmp,
+        "bvslt" => Kind::SLtCmp,
+        "bvsle" => Kind::SLeCmp,
+        "bvsgt" => Kind::SGtCmp,
+        "bvsge" => Kind::SGeCmp,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// Parses a bit-vector literal in binary (`#b0101`) or hexadecimal (`#x0f`)
+/// form into its value and width.
+fn parse_bit_vector_literal(literal: &str) -> Option<(u128, u32)> {
+    let (digits, radix, bits_per_digit) = if let Some(digits) = literal.strip_prefix("#b") {
+        (digits, 2, 1)
+    } else if let Some(digits) = literal.strip_prefix("#x") {
+        (digits, 16, 4)
+    } else {
+        return None;
+    };
+    let width = digits.len() * bits_per_digit;
+    if width == 0 || width > 128 || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    let value = u128::from_str_radix(digits, radix).ok()?;
+    Some((value, width as u32))
+}
+
+/// Splits an indexed identifier such as `(_ BitVec 32)` into its name and
+/// indices.
+fn parse_indexed_identifier<'a>(
+    sexpr: &SExpr,
+    items: &'a [SExpr],
+) -> ParseResult<(&'a str, Vec<u32>)> {
+    match items {
+        [underscore, name, indices @ ..]
+            if underscore.kind == SExprKind::Symbol("_".into()) && !indices.is_empty() =>
+        {
+            let indices = indices
+                .iter()
+                .map(|index| match &index.kind {
+                    SExprKind::Numeral(value) => value
+                        .parse()
+                        .or_else(|_| index.error(format!("invalid index `{}`", value))),
+                    _ => index.error(format!("expected an index, found {}", index)),
+                })
+                .collect::<ParseResult<_>>()?;
+            Ok((name.as_symbol()?, indices))
+        }
+        _ => sexpr.error(format!("expected an indexed identifier, found {}", sexpr)),
+    }
+}
+
+/// Parses a bit-vector constant of the form `(_ bv<value> <width>)`.
+fn parse_bit_vector_constant(sexpr: &SExpr, items: &[SExpr]) -> ParseResult<ast::Expression> {
+    let (name, indices) = parse_indexed_identifier(sexpr, items)?;
+    if let [width] = indices.as_slice() {
+        if let Some(Ok(value)) = name.strip_prefix("bv").map(str::parse::<u128>) {
+            let fits = *width == 128 || (*width < 128 && value >> *width == 0);
+            if *width > 0 && fits {
+                let width = *width;
+                return Ok(ast::Constant::BitVector { value, width }.into());
+            }
+        }
+    }
+    sexpr.error(format!("invalid bit-vector constant `{}`", sexpr))
+}
+
+fn parse_bit_vector_conversion(
+    sexpr: &SExpr,
+    items: &[SExpr],
+) -> ParseResult<ast::BitVectorConversionKind> {
+    use ast::BitVectorConversionKind as Kind;
+    let (name, indices) = parse_indexed_identifier(sexpr, items)?;
+    let kind = match (name, indices.as_slice()) {
+        ("extract", [high, low]) if high >= low => Kind::Extract {
+            high: *high,
+            low: *low,
+        },
+        ("zero_extend", [bits]) => Kind::ZeroExtend { bits: *bits },
+        ("sign_extend", [bits]) => Kind::SignExtend { bits: *bits },
+        ("int2bv", [width]) => Kind::FromInt { width: *width },
+        _ => return sexpr.error(format!("unsupported operator `{}`", sexpr)),
+    };
+    Ok(kind)
+}
+
+fn parse_rounding_mode(sexpr: &SExpr) -> ParseResult<RoundingMode> {
+    let symbol = sexpr.as_symbol()?;
+    match RoundingMode::from_abbreviation(symbol) {
+        Some(rounding_mode) => Ok(rounding_mode),
+        None => sexpr.error(format!("expected a rounding mode, found {}", symbol)),
+    }
+}
+
+/// Parses a floating-point literal of the form `(fp <sign> <exponent>
+/// <significand>)` into its bits and format.
+fn parse_float_literal(sexpr: &SExpr, items: &[SExpr]) -> ParseResult<(u128, u32, u32)> {
+    let parts = items[1..]
+        .iter()
+        .map(|part| {
+            part.as_symbol()
+                .ok()
+                .and_then(parse_bit_vector_literal)
+                .map_or_else(
+                    || part.error(format!("expected a bit-vector literal: {}", part)),
+                    Ok,
+                )
+        })
+        .collect::<ParseResult<Vec<_>>>()?;
+    match parts.as_slice() {
+        &[(sign, 1), (biased_exponent, exponent), (fraction, fraction_width)]
+            if float::is_supported_format(exponent, fraction_width + 1) =>
+        {
+            let significand = fraction_width + 1;
+            let bits =
+                float::from_parts(sign == 1, biased_exponent, fraction, exponent, significand);
+            Ok((bits, exponent, significand))
+        }
+        _ => sexpr.error(format!("invalid floating-point literal `{}`", sexpr)),
+    }
+}
+
+/// Parses a special floating-point value such as `(_ +oo 8 24)` into its bits
+/// and format. Returns `None` for other indexed identifiers.
+fn parse_special_float(sexpr: &SExpr, items: &[SExpr]) -> ParseResult<Option<(u128, u32, u32)>> {
+    let (name, indices) = parse_indexed_identifier(sexpr, items)?;
+    match indices.as_slice() {
+        &[exponent, significand] if float::is_supported_format(exponent, significand) => {
+            Ok(float::special_value(name, exponent, significand)
+                .map(|bits| (bits, exponent, significand)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn is_float_conversion(indexed: &[SExpr]) -> bool {
+    match indexed {
+        [underscore, name, ..] if underscore.kind == SExprKind::Symbol("_".into()) => matches!(
+            &name.kind,
+            SExprKind::Symbol(name)
+                if name == "to_fp"
+                    || name == "to_fp_unsigned"
+                    || name == "fp.to_sbv"
+                    || name == "fp.to_ubv"
+        ),
+        _ => false,
+    }
+}
+
+/// The kind and arity of a floating-point operation that takes a rounding
+/// mode as its first argument.
+fn rounded_float_operation(
+    operator: &str,
+    rounding_mode: RoundingMode,
+) -> Option<(ast::FloatOperationKind, usize)> {
+    use ast::FloatOperationKind as Kind;
+    let operation = match operator {
+        "fp.add" => (Kind::Add(rounding_mode), 2),
+        "fp.sub" => (Kind::Sub(rounding_mode), 2),
+        "fp.mul" => (Kind::Mul(rounding_mode), 2),
+        "fp.div" => (Kind::Div(rounding_mode), 2),
+        "fp.fma" => (Kind::Fma(rounding_mode), 3),
+        "fp.sqrt" => (Kind::Sqrt(rounding_mode), 1),
+        "fp.roundToIntegral" => (Kind::RoundToIntegral(rounding_mode), 1),
+        _ => return None,
+    };
+    Some(operation)
+}
+
+/// The kind and arity of a floating-point operation without a rounding mode.
+fn float_operation(operator: &str) -> Option<(ast::FloatOperationKind, usize)> {
+    use ast::FloatOperationKind as Kind;
+    let operation = match operator {
+        "fp.rem" => (Kind::Rem, 2),
+        "fp.abs" => (Kind::Abs, 1),
+        "fp.min" => (Kind::Min, 2),
+        "fp.max" => (Kind::Max, 2),
+        "fp.eq" => (Kind::Eq, 2),
+        "fp.isNormal" => (Kind::IsNormal, 1),
+        "fp.isSubnormal" => (Kind::IsSubnormal, 1),
+        "fp.isZero" => (Kind::IsZero, 1),
+        "fp.isInfinite" => (Kind::IsInfinite, 1),
+        "fp.isNaN" => (Kind::IsNaN, 1),
+        "fp.isNegative" => (Kind::IsNegative, 1),
+        "fp.isPositive" => (Kind::IsPositive, 1),
+        _ => return None,
+    };
+    Some(operation)
+}
+
+fn parse_numeral(numeral: &str) -> BigInt {
+    // The reader accepts only optionally negated sequences of digits as
+    // numerals.
+    numeral.parse().unwrap()
+}
+
+/// Returns the quotient if `args` are two non-negative real literals with
+/// integral values and a non-zero divisor.
+fn rational_operands(args: &[ast::Expression]) -> Option<(BigInt, BigInt)> {
+    let integral = |arg: &ast::Expression| match arg {
+        ast::Expression::Constant(ast::Constant::Real {
+            numerator,
+            denominator,
+        }) if *denominator == 1.into() && *numerator >= 0.into() => Some(numerator.clone()),
+        _ => None,
+    };
+    let numerator = integral(&args[0])?;
+    let denominator = integral(&args[1])?;
+    real::normalize(numerator, denominator)
+}
+
+fn expect_arity(sexpr: &SExpr, args: &[SExpr], arity: usize) -> ParseResult<()> {
+    if args.len() != arity {
+        return sexpr.error(format!(
+            "expected {} arguments, found {}",
+            arity,
+            args.len()
+        ));
+    }
+    Ok(())
+}
+
+fn left_fold(kind: ast::BinaryOperationKind, args: Vec<ast::Expression>) -> ast::Expression {
+    let mut args = args.into_iter();
+    let first = args.next().unwrap();
+    args.fold(first, |left, right| {
+        ast::Expression::binary_operation(kind, left, right)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_expression, parse_model_sort, parse_model_value, parse_query,
+        resolve_as_array_values, ParseError,
+    };
+    use crate::functions::function_groups;
+    use crate::solvers::test_context::StringContext;
+    use rsmt2::print::{Expr2Smt, Sort2Smt};
+    use svirpti_vir::common::float::{self, RoundingMode};
+    use svirpti_vir::smt as ast;
+    use svirpti_vir::smt::{
+        AdtHelpers, ArrayHelpers, BinaryOperationHelpers, BitVectorConversionHelpers,
+        ConstantHelpers, FloatHelpers, FunctionApplicationHelpers, LabelledExpressionHelpers,
+        LetHelpers, QuantifierHelpers, SeqHelpers, UnaryOperationHelpers, VariableHelpers,
+    };
+
+    fn print_sort(sort: &ast::Sort, context: &StringContext) -> String {
+        let mut buffer = Vec::new();
+        sort.sort_to_smt2(&mut buffer, context).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    /// Prints `query` in the same way as the Z3 solver sends it.
+    fn print_query(query: &ast::Query) -> String {
+        let mut context = StringContext::default();
+        for variable in &query.declarations.variables {
+            context
+                .variables
+                .insert(variable.name.clone(), variable.clone());
+        }
+        for function in &query.declarations.functions {
+            context
+                .functions
+                .insert(function.name.clone(), function.clone());
+        }
+        context.adts = query.declarations.adts.clone();
+        let mut script = String::new();
+        for sort in &query.declarations.sorts {
+            script.push_str(&format!("(declare-sort {} 0)\n", sort.name));
+        }
+        if !query.declarations.adts.is_empty() {
+            let mut buffer = Vec::new();
+            ast::AdtDeclaration::write_declare_datatypes(
+                &query.declarations.adts,
+                &mut buffer,
+                &context,
+            )
+            .unwrap();
+            script.push_str(&String::from_utf8(buffer).unwrap());
+            script.push('\n');
+        }
+        for variable in &query.declarations.variables {
+            let sort = print_sort(&variable.sort, &context);
+            script.push_str(&format!("(declare-fun {} () {})\n", variable.name, sort));
+        }
+        for group in function_groups(&query.declarations.functions) {
+            let function = group.functions[0];
+            if group.recursive || function.body.is_some() {
+                let mut buffer = Vec::new();
+                if group.recursive {
+                    let functions: Vec<_> = group.functions.into_iter().cloned().collect();
+                    ast::FunctionDeclaration::write_define_funs_rec(
+                        &functions,
+                        &mut buffer,
+                        &context,
+                    )
+                    .unwrap();
+                } else {
+                    function.write_define_fun(&mut buffer, &context).unwrap();
+                }
+                script.push_str(&String::from_utf8(buffer).unwrap());
+                script.push('\n');
+                continue;
+            }
+            let parameters: Vec<_> = function
+                .parameters
+                .iter()
+                .map(|parameter| print_sort(&parameter.sort, &context))
+                .collect();
+            let sort = print_sort(&function.return_sort, &context);
+            script.push_str(&format!(
+                "(declare-fun {} ({}) {})\n",
+                function.name,
+                parameters.join(" "),
+                sort
+            ));
+        }
+        for label in &query.declarations.labels {
+            script.push_str(&format!("(declare-fun {} () Bool)\n", label.name));
+        }
+        for assertion in &query.assertions {
+            let mut buffer = Vec::new();
+            assertion.expr_to_smt2(&mut buffer, &context).unwrap();
+            script.push_str(&format!(
+                "(assert {})\n",
+                String::from_utf8(buffer).unwrap()
+            ));
+        }
+        script
+    }
+
+    fn variable(name: &str, sort: ast::Sort) -> ast::VariableDeclaration {
+        ast::VariableDeclaration {
+            name: name.into(),
+            sort,
+        }
+    }
+
+    fn var(name: &str) -> ast::Expression {
+        ast::Expression::variable(name.into())
+    }
+
+    fn example_query() -> ast::Query {
+        let nat = ast::Sort::Uninterpreted { name: "Nat".into() };
+        let count = |arg| ast::Expression::call("count".into(), vec![arg]);
+        ast::Query {
+            declarations: ast::Declarations {
+                sorts: vec![ast::UninterpretedSortDeclaration { name: "Nat".into() }],
+                adts: Vec::new(),
+                variables: vec![
+                    variable("x", ast::Sort::Int),
+                    variable("b", ast::Sort::Bool),
+                    variable("zero", nat.clone()),
+                ],
+                functions: vec![ast::FunctionDeclaration {
+                    name: "count".into(),
+                    parameters: vec![variable("arg0", nat.clone())],
+                    return_sort: ast::Sort::Int,
+                    preconditions: Vec::new(),
+                    body: None,
+                }],
+                labels: vec![
+                    ast::LabelDeclaration { name: "l1".into() },
+                    ast::LabelDeclaration { name: "l2".into() },
+                ],
+            },
+            assertions: vec![
+                ast::Expression::equals(count(var("zero")), 0.into()),
+                ast::Expression::forall(
+                    vec![ast::BoundedVariableDecl {
+                        name: "n".into(),
+                        sort: nat,
+                    }],
+                    vec![ast::Trigger {
+                        parts: vec![count(var("n"))],
+                    }],
+                    ast::Expression::greater_equals(count(var("n")), 0.into()),
+                ),
+                ast::Expression::label_positive(
+                    "l1".into(),
+                    ast::Expression::implies(
+                        var("b"),
+                        ast::Expression::less_than(
+                            ast::Expression::minus(var("x")),
+                            ast::Expression::module(var("x"), 2.into()),
+                        ),
+                    ),
+                ),
+                ast::Expression::not(ast::Expression::label_negative(
+                    "l2".into(),
+                    ast::Expression::greater_than(
+                        ast::Expression::Conditional(ast::Conditional {
+                            guard: Box::new(false.into()),
+                            then_expr: Box::new(ast::Expression::divide(var("x"), 3.into())),
+                            else_expr: Box::new(ast::Expression::multiply(
+                                ast::Expression::subtract(var("x"), 1.into()),
+                                (-4).into(),
+                            )),
+                        }),
+                        0.into(),
+                    ),
+                )),
+            ],
+        }
+    }
+
+    #[test]
+    fn check_round_trip() {
+        let query = example_query();
+        let script = print_query(&query);
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+    }
+
+    #[test]
+    fn check_not_equals_round_trip() {
+        // `NeCmp` is printed as `(not (= …))`, so it comes back as a negated
+        // equality, which is then stable.
+        let declarations = ast::Declarations {
+            sorts: Vec::new(),
+            adts: Vec::new(),
+            variables: vec![variable("x", ast::Sort::Int)],
+            functions: Vec::new(),
+            labels: Vec::new(),
+        };
+        let query = ast::Query {
+            declarations,
+            assertions: vec![ast::Expression::not_equals(var("x"), 1.into())],
+        };
+        let script = print_query(&query);
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(
+            parsed.assertions[0],
+            ast::Expression::not(ast::Expression::equals(var("x"), 1.into()))
+        );
+        assert_eq!(parse_query(&print_query(&parsed)).unwrap(), parsed);
+    }
+
+    #[test]
+    fn check_big_integer_round_trip() {
+        let script = "(declare-fun x () Int)\n\
+            (assert (< 18446744073709551616 (* x (- 170141183460469231731687303715884105728)) ))\n";
+        let query = parse_query(script).unwrap();
+        let big = |digits: &str| -> ast::Expression {
+            ast::Constant::Int(digits.parse().unwrap()).into()
+        };
+        assert_eq!(
+            query.assertions,
+            vec![ast::Expression::less_than(
+                big("18446744073709551616"),
+                ast::Expression::multiply(
+                    var("x"),
+                    ast::Expression::minus(big("170141183460469231731687303715884105728"))
+                ),
+            )]
+        );
+        assert_eq!(parse_query(&print_query(&query)).unwrap(), query);
+        let value = parse_model_value("(- 18446744073709551616)", &ast::Sort::Int).unwrap();
+        assert_eq!(
+            value,
+            ast::Value::Int("-18446744073709551616".parse().unwrap())
+        );
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"Int":"-18446744073709551616"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&ast::Value::Int(5.into())).unwrap(),
+            r#"{"Int":5}"#
+        );
+        let json = r#"[{"Int":5},{"Int":"-18446744073709551616"}]"#;
+        let values: Vec<ast::Value> = serde_json::from_str(json).unwrap();
+        assert_eq!(values, vec![ast::Value::Int(5.into()), value]);
+        let value =
+            parse_model_value("(- (/ 1.0 18446744073709551616.0))", &ast::Sort::Real).unwrap();
+        assert_eq!(
+            value.to_string(),
+            "-0.0000000000000000000542101086242752217003726400434970855712890625"
+        );
+        let value = parse_model_value("(/ 18446744073709551617.0 3.0)", &ast::Sort::Real).unwrap();
+        assert_eq!(value.to_string(), "(18446744073709551617.0/3.0)");
+    }
+
+    #[test]
+    fn check_real_round_trip() {
+        let real = |numerator: i64, denominator: i64| -> ast::Expression {
+            ast::Constant::Real {
+                numerator: numerator.into(),
+                denominator: denominator.into(),
+            }
+            .into()
+        };
+        let declarations = ast::Declarations {
+            sorts: Vec::new(),
+            adts: Vec::new(),
+            variables: vec![
+                variable("r", ast::Sort::Real),
+                variable("i", ast::Sort::Int),
+            ],
+            functions: Vec::new(),
+            labels: Vec::new(),
+        };
+        let query = ast::Query {
+            declarations,
+            assertions: vec![
+                ast::Expression::less_than(
+                    ast::Expression::divide(var("r"), real(5, 2)),
+                    ast::Expression::add(real(-1, 3), real(-3, 4)),
+                ),
+                ast::Expression::equals(ast::Expression::to_real(var("i")), real(2, 1)),
+                ast::Expression::is_int(ast::Expression::to_real(ast::Expression::to_int(var(
+                    "r",
+                )))),
+            ],
+        };
+        let script = print_query(&query);
+        assert!(script.contains("(/ r (/ 5.0 2.0) )"), "{}", script);
+        assert!(script.contains("(- (/ 1.0 3.0))"), "{}", script);
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+    }
+
+    #[test]
+    fn check_bit_vector_round_trip() {
+        use ast::BinaryOperationKind as Kind;
+        let byte = ast::Sort::BitVector { width: 8 };
+        let bv =
+            |value, width| ast::Expression::Constant(ast::Constant::BitVector { value, width });
+        let query = ast::Query {
+            declarations: ast::Declarations {
+                sorts: Vec::new(),
+                adts: Vec::new(),
+                variables: vec![variable("a", byte.clone()), variable("n", ast::Sort::Int)],
+                functions: vec![ast::FunctionDeclaration {
+                    name: "f".into(),
+                    parameters: vec![variable("arg0", byte.clone())],
+                    return_sort: ast::Sort::BitVector { width: 16 },
+                    preconditions: Vec::new(),
+                    body: None,
+                }],
+                labels: Vec::new(),
+            },
+            assertions: vec![
+                ast::Expression::less_than(
+                    ast::Expression::add(var("a"), bv(1, 8)),
+                    ast::Expression::minus(ast::Expression::not(var("a"))),
+                ),
+                ast::Expression::binary_operation(
+                    Kind::SLeCmp,
+                    ast::Expression::binary_operation(Kind::AShr, var("a"), bv(255, 8)),
+                    ast::Expression::divide(var("a"), bv(3, 8)),
+                ),
+                ast::Expression::equals(
+                    ast::Expression::binary_operation(Kind::Concat, var("a"), var("a")),
+                    ast::Expression::call(
+                        "f".into(),
+                        vec![ast::Expression::extract(
+                            ast::Expression::sign_extend(var("a"), 8),
+                            11,
+                            4,
+                        )],
+                    ),
+                ),
+                ast::Expression::equals(
+                    ast::Expression::bit_vector_to_int(ast::Expression::zero_extend(var("a"), 1)),
+                    ast::Expression::bit_vector_to_int(ast::Expression::int_to_bit_vector(
+                        var("n"),
+                        9,
+                    )),
+                ),
+            ],
+        };
+        let script = print_query(&query);
+        assert!(script.contains("(declare-fun a () (_ BitVec 8))"));
+        assert!(script.contains("(bvult (bvadd a (_ bv1 8) ) (bvneg (bvnot a ) ) )"));
+        assert!(script.contains("(bvsle (bvashr a (_ bv255 8) ) (bvudiv a (_ bv3 8) ) )"));
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+        assert_eq!(
+            parse_expression("(bvor #b0101 #x0f)", &query.declarations).unwrap(),
+            ast::Expression::binary_operation(Kind::BitOr, bv(5, 4), bv(15, 8))
+        );
+        assert!(parse_expression("(_ bv256 8)", &query.declarations).is_err());
+        assert!(parse_expression("((_ extract 0 1) a)", &query.declarations).is_err());
+    }
+
+    #[test]
+    fn check_float_round_trip() {
+        let (exponent, significand) = float::F32;
+        let f32_sort = ast::Sort::Float {
+            exponent,
+            significand,
+        };
+        let f64_sort = ast::Sort::Float {
+            exponent: 11,
+            significand: 53,
+        };
+        let query = ast::Query {
+            declarations: ast::Declarations {
+                sorts: Vec::new(),
+                adts: Vec::new(),
+                variables: vec![
+                    variable("x", f32_sort.clone()),
+                    variable("y", f32_sort),
+                    variable("d", f64_sort),
+                    variable("b", ast::Sort::BitVector { width: 32 }),
+                ],
+                functions: Vec::new(),
+                labels: Vec::new(),
+            },
+            assertions: vec![
+                ast::Expression::less_than(
+                    ast::Expression::fp_add(
+                        RoundingMode::NearestTiesToEven,
+                        var("x"),
+                        ast::Expression::f32(1.5),
+                    ),
+                    ast::Expression::minus(var("y")),
+                ),
+                ast::Expression::fp_eq(
+                    ast::Expression::fp_sqrt(RoundingMode::TowardZero, var("x")),
+                    ast::Expression::to_float(
+                        RoundingMode::NearestTiesToEven,
+                        var("d"),
+                        exponent,
+                        significand,
+                    ),
+                ),
+                ast::Expression::not(ast::Expression::fp_is_nan(
+                    ast::Expression::float_conversion(
+                        ast::FloatConversionKind::FromBits {
+                            exponent,
+                            significand,
+                        },
+                        var("b"),
+                    ),
+                )),
+                ast::Expression::equals(
+                    ast::Expression::float_to_signed(RoundingMode::TowardZero, var("x"), 32),
+                    var("b"),
+                ),
+                ast::Expression::fp_is_infinite(ast::Expression::f32(f32::NEG_INFINITY)),
+            ],
+        };
+        let script = print_query(&query);
+        assert!(
+            script.contains("(declare-fun x () (_ FloatingPoint 8 24))"),
+            "{}",
+            script
+        );
+        assert!(
+            script.contains("(fp.add RNE x (fp #b0 #b01111111 #b10000000000000000000000))"),
+            "{}",
+            script
+        );
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+        assert_eq!(
+            parse_expression("(_ -oo 8 24)", &query.declarations).unwrap(),
+            ast::Expression::f32(f32::NEG_INFINITY)
+        );
+        assert!(parse_expression("(fp.add x y)", &query.declarations).is_err());
+        assert!(parse_expression("((_ fp.to_sbv 32) x)", &query.declarations).is_err());
+        assert!(parse_expression("((_ to_fp 100 100) RNE x)", &query.declarations).is_err());
+        assert!(parse_expression("((_ to_fp 8 1) x)", &query.declarations).is_err());
+        assert!(parse_query("(declare-fun y () (_ FloatingPoint 100 100))").is_err());
+    }
+
+    #[test]
+    fn check_array_round_trip() {
+        let memory = ast::Sort::Array {
+            index: Box::new(ast::Sort::Int),
+            element: Box::new(ast::Sort::Array {
+                index: Box::new(ast::Sort::Int),
+                element: Box::new(ast::Sort::Bool),
+            }),
+        };
+        let query = ast::Query {
+            declarations: ast::Declarations {
+                sorts: Vec::new(),
+                adts: Vec::new(),
+                variables: vec![variable("m", memory.clone()), variable("n", memory)],
+                functions: Vec::new(),
+                labels: Vec::new(),
+            },
+            assertions: vec![
+                ast::Expression::select(ast::Expression::select(var("m"), 1.into()), 2.into()),
+                ast::Expression::equals(
+                    var("n"),
+                    ast::Expression::store(
+                        var("m"),
+                        3.into(),
+                        ast::Expression::constant_array(ast::Sort::Int, false.into()),
+                    ),
+                ),
+            ],
+        };
+        let script = print_query(&query);
+        assert!(script.contains("(declare-fun m () (Array Int (Array Int Bool)))"));
+        assert!(script.contains("(store m 3 ((as const (Array Int Bool)) false))"));
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+        assert!(parse_expression("((as const Int) 0)", &query.declarations).is_err());
+    }
+
+    #[test]
+    fn check_seq_round_trip() {
+        let ints = ast::Sort::Seq {
+            element: Box::new(ast::Sort::Int),
+        };
+        let query = ast::Query {
+            declarations: ast::Declarations {
+                sorts: Vec::new(),
+                adts: Vec::new(),
+                variables: vec![variable("s", ints.clone()), variable("t", ints)],
+                functions: Vec::new(),
+                labels: Vec::new(),
+            },
+            assertions: vec![
+                ast::Expression::equals(
+                    ast::Expression::seq_length(ast::Expression::seq_concat(
+                        var("s"),
+                        ast::Expression::seq_unit(1.into()),
+                    )),
+                    ast::Expression::seq_index(var("t"), 0.into()),
+                ),
+                ast::Expression::equals(
+                    ast::Expression::seq_update(
+                        ast::Expression::seq_subsequence(var("s"), 1.into(), 2.into()),
+                        0.into(),
+                        3.into(),
+                    ),
+                    ast::Expression::empty_seq(ast::Sort::Int),
+                ),
+            ],
+        };
+        let script = print_query(&query);
+        assert!(
+            script.contains("(declare-fun s () (Seq Int))"),
+            "{}",
+            script
+        );
+        assert!(script.contains("(as seq.empty (Seq Int))"), "{}", script);
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+        assert!(parse_expression("(seq.update s 0 t)", &query.declarations).is_err());
+    }
+
+    #[test]
+    fn check_adt_round_trip() {
+        let list = ast::Sort::Adt {
+            name: "List".into(),
+        };
+        let adts = vec![ast::AdtDeclaration {
+            name: "List".into(),
+            constructors: vec![
+                ast::AdtConstructorDeclaration {
+                    name: "Nil".into(),
+                    fields: Vec::new(),
+                },
+                ast::AdtConstructorDeclaration {
+                    name: "Cons".into(),
+                    fields: vec![
+                        ast::AdtFieldDeclaration {
+                            name: "head".into(),
+                            sort: ast::Sort::Int,
+                        },
+                        ast::AdtFieldDeclaration {
+                            name: "tail".into(),
+                            sort: list.clone(),
+                        },
+                    ],
+                },
+            ],
+        }];
+        let nil = || ast::Expression::construct("List".into(), "Nil".into(), Vec::new());
+        let query = ast::Query {
+            declarations: ast::Declarations {
+                sorts: Vec::new(),
+                adts,
+                variables: vec![variable("l", list)],
+                functions: Vec::new(),
+                labels: Vec::new(),
+            },
+            assertions: vec![
+                ast::Expression::is_constructor(var("l"), "Cons".into()),
+                ast::Expression::equals(
+                    ast::Expression::field(var("l"), "tail".into()),
+                    ast::Expression::construct("List".into(), "Cons".into(), vec![1.into(), nil()]),
+                ),
+                ast::Expression::AdtMatch(ast::AdtMatch {
+                    adt: "List".into(),
+                    base: Box::new(var("l")),
+                    cases: vec![
+                        ast::AdtMatchCase {
+                            constructor: "Nil".into(),
+                            binders: Vec::new(),
+                            body: Box::new(false.into()),
+                        },
+                        ast::AdtMatchCase {
+                            constructor: "Cons".into(),
+                            binders: vec!["h".into(), "t".into()],
+                            body: Box::new(ast::Expression::not(ast::Expression::equals(
+                                var("t"),
+                                nil(),
+                            ))),
+                        },
+                    ],
+                }),
+            ],
+        };
+        let script = print_query(&query);
+        assert!(script
+            .contains("(declare-datatypes ((List 0)) (((Nil) (Cons (head Int) (tail List)))))"));
+        assert!(script.contains("(assert ((_ is Cons) l))"));
+        assert!(script.contains("(match l ((Nil false) ((Cons h t) (not (= t Nil ) ))))"));
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+        assert!(parse_expression("(head l l)", &query.declarations).is_err());
+        assert!(parse_expression("(match l ((x true)))", &query.declarations).is_err());
+        assert!(parse_query("(declare-datatypes ((T 1)) (((C))))").is_err());
+    }
+
+    #[test]
+    fn check_function_definition_round_trip() {
+        let n = || variable("n", ast::Sort::Int);
+        let sum = |arg| ast::Expression::call("sum".into(), vec![arg]);
+        let twice = |arg| ast::Expression::call("twice".into(), vec![arg]);
+        let query = ast::Query {
+            declarations: ast::Declarations {
+                sorts: Vec::new(),
+                adts: Vec::new(),
+                variables: Vec::new(),
+                functions: vec![
+                    ast::FunctionDeclaration {
+                        name: "twice".into(),
+                        parameters: vec![n()],
+                        return_sort: ast::Sort::Int,
+                        preconditions: Vec::new(),
+                        body: Some(ast::Expression::add(var("n"), var("n"))),
+                    },
+                    ast::FunctionDeclaration {
+                        name: "sum".into(),
+                        parameters: vec![n()],
+                        return_sort: ast::Sort::Int,
+                        preconditions: Vec::new(),
+                        body: Some(ast::Expression::Conditional(ast::Conditional {
+                            guard: Box::new(ast::Expression::less_equals(var("n"), 0.into())),
+                            then_expr: Box::new(0.into()),
+                            else_expr: Box::new(ast::Expression::add(
+                                var("n"),
+                                sum(ast::Expression::subtract(var("n"), 1.into())),
+                            )),
+                        })),
+                    },
+                ],
+                labels: Vec::new(),
+            },
+            assertions: vec![ast::Expression::equals(sum(3.into()), twice(3.into()))],
+        };
+        let script = print_query(&query);
+        assert!(script.contains("(define-fun twice ((n Int)) Int "));
+        assert!(script.contains("(define-funs-rec ((sum ((n Int)) Int)) ((ite "));
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+        assert_eq!(
+            parse_query("(define-fun-rec f ((n Int)) Int (f n))").unwrap(),
+            parse_query("(define-funs-rec ((f ((n Int)) Int)) ((f n)))").unwrap()
+        );
+        assert!(parse_query("(define-fun f ((n Int)) Int (f n))").is_err());
+        assert!(parse_query("(define-fun c () Int 1)").is_err());
+    }
+
+    #[test]
+    fn check_let_round_trip() {
+        let query = ast::Query {
+            declarations: ast::Declarations {
+                sorts: Vec::new(),
+                adts: Vec::new(),
+                variables: vec![variable("x", ast::Sort::Int)],
+                functions: Vec::new(),
+                labels: Vec::new(),
+            },
+            assertions: vec![ast::Expression::let_in(
+                "y".into(),
+                ast::Expression::add(var("x"), 1.into()),
+                ast::Expression::let_in(
+                    "x".into(),
+                    ast::Expression::multiply(var("y"), var("y")),
+                    ast::Expression::greater_than(var("x"), var("y")),
+                ),
+            )],
+        };
+        let script = print_query(&query);
+        assert!(script.contains("(let ((y (+ x 1 ))) (let ((x (* y y ))) (> x y ))))"));
+        let parsed = parse_query(&script).unwrap();
+        assert_eq!(parsed, query);
+        assert_eq!(print_query(&parsed), script);
+        assert_eq!(
+            parse_expression("(let ((a 1) (b x)) (+ a b))", &query.declarations).unwrap(),
+            parse_expression("(let ((a 1)) (let ((b x)) (+ a b)))", &query.declarations).unwrap()
+        );
+        assert!(parse_expression("(let ((a 1) (b a)) b)", &query.declarations).is_err());
+        assert!(parse_expression("(let ((a 1)) b)", &query.declarations).is_err());
+        assert!(parse_expression("(+ (let ((a 1)) a) a)", &query.declarations).is_err());
+    }
+
+    #[test]
+    fn check_model_values() {
+        let sort = parse_model_sort("(Array Int (_ BitVec 4))", &|_| None).unwrap();
+        assert_eq!(
+            sort,
+            ast::Sort::Array {
+                index: Box::new(ast::Sort::Int),
+                element: Box::new(ast::Sort::BitVector { width: 4 }),
+            }
+        );
+        assert!(parse_model_sort("(Array Int S)", &|_| None).is_err());
+        let value = parse_model_value(
+            "(store (store ((as const (Array Int (_ BitVec 4))) #x0) (- 1) #x5) 2 #b0111)",
+            &sort,
+        )
+        .unwrap();
+        let bv = |value| ast::Value::BitVector { value, width: 4 };
+        assert_eq!(
+            value,
+            ast::Value::Array {
+                entries: vec![
+                    (ast::Value::Int((-1).into()), bv(5)),
+                    (ast::Value::Int(2.into()), bv(7)),
+                ],
+                default: Box::new(bv(0)),
+            }
+        );
+        assert_eq!(value.to_string(), "[-1 := 5bv4, 2 := 7bv4, _ := 0bv4]");
+        let opaque = parse_model_value("(_ as-array k!0)", &sort).unwrap();
+        assert_eq!(opaque, ast::Value::Opaque("(_ as-array k!0)".into()));
+        let item = |name: &str, args: Vec<ast::ModelItemArg>, sort, value| ast::ModelItem {
+            name: name.into(),
+            args,
+            sort,
+            value,
+        };
+        let mut model = ast::Model {
+            items: vec![
+                item("a", Vec::new(), sort.clone(), opaque),
+                item(
+                    "k!0",
+                    vec![ast::ModelItemArg {
+                        name: "x!0".into(),
+                        sort: ast::Sort::Int,
+                    }],
+                    ast::Sort::BitVector { width: 4 },
+                    ast::Value::Opaque("(ite (= x!0 1) #x3 (ite (= 2 x!0) #x4 #x0))".into()),
+                ),
+                item(
+                    "b",
+                    Vec::new(),
+                    sort.clone(),
+                    ast::Value::Opaque("(_ as-array k!1)".into()),
+                ),
+            ],
+        };
+        resolve_as_array_values(&mut model);
+        assert_eq!(
+            model.items[0].value.to_string(),
+            "[1 := 3bv4, 2 := 4bv4, _ := 0bv4]"
+        );
+        assert_eq!(model.items[2].value.to_string(), "(_ as-array k!1)");
+        let list = ast::Sort::Adt {
+            name: "List".into(),
+        };
+        let value = parse_model_value("(Cons (- 1) (Cons 2 Nil))", &list).unwrap();
+        assert_eq!(value.to_string(), "Cons(-1, Cons(2, Nil))");
+        let value =
+            parse_model_value("(Pair T!val!0 ((as const (Array Int Bool)) true))", &list).unwrap();
+        assert_eq!(
+            value,
+            ast::Value::Adt {
+                constructor: "Pair".into(),
+                args: vec![
+                    ast::Value::Uninterpreted {
+                        sort: "T".into(),
+                        value: "T!val!0".into(),
+                    },
+                    ast::Value::Array {
+                        entries: Vec::new(),
+                        default: Box::new(ast::Value::Bool(true)),
+                    },
+                ],
+            }
+        );
+        let real = |numerator: i64, denominator: i64| ast::Value::Real {
+            numerator: numerator.into(),
+            denominator: denominator.into(),
+        };
+        let values = ["2.0", "(- 0.25)", "(/ 2.0 6.0)", "(- (/ 1.0 3.0))"];
+        let values = values
+            .iter()
+            .map(|value| parse_model_value(value, &ast::Sort::Real).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![real(2, 1), real(-1, 4), real(1, 3), real(-1, 3)]
+        );
+        assert_eq!(values[1].to_string(), "-0.25");
+        assert_eq!(values[3].to_string(), "(-1.0/3.0)");
+        let value = parse_model_value("(Cons (- 1.5) (Cons (/ 1.0 3.0) Nil))", &list).unwrap();
+        assert_eq!(value.to_string(), "Cons(-1.5, Cons((1.0/3.0), Nil))");
+        let ints = parse_model_sort("(Seq Int)", &|_| None).unwrap();
+        let value = parse_model_value("(seq.++ (seq.unit 1) (seq.unit (- 2)) (seq.unit 3))", &ints)
+            .unwrap();
+        assert_eq!(value.to_string(), "[1, -2, 3]");
+        let value = parse_model_value("(as seq.empty (Seq Int))", &ints).unwrap();
+        assert_eq!(value, ast::Value::Seq(Vec::new()));
+        let value = parse_model_value("(Box (seq.unit 1))", &list).unwrap();
+        assert_eq!(value.to_string(), "Box([1])");
+        let set = ast::Sort::Set {
+            element: Box::new(ast::Sort::Int),
+        };
+        let value =
+            parse_model_value("(store ((as const (Array Int Bool)) false) 1 true)", &set).unwrap();
+        assert_eq!(value.to_string(), "[1 := true, _ := false]");
+        let f32_sort = parse_model_sort("(_ FloatingPoint 8 24)", &|_| None).unwrap();
+        assert_eq!(f32_sort, parse_model_sort("Float32", &|_| None).unwrap());
+        let value =
+            parse_model_value("(fp #b0 #b01111111 #b10000000000000000000000)", &f32_sort).unwrap();
+        assert_eq!(value.to_string(), "1.5f32");
+        let value = parse_model_value("(_ NaN 8 24)", &f32_sort).unwrap();
+        assert_eq!(value.to_string(), "NaNf32");
+        let f64_sort = parse_model_sort("Float64", &|_| None).unwrap();
+        let value = parse_model_value("(_ -zero 11 53)", &f64_sort).unwrap();
+        assert_eq!(value.to_string(), "-0.0f64");
+        assert!(parse_model_value("(_ +zero 8 24)", &f64_sort).is_err());
+        let value = parse_model_value("(Box (fp #b1 #b10000000 #b00000000000000000000000))", &list)
+            .unwrap();
+        assert_eq!(value.to_string(), "Box(-2.0f32)");
+    }
+
+    #[test]
+    fn check_benchmark_syntax() {
+        let query = parse_query(
+            "; A comment.
+            (set-info :status unsat)
+            (set-logic QF_LIA)
+            (declare-const |a b| Int)
+            (declare-fun y () Int)
+            (assert (and (< 0 |a b| y) (distinct y 3) (= (+ 1 2 3) y)))
+            (check-sat)
+            (exit)",
+        )
+        .unwrap();
+        assert_eq!(query.declarations.variables.len(), 2);
+        let expected = ast::Expression::and(
+            ast::Expression::and(
+                ast::Expression::and(
+                    ast::Expression::less_than(0.into(), var("a b")),
+                    ast::Expression::less_than(var("a b"), var("y")),
+                ),
+                ast::Expression::not_equals(var("y"), 3.into()),
+            ),
+            ast::Expression::equals(
+                ast::Expression::add(ast::Expression::add(1.into(), 2.into()), 3.into()),
+                var("y"),
+            ),
+        );
+        assert_eq!(query.assertions, vec![expected]);
+        assert_eq!(
+            parse_expression("(- y)", &query.declarations).unwrap(),
+            ast::Expression::minus(var("y"))
+        );
+    }
+
+    #[test]
+    fn check_errors() {
+        assert_eq!(
+            parse_query("(declare-fun x () Int)\n(assert (> x y))"),
+            Err(ParseError {
+                line: 2,
+                message: "undeclared symbol `y`".into()
+            })
+        );
+        assert_eq!(
+            parse_query("(assert true"),
+            Err(ParseError {
+                line: 1,
+                message: "unclosed `(`".into()
+            })
+        );
+        assert!(parse_query("(push 1)").is_err());
+        assert!(parse_query("(declare-fun x () Foo)").is_err());
+    }
+}
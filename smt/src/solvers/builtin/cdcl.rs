@@ -0,0 +1,379 @@
+//! A small CDCL SAT solver with a hook for a theory solver.
+//!
+//! The implementation follows the classic MiniSat design: two watched
+//! literals, first-UIP conflict analysis with non-chronological backjumping,
+//! and VSIDS-like variable activities with phase saving.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(super) struct Var(u32);
+
+impl Var {
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(super) struct Lit(u32);
+
+impl Lit {
+    pub(super) fn new(var: Var, positive: bool) -> Self {
+        Lit(var.0 * 2 + if positive { 0 } else { 1 })
+    }
+    pub(super) fn var(self) -> Var {
+        Var(self.0 / 2)
+    }
+    pub(super) fn is_positive(self) -> bool {
+        self.0 & 1 == 0
+    }
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl std::ops::Not for Lit {
+    type Output = Lit;
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SolveResult {
+    Sat,
+    Unsat,
+    /// The conflict limit was reached or the search was interrupted.
+    Unknown,
+}
+
+/// A theory solver that checks the consistency of the assigned literals.
+pub(super) trait Theory {
+    /// Checks whether the literals on `trail` are consistent in the theory.
+    /// If not, returns a conflict clause: a clause whose literals are all
+    /// false under the current assignment.
+    fn check(&mut self, trail: &[Lit]) -> Option<Vec<Lit>>;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct SearchStatistics {
+    pub(super) decisions: u64,
+    pub(super) propagations: u64,
+    pub(super) conflicts: u64,
+    pub(super) theory_conflicts: u64,
+}
+
+#[derive(Default)]
+pub(super) struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    /// Unit clauses added before solving.
+    units: Vec<Lit>,
+    /// For each literal, the clauses in which it is watched.
+    watches: Vec<Vec<usize>>,
+    assignment: Vec<Option<bool>>,
+    level: Vec<usize>,
+    reason: Vec<Option<usize>>,
+    activity: Vec<f64>,
+    activity_increment: f64,
+    saved_phase: Vec<bool>,
+    trail: Vec<Lit>,
+    /// The trail length at the start of each decision level.
+    trail_limits: Vec<usize>,
+    propagation_head: usize,
+    /// Set when an empty clause was added.
+    trivially_unsat: bool,
+    statistics: SearchStatistics,
+}
+
+fn lit_value(assignment: &[Option<bool>], lit: Lit) -> Option<bool> {
+    assignment[lit.var().index()].map(|value| value == lit.is_positive())
+}
+
+impl Solver {
+    pub(super) fn new() -> Self {
+        Self {
+            activity_increment: 1.0,
+            ..Default::default()
+        }
+    }
+    pub(super) fn new_var(&mut self) -> Var {
+        let var = Var(self.assignment.len() as u32);
+        self.assignment.push(None);
+        self.level.push(0);
+        self.reason.push(None);
+        self.activity.push(0.0);
+        self.saved_phase.push(false);
+        self.watches.push(Vec::new());
+        self.watches.push(Vec::new());
+        var
+    }
+    /// The value of `var` in the last satisfying assignment.
+    pub(super) fn value(&self, var: Var) -> Option<bool> {
+        self.assignment[var.index()]
+    }
+    pub(super) fn statistics(&self) -> SearchStatistics {
+        self.statistics
+    }
+    /// The assigned literals in the order of assignment.
+    pub(super) fn trail(&self) -> &[Lit] {
+        &self.trail
+    }
+    /// Adds a clause. Must be called before `solve`.
+    pub(super) fn add_clause(&mut self, mut clause: Vec<Lit>) {
+        clause.sort_unstable();
+        clause.dedup();
+        if clause.windows(2).any(|pair| pair[0] == !pair[1]) {
+            // A tautology.
+            return;
+        }
+        match clause.len() {
+            0 => self.trivially_unsat = true,
+            1 => self.units.push(clause[0]),
+            _ => {
+                self.attach_clause(clause);
+            }
+        }
+    }
+    fn attach_clause(&mut self, clause: Vec<Lit>) -> usize {
+        let id = self.clauses.len();
+        self.watches[clause[0].index()].push(id);
+        self.watches[clause[1].index()].push(id);
+        self.clauses.push(clause);
+        id
+    }
+    fn decision_level(&self) -> usize {
+        self.trail_limits.len()
+    }
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var().index();
+        debug_assert!(self.assignment[var].is_none());
+        self.assignment[var] = Some(lit.is_positive());
+        self.level[var] = self.decision_level();
+        self.reason[var] = reason;
+        self.trail.push(lit);
+    }
+    /// Propagates all enqueued literals and returns a conflicting clause, if
+    /// any.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagation_head < self.trail.len() {
+            let false_lit = !self.trail[self.propagation_head];
+            self.propagation_head += 1;
+            let mut watchers = std::mem::take(&mut self.watches[false_lit.index()]);
+            let mut conflict = None;
+            let mut i = 0;
+            while i < watchers.len() {
+                let id = watchers[i];
+                let clause = &mut self.clauses[id];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+                if lit_value(&self.assignment, first) == Some(true) {
+                    i += 1;
+                    continue;
+                }
+                let assignment = &self.assignment;
+                let replacement =
+                    (2..clause.len()).find(|&k| lit_value(assignment, clause[k]) != Some(false));
+                if let Some(k) = replacement {
+                    clause.swap(1, k);
+                    let new_watch = clause[1];
+                    self.watches[new_watch.index()].push(id);
+                    watchers.swap_remove(i);
+                    continue;
+                }
+                if lit_value(&self.assignment, first) == Some(false) {
+                    conflict = Some(id);
+                    break;
+                }
+                self.enqueue(first, Some(id));
+                self.statistics.propagations += 1;
+                i += 1;
+            }
+            self.watches[false_lit.index()] = watchers;
+            if conflict.is_some() {
+                self.propagation_head = self.trail.len();
+                return conflict;
+            }
+        }
+        None
+    }
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let limit = self.trail_limits[level];
+        for lit in self.trail.drain(limit..) {
+            let var = lit.var().index();
+            self.saved_phase[var] = lit.is_positive();
+            self.assignment[var] = None;
+            self.reason[var] = None;
+        }
+        self.trail_limits.truncate(level);
+        self.propagation_head = self.trail.len();
+    }
+    fn bump_activity(&mut self, var: Var) {
+        let activity = &mut self.activity[var.index()];
+        *activity += self.activity_increment;
+        if *activity > 1e100 {
+            for activity in &mut self.activity {
+                *activity *= 1e-100;
+            }
+            self.activity_increment *= 1e-100;
+        }
+    }
+    /// Computes the first-UIP clause of the conflict and the level to
+    /// backjump to. The asserting literal is the first one in the clause.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut seen = vec![false; self.assignment.len()];
+        let mut learnt = vec![Lit(0)];
+        let mut pending = 0;
+        let mut clause = conflict;
+        let mut implied: Option<Lit> = None;
+        let mut index = self.trail.len();
+        loop {
+            for k in 0..self.clauses[clause].len() {
+                let lit = self.clauses[clause][k];
+                let var = lit.var();
+                if Some(var) == implied.map(Lit::var)
+                    || seen[var.index()]
+                    || self.level[var.index()] == 0
+                {
+                    continue;
+                }
+                seen[var.index()] = true;
+                self.bump_activity(var);
+                if self.level[var.index()] == self.decision_level() {
+                    pending += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+            loop {
+                index -= 1;
+                if seen[self.trail[index].var().index()] {
+                    break;
+                }
+            }
+            let lit = self.trail[index];
+            seen[lit.var().index()] = false;
+            pending -= 1;
+            if pending == 0 {
+                learnt[0] = !lit;
+                break;
+            }
+            clause = self.reason[lit.var().index()].unwrap();
+            implied = Some(lit);
+        }
+        let mut backjump_level = 0;
+        for k in 1..learnt.len() {
+            let level = self.level[learnt[k].var().index()];
+            if level > backjump_level {
+                backjump_level = level;
+                learnt.swap(1, k);
+            }
+        }
+        self.activity_increment *= 1.05;
+        (learnt, backjump_level)
+    }
+    /// Learns from the conflict and backjumps. Returns `false` if the
+    /// conflict cannot be resolved.
+    fn resolve_conflict(&mut self, conflict: usize) -> bool {
+        let conflict_level = self.clauses[conflict]
+            .iter()
+            .map(|lit| self.level[lit.var().index()])
+            .max()
+            .unwrap_or(0);
+        if conflict_level == 0 {
+            return false;
+        }
+        self.backtrack(conflict_level);
+        let (learnt, backjump_level) = self.analyze(conflict);
+        self.backtrack(backjump_level);
+        let asserting = learnt[0];
+        if learnt.len() == 1 {
+            self.enqueue(asserting, None);
+        } else {
+            let id = self.attach_clause(learnt);
+            self.enqueue(asserting, Some(id));
+        }
+        true
+    }
+    fn pick_branching_literal(&self) -> Option<Lit> {
+        let mut best: Option<usize> = None;
+        for var in 0..self.assignment.len() {
+            if self.assignment[var].is_some() {
+                continue;
+            }
+            match best {
+                Some(best) if self.activity[best] >= self.activity[var] => {}
+                _ => best = Some(var),
+            }
+        }
+        best.map(|var| Lit::new(Var(var as u32), self.saved_phase[var]))
+    }
+    /// Searches for an assignment that satisfies all clauses and is
+    /// consistent with `theory`. Gives up after `max_conflicts` conflicts or
+    /// when `interrupted` is set.
+    pub(super) fn solve(
+        &mut self,
+        theory: &mut impl Theory,
+        max_conflicts: Option<u64>,
+        interrupted: &AtomicBool,
+    ) -> SolveResult {
+        if self.trivially_unsat {
+            return SolveResult::Unsat;
+        }
+        for lit in std::mem::take(&mut self.units) {
+            match lit_value(&self.assignment, lit) {
+                Some(false) => return SolveResult::Unsat,
+                Some(true) => {}
+                None => self.enqueue(lit, None),
+            }
+        }
+        loop {
+            if interrupted.load(Ordering::Relaxed) {
+                return SolveResult::Unknown;
+            }
+            let conflict = if let Some(conflict) = self.propagate() {
+                Some(conflict)
+            } else if let Some(mut clause) = theory.check(&self.trail) {
+                self.statistics.theory_conflicts += 1;
+                clause.sort_unstable();
+                clause.dedup();
+                if clause.len() < 2 {
+                    // A unit theory conflict holds at every level.
+                    if clause.is_empty() || self.level[clause[0].var().index()] == 0 {
+                        return SolveResult::Unsat;
+                    }
+                    self.backtrack(0);
+                    self.enqueue(clause[0], None);
+                    continue;
+                }
+                // Watch the two literals assigned last.
+                clause.sort_by_key(|lit| std::cmp::Reverse(self.level[lit.var().index()]));
+                Some(self.attach_clause(clause))
+            } else {
+                None
+            };
+            if let Some(conflict) = conflict {
+                if !self.resolve_conflict(conflict) {
+                    return SolveResult::Unsat;
+                }
+                self.statistics.conflicts += 1;
+                if Some(self.statistics.conflicts) == max_conflicts {
+                    return SolveResult::Unknown;
+                }
+                continue;
+            }
+            if let Some(lit) = self.pick_branching_literal() {
+                self.trail_limits.push(self.trail.len());
+                self.enqueue(lit, None);
+                self.statistics.decisions += 1;
+            } else {
+                return SolveResult::Sat;
+            }
+        }
+    }
+}
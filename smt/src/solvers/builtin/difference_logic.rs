@@ -0,0 +1,130 @@
+//! A theory solver for integer difference logic: conjunctions of constraints
+//! of the form `x - y <= bound`.
+
+use super::cdcl::{Lit, Theory, Var};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(super) struct IntVar(pub(super) usize);
+
+/// The integer variable that is fixed to zero.
+pub(super) const ZERO: IntVar = IntVar(0);
+
+/// The constraint `x - y <= bound`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Atom {
+    pub(super) x: IntVar,
+    pub(super) y: IntVar,
+    pub(super) bound: i128,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    weight: i128,
+    lit: Lit,
+}
+
+pub(super) struct DifferenceLogic {
+    variable_count: usize,
+    atoms: HashMap<Var, Atom>,
+}
+
+impl DifferenceLogic {
+    pub(super) fn new() -> Self {
+        Self {
+            variable_count: 1,
+            atoms: HashMap::new(),
+        }
+    }
+    pub(super) fn new_int_var(&mut self) -> IntVar {
+        self.variable_count += 1;
+        IntVar(self.variable_count - 1)
+    }
+    /// Makes `var` stand for `atom`.
+    pub(super) fn register_atom(&mut self, var: Var, atom: Atom) {
+        self.atoms.insert(var, atom);
+    }
+    /// Each assigned atom `x - y <= bound` is an edge from `y` to `x`. The
+    /// negation `x - y > bound` is `y - x <= -bound - 1` over integers.
+    fn edges(&self, trail: &[Lit]) -> Vec<Edge> {
+        trail
+            .iter()
+            .filter_map(|&lit| {
+                self.atoms.get(&lit.var()).map(|atom| {
+                    if lit.is_positive() {
+                        Edge {
+                            from: atom.y.0,
+                            to: atom.x.0,
+                            weight: atom.bound,
+                            lit,
+                        }
+                    } else {
+                        Edge {
+                            from: atom.x.0,
+                            to: atom.y.0,
+                            weight: -atom.bound - 1,
+                            lit,
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+    /// Runs Bellman-Ford from a virtual source connected to all variables.
+    /// Returns the distances or the edges of a negative cycle.
+    fn shortest_paths(&self, edges: &[Edge]) -> Result<Vec<i128>, Vec<usize>> {
+        let mut distance = vec![0; self.variable_count];
+        let mut predecessor: Vec<Option<usize>> = vec![None; self.variable_count];
+        let mut relaxed = None;
+        for _ in 0..self.variable_count {
+            relaxed = None;
+            for (id, edge) in edges.iter().enumerate() {
+                let candidate = distance[edge.from] + edge.weight;
+                if candidate < distance[edge.to] {
+                    distance[edge.to] = candidate;
+                    predecessor[edge.to] = Some(id);
+                    relaxed = Some(edge.to);
+                }
+            }
+            if relaxed.is_none() {
+                return Ok(distance);
+            }
+        }
+        // The last iteration still relaxed an edge, so there is a negative
+        // cycle reachable backwards from `relaxed`. Walking back
+        // `variable_count` steps is guaranteed to land on the cycle.
+        let mut node = relaxed.unwrap();
+        for _ in 0..self.variable_count {
+            node = edges[predecessor[node].unwrap()].from;
+        }
+        let start = node;
+        let mut cycle = Vec::new();
+        loop {
+            let id = predecessor[node].unwrap();
+            cycle.push(id);
+            node = edges[id].from;
+            if node == start {
+                return Err(cycle);
+            }
+        }
+    }
+    /// Computes values of all integer variables that satisfy the atoms
+    /// assigned on `trail`. Must be called only with a consistent trail.
+    pub(super) fn solution(&self, trail: &[Lit]) -> Vec<i128> {
+        let distance = self
+            .shortest_paths(&self.edges(trail))
+            .unwrap_or_else(|_| unreachable!("inconsistent trail"));
+        let zero = distance[ZERO.0];
+        distance.into_iter().map(|value| value - zero).collect()
+    }
+}
+
+impl Theory for DifferenceLogic {
+    fn check(&mut self, trail: &[Lit]) -> Option<Vec<Lit>> {
+        let edges = self.edges(trail);
+        self.shortest_paths(&edges)
+            .err()
+            .map(|cycle| cycle.into_iter().map(|id| !edges[id].lit).collect())
+    }
+}
@@ -0,0 +1,553 @@
+//! Tseitin encoding of SMT expressions into clauses over Boolean variables
+//! and difference logic atoms.
+
+use super::cdcl::{Lit, Solver, Var};
+use super::difference_logic::{Atom, DifferenceLogic, IntVar, ZERO};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use svirpti_vir::smt as ast;
+
+pub(super) type EncodingResult<T> = Result<T, String>;
+
+/// `sum(coefficient * variable) + constant`.
+#[derive(Debug, Clone, Default)]
+struct LinearTerm {
+    coefficients: BTreeMap<IntVar, i128>,
+    constant: i128,
+}
+
+impl LinearTerm {
+    /// The operations return `None` if a coefficient or the constant
+    /// overflows.
+    fn scale(mut self, factor: i128) -> Option<Self> {
+        for coefficient in self.coefficients.values_mut() {
+            *coefficient = coefficient.checked_mul(factor)?;
+        }
+        self.constant = self.constant.checked_mul(factor)?;
+        Some(self)
+    }
+    fn add(mut self, other: Self) -> Option<Self> {
+        for (variable, coefficient) in other.coefficients {
+            let sum = self.coefficients.entry(variable).or_insert(0);
+            *sum = sum.checked_add(coefficient)?;
+        }
+        self.coefficients.retain(|_, coefficient| *coefficient != 0);
+        self.constant = self.constant.checked_add(other.constant)?;
+        Some(self)
+    }
+    fn sub(self, other: Self) -> Option<Self> {
+        self.add(other.scale(-1)?)
+    }
+    fn offset(mut self, constant: i128) -> Option<Self> {
+        self.constant = self.constant.checked_add(constant)?;
+        Some(self)
+    }
+}
+
+fn overflow(expression: &impl std::fmt::Display) -> String {
+    format!("unsupported integer expression: {} overflows", expression)
+}
+
+/// `-value`, where `value` is a coefficient or the constant of `term`.
+fn negate(term: &LinearTerm, value: i128) -> EncodingResult<i128> {
+    value
+        .checked_neg()
+        .ok_or_else(|| format!("the constraint {:?} <= 0 overflows", term))
+}
+
+/// The encoding of the value of a let binding.
+#[derive(Clone)]
+enum Binding {
+    Bool(Lit),
+    Int(LinearTerm),
+}
+
+fn floor_div(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+pub(super) struct Encoder<'a> {
+    sat: &'a mut Solver,
+    theory: &'a mut DifferenceLogic,
+    bool_variables: &'a HashMap<ast::IdentSymbol, Var>,
+    int_variables: &'a HashMap<ast::IdentSymbol, IntVar>,
+    atoms: HashMap<Atom, Var>,
+    true_lit: Lit,
+    /// The enclosing let bindings, innermost last.
+    bindings: Vec<(ast::IdentSymbol, Binding)>,
+}
+
+impl<'a> Encoder<'a> {
+    pub(super) fn new(
+        sat: &'a mut Solver,
+        theory: &'a mut DifferenceLogic,
+        bool_variables: &'a HashMap<ast::IdentSymbol, Var>,
+        int_variables: &'a HashMap<ast::IdentSymbol, IntVar>,
+    ) -> Self {
+        let true_lit = Lit::new(sat.new_var(), true);
+        sat.add_clause(vec![true_lit]);
+        Self {
+            sat,
+            theory,
+            bool_variables,
+            int_variables,
+            atoms: HashMap::new(),
+            true_lit,
+            bindings: Vec::new(),
+        }
+    }
+    pub(super) fn assert(&mut self, expression: &ast::Expression) -> EncodingResult<()> {
+        let lit = self.encode_bool(expression)?;
+        self.sat.add_clause(vec![lit]);
+        Ok(())
+    }
+    fn fresh_lit(&mut self) -> Lit {
+        Lit::new(self.sat.new_var(), true)
+    }
+    fn constant(&self, value: bool) -> Lit {
+        if value {
+            self.true_lit
+        } else {
+            !self.true_lit
+        }
+    }
+    fn and(&mut self, args: &[Lit]) -> Lit {
+        let result = self.fresh_lit();
+        for &arg in args {
+            self.sat.add_clause(vec![!result, arg]);
+        }
+        let mut clause: Vec<_> = args.iter().map(|&arg| !arg).collect();
+        clause.push(result);
+        self.sat.add_clause(clause);
+        result
+    }
+    fn or(&mut self, args: &[Lit]) -> Lit {
+        let negated: Vec<_> = args.iter().map(|&arg| !arg).collect();
+        !self.and(&negated)
+    }
+    fn iff(&mut self, left: Lit, right: Lit) -> Lit {
+        let result = self.fresh_lit();
+        self.sat.add_clause(vec![!result, !left, right]);
+        self.sat.add_clause(vec![!result, left, !right]);
+        self.sat.add_clause(vec![result, left, right]);
+        self.sat.add_clause(vec![result, !left, !right]);
+        result
+    }
+    fn ite(&mut self, guard: Lit, then_lit: Lit, else_lit: Lit) -> Lit {
+        let result = self.fresh_lit();
+        self.sat.add_clause(vec![!guard, !result, then_lit]);
+        self.sat.add_clause(vec![!guard, result, !then_lit]);
+        self.sat.add_clause(vec![guard, !result, else_lit]);
+        self.sat.add_clause(vec![guard, result, !else_lit]);
+        result
+    }
+    fn atom(&mut self, x: IntVar, y: IntVar, bound: i128) -> Lit {
+        // `y - x <= -bound - 1` is the negation of `x - y <= bound`.
+        let negated = Atom {
+            x: y,
+            y: x,
+            bound: -bound - 1,
+        };
+        if let Some(&var) = self.atoms.get(&negated) {
+            return Lit::new(var, false);
+        }
+        let atom = Atom { x, y, bound };
+        if let Some(&var) = self.atoms.get(&atom) {
+            return Lit::new(var, true);
+        }
+        let var = self.sat.new_var();
+        self.atoms.insert(atom, var);
+        self.theory.register_atom(var, atom);
+        Lit::new(var, true)
+    }
+    /// Encodes `term <= 0`.
+    fn less_or_equal_zero(&mut self, term: LinearTerm) -> EncodingResult<Lit> {
+        let mut coefficients = term.coefficients.iter();
+        let lit = match (
+            coefficients.next(),
+            coefficients.next(),
+            coefficients.next(),
+        ) {
+            (None, _, _) => self.constant(term.constant <= 0),
+            (Some((&x, &a)), None, _) => {
+                let bound = negate(&term, term.constant)?;
+                if a > 0 {
+                    // a*x + k <= 0  <=>  x <= floor(-k / a)
+                    self.atom(x, ZERO, floor_div(bound, a))
+                } else {
+                    // a*x + k <= 0  <=>  -x <= floor(-k / -a)
+                    self.atom(ZERO, x, floor_div(bound, negate(&term, a)?))
+                }
+            }
+            (Some((&x, &a)), Some((&y, &b)), None) if Some(a) == b.checked_neg() => {
+                let bound = negate(&term, term.constant)?;
+                if a > 0 {
+                    self.atom(x, y, floor_div(bound, a))
+                } else {
+                    self.atom(y, x, floor_div(bound, b))
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "the constraint {:?} <= 0 is not in difference logic",
+                    term
+                ))
+            }
+        };
+        Ok(lit)
+    }
+    fn binding(&self, name: &ast::IdentSymbol) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|(variable, _)| variable == name)
+            .map(|(_, binding)| binding)
+    }
+    fn is_bool(&mut self, expression: &ast::Expression) -> EncodingResult<bool> {
+        let is_bool = match expression {
+            ast::Expression::Variable(ast::Variable { name }) if self.binding(name).is_some() => {
+                matches!(self.binding(name), Some(Binding::Bool(_)))
+            }
+            ast::Expression::Variable(ast::Variable { name })
+            | ast::Expression::FunctionApplication(ast::FunctionApplication {
+                function: name,
+                ..
+            }) => {
+                if self.bool_variables.contains_key(name) {
+                    true
+                } else if self.int_variables.contains_key(name) {
+                    false
+                } else {
+                    return Err(format!("unsupported symbol: {}", name));
+                }
+            }
+            ast::Expression::Constant(constant) => matches!(constant, ast::Constant::Bool(_)),
+            ast::Expression::UnaryOperation(operation) => matches!(
+                operation.kind,
+                ast::UnaryOperationKind::Not | ast::UnaryOperationKind::IsInt
+            ),
+            ast::Expression::BinaryOperation(operation) => !matches!(
+                operation.kind,
+                ast::BinaryOperationKind::Add
+                    | ast::BinaryOperationKind::Sub
+                    | ast::BinaryOperationKind::Mul
+                    | ast::BinaryOperationKind::Div
+                    | ast::BinaryOperationKind::Mod
+                    | ast::BinaryOperationKind::BitAnd
+                    | ast::BinaryOperationKind::BitOr
+                    | ast::BinaryOperationKind::BitXor
+                    | ast::BinaryOperationKind::Shl
+                    | ast::BinaryOperationKind::LShr
+                    | ast::BinaryOperationKind::AShr
+                    | ast::BinaryOperationKind::SDiv
+                    | ast::BinaryOperationKind::SRem
+                    | ast::BinaryOperationKind::Concat
+            ),
+            ast::Expression::BitVectorConversion(_) => false,
+            ast::Expression::ArraySelect(_)
+            | ast::Expression::ArrayStore(_)
+            | ast::Expression::ConstantArray(_) => {
+                return Err(format!("arrays are not supported: {}", expression));
+            }
+            ast::Expression::AdtConstructor(_)
+            | ast::Expression::AdtFieldAccess(_)
+            | ast::Expression::AdtTester(_)
+            | ast::Expression::AdtMatch(_) => {
+                return Err(format!("datatypes are not supported: {}", expression));
+            }
+            ast::Expression::FloatOperation(_) | ast::Expression::FloatConversion(_) => {
+                return Err(format!(
+                    "floating-point numbers are not supported: {}",
+                    expression
+                ));
+            }
+            ast::Expression::SeqOperation(_)
+            | ast::Expression::EmptySeq(_)
+            | ast::Expression::SetOperation(_)
+            | ast::Expression::EmptySet(_) => {
+                return Err(format!(
+                    "sequences and sets are not supported: {}",
+                    expression
+                ));
+            }
+            ast::Expression::Conditional(conditional) => self.is_bool(&conditional.then_expr)?,
+            ast::Expression::Let(binding) => {
+                // Only the kind of the binding matters here.
+                let placeholder = if self.is_bool(&binding.value)? {
+                    Binding::Bool(self.true_lit)
+                } else {
+                    Binding::Int(LinearTerm::default())
+                };
+                self.bindings.push((binding.variable.clone(), placeholder));
+                let is_bool = self.is_bool(&binding.body);
+                self.bindings.pop();
+                is_bool?
+            }
+            ast::Expression::Quantifier(_) | ast::Expression::LabelledExpression(_) => true,
+        };
+        Ok(is_bool)
+    }
+    fn encode_bool(&mut self, expression: &ast::Expression) -> EncodingResult<Lit> {
+        let lit = match expression {
+            ast::Expression::Variable(ast::Variable { name }) if self.binding(name).is_some() => {
+                match self.binding(name) {
+                    Some(Binding::Bool(lit)) => *lit,
+                    _ => return Err(format!("{} is not a Boolean symbol", name)),
+                }
+            }
+            ast::Expression::Variable(ast::Variable { name })
+            | ast::Expression::FunctionApplication(ast::FunctionApplication {
+                function: name,
+                ..
+            }) => {
+                if let ast::Expression::FunctionApplication(application) = expression {
+                    if !application.args.is_empty() {
+                        return Err(format!("unsupported function application: {}", expression));
+                    }
+                }
+                if let Some(&var) = self.bool_variables.get(name) {
+                    Lit::new(var, true)
+                } else {
+                    return Err(format!("{} is not a Boolean symbol", name));
+                }
+            }
+            ast::Expression::Constant(ast::Constant::Bool(value)) => self.constant(*value),
+            ast::Expression::Constant(
+                ast::Constant::Int(_)
+                | ast::Constant::Real { .. }
+                | ast::Constant::BitVector { .. }
+                | ast::Constant::Float { .. },
+            )
+            | ast::Expression::BitVectorConversion(_) => {
+                return Err(format!("{} is not a Boolean expression", expression));
+            }
+            ast::Expression::UnaryOperation(operation) => match operation.kind {
+                ast::UnaryOperationKind::Not => !self.encode_bool(&operation.arg)?,
+                ast::UnaryOperationKind::Minus
+                | ast::UnaryOperationKind::ToReal
+                | ast::UnaryOperationKind::ToInt => {
+                    return Err(format!("{} is not a Boolean expression", expression));
+                }
+                ast::UnaryOperationKind::IsInt => {
+                    return Err(format!("unsupported real expression: {}", expression));
+                }
+            },
+            ast::Expression::BinaryOperation(operation) => self.encode_binary(operation)?,
+            ast::Expression::Conditional(conditional) => {
+                let guard = self.encode_bool(&conditional.guard)?;
+                let then_lit = self.encode_bool(&conditional.then_expr)?;
+                let else_lit = self.encode_bool(&conditional.else_expr)?;
+                self.ite(guard, then_lit, else_lit)
+            }
+            ast::Expression::ArraySelect(_)
+            | ast::Expression::ArrayStore(_)
+            | ast::Expression::ConstantArray(_) => {
+                return Err(format!("arrays are not supported: {}", expression));
+            }
+            ast::Expression::AdtConstructor(_)
+            | ast::Expression::AdtFieldAccess(_)
+            | ast::Expression::AdtTester(_)
+            | ast::Expression::AdtMatch(_) => {
+                return Err(format!("datatypes are not supported: {}", expression));
+            }
+            ast::Expression::FloatOperation(_) | ast::Expression::FloatConversion(_) => {
+                return Err(format!(
+                    "floating-point numbers are not supported: {}",
+                    expression
+                ));
+            }
+            ast::Expression::SeqOperation(_)
+            | ast::Expression::EmptySeq(_)
+            | ast::Expression::SetOperation(_)
+            | ast::Expression::EmptySet(_) => {
+                return Err(format!(
+                    "sequences and sets are not supported: {}",
+                    expression
+                ));
+            }
+            ast::Expression::Quantifier(_) => {
+                return Err(format!("quantifiers are not supported: {}", expression));
+            }
+            ast::Expression::Let(binding) => {
+                self.bind(binding)?;
+                let lit = self.encode_bool(&binding.body);
+                self.bindings.pop();
+                lit?
+            }
+            ast::Expression::LabelledExpression(labelled) => {
+                let label = if let Some(&var) = self.bool_variables.get(&labelled.name) {
+                    Lit::new(var, true)
+                } else {
+                    return Err(format!("undeclared label: {}", labelled.name));
+                };
+                let inner = self.encode_bool(&labelled.expression)?;
+                // Mirrors how labelled expressions are printed for Z3.
+                match labelled.positivity {
+                    ast::LabelPositivity::Positive => self.and(&[label, inner]),
+                    ast::LabelPositivity::Negative => self.or(&[label, inner]),
+                }
+            }
+        };
+        Ok(lit)
+    }
+    /// Encodes the value of `binding` once and makes it available to its body.
+    fn bind(&mut self, binding: &ast::Let) -> EncodingResult<()> {
+        let value = if self.is_bool(&binding.value)? {
+            Binding::Bool(self.encode_bool(&binding.value)?)
+        } else {
+            Binding::Int(self.encode_int(&binding.value)?)
+        };
+        self.bindings.push((binding.variable.clone(), value));
+        Ok(())
+    }
+    fn encode_binary(&mut self, operation: &ast::BinaryOperation) -> EncodingResult<Lit> {
+        use ast::BinaryOperationKind::*;
+        let lit = match operation.kind {
+            And | Or | Implies => {
+                let left = self.encode_bool(&operation.left)?;
+                let right = self.encode_bool(&operation.right)?;
+                match operation.kind {
+                    And => self.and(&[left, right]),
+                    Or => self.or(&[left, right]),
+                    _ => self.or(&[!left, right]),
+                }
+            }
+            EqCmp | NeCmp if self.is_bool(&operation.left)? => {
+                let left = self.encode_bool(&operation.left)?;
+                let right = self.encode_bool(&operation.right)?;
+                let equal = self.iff(left, right);
+                if operation.kind == EqCmp {
+                    equal
+                } else {
+                    !equal
+                }
+            }
+            EqCmp | NeCmp | GtCmp | GeCmp | LtCmp | LeCmp => {
+                let left = self.encode_int(&operation.left)?;
+                let right = self.encode_int(&operation.right)?;
+                self.compare(operation.kind, left, right)?
+            }
+            SGtCmp | SGeCmp | SLtCmp | SLeCmp => {
+                return Err(format!("bit-vectors are not supported: {}", operation));
+            }
+            Add | Sub | Mul | Div | Mod | BitAnd | BitOr | BitXor | Shl | LShr | AShr | SDiv
+            | SRem | Concat => {
+                return Err(format!("{} is not a Boolean expression", operation));
+            }
+        };
+        Ok(lit)
+    }
+    fn compare(
+        &mut self,
+        kind: ast::BinaryOperationKind,
+        left: LinearTerm,
+        right: LinearTerm,
+    ) -> EncodingResult<Lit> {
+        use ast::BinaryOperationKind::*;
+        let overflows = || format!("the comparison of {:?} and {:?} overflows", left, right);
+        let difference = left.clone().sub(right.clone()).ok_or_else(overflows)?;
+        let negated = right.clone().sub(left.clone()).ok_or_else(overflows)?;
+        let lit = match kind {
+            LeCmp => self.less_or_equal_zero(difference)?,
+            LtCmp => self.less_or_equal_zero(difference.offset(1).ok_or_else(overflows)?)?,
+            GeCmp => self.less_or_equal_zero(negated)?,
+            GtCmp => self.less_or_equal_zero(negated.offset(1).ok_or_else(overflows)?)?,
+            EqCmp | NeCmp => {
+                let lower = self.less_or_equal_zero(difference)?;
+                let upper = self.less_or_equal_zero(negated)?;
+                let equal = self.and(&[lower, upper]);
+                if kind == EqCmp {
+                    equal
+                } else {
+                    !equal
+                }
+            }
+            _ => unreachable!("{:?} is not a comparison", kind),
+        };
+        Ok(lit)
+    }
+    fn encode_int(&mut self, expression: &ast::Expression) -> EncodingResult<LinearTerm> {
+        let term = match expression {
+            ast::Expression::Variable(ast::Variable { name }) if self.binding(name).is_some() => {
+                match self.binding(name) {
+                    Some(Binding::Int(term)) => term.clone(),
+                    _ => return Err(format!("{} is not an integer symbol", name)),
+                }
+            }
+            ast::Expression::Variable(ast::Variable { name })
+            | ast::Expression::FunctionApplication(ast::FunctionApplication {
+                function: name,
+                ..
+            }) => {
+                if let ast::Expression::FunctionApplication(application) = expression {
+                    if !application.args.is_empty() {
+                        return Err(format!("unsupported function application: {}", expression));
+                    }
+                }
+                if let Some(&var) = self.int_variables.get(name) {
+                    let mut term = LinearTerm::default();
+                    term.coefficients.insert(var, 1);
+                    term
+                } else {
+                    return Err(format!("{} is not an integer symbol", name));
+                }
+            }
+            ast::Expression::Constant(ast::Constant::Int(value)) => LinearTerm {
+                coefficients: BTreeMap::new(),
+                constant: i128::try_from(value)
+                    .map_err(|_| format!("integer {} is out of range", value))?,
+            },
+            ast::Expression::UnaryOperation(operation)
+                if operation.kind == ast::UnaryOperationKind::Minus =>
+            {
+                let term = self.encode_int(&operation.arg)?;
+                term.scale(-1).ok_or_else(|| overflow(expression))?
+            }
+            ast::Expression::BinaryOperation(operation) => {
+                let left = self.encode_int(&operation.left)?;
+                let right = self.encode_int(&operation.right)?;
+                let term = match operation.kind {
+                    ast::BinaryOperationKind::Add => left.add(right),
+                    ast::BinaryOperationKind::Sub => left.sub(right),
+                    ast::BinaryOperationKind::Mul if left.coefficients.is_empty() => {
+                        right.scale(left.constant)
+                    }
+                    ast::BinaryOperationKind::Mul if right.coefficients.is_empty() => {
+                        left.scale(right.constant)
+                    }
+                    _ => return Err(format!("unsupported integer expression: {}", operation)),
+                };
+                term.ok_or_else(|| overflow(operation))?
+            }
+            ast::Expression::Conditional(conditional) => {
+                // Name the result with a fresh variable constrained by the
+                // guard.
+                let guard = self.encode_bool(&conditional.guard)?;
+                let then_term = self.encode_int(&conditional.then_expr)?;
+                let else_term = self.encode_int(&conditional.else_expr)?;
+                let mut result = LinearTerm::default();
+                result.coefficients.insert(self.theory.new_int_var(), 1);
+                let then_equal =
+                    self.compare(ast::BinaryOperationKind::EqCmp, result.clone(), then_term)?;
+                let else_equal =
+                    self.compare(ast::BinaryOperationKind::EqCmp, result.clone(), else_term)?;
+                self.sat.add_clause(vec![!guard, then_equal]);
+                self.sat.add_clause(vec![guard, else_equal]);
+                result
+            }
+            ast::Expression::Let(binding) => {
+                self.bind(binding)?;
+                let term = self.encode_int(&binding.body);
+                self.bindings.pop();
+                term?
+            }
+            _ => return Err(format!("unsupported integer expression: {}", expression)),
+        };
+        Ok(term)
+    }
+}
@@ -0,0 +1,770 @@
+//! An in-process solver for quantifier-free formulas over Booleans and
+//! integer difference logic.
+//!
+//! Every `check_sat` encodes all asserted expressions from scratch and decides
+//! them with a small CDCL core extended with a difference logic theory solver.
+//! Expressions outside of the supported fragment (uninterpreted functions,
+//! quantifiers, non-difference arithmetic, …) are reported as errors.
+
+use super::{Logic, SatResult, SmtSolver, SmtSolverError, SmtSolverResult, Statistics};
+use crate::cancellation::Interrupter;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use svirpti_vir::smt as ast;
+
+mod cdcl;
+mod difference_logic;
+mod encoder;
+
+#[derive(Default)]
+pub struct Configuration {
+    /// The number of conflicts after which `check_sat` gives up and returns
+    /// `SatResult::Unknown`. `None` means no limit.
+    pub max_conflicts: Option<u64>,
+}
+
+struct Solution {
+    model: ast::Model,
+    labels: Vec<ast::LabelSymbol>,
+}
+
+pub struct BuiltinSmtSolver {
+    conf: Configuration,
+    /// Declared Boolean and integer constants. Labels are Boolean constants.
+    constants: Vec<(ast::IdentSymbol, ast::Sort)>,
+    assertions: Vec<ast::Expression>,
+    /// The number of constants and assertions at each `push`.
+    scopes: Vec<(usize, usize)>,
+    solution: Option<Solution>,
+    statistics: Statistics,
+    /// Set from another thread to stop the search.
+    interrupted: Arc<AtomicBool>,
+}
+
+fn unsupported(message: String) -> SmtSolverError {
+    SmtSolverError::BuiltinSolverError { message }
+}
+
+impl BuiltinSmtSolver {
+    fn declare_constant(&mut self, name: &ast::IdentSymbol, sort: &ast::Sort) {
+        self.solution = None;
+        self.constants.push((name.clone(), sort.clone()));
+    }
+    fn solve(&mut self) -> SmtSolverResult<(SatResult, Option<Solution>)> {
+        let mut sat = cdcl::Solver::new();
+        let mut theory = difference_logic::DifferenceLogic::new();
+        let mut bool_variables = HashMap::new();
+        let mut int_variables = HashMap::new();
+        for (name, sort) in &self.constants {
+            match sort {
+                ast::Sort::Bool => {
+                    bool_variables.insert(name.clone(), sat.new_var());
+                }
+                ast::Sort::Int => {
+                    int_variables.insert(name.clone(), theory.new_int_var());
+                }
+                // Constants of other sorts are fine as long as they are not
+                // used.
+                _ => {}
+            }
+        }
+        let mut encoder =
+            encoder::Encoder::new(&mut sat, &mut theory, &bool_variables, &int_variables);
+        for assertion in &self.assertions {
+            encoder.assert(assertion).map_err(unsupported)?;
+        }
+        let result = sat.solve(&mut theory, self.conf.max_conflicts, &self.interrupted);
+        let search = sat.statistics();
+        self.statistics = Statistics::default();
+        for (key, value) in &[
+            ("decisions", search.decisions),
+            ("propagations", search.propagations),
+            ("conflicts", search.conflicts),
+            ("theory-conflicts", search.theory_conflicts),
+        ] {
+            self.statistics.values.insert((*key).into(), *value as f64);
+        }
+        match result {
+            cdcl::SolveResult::Sat => {}
+            cdcl::SolveResult::Unsat => return Ok((SatResult::Unsat, None)),
+            cdcl::SolveResult::Unknown => return Ok((SatResult::Unknown, None)),
+        }
+        let int_values = theory.solution(sat.trail());
+        let mut evaluator = Evaluator {
+            bool_values: HashMap::new(),
+            int_values: HashMap::new(),
+            let_values: Vec::new(),
+        };
+        let mut model = ast::Model { items: Vec::new() };
+        for (name, sort) in &self.constants {
+            let value = match sort {
+                ast::Sort::Bool => {
+                    let value = sat.value(bool_variables[name]).unwrap_or(false);
+                    evaluator.bool_values.insert(name.clone(), value);
+                    ast::Value::Bool(value)
+                }
+                ast::Sort::Int => {
+                    let value = int_values[int_variables[name].0];
+                    evaluator.int_values.insert(name.clone(), value);
+                    ast::Value::Int(value.into())
+                }
+                _ => continue,
+            };
+            model.items.push(ast::ModelItem {
+                name: name.clone(),
+                args: Vec::new(),
+                sort: sort.clone(),
+                value,
+            });
+        }
+        let mut labels = Vec::new();
+        for assertion in &self.assertions {
+            evaluator
+                .collect_labels(assertion, &mut labels)
+                .ok_or_else(|| unsupported(format!("evaluating {} overflows", assertion)))?;
+        }
+        Ok((SatResult::Sat, Some(Solution { model, labels })))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum EvaluatedValue {
+    Bool(bool),
+    Int(i128),
+}
+
+/// Evaluates expressions in a model of the supported fragment. The evaluation
+/// returns `None` if an integer operation overflows.
+struct Evaluator {
+    bool_values: HashMap<ast::IdentSymbol, bool>,
+    int_values: HashMap<ast::IdentSymbol, i128>,
+    /// The values of the enclosing let bindings, innermost last.
+    let_values: Vec<(ast::IdentSymbol, EvaluatedValue)>,
+}
+
+impl Evaluator {
+    fn eval_bool(&mut self, expression: &ast::Expression) -> Option<bool> {
+        match self.eval(expression)? {
+            EvaluatedValue::Bool(value) => Some(value),
+            EvaluatedValue::Int(_) => unreachable!("{} is not a Boolean", expression),
+        }
+    }
+    fn eval_int(&mut self, expression: &ast::Expression) -> Option<i128> {
+        match self.eval(expression)? {
+            EvaluatedValue::Int(value) => Some(value),
+            EvaluatedValue::Bool(_) => unreachable!("{} is not an integer", expression),
+        }
+    }
+    fn eval(&mut self, expression: &ast::Expression) -> Option<EvaluatedValue> {
+        use ast::BinaryOperationKind::*;
+        let value = match expression {
+            ast::Expression::Variable(ast::Variable { name })
+            | ast::Expression::FunctionApplication(ast::FunctionApplication {
+                function: name,
+                ..
+            }) => {
+                if let Some((_, value)) = self.let_values.iter().rev().find(|(n, _)| n == name) {
+                    *value
+                } else if let Some(&value) = self.bool_values.get(name) {
+                    EvaluatedValue::Bool(value)
+                } else {
+                    EvaluatedValue::Int(self.int_values[name])
+                }
+            }
+            ast::Expression::Constant(ast::Constant::Bool(value)) => EvaluatedValue::Bool(*value),
+            ast::Expression::Constant(ast::Constant::Int(value)) => {
+                // The encoder rejects constants that do not fit.
+                EvaluatedValue::Int(i128::try_from(value).unwrap())
+            }
+            ast::Expression::UnaryOperation(operation) => match operation.kind {
+                ast::UnaryOperationKind::Not => {
+                    EvaluatedValue::Bool(!self.eval_bool(&operation.arg)?)
+                }
+                ast::UnaryOperationKind::Minus => {
+                    EvaluatedValue::Int(self.eval_int(&operation.arg)?.checked_neg()?)
+                }
+                ast::UnaryOperationKind::ToReal
+                | ast::UnaryOperationKind::ToInt
+                | ast::UnaryOperationKind::IsInt => unreachable!("reals are not supported"),
+            },
+            ast::Expression::BinaryOperation(operation) => {
+                let left = self.eval(&operation.left)?;
+                let right = self.eval(&operation.right)?;
+                match (left, right) {
+                    (EvaluatedValue::Bool(left), EvaluatedValue::Bool(right)) => {
+                        EvaluatedValue::Bool(match operation.kind {
+                            EqCmp => left == right,
+                            NeCmp => left != right,
+                            And => left && right,
+                            Or => left || right,
+                            Implies => !left || right,
+                            kind => unreachable!("{:?} on Booleans", kind),
+                        })
+                    }
+                    (EvaluatedValue::Int(left), EvaluatedValue::Int(right)) => {
+                        match operation.kind {
+                            EqCmp => EvaluatedValue::Bool(left == right),
+                            NeCmp => EvaluatedValue::Bool(left != right),
+                            GtCmp => EvaluatedValue::Bool(left > right),
+                            GeCmp => EvaluatedValue::Bool(left >= right),
+                            LtCmp => EvaluatedValue::Bool(left < right),
+                            LeCmp => EvaluatedValue::Bool(left <= right),
+                            Add => EvaluatedValue::Int(left.checked_add(right)?),
+                            Sub => EvaluatedValue::Int(left.checked_sub(right)?),
+                            Mul => EvaluatedValue::Int(left.checked_mul(right)?),
+                            kind => unreachable!("{:?} on integers", kind),
+                        }
+                    }
+                    _ => unreachable!("ill-sorted expression: {}", operation),
+                }
+            }
+            ast::Expression::Conditional(conditional) => {
+                if self.eval_bool(&conditional.guard)? {
+                    self.eval(&conditional.then_expr)?
+                } else {
+                    self.eval(&conditional.else_expr)?
+                }
+            }
+            ast::Expression::Constant(ast::Constant::Real { .. }) => {
+                unreachable!("reals are not supported")
+            }
+            ast::Expression::Constant(ast::Constant::BitVector { .. })
+            | ast::Expression::BitVectorConversion(_) => {
+                unreachable!("bit-vectors are not supported")
+            }
+            ast::Expression::Constant(ast::Constant::Float { .. })
+            | ast::Expression::FloatOperation(_)
+            | ast::Expression::FloatConversion(_) => {
+                unreachable!("floating-point numbers are not supported")
+            }
+            ast::Expression::ArraySelect(_)
+            | ast::Expression::ArrayStore(_)
+            | ast::Expression::ConstantArray(_) => unreachable!("arrays are not supported"),
+            ast::Expression::SeqOperation(_)
+            | ast::Expression::EmptySeq(_)
+            | ast::Expression::SetOperation(_)
+            | ast::Expression::EmptySet(_) => unreachable!("sequences and sets are not supported"),
+            ast::Expression::AdtConstructor(_)
+            | ast::Expression::AdtFieldAccess(_)
+            | ast::Expression::AdtTester(_)
+            | ast::Expression::AdtMatch(_) => unreachable!("datatypes are not supported"),
+            ast::Expression::Quantifier(_) => unreachable!("quantifiers are not supported"),
+            ast::Expression::Let(binding) => {
+                let value = self.eval(&binding.value)?;
+                self.let_values.push((binding.variable.clone(), value));
+                let result = self.eval(&binding.body);
+                self.let_values.pop();
+                result?
+            }
+            ast::Expression::LabelledExpression(labelled) => {
+                let label = self.bool_values[&labelled.name];
+                let value = self.eval_bool(&labelled.expression)?;
+                EvaluatedValue::Bool(match labelled.positivity {
+                    ast::LabelPositivity::Positive => label && value,
+                    ast::LabelPositivity::Negative => label || value,
+                })
+            }
+        };
+        Some(value)
+    }
+    /// Collects the labels that Z3 would report: a positive label whose
+    /// expression is true or a negative label whose expression is false, if
+    /// the labelled expression is relevant for the value of the assertion.
+    /// When a Boolean operation is decided by one of its arguments, only that
+    /// argument is relevant.
+    fn collect_labels(
+        &mut self,
+        expression: &ast::Expression,
+        labels: &mut Vec<ast::LabelSymbol>,
+    ) -> Option<()> {
+        use ast::BinaryOperationKind::*;
+        match expression {
+            ast::Expression::LabelledExpression(labelled) => {
+                let value = self.eval_bool(expression)?;
+                let reported = match labelled.positivity {
+                    ast::LabelPositivity::Positive => value,
+                    ast::LabelPositivity::Negative => !value,
+                };
+                if reported && !labels.contains(&labelled.name) {
+                    labels.push(labelled.name.clone());
+                }
+                self.collect_labels(&labelled.expression, labels)?;
+            }
+            ast::Expression::UnaryOperation(operation) => {
+                self.collect_labels(&operation.arg, labels)?;
+            }
+            ast::Expression::BinaryOperation(operation)
+                if matches!(operation.kind, And | Or | Implies) =>
+            {
+                let left = self.eval_bool(&operation.left)?;
+                let right = self.eval_bool(&operation.right)?;
+                let (left_decides, right_decides) = match operation.kind {
+                    And => (!left, !right),
+                    Or => (left, right),
+                    _ => (!left, right),
+                };
+                if left_decides {
+                    self.collect_labels(&operation.left, labels)?;
+                } else if right_decides {
+                    self.collect_labels(&operation.right, labels)?;
+                } else {
+                    self.collect_labels(&operation.left, labels)?;
+                    self.collect_labels(&operation.right, labels)?;
+                }
+            }
+            ast::Expression::BinaryOperation(operation) => {
+                self.collect_labels(&operation.left, labels)?;
+                self.collect_labels(&operation.right, labels)?;
+            }
+            ast::Expression::Conditional(conditional) => {
+                self.collect_labels(&conditional.guard, labels)?;
+                if self.eval_bool(&conditional.guard)? {
+                    self.collect_labels(&conditional.then_expr, labels)?;
+                } else {
+                    self.collect_labels(&conditional.else_expr, labels)?;
+                }
+            }
+            ast::Expression::Let(binding) => {
+                self.collect_labels(&binding.value, labels)?;
+                let value = self.eval(&binding.value)?;
+                self.let_values.push((binding.variable.clone(), value));
+                let result = self.collect_labels(&binding.body, labels);
+                self.let_values.pop();
+                result?;
+            }
+            _ => {}
+        }
+        Some(())
+    }
+}
+
+impl BuiltinSmtSolver {
+    pub fn new(conf: Configuration) -> Self {
+        Self {
+            conf,
+            constants: Vec::new(),
+            assertions: Vec::new(),
+            scopes: Vec::new(),
+            solution: None,
+            statistics: Statistics::default(),
+            interrupted: Arc::new(AtomicBool::new(false)),
+        }
+    }
+    fn get_solution(&self) -> SmtSolverResult<&Solution> {
+        self.solution
+            .as_ref()
+            .ok_or_else(|| unsupported("no model is available".into()))
+    }
+}
+
+impl SmtSolver for BuiltinSmtSolver {
+    type Conf = Configuration;
+    type Error = SmtSolverError;
+    fn default() -> SmtSolverResult<Self> {
+        Ok(Self::new(Default::default()))
+    }
+    fn new(conf: Configuration) -> SmtSolverResult<Self> {
+        Ok(Self::new(conf))
+    }
+    fn declare_sort(
+        &mut self,
+        _sort: &ast::UninterpretedSortDeclaration,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        // Sorts are checked only when used.
+        Ok(())
+    }
+    fn declare_adts(
+        &mut self,
+        _adts: &[ast::AdtDeclaration],
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        Err(unsupported("datatypes are not supported".into()))
+    }
+    fn declare_function(
+        &mut self,
+        function: &ast::FunctionDeclaration,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        if function.body.is_some() {
+            return Err(unsupported("function definitions are not supported".into()));
+        }
+        // Functions with parameters are rejected when used.
+        if function.parameters.is_empty() {
+            self.declare_constant(&function.name, &function.return_sort);
+        }
+        Ok(())
+    }
+    fn define_recursive_functions(
+        &mut self,
+        _functions: &[ast::FunctionDeclaration],
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        Err(unsupported("function definitions are not supported".into()))
+    }
+    fn declare_label(
+        &mut self,
+        label: &ast::LabelDeclaration,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.declare_constant(&label.name, &ast::Sort::Bool);
+        Ok(())
+    }
+    fn declare_variable(
+        &mut self,
+        variable: &ast::VariableDeclaration,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.declare_constant(&variable.name, &variable.sort);
+        Ok(())
+    }
+    fn reset(&mut self) -> SmtSolverResult {
+        let conf = std::mem::take(&mut self.conf);
+        *self = Self::new(conf);
+        Ok(())
+    }
+    /// The built-in solver has no options.
+    fn set_option(&mut self, _option: &str, _value: &str) -> SmtSolverResult {
+        Ok(())
+    }
+    /// Unsupported constructs are rejected regardless of the logic.
+    fn set_logic(&mut self, _logic: Logic) -> SmtSolverResult {
+        Ok(())
+    }
+    fn push(&mut self) -> SmtSolverResult {
+        self.solution = None;
+        self.scopes
+            .push((self.constants.len(), self.assertions.len()));
+        Ok(())
+    }
+    fn pop(&mut self) -> SmtSolverResult {
+        self.solution = None;
+        let (constants, assertions) = self
+            .scopes
+            .pop()
+            .ok_or_else(|| unsupported("pop without a matching push".into()))?;
+        self.constants.truncate(constants);
+        self.assertions.truncate(assertions);
+        Ok(())
+    }
+    fn assert(
+        &mut self,
+        assertion: &ast::Expression,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solution = None;
+        self.assertions.push(assertion.clone());
+        Ok(())
+    }
+    fn check_sat(&mut self) -> SmtSolverResult<SatResult> {
+        let solved = self.solve();
+        // An interruption stops only one search.
+        self.interrupted.store(false, Ordering::Relaxed);
+        let (result, solution) = solved?;
+        self.solution = solution;
+        Ok(result)
+    }
+    fn get_labels(
+        &mut self,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult<Vec<ast::LabelSymbol>> {
+        Ok(self.get_solution()?.labels.clone())
+    }
+    fn get_model(&mut self, _context: &impl ast::Context) -> SmtSolverResult<ast::Model> {
+        Ok(self.get_solution()?.model.clone())
+    }
+    fn get_statistics(&mut self) -> SmtSolverResult<Statistics> {
+        Ok(self.statistics.clone())
+    }
+    /// Makes the running `check_sat` call, or the next one if none is running,
+    /// return `SatResult::Unknown`.
+    fn interrupter(&self) -> Option<Interrupter> {
+        let interrupted = self.interrupted.clone();
+        Some(Box::new(move || interrupted.store(true, Ordering::Relaxed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ast, BuiltinSmtSolver};
+    use crate::solvers::{test_context::StringContext, SatResult, SmtSolver, SmtSolverError};
+    use svirpti_vir::smt::{
+        BinaryOperationHelpers, LabelledExpressionHelpers, LetHelpers, QuantifierHelpers,
+        UnaryOperationHelpers, VariableHelpers,
+    };
+
+    fn var(name: &str) -> ast::Expression {
+        ast::Expression::variable(name.into())
+    }
+
+    fn declare(
+        solver: &mut BuiltinSmtSolver,
+        context: &StringContext,
+        name: &str,
+        sort: ast::Sort,
+    ) {
+        let variable = ast::VariableDeclaration {
+            name: name.into(),
+            sort,
+        };
+        solver.declare_variable(&variable, context).unwrap();
+    }
+
+    fn model_value(model: &ast::Model, name: &str) -> ast::Value {
+        let name: ast::IdentSymbol = name.into();
+        model
+            .items
+            .iter()
+            .find(|item| item.name == name)
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn check_arithmetic() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        declare(&mut solver, &context, "x", ast::Sort::Int);
+        solver
+            .assert(
+                &ast::Expression::equals(ast::Expression::add(var("x"), 2.into()), 4.into()),
+                &context,
+            )
+            .unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        let model = solver.get_model(&context).unwrap();
+        assert_eq!(model_value(&model, "x"), ast::Value::Int(2.into()));
+    }
+
+    #[test]
+    fn check_difference_cycle() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        for name in &["x", "y", "z"] {
+            declare(&mut solver, &context, name, ast::Sort::Int);
+        }
+        solver
+            .assert(&ast::Expression::less_than(var("x"), var("y")), &context)
+            .unwrap();
+        solver
+            .assert(&ast::Expression::less_than(var("y"), var("z")), &context)
+            .unwrap();
+        solver.push().unwrap();
+        solver
+            .assert(&ast::Expression::less_than(var("z"), var("x")), &context)
+            .unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Unsat);
+        solver.pop().unwrap();
+        solver
+            .assert(
+                &ast::Expression::less_than(var("z"), ast::Expression::add(var("x"), 3.into())),
+                &context,
+            )
+            .unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        let model = solver.get_model(&context).unwrap();
+        let value = |name| match model_value(&model, name) {
+            ast::Value::Int(value) => value,
+            value => unreachable!("{:?}", value),
+        };
+        assert!(value("x") < value("y"));
+        assert!(value("y") < value("z"));
+        assert!(value("z") < value("x") + 3);
+    }
+
+    #[test]
+    fn check_conditional() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        declare(&mut solver, &context, "b", ast::Sort::Bool);
+        declare(&mut solver, &context, "x", ast::Sort::Int);
+        let conditional = ast::Expression::Conditional(ast::Conditional {
+            guard: Box::new(var("b")),
+            then_expr: Box::new(ast::Expression::add(var("x"), 1.into())),
+            else_expr: Box::new(ast::Expression::subtract(var("x"), 1.into())),
+        });
+        solver
+            .assert(
+                &ast::Expression::greater_than(conditional, var("x")),
+                &context,
+            )
+            .unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        let model = solver.get_model(&context).unwrap();
+        assert_eq!(model_value(&model, "b"), ast::Value::Bool(true));
+        solver
+            .assert(&ast::Expression::not(var("b")), &context)
+            .unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Unsat);
+    }
+
+    #[test]
+    fn check_let() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        declare(&mut solver, &context, "x", ast::Sort::Int);
+        // let y = x + 1 in let b = y > 3 in let x = y + y in b && x < 10
+        let expr = ast::Expression::let_in(
+            "y".into(),
+            ast::Expression::add(var("x"), 1.into()),
+            ast::Expression::let_in(
+                "b".into(),
+                ast::Expression::greater_than(var("y"), 3.into()),
+                ast::Expression::let_in(
+                    "x".into(),
+                    ast::Expression::add(var("y"), var("y")),
+                    ast::Expression::and(var("b"), ast::Expression::less_than(var("x"), 10.into())),
+                ),
+            ),
+        );
+        solver.assert(&expr, &context).unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        let model = solver.get_model(&context).unwrap();
+        assert_eq!(model_value(&model, "x"), ast::Value::Int(3.into()));
+    }
+
+    /// Four pigeons do not fit into three holes.
+    #[test]
+    fn check_pigeonhole() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        let name = |pigeon, hole| format!("p{}h{}", pigeon, hole);
+        for pigeon in 0..4 {
+            for hole in 0..3 {
+                declare(&mut solver, &context, &name(pigeon, hole), ast::Sort::Bool);
+            }
+            let somewhere = (0..3)
+                .map(|hole| var(&name(pigeon, hole)))
+                .reduce(ast::Expression::or)
+                .unwrap();
+            solver.assert(&somewhere, &context).unwrap();
+        }
+        for hole in 0..3 {
+            for first in 0..4 {
+                for second in first + 1..4 {
+                    solver
+                        .assert(
+                            &ast::Expression::not(ast::Expression::and(
+                                var(&name(first, hole)),
+                                var(&name(second, hole)),
+                            )),
+                            &context,
+                        )
+                        .unwrap();
+                }
+            }
+        }
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Unsat);
+        let statistics = solver.get_statistics().unwrap();
+        assert!(statistics.get("conflicts").unwrap() > 0.0);
+    }
+
+    #[test]
+    fn check_labels() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        declare(&mut solver, &context, "k", ast::Sort::Int);
+        for label in &["InBounds", "Small", "Large"] {
+            solver
+                .declare_label(
+                    &ast::LabelDeclaration {
+                        name: (*label).into(),
+                    },
+                    &context,
+                )
+                .unwrap();
+        }
+        // !(InBounds: k >= 0 ==> (Small: k < 10) && (Large: k >= 10))
+        let expr = ast::Expression::not(ast::Expression::implies(
+            ast::Expression::label_positive(
+                "InBounds".into(),
+                ast::Expression::greater_equals(var("k"), 0.into()),
+            ),
+            ast::Expression::and(
+                ast::Expression::label_negative(
+                    "Small".into(),
+                    ast::Expression::less_than(var("k"), 10.into()),
+                ),
+                ast::Expression::label_negative(
+                    "Large".into(),
+                    ast::Expression::greater_equals(var("k"), 10.into()),
+                ),
+            ),
+        ));
+        solver.assert(&expr, &context).unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        let model = solver.get_model(&context).unwrap();
+        let labels = solver.get_labels(&context).unwrap();
+        let k = match model_value(&model, "k") {
+            ast::Value::Int(k) => k,
+            value => unreachable!("{:?}", value),
+        };
+        let failing: ast::LabelSymbol = if k < 10.into() { "Large" } else { "Small" }.into();
+        assert_eq!(labels, vec!["InBounds".into(), failing.clone()]);
+        assert!(model.get_label(&"InBounds".into()));
+        assert!(!model.get_label(&failing));
+    }
+
+    #[test]
+    fn check_interrupt_stops_one_search() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        declare(&mut solver, &context, "x", ast::Sort::Int);
+        solver
+            .assert(&ast::Expression::greater_than(var("x"), 0.into()), &context)
+            .unwrap();
+        let interrupt = solver.interrupter().unwrap();
+        interrupt();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Unknown);
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+    }
+
+    #[test]
+    fn check_overflow() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        declare(&mut solver, &context, "x", ast::Sort::Int);
+        // x * 10^18 * 10^18 * 10^18 > 0 does not fit into the coefficients.
+        let large: i64 = 1_000_000_000_000_000_000;
+        let product = ast::Expression::multiply(
+            ast::Expression::multiply(
+                ast::Expression::multiply(var("x"), large.into()),
+                large.into(),
+            ),
+            large.into(),
+        );
+        solver
+            .assert(&ast::Expression::greater_than(product, 0.into()), &context)
+            .unwrap();
+        assert!(matches!(
+            solver.check_sat(),
+            Err(SmtSolverError::BuiltinSolverError { .. })
+        ));
+    }
+
+    #[test]
+    fn check_unsupported() {
+        let context = StringContext::default();
+        let mut solver = BuiltinSmtSolver::default().unwrap();
+        solver
+            .assert(
+                &ast::Expression::forall(
+                    vec![ast::BoundedVariableDecl {
+                        name: "n".into(),
+                        sort: ast::Sort::Int,
+                    }],
+                    vec![],
+                    ast::Expression::greater_than(var("n"), 0.into()),
+                ),
+                &context,
+            )
+            .unwrap();
+        assert!(matches!(
+            solver.check_sat(),
+            Err(SmtSolverError::BuiltinSolverError { .. })
+        ));
+    }
+}
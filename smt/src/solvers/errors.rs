@@ -8,4 +8,22 @@ pub enum SmtSolverError {
         #[from]
         source: Box<dyn std::error::Error>,
     },
+    #[error("failed to access the solver recording")]
+    RecordingIoError {
+        #[from]
+        source: std::io::Error,
+    },
+    #[error("malformed solver recording")]
+    RecordingFormatError {
+        #[from]
+        source: serde_json::Error,
+    },
+    #[error("replay diverged from the recording: expected {expected}, found {found}")]
+    ReplayMismatch { expected: String, found: String },
+    #[error("replay reached the end of the recording, found {found}")]
+    ReplayExhausted { found: String },
+    #[error("the recording {path} does not exist, set SVIRPTI_RERECORD to record it")]
+    RecordingMissing { path: std::path::PathBuf },
+    #[error("the built-in solver failed: {message}")]
+    BuiltinSolverError { message: String },
 }
@@ -3,9 +3,18 @@
 //! labels.)
 
 pub use self::errors::SmtSolverError;
+pub use self::options::{infer_logic, Logic, OptionProfile};
+use crate::cancellation::Interrupter;
+use std::collections::BTreeMap;
 use svirpti_vir::smt as ast;
 
+pub mod builtin;
 pub mod errors;
+pub mod options;
+pub mod pool;
+pub mod recording;
+#[cfg(test)]
+pub(crate) mod test_context;
 pub mod z3_smt2;
 
 pub enum SatisfiabilityResult<Model> {
@@ -14,13 +23,26 @@ pub enum SatisfiabilityResult<Model> {
     Sat(Model),
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum SatResult {
     Unsat,
     Unknown,
     Sat,
 }
 
+/// Statistics reported by a solver after a query, such as the number of
+/// conflicts or quantifier instantiations. The keys are solver specific.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Statistics {
+    pub values: BTreeMap<String, f64>,
+}
+
+impl Statistics {
+    pub fn get(&self, key: &str) -> Option<f64> {
+        self.values.get(key).copied()
+    }
+}
+
 pub type SmtSolverResult<T = ()> = Result<T, SmtSolverError>;
 
 pub trait SmtSolver: Sized {
@@ -28,16 +50,43 @@ pub trait SmtSolver: Sized {
     type Error: std::fmt::Debug;
     fn default() -> SmtSolverResult<Self>;
     fn new(conf: Self::Conf) -> SmtSolverResult<Self>;
+    /// Takes an idle solver from `pool`, or creates a new one if the pool is
+    /// empty.
+    fn from_pool(pool: &pool::SolverPool<Self>) -> SmtSolverResult<pool::PooledSolver<Self>> {
+        pool.acquire()
+    }
+    /// Brings the solver back to the state right after construction, so that
+    /// it can be reused for an unrelated query.
+    fn reset(&mut self) -> SmtSolverResult;
+    /// Sets a solver specific option. Solvers may ignore options they do not
+    /// know.
+    fn set_option(&mut self, option: &str, value: &str) -> SmtSolverResult;
+    /// Sets the logic of the query. Must be called before any declaration.
+    fn set_logic(&mut self, logic: Logic) -> SmtSolverResult;
     fn declare_sort(
         &mut self,
         sort: &ast::UninterpretedSortDeclaration,
         context: &impl ast::Context,
     ) -> SmtSolverResult;
+    /// Declares `adts` together, so that they may refer to each other.
+    fn declare_adts(
+        &mut self,
+        adts: &[ast::AdtDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult;
+    /// Declares `function`, or defines it if it has a body. The body must
+    /// not call `function`.
     fn declare_function(
         &mut self,
         function: &ast::FunctionDeclaration,
         context: &impl ast::Context,
     ) -> SmtSolverResult;
+    /// Defines `functions` together, so that their bodies may call each other.
+    fn define_recursive_functions(
+        &mut self,
+        functions: &[ast::FunctionDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult;
     fn declare_label(
         &mut self,
         label: &ast::LabelDeclaration,
@@ -59,4 +108,9 @@ pub trait SmtSolver: Sized {
     fn get_labels(&mut self, context: &impl ast::Context)
         -> SmtSolverResult<Vec<ast::LabelSymbol>>;
     fn get_model(&mut self, context: &impl ast::Context) -> SmtSolverResult<ast::Model>;
+    /// The statistics of the last `check_sat`.
+    fn get_statistics(&mut self) -> SmtSolverResult<Statistics>;
+    /// A function that interrupts a running `check_sat` from another thread,
+    /// if the solver supports it.
+    fn interrupter(&self) -> Option<Interrupter>;
 }
@@ -0,0 +1,462 @@
+//! Named solver option profiles and `set-logic` inference.
+
+use svirpti_vir::smt as ast;
+
+/// A named set of solver options. The option names are Z3 specific; other
+/// solvers may ignore them.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OptionProfile {
+    pub name: String,
+    pub options: Vec<(String, String)>,
+}
+
+impl OptionProfile {
+    pub fn new(name: &str, options: &[(&str, &str)]) -> Self {
+        Self {
+            name: name.into(),
+            options: options
+                .iter()
+                .map(|&(option, value)| (option.into(), value.into()))
+                .collect(),
+        }
+    }
+    /// The options that the Z3 solver is configured with by default.
+    pub fn default_profile() -> Self {
+        Self::new(
+            "default",
+            &[
+                (":AUTO_CONFIG", "false"),
+                (":smt.MBQI", "false"),
+                (":TYPE_CHECK", "true"),
+            ],
+        )
+    }
+    /// Aggressive E-matching for queries with many quantifiers.
+    pub fn quantifier_heavy() -> Self {
+        Self::default_profile().merge(&Self::new(
+            "quantifier-heavy",
+            &[
+                (":smt.qi.eager_threshold", "1000"),
+                (":smt.qi.max_multi_patterns", "1000"),
+            ],
+        ))
+    }
+    /// Nonlinear integer and real arithmetic.
+    pub fn nonlinear() -> Self {
+        Self::default_profile().merge(&Self::new(
+            "nonlinear",
+            &[(":smt.arith.nl", "true"), (":smt.arith.nl.gb", "true")],
+        ))
+    }
+    /// Gives up early instead of searching for a long time.
+    pub fn fast() -> Self {
+        Self::default_profile().merge(&Self::new(
+            "fast",
+            &[(":smt.qi.eager_threshold", "10"), (":timeout", "2000")],
+        ))
+    }
+    /// Looks up one of the predefined profiles by name.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default_profile()),
+            "quantifier-heavy" => Some(Self::quantifier_heavy()),
+            "nonlinear" => Some(Self::nonlinear()),
+            "fast" => Some(Self::fast()),
+            _ => None,
+        }
+    }
+    /// Combines two profiles. The options of `other` take precedence.
+    pub fn merge(&self, other: &OptionProfile) -> OptionProfile {
+        let mut options: Vec<_> = self
+            .options
+            .iter()
+            .filter(|(option, _)| other.options.iter().all(|(other, _)| other != option))
+            .cloned()
+            .collect();
+        options.extend(other.options.iter().cloned());
+        let name = if self.name == "default" {
+            other.name.clone()
+        } else {
+            format!("{}+{}", self.name, other.name)
+        };
+        OptionProfile { name, options }
+    }
+}
+
+/// The quantifier-free SMT-LIB logics that can be inferred for a query.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Logic {
+    QF_UF,
+    QF_LIA,
+    QF_NIA,
+    QF_LRA,
+    QF_UFLIA,
+    QF_UFLRA,
+    QF_UFNRA,
+    QF_BV,
+    QF_UFBV,
+    QF_AX,
+    QF_AUFLIA,
+    QF_ABV,
+    QF_AUFBV,
+}
+
+#[derive(Default)]
+struct Features {
+    quantifiers: bool,
+    arithmetic: bool,
+    nonlinear: bool,
+    int: bool,
+    real: bool,
+    bit_vectors: bool,
+    floats: bool,
+    arrays: bool,
+    uninterpreted: bool,
+    datatypes: bool,
+    sequences: bool,
+    sets: bool,
+}
+
+impl Features {
+    fn add_sort(&mut self, sort: &ast::Sort) {
+        match sort {
+            ast::Sort::Bool => {}
+            ast::Sort::Int => self.int = true,
+            ast::Sort::Real => self.real = true,
+            ast::Sort::BitVector { .. } => self.bit_vectors = true,
+            ast::Sort::Float { .. } => self.floats = true,
+            ast::Sort::Array { index, element } => {
+                self.arrays = true;
+                self.add_sort(index);
+                self.add_sort(element);
+            }
+            ast::Sort::Uninterpreted { .. } => self.uninterpreted = true,
+            ast::Sort::Adt { .. } => self.datatypes = true,
+            ast::Sort::Seq { element } => {
+                self.sequences = true;
+                self.add_sort(element);
+            }
+            ast::Sort::Set { element } => {
+                self.sets = true;
+                self.add_sort(element);
+            }
+        }
+    }
+    fn add_expression(&mut self, expression: &ast::Expression) {
+        match expression {
+            ast::Expression::Variable(_) => {}
+            ast::Expression::Constant(constant) => match constant {
+                ast::Constant::Bool(_) => {}
+                ast::Constant::Int(_) => self.arithmetic = true,
+                ast::Constant::Real { .. } => {
+                    self.arithmetic = true;
+                    self.real = true;
+                }
+                ast::Constant::BitVector { .. } => self.bit_vectors = true,
+                ast::Constant::Float { .. } => self.floats = true,
+            },
+            ast::Expression::UnaryOperation(operation) => {
+                if matches!(
+                    operation.kind,
+                    ast::UnaryOperationKind::ToReal
+                        | ast::UnaryOperationKind::ToInt
+                        | ast::UnaryOperationKind::IsInt
+                ) {
+                    // Conversions mix integers and reals.
+                    self.int = true;
+                    self.real = true;
+                }
+                self.add_expression(&operation.arg)
+            }
+            ast::Expression::BinaryOperation(operation) => {
+                use ast::BinaryOperationKind as Kind;
+                let nonlinear = match operation.kind {
+                    Kind::Mul => !is_numeral(&operation.left) && !is_numeral(&operation.right),
+                    Kind::Div | Kind::Mod => !is_numeral(&operation.right),
+                    _ => false,
+                };
+                self.nonlinear |= nonlinear;
+                self.add_expression(&operation.left);
+                self.add_expression(&operation.right);
+            }
+            ast::Expression::BitVectorConversion(conversion) => {
+                self.bit_vectors = true;
+                if let ast::BitVectorConversionKind::FromInt { .. }
+                | ast::BitVectorConversionKind::ToInt = conversion.kind
+                {
+                    self.arithmetic = true;
+                }
+                self.add_expression(&conversion.arg);
+            }
+            ast::Expression::FloatOperation(operation) => {
+                self.floats = true;
+                for arg in &operation.args {
+                    self.add_expression(arg);
+                }
+            }
+            ast::Expression::FloatConversion(conversion) => {
+                self.floats = true;
+                self.add_expression(&conversion.arg);
+            }
+            ast::Expression::ArraySelect(select) => {
+                self.arrays = true;
+                self.add_expression(&select.array);
+                self.add_expression(&select.index);
+            }
+            ast::Expression::ArrayStore(store) => {
+                self.arrays = true;
+                self.add_expression(&store.array);
+                self.add_expression(&store.index);
+                self.add_expression(&store.value);
+            }
+            ast::Expression::ConstantArray(array) => {
+                self.arrays = true;
+                self.add_sort(&array.index_sort);
+                self.add_expression(&array.value);
+            }
+            ast::Expression::Let(binding) => {
+                self.add_expression(&binding.value);
+                self.add_expression(&binding.body);
+            }
+            ast::Expression::Conditional(conditional) => {
+                self.add_expression(&conditional.guard);
+                self.add_expression(&conditional.then_expr);
+                self.add_expression(&conditional.else_expr);
+            }
+            ast::Expression::AdtConstructor(_)
+            | ast::Expression::AdtFieldAccess(_)
+            | ast::Expression::AdtTester(_)
+            | ast::Expression::AdtMatch(_) => self.datatypes = true,
+            ast::Expression::Quantifier(_) => self.quantifiers = true,
+            ast::Expression::FunctionApplication(application) => {
+                self.uninterpreted = true;
+                for arg in &application.args {
+                    self.add_expression(arg);
+                }
+            }
+            ast::Expression::SeqOperation(operation) => {
+                self.sequences = true;
+                for arg in &operation.args {
+                    self.add_expression(arg);
+                }
+            }
+            ast::Expression::EmptySeq(seq) => {
+                self.sequences = true;
+                self.add_sort(&seq.element_sort);
+            }
+            ast::Expression::SetOperation(operation) => {
+                self.sets = true;
+                for arg in &operation.args {
+                    self.add_expression(arg);
+                }
+            }
+            ast::Expression::EmptySet(set) => {
+                self.sets = true;
+                self.add_sort(&set.element_sort);
+            }
+            ast::Expression::LabelledExpression(labelled) => {
+                self.add_expression(&labelled.expression)
+            }
+        }
+    }
+}
+
+fn is_numeral(expression: &ast::Expression) -> bool {
+    match expression {
+        ast::Expression::Constant(ast::Constant::Int(_)) => true,
+        ast::Expression::UnaryOperation(operation) => {
+            operation.kind == ast::UnaryOperationKind::Minus && is_numeral(&operation.arg)
+        }
+        _ => false,
+    }
+}
+
+/// Infers the logic of a quantifier-free query. Returns `None` for queries
+/// with quantifiers, recursive functions, datatypes, sequences, sets,
+/// floating-point numbers, or that mix integers, reals, or bit-vectors, and
+/// for combinations without a standard logic.
+pub fn infer_logic(
+    declarations: &ast::Declarations,
+    assertions: &[ast::Expression],
+) -> Option<Logic> {
+    let mut features = Features {
+        datatypes: !declarations.adts.is_empty(),
+        ..Features::default()
+    };
+    if !declarations.sorts.is_empty() || !declarations.functions.is_empty() {
+        features.uninterpreted = true;
+    }
+    for variable in &declarations.variables {
+        features.add_sort(&variable.sort);
+    }
+    for function in &declarations.functions {
+        features.add_sort(&function.return_sort);
+        for parameter in &function.parameters {
+            features.add_sort(&parameter.sort);
+        }
+        if let Some(body) = &function.body {
+            features.add_expression(body);
+        }
+    }
+    // Recursive definitions are quantified axioms.
+    if crate::functions::function_groups(&declarations.functions)
+        .iter()
+        .any(|group| group.recursive)
+    {
+        features.quantifiers = true;
+    }
+    for assertion in assertions {
+        features.add_expression(assertion);
+    }
+    if features.quantifiers
+        || features.datatypes
+        || features.sequences
+        || features.sets
+        || features.floats
+        || (features.int && features.real)
+    {
+        return None;
+    }
+    if features.bit_vectors {
+        if features.int || features.real || features.arithmetic {
+            return None;
+        }
+        return Some(match (features.arrays, features.uninterpreted) {
+            (false, false) => Logic::QF_BV,
+            (false, true) => Logic::QF_UFBV,
+            (true, false) => Logic::QF_ABV,
+            (true, true) => Logic::QF_AUFBV,
+        });
+    }
+    let int = features.int || (features.arithmetic && !features.real);
+    if features.arrays {
+        // The standard array logics support only linear integer arithmetic.
+        if features.real || features.nonlinear {
+            return None;
+        }
+        return Some(if int || features.uninterpreted {
+            Logic::QF_AUFLIA
+        } else {
+            Logic::QF_AX
+        });
+    }
+    let logic = match (
+        features.uninterpreted,
+        int,
+        features.real,
+        features.nonlinear,
+    ) {
+        (_, false, false, _) => Logic::QF_UF,
+        (false, true, _, false) => Logic::QF_LIA,
+        (false, true, _, true) => Logic::QF_NIA,
+        (false, _, true, false) => Logic::QF_LRA,
+        (true, true, _, false) => Logic::QF_UFLIA,
+        (true, _, true, false) => Logic::QF_UFLRA,
+        (true, _, true, true) => Logic::QF_UFNRA,
+        // There is no standard logic for QF_NRA or QF_UFNIA that all
+        // solvers accept.
+        _ => return None,
+    };
+    Some(logic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{infer_logic, Logic, OptionProfile};
+    use crate::parser::parse_query;
+
+    fn logic_of(script: &str) -> Option<Logic> {
+        let query = parse_query(script).unwrap();
+        infer_logic(&query.declarations, &query.assertions)
+    }
+
+    #[test]
+    fn check_infer_logic() {
+        assert_eq!(
+            logic_of("(declare-fun b () Bool) (assert (not b))"),
+            Some(Logic::QF_UF)
+        );
+        assert_eq!(
+            logic_of("(declare-fun x () Int) (assert (< (* 2 x) (div x 3)))"),
+            Some(Logic::QF_LIA)
+        );
+        assert_eq!(
+            logic_of("(declare-fun x () Int) (assert (< (* x x) 0))"),
+            Some(Logic::QF_NIA)
+        );
+        assert_eq!(
+            logic_of("(declare-fun x () Real) (assert (< x 0))"),
+            Some(Logic::QF_LRA)
+        );
+        assert_eq!(logic_of("(assert (< 0.5 1.5))"), Some(Logic::QF_LRA));
+        assert_eq!(logic_of("(assert (is_int 0.5))"), None);
+        assert_eq!(
+            logic_of("(declare-sort S 0) (declare-fun f (S) S) (declare-fun s () S) (assert (= (f s) s))"),
+            Some(Logic::QF_UF)
+        );
+        assert_eq!(
+            logic_of("(declare-fun f (Int) Int) (assert (< (f 1) 0))"),
+            Some(Logic::QF_UFLIA)
+        );
+        assert_eq!(
+            logic_of("(declare-fun f (Int) Int) (assert (forall ((y Int)) (< (f y) 0)))"),
+            None
+        );
+        assert_eq!(
+            logic_of("(define-fun f ((x Int)) Int (+ x 1)) (assert (< (f 1) 0))"),
+            Some(Logic::QF_UFLIA)
+        );
+        assert_eq!(
+            logic_of("(define-fun-rec f ((x Int)) Int (f x)) (assert (< (f 1) 0))"),
+            None
+        );
+        assert_eq!(
+            logic_of("(declare-fun x () Int) (declare-fun y () Real) (assert (< x 0))"),
+            None
+        );
+        assert_eq!(
+            logic_of("(declare-fun x () (_ BitVec 8)) (assert (bvult (bvadd x #x01) x))"),
+            Some(Logic::QF_BV)
+        );
+        assert_eq!(
+            logic_of("(declare-fun x () (_ BitVec 8)) (assert (< (bv2nat x) 0))"),
+            None
+        );
+        assert_eq!(
+            logic_of("(declare-fun a () (Array Int Int)) (assert (< (select a 0) 1))"),
+            Some(Logic::QF_AUFLIA)
+        );
+        assert_eq!(
+            logic_of(
+                "(declare-fun a () (Array Bool Bool)) (assert (select (store a true false) true))"
+            ),
+            Some(Logic::QF_AX)
+        );
+        assert_eq!(
+            logic_of("(declare-fun a () (Array (_ BitVec 8) (_ BitVec 8))) (assert (= (select a #x00) #x01))"),
+            Some(Logic::QF_ABV)
+        );
+    }
+
+    #[test]
+    fn check_profiles() {
+        let profile = OptionProfile::named("quantifier-heavy").unwrap();
+        assert_eq!(profile.name, "quantifier-heavy");
+        assert!(profile
+            .options
+            .contains(&(":AUTO_CONFIG".into(), "false".into())));
+        let merged = profile.merge(&OptionProfile::fast());
+        assert_eq!(merged.name, "quantifier-heavy+fast");
+        let thresholds: Vec<_> = merged
+            .options
+            .iter()
+            .filter(|(option, _)| option == ":smt.qi.eager_threshold")
+            .collect();
+        assert_eq!(
+            thresholds,
+            vec![&(":smt.qi.eager_threshold".into(), "10".into())]
+        );
+        assert!(OptionProfile::named("unknown").is_none());
+    }
+}
@@ -0,0 +1,326 @@
+//! A pool of long-lived solvers that are reset between queries instead of
+//! being created anew.
+//!
+//! Starting an external solver process and configuring it can take longer
+//! than a small query, so reusing the processes pays off when verifying many
+//! small fragments.
+
+use super::{Logic, SatResult, SmtSolver, SmtSolverResult, Statistics};
+use crate::cancellation::Interrupter;
+use std::sync::{Arc, Mutex};
+use svirpti_vir::smt as ast;
+
+/// A function that creates a new solver for the pool.
+pub type SolverFactory<S> = Box<dyn Fn() -> SmtSolverResult<S> + Send + Sync>;
+
+pub struct PoolConfiguration {
+    /// The maximum number of idle solvers kept in the pool. Solvers returned
+    /// to a full pool are dropped.
+    pub size: usize,
+    /// Whether to check that an idle solver still responds before handing it
+    /// out. Solvers that fail the check are dropped and replaced.
+    pub check_health: bool,
+}
+
+impl Default for PoolConfiguration {
+    fn default() -> Self {
+        Self {
+            size: 4,
+            check_health: true,
+        }
+    }
+}
+
+struct Inner<S> {
+    conf: PoolConfiguration,
+    factory: SolverFactory<S>,
+    idle: Mutex<Vec<S>>,
+}
+
+/// A pool of solvers. Clones share the same idle solvers.
+pub struct SolverPool<S> {
+    inner: Arc<Inner<S>>,
+}
+
+impl<S> Clone for SolverPool<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S: SmtSolver + 'static> SolverPool<S> {
+    /// Creates a pool whose solvers are constructed with `S::default`.
+    pub fn new(conf: PoolConfiguration) -> Self {
+        Self::with_factory(conf, Box::new(S::default))
+    }
+}
+
+impl<S: SmtSolver> SolverPool<S> {
+    pub fn with_factory(conf: PoolConfiguration, factory: SolverFactory<S>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                conf,
+                factory,
+                idle: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+    /// The number of solvers waiting to be reused.
+    pub fn idle_count(&self) -> usize {
+        self.inner.idle.lock().unwrap().len()
+    }
+    /// Takes an idle solver, or creates a new one if there is no healthy idle
+    /// solver. The solver returns to the pool when dropped.
+    pub fn acquire(&self) -> SmtSolverResult<PooledSolver<S>> {
+        loop {
+            let solver = self.inner.idle.lock().unwrap().pop();
+            match solver {
+                Some(mut solver) => {
+                    if !self.inner.conf.check_health || is_healthy(&mut solver) {
+                        return Ok(self.wrap(solver));
+                    }
+                }
+                None => {
+                    let solver = (self.inner.factory)()?;
+                    return Ok(self.wrap(solver));
+                }
+            }
+        }
+    }
+    fn wrap(&self, solver: S) -> PooledSolver<S> {
+        PooledSolver {
+            solver: Some(solver),
+            pool: self.clone(),
+        }
+    }
+    /// Resets `solver` and keeps it for reuse unless the reset fails or the
+    /// pool is full.
+    fn release(&self, mut solver: S) {
+        if solver.reset().is_err() {
+            return;
+        }
+        let mut idle = self.inner.idle.lock().unwrap();
+        if idle.len() < self.inner.conf.size {
+            idle.push(solver);
+        }
+    }
+}
+
+/// An idle solver must still respond. Asking for the statistics is a round
+/// trip to the solver that, unlike a query, leaves its state unchanged.
+fn is_healthy<S: SmtSolver>(solver: &mut S) -> bool {
+    solver.get_statistics().is_ok()
+}
+
+/// A solver borrowed from a [`SolverPool`]. It is reset and returned to the
+/// pool when dropped.
+pub struct PooledSolver<S: SmtSolver> {
+    /// Always `Some` except while dropping.
+    solver: Option<S>,
+    pool: SolverPool<S>,
+}
+
+impl<S: SmtSolver> PooledSolver<S> {
+    fn solver(&mut self) -> &mut S {
+        self.solver.as_mut().unwrap()
+    }
+}
+
+impl<S: SmtSolver> Drop for PooledSolver<S> {
+    fn drop(&mut self) {
+        if let Some(solver) = self.solver.take() {
+            self.pool.release(solver);
+        }
+    }
+}
+
+impl<S: SmtSolver + 'static> SmtSolver for PooledSolver<S> {
+    type Conf = SolverPool<S>;
+    type Error = S::Error;
+    /// Takes a solver from a new pool with the default configuration, which
+    /// gives no reuse. Use [`SolverPool::acquire`] or `verify_with_pool` to
+    /// share a pool.
+    fn default() -> SmtSolverResult<Self> {
+        SolverPool::new(Default::default()).acquire()
+    }
+    fn new(pool: SolverPool<S>) -> SmtSolverResult<Self> {
+        pool.acquire()
+    }
+    fn reset(&mut self) -> SmtSolverResult {
+        self.solver().reset()
+    }
+    fn set_option(&mut self, option: &str, value: &str) -> SmtSolverResult {
+        self.solver().set_option(option, value)
+    }
+    fn set_logic(&mut self, logic: Logic) -> SmtSolverResult {
+        self.solver().set_logic(logic)
+    }
+    fn declare_sort(
+        &mut self,
+        sort: &ast::UninterpretedSortDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver().declare_sort(sort, context)
+    }
+    fn declare_adts(
+        &mut self,
+        adts: &[ast::AdtDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver().declare_adts(adts, context)
+    }
+    fn declare_function(
+        &mut self,
+        function: &ast::FunctionDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver().declare_function(function, context)
+    }
+    fn define_recursive_functions(
+        &mut self,
+        functions: &[ast::FunctionDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver().define_recursive_functions(functions, context)
+    }
+    fn declare_label(
+        &mut self,
+        label: &ast::LabelDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver().declare_label(label, context)
+    }
+    fn declare_variable(
+        &mut self,
+        variable: &ast::VariableDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver().declare_variable(variable, context)
+    }
+    fn push(&mut self) -> SmtSolverResult {
+        self.solver().push()
+    }
+    fn pop(&mut self) -> SmtSolverResult {
+        self.solver().pop()
+    }
+    fn assert(
+        &mut self,
+        assertion: &ast::Expression,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver().assert(assertion, context)
+    }
+    fn check_sat(&mut self) -> SmtSolverResult<SatResult> {
+        self.solver().check_sat()
+    }
+    fn get_labels(
+        &mut self,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult<Vec<ast::LabelSymbol>> {
+        self.solver().get_labels(context)
+    }
+    fn get_model(&mut self, context: &impl ast::Context) -> SmtSolverResult<ast::Model> {
+        self.solver().get_model(context)
+    }
+    fn get_statistics(&mut self) -> SmtSolverResult<Statistics> {
+        self.solver().get_statistics()
+    }
+    fn interrupter(&self) -> Option<Interrupter> {
+        self.solver.as_ref().unwrap().interrupter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PoolConfiguration, SolverPool};
+    use crate::solvers::{
+        builtin::BuiltinSmtSolver,
+        recording::{ReplaySolver, SolverEvent},
+        test_context::StringContext,
+        SatResult, SmtSolver,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn counting_pool(size: usize, created: Arc<AtomicUsize>) -> SolverPool<BuiltinSmtSolver> {
+        SolverPool::with_factory(
+            PoolConfiguration {
+                size,
+                check_health: true,
+            },
+            Box::new(move || {
+                created.fetch_add(1, Ordering::SeqCst);
+                BuiltinSmtSolver::default()
+            }),
+        )
+    }
+
+    #[test]
+    fn check_reuse() {
+        let context = StringContext::default();
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = counting_pool(1, created.clone());
+        {
+            let mut solver = BuiltinSmtSolver::from_pool(&pool).unwrap();
+            solver.assert(&false.into(), &context).unwrap();
+            assert_eq!(solver.check_sat().unwrap(), SatResult::Unsat);
+        }
+        assert_eq!(pool.idle_count(), 1);
+        {
+            // The assertion from the previous query is gone.
+            let mut solver = pool.acquire().unwrap();
+            assert_eq!(pool.idle_count(), 0);
+            assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        }
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn check_size_limit() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = counting_pool(1, created.clone());
+        let first = pool.acquire().unwrap();
+        let second = pool.acquire().unwrap();
+        drop(first);
+        drop(second);
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    #[test]
+    fn check_interrupted_solver_is_reused() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = counting_pool(1, created.clone());
+        {
+            let mut solver = pool.acquire().unwrap();
+            (solver.interrupter().unwrap())();
+            assert_eq!(solver.check_sat().unwrap(), SatResult::Unknown);
+        }
+        let mut solver = pool.acquire().unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn check_unhealthy_solver_is_replaced() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let counter = created.clone();
+        // The replayed solver survives the reset when it is released, but
+        // fails the health check because it cannot answer `get_statistics`.
+        let pool = SolverPool::with_factory(
+            Default::default(),
+            Box::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(ReplaySolver::from_events(vec![SolverEvent::Reset]))
+            }),
+        );
+        drop(pool.acquire().unwrap());
+        assert_eq!(pool.idle_count(), 1);
+        let solver = pool.acquire().unwrap();
+        assert_eq!(pool.idle_count(), 0);
+        assert!(!solver.solver.as_ref().unwrap().is_finished());
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,674 @@
+//! Solvers for recording an interaction with an SMT solver and replaying it
+//! later without the solver being installed.
+//!
+//! A recording is a JSON Lines file: each line is one serialized
+//! [`SolverEvent`]. [`RecordedSolver`] lets tests replay checked-in
+//! recordings and re-record them with a real solver on demand.
+
+use super::{Logic, SatResult, SmtSolver, SmtSolverError, SmtSolverResult, Statistics};
+use crate::cancellation::Interrupter;
+use rsmt2::print::Expr2Smt;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use svirpti_vir::smt as ast;
+
+/// A single interaction with the solver together with its outcome.
+#[derive(PartialEq, Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum SolverEvent {
+    DeclareSort(ast::UninterpretedSortDeclaration),
+    DeclareAdts(Vec<ast::AdtDeclaration>),
+    DeclareFunction(ast::FunctionDeclaration),
+    DefineRecursiveFunctions(Vec<ast::FunctionDeclaration>),
+    DeclareLabel(ast::LabelDeclaration),
+    DeclareVariable(ast::VariableDeclaration),
+    Push,
+    Pop,
+    Assert(ast::Expression),
+    CheckSat(SatResult),
+    GetLabels(Vec<ast::LabelSymbol>),
+    GetModel(ast::Model),
+    GetStatistics(Statistics),
+    Reset,
+    SetOption(String, String),
+    SetLogic(Logic),
+}
+
+impl SolverEvent {
+    fn kind(&self) -> &'static str {
+        match self {
+            SolverEvent::DeclareSort(_) => "declare-sort",
+            SolverEvent::DeclareAdts(_) => "declare-adts",
+            SolverEvent::DeclareFunction(_) => "declare-function",
+            SolverEvent::DefineRecursiveFunctions(_) => "define-recursive-functions",
+            SolverEvent::DeclareLabel(_) => "declare-label",
+            SolverEvent::DeclareVariable(_) => "declare-variable",
+            SolverEvent::Push => "push",
+            SolverEvent::Pop => "pop",
+            SolverEvent::Assert(_) => "assert",
+            SolverEvent::CheckSat(_) => "check-sat",
+            SolverEvent::GetLabels(_) => "get-labels",
+            SolverEvent::GetModel(_) => "get-model",
+            SolverEvent::GetStatistics(_) => "get-statistics",
+            SolverEvent::Reset => "reset",
+            SolverEvent::SetOption(..) => "set-option",
+            SolverEvent::SetLogic(_) => "set-logic",
+        }
+    }
+}
+
+/// Reads the events stored in the recording at `path`.
+pub fn read_recording(path: impl AsRef<Path>) -> SmtSolverResult<Vec<SolverEvent>> {
+    let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    let mut events = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            events.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(events)
+}
+
+/// Writes `events` to a new recording at `path`.
+pub fn write_recording(path: impl AsRef<Path>, events: &[SolverEvent]) -> SmtSolverResult {
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    for event in events {
+        serde_json::to_writer(&mut writer, event)?;
+        writeln!(writer)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn get_record_path() -> Option<PathBuf> {
+    std::env::var_os("SVIRPTI_RECORD_PATH").map(PathBuf::from)
+}
+
+fn get_replay_path() -> SmtSolverResult<PathBuf> {
+    std::env::var_os("SVIRPTI_REPLAY_PATH")
+        .map(PathBuf::from)
+        .ok_or_else(|| SmtSolverError::RecordingIoError {
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "SVIRPTI_REPLAY_PATH is not set",
+            ),
+        })
+}
+
+fn is_rerecording() -> bool {
+    std::env::var_os("SVIRPTI_RERECORD").is_some()
+}
+
+pub struct RecordingConfiguration<Conf> {
+    /// The configuration of the wrapped solver.
+    pub solver: Conf,
+    /// Where to write the recording. If `None`, the events are only kept in
+    /// memory.
+    pub path: Option<PathBuf>,
+}
+
+/// A solver that forwards everything to `S` and records the interaction.
+pub struct RecordingSolver<S: SmtSolver> {
+    solver: S,
+    events: Vec<SolverEvent>,
+    writer: Option<std::io::BufWriter<std::fs::File>>,
+}
+
+impl<S: SmtSolver> RecordingSolver<S> {
+    pub fn wrap(solver: S, path: Option<PathBuf>) -> SmtSolverResult<Self> {
+        let writer = if let Some(path) = path {
+            Some(std::io::BufWriter::new(std::fs::File::create(path)?))
+        } else {
+            None
+        };
+        Ok(Self {
+            solver,
+            events: Vec::new(),
+            writer,
+        })
+    }
+    /// The events recorded so far.
+    pub fn events(&self) -> &[SolverEvent] {
+        &self.events
+    }
+    fn record(&mut self, event: SolverEvent) -> SmtSolverResult {
+        if let Some(writer) = &mut self.writer {
+            serde_json::to_writer(&mut *writer, &event)?;
+            writeln!(writer)?;
+            // Flush eagerly so that the recording survives a crash.
+            writer.flush()?;
+        }
+        self.events.push(event);
+        Ok(())
+    }
+}
+
+impl<S: SmtSolver> SmtSolver for RecordingSolver<S> {
+    type Conf = RecordingConfiguration<S::Conf>;
+    type Error = S::Error;
+    /// Wraps the default solver and writes the recording to the path given in
+    /// the `SVIRPTI_RECORD_PATH` environment variable, if set.
+    fn default() -> SmtSolverResult<Self> {
+        Self::wrap(S::default()?, get_record_path())
+    }
+    fn new(conf: Self::Conf) -> SmtSolverResult<Self> {
+        Self::wrap(S::new(conf.solver)?, conf.path)
+    }
+    fn declare_sort(
+        &mut self,
+        sort: &ast::UninterpretedSortDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver.declare_sort(sort, context)?;
+        self.record(SolverEvent::DeclareSort(sort.clone()))
+    }
+    fn declare_adts(
+        &mut self,
+        adts: &[ast::AdtDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver.declare_adts(adts, context)?;
+        self.record(SolverEvent::DeclareAdts(adts.to_vec()))
+    }
+    fn declare_function(
+        &mut self,
+        function: &ast::FunctionDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver.declare_function(function, context)?;
+        self.record(SolverEvent::DeclareFunction(function.clone()))
+    }
+    fn define_recursive_functions(
+        &mut self,
+        functions: &[ast::FunctionDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver.define_recursive_functions(functions, context)?;
+        self.record(SolverEvent::DefineRecursiveFunctions(functions.to_vec()))
+    }
+    fn declare_label(
+        &mut self,
+        label: &ast::LabelDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver.declare_label(label, context)?;
+        self.record(SolverEvent::DeclareLabel(label.clone()))
+    }
+    fn declare_variable(
+        &mut self,
+        variable: &ast::VariableDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver.declare_variable(variable, context)?;
+        self.record(SolverEvent::DeclareVariable(variable.clone()))
+    }
+    fn reset(&mut self) -> SmtSolverResult {
+        self.solver.reset()?;
+        self.record(SolverEvent::Reset)
+    }
+    fn set_option(&mut self, option: &str, value: &str) -> SmtSolverResult {
+        self.solver.set_option(option, value)?;
+        self.record(SolverEvent::SetOption(option.into(), value.into()))
+    }
+    fn set_logic(&mut self, logic: Logic) -> SmtSolverResult {
+        self.solver.set_logic(logic)?;
+        self.record(SolverEvent::SetLogic(logic))
+    }
+    fn push(&mut self) -> SmtSolverResult {
+        self.solver.push()?;
+        self.record(SolverEvent::Push)
+    }
+    fn pop(&mut self) -> SmtSolverResult {
+        self.solver.pop()?;
+        self.record(SolverEvent::Pop)
+    }
+    fn assert(
+        &mut self,
+        assertion: &ast::Expression,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.solver.assert(assertion, context)?;
+        self.record(SolverEvent::Assert(assertion.clone()))
+    }
+    fn check_sat(&mut self) -> SmtSolverResult<SatResult> {
+        let result = self.solver.check_sat()?;
+        self.record(SolverEvent::CheckSat(result))?;
+        Ok(result)
+    }
+    fn get_labels(
+        &mut self,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult<Vec<ast::LabelSymbol>> {
+        let labels = self.solver.get_labels(context)?;
+        self.record(SolverEvent::GetLabels(labels.clone()))?;
+        Ok(labels)
+    }
+    fn get_model(&mut self, context: &impl ast::Context) -> SmtSolverResult<ast::Model> {
+        let model = self.solver.get_model(context)?;
+        self.record(SolverEvent::GetModel(model.clone()))?;
+        Ok(model)
+    }
+    fn get_statistics(&mut self) -> SmtSolverResult<Statistics> {
+        let statistics = self.solver.get_statistics()?;
+        self.record(SolverEvent::GetStatistics(statistics.clone()))?;
+        Ok(statistics)
+    }
+    fn interrupter(&self) -> Option<Interrupter> {
+        self.solver.interrupter()
+    }
+}
+
+pub struct ReplayConfiguration {
+    /// The recording to replay.
+    pub path: PathBuf,
+}
+
+/// A solver that answers from a recording made by [`RecordingSolver`].
+///
+/// Every call must match the next recorded event; otherwise, the replay fails
+/// with [`SmtSolverError::ReplayMismatch`]. Assertions and function
+/// definitions are still printed, to a sink, so that a replay also checks that
+/// they can be sent to a solver.
+pub struct ReplaySolver {
+    events: std::vec::IntoIter<SolverEvent>,
+}
+
+impl ReplaySolver {
+    pub fn from_events(events: Vec<SolverEvent>) -> Self {
+        Self {
+            events: events.into_iter(),
+        }
+    }
+    /// Returns `true` if all recorded events were replayed.
+    pub fn is_finished(&self) -> bool {
+        self.events.as_slice().is_empty()
+    }
+    fn next_event(&mut self, found: &'static str) -> SmtSolverResult<SolverEvent> {
+        if let Some(event) = self.events.next() {
+            if event.kind() == found {
+                Ok(event)
+            } else {
+                Err(SmtSolverError::ReplayMismatch {
+                    expected: event.kind().into(),
+                    found: found.into(),
+                })
+            }
+        } else {
+            Err(SmtSolverError::ReplayExhausted {
+                found: found.into(),
+            })
+        }
+    }
+    fn expect(&mut self, found: SolverEvent) -> SmtSolverResult {
+        let expected = self.next_event(found.kind())?;
+        if expected == found {
+            Ok(())
+        } else {
+            Err(SmtSolverError::ReplayMismatch {
+                expected: format!("{:?}", expected),
+                found: format!("{:?}", found),
+            })
+        }
+    }
+}
+
+impl SmtSolver for ReplaySolver {
+    type Conf = ReplayConfiguration;
+    type Error = SmtSolverError;
+    /// Replays the recording given in the `SVIRPTI_REPLAY_PATH` environment
+    /// variable.
+    fn default() -> SmtSolverResult<Self> {
+        Self::new(ReplayConfiguration {
+            path: get_replay_path()?,
+        })
+    }
+    fn new(conf: Self::Conf) -> SmtSolverResult<Self> {
+        Ok(Self::from_events(read_recording(conf.path)?))
+    }
+    fn declare_sort(
+        &mut self,
+        sort: &ast::UninterpretedSortDeclaration,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.expect(SolverEvent::DeclareSort(sort.clone()))
+    }
+    fn declare_adts(
+        &mut self,
+        adts: &[ast::AdtDeclaration],
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.expect(SolverEvent::DeclareAdts(adts.to_vec()))
+    }
+    fn declare_function(
+        &mut self,
+        function: &ast::FunctionDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        if function.body.is_some() {
+            function.write_define_fun(&mut std::io::sink(), context)?;
+        }
+        self.expect(SolverEvent::DeclareFunction(function.clone()))
+    }
+    fn define_recursive_functions(
+        &mut self,
+        functions: &[ast::FunctionDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        ast::FunctionDeclaration::write_define_funs_rec(functions, &mut std::io::sink(), context)?;
+        self.expect(SolverEvent::DefineRecursiveFunctions(functions.to_vec()))
+    }
+    fn declare_label(
+        &mut self,
+        label: &ast::LabelDeclaration,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.expect(SolverEvent::DeclareLabel(label.clone()))
+    }
+    fn declare_variable(
+        &mut self,
+        variable: &ast::VariableDeclaration,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        self.expect(SolverEvent::DeclareVariable(variable.clone()))
+    }
+    fn reset(&mut self) -> SmtSolverResult {
+        self.expect(SolverEvent::Reset)
+    }
+    fn set_option(&mut self, option: &str, value: &str) -> SmtSolverResult {
+        self.expect(SolverEvent::SetOption(option.into(), value.into()))
+    }
+    fn set_logic(&mut self, logic: Logic) -> SmtSolverResult {
+        self.expect(SolverEvent::SetLogic(logic))
+    }
+    fn push(&mut self) -> SmtSolverResult {
+        self.expect(SolverEvent::Push)
+    }
+    fn pop(&mut self) -> SmtSolverResult {
+        self.expect(SolverEvent::Pop)
+    }
+    fn assert(
+        &mut self,
+        assertion: &ast::Expression,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        assertion.expr_to_smt2(&mut std::io::sink(), context)?;
+        self.expect(SolverEvent::Assert(assertion.clone()))
+    }
+    fn check_sat(&mut self) -> SmtSolverResult<SatResult> {
+        match self.next_event("check-sat")? {
+            SolverEvent::CheckSat(result) => Ok(result),
+            _ => unreachable!(),
+        }
+    }
+    fn get_labels(
+        &mut self,
+        _context: &impl ast::Context,
+    ) -> SmtSolverResult<Vec<ast::LabelSymbol>> {
+        match self.next_event("get-labels")? {
+            SolverEvent::GetLabels(labels) => Ok(labels),
+            _ => unreachable!(),
+        }
+    }
+    fn get_model(&mut self, _context: &impl ast::Context) -> SmtSolverResult<ast::Model> {
+        match self.next_event("get-model")? {
+            SolverEvent::GetModel(model) => Ok(model),
+            _ => unreachable!(),
+        }
+    }
+    fn get_statistics(&mut self) -> SmtSolverResult<Statistics> {
+        match self.next_event("get-statistics")? {
+            SolverEvent::GetStatistics(statistics) => Ok(statistics),
+            _ => unreachable!(),
+        }
+    }
+    /// Replaying does not block, so there is nothing to interrupt.
+    fn interrupter(&self) -> Option<Interrupter> {
+        None
+    }
+}
+
+pub struct RecordedConfiguration<Conf> {
+    /// The configuration of the solver used for re-recording.
+    pub solver: Conf,
+    /// The recording to replay or to write.
+    pub path: PathBuf,
+}
+
+/// A solver for tests that replays the recording at a path, so that the tests
+/// run without `S` being installed. If the `SVIRPTI_RERECORD` environment
+/// variable is set, it runs `S` instead and writes a new recording to the
+/// path. A missing recording is an error rather than being recorded silently.
+pub enum RecordedSolver<S: SmtSolver> {
+    Record(RecordingSolver<S>),
+    Replay(ReplaySolver),
+}
+
+impl<S: SmtSolver> RecordedSolver<S> {
+    /// Replays or records `path` with the default configuration of `S`.
+    pub fn open(path: impl Into<PathBuf>) -> SmtSolverResult<Self> {
+        let path = path.into();
+        if is_rerecording() {
+            Ok(Self::Record(RecordingSolver::wrap(
+                S::default()?,
+                Some(path),
+            )?))
+        } else {
+            Self::replay(path)
+        }
+    }
+    fn replay(path: PathBuf) -> SmtSolverResult<Self> {
+        if !path.exists() {
+            return Err(SmtSolverError::RecordingMissing { path });
+        }
+        Ok(Self::Replay(ReplaySolver::new(ReplayConfiguration {
+            path,
+        })?))
+    }
+}
+
+/// Forwards a call to the active solver.
+macro_rules! forward {
+    ($self: ident . $method: ident ( $($arg: expr),* )) => {
+        match $self {
+            RecordedSolver::Record(solver) => solver.$method($($arg),*),
+            RecordedSolver::Replay(solver) => solver.$method($($arg),*),
+        }
+    };
+}
+
+impl<S: SmtSolver> SmtSolver for RecordedSolver<S> {
+    type Conf = RecordedConfiguration<S::Conf>;
+    type Error = S::Error;
+    /// Uses the recording given in the `SVIRPTI_REPLAY_PATH` environment
+    /// variable.
+    fn default() -> SmtSolverResult<Self> {
+        Self::open(get_replay_path()?)
+    }
+    fn new(conf: Self::Conf) -> SmtSolverResult<Self> {
+        if is_rerecording() {
+            Ok(Self::Record(RecordingSolver::wrap(
+                S::new(conf.solver)?,
+                Some(conf.path),
+            )?))
+        } else {
+            Self::replay(conf.path)
+        }
+    }
+    fn declare_sort(
+        &mut self,
+        sort: &ast::UninterpretedSortDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        forward!(self.declare_sort(sort, context))
+    }
+    fn declare_adts(
+        &mut self,
+        adts: &[ast::AdtDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        forward!(self.declare_adts(adts, context))
+    }
+    fn declare_function(
+        &mut self,
+        function: &ast::FunctionDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        forward!(self.declare_function(function, context))
+    }
+    fn define_recursive_functions(
+        &mut self,
+        functions: &[ast::FunctionDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        forward!(self.define_recursive_functions(functions, context))
+    }
+    fn declare_label(
+        &mut self,
+        label: &ast::LabelDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        forward!(self.declare_label(label, context))
+    }
+    fn declare_variable(
+        &mut self,
+        variable: &ast::VariableDeclaration,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        forward!(self.declare_variable(variable, context))
+    }
+    fn reset(&mut self) -> SmtSolverResult {
+        forward!(self.reset())
+    }
+    fn set_option(&mut self, option: &str, value: &str) -> SmtSolverResult {
+        forward!(self.set_option(option, value))
+    }
+    fn set_logic(&mut self, logic: Logic) -> SmtSolverResult {
+        forward!(self.set_logic(logic))
+    }
+    fn push(&mut self) -> SmtSolverResult {
+        forward!(self.push())
+    }
+    fn pop(&mut self) -> SmtSolverResult {
+        forward!(self.pop())
+    }
+    fn assert(
+        &mut self,
+        assertion: &ast::Expression,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        forward!(self.assert(assertion, context))
+    }
+    fn check_sat(&mut self) -> SmtSolverResult<SatResult> {
+        forward!(self.check_sat())
+    }
+    fn get_labels(
+        &mut self,
+        context: &impl ast::Context,
+    ) -> SmtSolverResult<Vec<ast::LabelSymbol>> {
+        forward!(self.get_labels(context))
+    }
+    fn get_model(&mut self, context: &impl ast::Context) -> SmtSolverResult<ast::Model> {
+        forward!(self.get_model(context))
+    }
+    fn get_statistics(&mut self) -> SmtSolverResult<Statistics> {
+        forward!(self.get_statistics())
+    }
+    fn interrupter(&self) -> Option<Interrupter> {
+        forward!(self.interrupter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ast, RecordingSolver, ReplaySolver, SolverEvent};
+    use crate::solvers::{test_context::StringContext, SatResult, SmtSolver, SmtSolverError};
+    use svirpti_vir::smt::{BinaryOperationHelpers, VariableHelpers};
+
+    fn recorded_session() -> Vec<SolverEvent> {
+        let x = ast::VariableDeclaration {
+            name: "x".into(),
+            sort: ast::Sort::Int,
+        };
+        vec![
+            SolverEvent::DeclareVariable(x),
+            SolverEvent::Push,
+            SolverEvent::Assert(ast::Expression::equals(
+                ast::Expression::variable("x".into()),
+                4.into(),
+            )),
+            SolverEvent::CheckSat(SatResult::Sat),
+            SolverEvent::GetLabels(vec![]),
+            SolverEvent::GetModel(ast::Model {
+                items: vec![ast::ModelItem {
+                    name: "x".into(),
+                    args: vec![],
+                    sort: ast::Sort::Int,
+                    value: ast::Value::Int(4.into()),
+                }],
+            }),
+            SolverEvent::Pop,
+        ]
+    }
+
+    fn run_session(solver: &mut impl SmtSolver, context: &StringContext) {
+        let x = ast::VariableDeclaration {
+            name: "x".into(),
+            sort: ast::Sort::Int,
+        };
+        solver.declare_variable(&x, context).unwrap();
+        solver.push().unwrap();
+        solver
+            .assert(
+                &ast::Expression::equals(ast::Expression::variable("x".into()), 4.into()),
+                context,
+            )
+            .unwrap();
+        assert_eq!(solver.check_sat().unwrap(), SatResult::Sat);
+        assert!(solver.get_labels(context).unwrap().is_empty());
+        let model = solver.get_model(context).unwrap();
+        assert_eq!(model.items[0].value, ast::Value::Int(4.into()));
+        solver.pop().unwrap();
+    }
+
+    #[test]
+    fn check_record_replay_round_trip() {
+        let context = StringContext::default();
+        let replay = ReplaySolver::from_events(recorded_session());
+        let mut solver = RecordingSolver::wrap(replay, None).unwrap();
+        run_session(&mut solver, &context);
+        assert!(solver.solver.is_finished());
+        assert_eq!(solver.events(), recorded_session().as_slice());
+    }
+
+    #[test]
+    fn check_recording_file() {
+        let context = StringContext::default();
+        let path =
+            std::env::temp_dir().join(format!("svirpti-recording-{}.jsonl", std::process::id()));
+        let replay = ReplaySolver::from_events(recorded_session());
+        let mut solver = RecordingSolver::wrap(replay, Some(path.clone())).unwrap();
+        run_session(&mut solver, &context);
+        drop(solver);
+        let events = super::read_recording(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(events, recorded_session());
+    }
+
+    #[test]
+    fn check_replay_mismatch() {
+        let context = StringContext::default();
+        let mut solver = ReplaySolver::from_events(recorded_session());
+        let y = ast::VariableDeclaration {
+            name: "y".into(),
+            sort: ast::Sort::Int,
+        };
+        assert!(matches!(
+            solver.declare_variable(&y, &context),
+            Err(SmtSolverError::ReplayMismatch { .. })
+        ));
+        assert!(matches!(
+            solver.check_sat(),
+            Err(SmtSolverError::ReplayMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,74 @@
+//! A minimal SMT context shared by the solver tests.
+
+use std::collections::HashMap;
+use svirpti_vir::smt as ast;
+
+#[derive(Default)]
+pub(crate) struct StringContext {
+    pub(crate) variables: HashMap<ast::VariableSymbol, ast::VariableDeclaration>,
+    pub(crate) functions: HashMap<ast::FunctionSymbol, ast::FunctionDeclaration>,
+    pub(crate) adts: Vec<ast::AdtDeclaration>,
+    pub(crate) bound_variables: ast::BoundVariables,
+}
+
+impl ast::Context for StringContext {
+    fn write_uninterpreted_sort_name<Writer: std::io::Write>(
+        &self,
+        writer: &mut Writer,
+        symbol: &ast::UninterpretedSortSymbol,
+    ) -> rsmt2::SmtRes<()> {
+        write!(writer, "{}", symbol)?;
+        Ok(())
+    }
+    fn write_variable_symbol<Writer: std::io::Write>(
+        &self,
+        writer: &mut Writer,
+        symbol: &ast::VariableSymbol,
+    ) -> rsmt2::SmtRes<()> {
+        write!(writer, "{}", symbol)?;
+        Ok(())
+    }
+    fn write_label_symbol<Writer: std::io::Write>(
+        &self,
+        writer: &mut Writer,
+        symbol: &ast::VariableSymbol,
+    ) -> rsmt2::SmtRes<()> {
+        write!(writer, "{}", symbol)?;
+        Ok(())
+    }
+    fn get_variable_sort<'a>(&'a self, variable: &'a ast::VariableSymbol) -> &'a ast::Sort {
+        &self.variables[variable].sort
+    }
+    fn get_function_sort<'a>(&'a self, function: &'a ast::FunctionSymbol) -> &'a ast::Sort {
+        &self.functions[function].return_sort
+    }
+    fn get_field_sort<'a>(&'a self, field: &'a ast::FieldNameSymbol) -> &'a ast::Sort {
+        self.adts
+            .iter()
+            .flat_map(|adt| &adt.constructors)
+            .flat_map(|constructor| &constructor.fields)
+            .find(|declaration| &declaration.name == field)
+            .map(|declaration| &declaration.sort)
+            .unwrap()
+    }
+    fn get_constructor_fields<'a>(
+        &'a self,
+        constructor: &'a ast::AdtConstructorSymbol,
+    ) -> &'a [ast::AdtFieldDeclaration] {
+        self.adts
+            .iter()
+            .flat_map(|adt| &adt.constructors)
+            .find(|declaration| &declaration.name == constructor)
+            .map(|declaration| declaration.fields.as_slice())
+            .unwrap()
+    }
+    fn is_adt(&self, name: &ast::AdtNameSymbol) -> bool {
+        self.adts.iter().any(|adt| &adt.name == name)
+    }
+    fn resolve_ident(&self, ident: &str) -> rsmt2::SmtRes<ast::IdentSymbol> {
+        Ok(ident.into())
+    }
+    fn bound_variables(&self) -> &ast::BoundVariables {
+        &self.bound_variables
+    }
+}
@@ -1,9 +1,12 @@
-use super::{SatResult, SmtSolverError, SmtSolverResult};
+use super::{Logic, OptionProfile, SatResult, SmtSolverError, SmtSolverResult, Statistics};
+use crate::cancellation::Interrupter;
 use rsmt2::{
     parse::{IdentParser, ModelParser},
     SmtConf, SmtRes, Solver,
 };
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use svirpti_vir::common::integer::BigInt;
 use svirpti_vir::smt as ast;
 
 #[derive(Clone, Copy)]
@@ -29,13 +32,27 @@ impl<'a, 'b, C: ast::Context>
             "Bool" => Ok(ast::Sort::Bool),
             "Int" => Ok(ast::Sort::Int),
             "Real" => Ok(ast::Sort::Real),
-            name => Ok(ast::Sort::Uninterpreted {
-                name: context.resolve_ident(name)?,
-            }),
+            _ if input.starts_with('(') => crate::parser::parse_model_sort(input, &|name| {
+                context
+                    .resolve_ident(name)
+                    .ok()
+                    .map(|name| sort_named(name, context))
+            })
+            .map_err(|error| error.to_string().into()),
+            name => Ok(sort_named(context.resolve_ident(name)?, context)),
         }
     }
 }
 
+/// The datatype or uninterpreted sort called `name`.
+fn sort_named(name: ast::IdentSymbol, context: &impl ast::Context) -> ast::Sort {
+    if context.is_adt(&name) {
+        ast::Sort::Adt { name }
+    } else {
+        ast::Sort::Uninterpreted { name }
+    }
+}
+
 impl<'a, Br: ::std::io::BufRead>
     ModelParser<ast::IdentSymbol, ast::Sort, ast::Value, &'a mut rsmt2::parse::SmtParser<Br>>
     for Parser
@@ -44,14 +61,19 @@ impl<'a, Br: ::std::io::BufRead>
         self,
         parser: &'a mut rsmt2::parse::SmtParser<Br>,
         _name: &ast::IdentSymbol,
-        _args: &[(ast::IdentSymbol, ast::Sort)],
+        args: &[(ast::IdentSymbol, ast::Sort)],
         out_sort: &ast::Sort,
     ) -> SmtRes<ast::Value> {
+        if !args.is_empty() {
+            // The interpretation of a function, which is only used to resolve
+            // the arrays that refer to it.
+            return Ok(ast::Value::Opaque(parser.get_sexpr()?.to_string()));
+        }
         match out_sort {
             ast::Sort::Bool => parser.bool().map(ast::Value::Bool),
             ast::Sort::Int => {
                 let value = parser.try_int(|input, positive| {
-                    i64::from_str(input).map(|num| if positive { num } else { -num })
+                    BigInt::from_str(input).map(|num| if positive { num } else { -num })
                 })?;
                 if let Some(number) = value {
                     Ok(ast::Value::Int(number))
@@ -59,6 +81,17 @@ impl<'a, Br: ::std::io::BufRead>
                     parser.fail_with("expected integer")
                 }
             }
+            ast::Sort::Real
+            | ast::Sort::BitVector { .. }
+            | ast::Sort::Float { .. }
+            | ast::Sort::Array { .. }
+            | ast::Sort::Seq { .. }
+            | ast::Sort::Set { .. }
+            | ast::Sort::Adt { .. } => {
+                let value = parser.get_sexpr()?;
+                crate::parser::parse_model_value(value, out_sort)
+                    .map_err(|error| error.to_string().into())
+            }
             x => unimplemented!("{:?}", x),
         }
     }
@@ -96,14 +129,7 @@ impl Default for Configuration {
             .into_iter()
             .map(|attribute| (attribute.into()))
             .collect();
-        let options = vec![
-            (":AUTO_CONFIG", "false"),
-            (":smt.MBQI", "false"),
-            (":TYPE_CHECK", "true"),
-        ]
-        .into_iter()
-        .map(|(option, value)| (option.into(), value.into()))
-        .collect();
+        let options = OptionProfile::default_profile().options;
         // let tee_path = Some("/tmp/test.smt2".into());
         let tee_path = None;
         Self {
@@ -118,6 +144,13 @@ impl Default for Configuration {
 // TODO: Rename to not mention Z3 in its name.
 pub struct Z3SmtSolver {
     solver: Solver<Parser>,
+    /// Whether the solver process has not been reaped yet, so that its pid
+    /// cannot have been reused by another process. Shared with the
+    /// interrupters.
+    process_alive: Arc<Mutex<bool>>,
+    /// Kept for re-applying after a `reset`.
+    attributes: Vec<String>,
+    options: Vec<(String, String)>,
 }
 
 impl std::convert::From<rsmt2::errors::Error> for SmtSolverError {
@@ -135,13 +168,32 @@ impl Z3SmtSolver {
         if let Some(tee_path) = conf.tee_path {
             solver.path_tee(tee_path).unwrap();
         }
-        for attribute in &conf.attributes {
-            solver.set_info(attribute)?;
+        let mut solver = Self {
+            solver,
+            process_alive: Arc::new(Mutex::new(true)),
+            attributes: conf.attributes,
+            options: conf.options,
+        };
+        solver.configure()?;
+        Ok(solver)
+    }
+    fn configure(&mut self) -> SmtSolverResult {
+        for attribute in &self.attributes {
+            self.solver.set_info(attribute)?;
         }
-        for (option, value) in &conf.options {
-            solver.set_option(option, value)?;
+        for (option, value) in &self.options {
+            self.solver.set_option(option, value)?;
         }
-        Ok(Self { solver })
+        Ok(())
+    }
+    /// Sends a command that rsmt2 cannot print, such as a declaration that
+    /// involves our sorts.
+    fn send_command(&mut self, mut command: Vec<u8>) -> SmtSolverResult {
+        command.push(b'\n');
+        std::io::Write::write_all(&mut self.solver, &command)
+            .and_then(|()| std::io::Write::flush(&mut self.solver))
+            .map_err(rsmt2::errors::Error::from)?;
+        Ok(())
     }
     /// We cannot use the `Default` trait because this is potentially failing
     /// operation.
@@ -150,6 +202,33 @@ impl Z3SmtSolver {
     }
 }
 
+impl Drop for Z3SmtSolver {
+    fn drop(&mut self) {
+        // The fields are dropped after this, so the process is reaped by
+        // `solver` only once no interrupter can signal it anymore.
+        *self.process_alive.lock().unwrap() = false;
+    }
+}
+
+/// Parses the response to `(get-info :all-statistics)`, which looks like
+/// `(:conflicts 2 :decisions 10 :memory 19.31)`. Non-numeric values are
+/// skipped.
+fn parse_statistics(response: &str) -> Statistics {
+    let mut statistics = Statistics::default();
+    let tokens: Vec<_> = response
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|token| !token.is_empty())
+        .collect();
+    for pair in tokens.windows(2) {
+        if let Some(key) = pair[0].strip_prefix(':') {
+            if let Ok(value) = f64::from_str(pair[1]) {
+                statistics.values.insert(key.into(), value);
+            }
+        }
+    }
+    statistics
+}
+
 fn get_z3_path() -> String {
     std::env::var("Z3_EXE").unwrap_or_else(|_err| "z3".into())
 }
@@ -174,11 +253,27 @@ impl super::SmtSolver for Z3SmtSolver {
         self.solver.declare_sort_with(sort, 0, context)?;
         Ok(())
     }
+    fn declare_adts(
+        &mut self,
+        adts: &[ast::AdtDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        // rsmt2 cannot print datatypes with our sorts, so we send the command
+        // ourselves.
+        let mut command = Vec::new();
+        ast::AdtDeclaration::write_declare_datatypes(adts, &mut command, context)?;
+        self.send_command(command)
+    }
     fn declare_function(
         &mut self,
         function: &ast::FunctionDeclaration,
         context: &impl ast::Context,
     ) -> SmtSolverResult {
+        if function.body.is_some() {
+            let mut command = Vec::new();
+            function.write_define_fun(&mut command, context)?;
+            return self.send_command(command);
+        }
         self.solver.declare_fun_with::<_, _, ast::Sort, _, _>(
             &function.name,
             &function
@@ -191,6 +286,15 @@ impl super::SmtSolver for Z3SmtSolver {
         )?;
         Ok(())
     }
+    fn define_recursive_functions(
+        &mut self,
+        functions: &[ast::FunctionDeclaration],
+        context: &impl ast::Context,
+    ) -> SmtSolverResult {
+        let mut command = Vec::new();
+        ast::FunctionDeclaration::write_define_funs_rec(functions, &mut command, context)?;
+        self.send_command(command)
+    }
     fn declare_label(
         &mut self,
         label: &ast::LabelDeclaration,
@@ -217,6 +321,35 @@ impl super::SmtSolver for Z3SmtSolver {
         )?;
         Ok(())
     }
+    /// Sends `(reset)`, which also restores the default options, and then
+    /// configures the solver again.
+    fn reset(&mut self) -> SmtSolverResult {
+        self.solver.reset()?;
+        self.configure()
+    }
+    fn set_option(&mut self, option: &str, value: &str) -> SmtSolverResult {
+        self.solver.set_option(option, value)?;
+        Ok(())
+    }
+    fn set_logic(&mut self, logic: Logic) -> SmtSolverResult {
+        let logic = match logic {
+            Logic::QF_UF => rsmt2::Logic::QF_UF,
+            Logic::QF_LIA => rsmt2::Logic::QF_LIA,
+            Logic::QF_NIA => rsmt2::Logic::QF_NIA,
+            Logic::QF_LRA => rsmt2::Logic::QF_LRA,
+            Logic::QF_UFLIA => rsmt2::Logic::QF_UFLIA,
+            Logic::QF_UFLRA => rsmt2::Logic::QF_UFLRA,
+            Logic::QF_UFNRA => rsmt2::Logic::QF_UFNRA,
+            Logic::QF_BV => rsmt2::Logic::QF_BV,
+            Logic::QF_UFBV => rsmt2::Logic::QF_UFBV,
+            Logic::QF_AX => rsmt2::Logic::QF_AX,
+            Logic::QF_AUFLIA => rsmt2::Logic::QF_AUFLIA,
+            Logic::QF_ABV => rsmt2::Logic::QF_ABV,
+            Logic::QF_AUFBV => rsmt2::Logic::QF_AUFBV,
+        };
+        self.solver.set_logic(logic)?;
+        Ok(())
+    }
     fn push(&mut self) -> SmtSolverResult {
         self.solver.push(1)?;
         Ok(())
@@ -261,65 +394,71 @@ impl super::SmtSolver for Z3SmtSolver {
                 value,
             })
         }
+        crate::parser::resolve_as_array_values(&mut model);
         Ok(model)
     }
+    fn get_statistics(&mut self) -> SmtSolverResult<Statistics> {
+        let response = self.solver.get_info(":all-statistics")?;
+        Ok(parse_statistics(&response))
+    }
+    /// Kills the solver process. The solver cannot be used afterwards.
+    #[cfg(unix)]
+    fn interrupter(&self) -> Option<Interrupter> {
+        let pid = self.solver.child_id() as libc::pid_t;
+        let process_alive = self.process_alive.clone();
+        Some(Box::new(move || {
+            // Holding the lock keeps `drop` from letting the process be
+            // reaped while it is signalled.
+            let process_alive = process_alive.lock().unwrap();
+            if *process_alive {
+                // SAFETY: `kill` does not access memory. `pid` is the pid of
+                // our child process, which is not reaped while
+                // `process_alive` is set, so the pid is not reused yet.
+                unsafe {
+                    libc::kill(pid, libc::SIGKILL);
+                }
+            }
+        }))
+    }
+    #[cfg(not(unix))]
+    fn interrupter(&self) -> Option<Interrupter> {
+        None
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-
-    use super::{ast, Z3SmtSolver};
-    use crate::solvers::SmtSolver;
+    use super::{ast, parse_statistics, Z3SmtSolver};
+    use crate::solvers::{recording::RecordedSolver, test_context::StringContext, SmtSolver};
     use svirpti_vir::smt::{
         BinaryOperationHelpers, FunctionApplicationHelpers, LabelledExpressionHelpers,
         QuantifierHelpers, UnaryOperationHelpers, VariableHelpers,
     };
 
-    #[derive(Default)]
-    struct StringContext {
-        variables: HashMap<ast::VariableSymbol, ast::VariableDeclaration>,
-        functions: HashMap<ast::FunctionSymbol, ast::FunctionDeclaration>,
-    }
-    impl ast::Context for StringContext {
-        fn write_uninterpreted_sort_name<Writer: std::io::Write>(
-            &self,
-            writer: &mut Writer,
-            symbol: &ast::UninterpretedSortSymbol,
-        ) -> rsmt2::SmtRes<()> {
-            write!(writer, "{}", symbol)?;
-            Ok(())
-        }
-        fn write_variable_symbol<Writer: std::io::Write>(
-            &self,
-            writer: &mut Writer,
-            symbol: &ast::VariableSymbol,
-        ) -> rsmt2::SmtRes<()> {
-            write!(writer, "{}", symbol)?;
-            Ok(())
-        }
-        fn write_label_symbol<Writer: std::io::Write>(
-            &self,
-            writer: &mut Writer,
-            symbol: &ast::VariableSymbol,
-        ) -> rsmt2::SmtRes<()> {
-            write!(writer, "{}", symbol)?;
-            Ok(())
-        }
-        fn get_variable_sort<'a>(&'a self, variable: &'a ast::VariableSymbol) -> &'a ast::Sort {
-            &self.variables[variable].sort
-        }
-        fn get_function_sort<'a>(&'a self, function: &'a ast::FunctionSymbol) -> &'a ast::Sort {
-            &self.functions[function].return_sort
-        }
-        fn resolve_ident(&self, ident: &str) -> rsmt2::SmtRes<ast::IdentSymbol> {
-            Ok(ident.into())
-        }
+    /// Replays the recording of the test `name`. Set `SVIRPTI_RERECORD` to run
+    /// Z3 and record the test again.
+    fn recorded_z3(name: &str) -> RecordedSolver<Z3SmtSolver> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/solvers/recordings")
+            .join(format!("{}.jsonl", name));
+        RecordedSolver::open(path).unwrap()
     }
+
+    #[test]
+    fn check_parse_statistics() {
+        let statistics = parse_statistics(
+            "(:added-eqs 5\n :conflicts 2\n :max-memory 19.46\n :rlimit-count 312)",
+        );
+        assert_eq!(statistics.values.len(), 4);
+        assert_eq!(statistics.get("conflicts"), Some(2.0));
+        assert_eq!(statistics.get("max-memory"), Some(19.46));
+        assert_eq!(statistics.get("decisions"), None);
+    }
+
     #[test]
     fn check_z3_installation() {
         let mut context = StringContext::default();
-        let mut z3 = Z3SmtSolver::default().unwrap();
+        let mut z3 = recorded_z3("check_z3_installation");
         let x = ast::VariableDeclaration {
             name: "x".into(),
             sort: ast::Sort::Int,
@@ -344,7 +483,7 @@ mod tests {
     #[test]
     fn check_quantifiers() {
         let mut context = StringContext::default();
-        let mut z3: Z3SmtSolver = Z3SmtSolver::default().unwrap();
+        let mut z3 = recorded_z3("check_quantifiers");
         z3.declare_sort(
             &ast::UninterpretedSortDeclaration { name: "Nat".into() },
             &context,
@@ -355,6 +494,8 @@ mod tests {
             name: "zero".into(),
             parameters: vec![],
             return_sort: nat.clone(),
+            preconditions: Vec::new(),
+            body: None,
         };
         z3.declare_function(&zero, &context).unwrap();
         context.functions.insert("zero".into(), zero);
@@ -366,6 +507,8 @@ mod tests {
                 sort: nat.clone(),
             }],
             return_sort: nat.clone(),
+            preconditions: Vec::new(),
+            body: None,
         };
         z3.declare_function(&succ, &context).unwrap();
         context.functions.insert("succ".into(), succ);
@@ -377,6 +520,8 @@ mod tests {
                 sort: nat.clone(),
             }],
             return_sort: ast::Sort::Int,
+            preconditions: Vec::new(),
+            body: None,
         };
         z3.declare_function(&count, &context).unwrap();
         context.functions.insert("count".into(), count);
@@ -467,10 +612,35 @@ mod tests {
 
         assert_eq!(z3.check_sat().unwrap(), crate::solvers::SatResult::Unsat);
     }
+    #[test]
+    fn check_quantified_bit_vectors() {
+        let context = StringContext::default();
+        let mut z3 = recorded_z3("check_quantified_bit_vectors");
+        // !(forall b: (_ BitVec 8) :: !(!b) == b)
+        z3.assert(
+            &ast::Expression::not(ast::Expression::forall(
+                vec![ast::BoundedVariableDecl {
+                    name: "b".into(),
+                    sort: ast::Sort::BitVector { width: 8 },
+                }],
+                vec![],
+                ast::Expression::equals(
+                    ast::Expression::not(ast::Expression::not(ast::Expression::variable(
+                        "b".into(),
+                    ))),
+                    ast::Expression::variable("b".into()),
+                ),
+            )),
+            &context,
+        )
+        .unwrap();
+        assert_eq!(z3.check_sat().unwrap(), crate::solvers::SatResult::Unsat);
+    }
+
     #[test]
     fn check_z3_labels() {
         let mut context = StringContext::default();
-        let mut z3: Z3SmtSolver = Z3SmtSolver::default().unwrap();
+        let mut z3 = recorded_z3("check_z3_labels");
         let k = ast::VariableDeclaration {
             name: "k".into(),
             sort: ast::Sort::Int,
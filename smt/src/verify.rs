@@ -1,18 +1,70 @@
-use crate::solvers::{self, SatResult, SmtSolver};
+use crate::cancellation::CancellationToken;
+use crate::functions;
+use crate::solvers::pool::{PooledSolver, SolverPool};
+use crate::solvers::{self, OptionProfile, SatResult, SmtSolver, Statistics};
 use solvers::SmtSolverError;
+use std::time::{Duration, Instant};
 use svirpti_vir::smt as ast;
 use thiserror::Error;
 
 #[derive(Debug)]
 pub enum VerificationResult<S: SmtSolver> {
-    Success,
+    Success(QueryStatistics),
     Failure(VerificationFailure<S>),
+    /// The cancellation token was cancelled before the verification
+    /// finished.
+    Cancelled,
+}
+
+impl<S: SmtSolver> VerificationResult<S> {
+    /// The statistics of the verification query, unless it was cancelled.
+    pub fn get_statistics(&self) -> Option<&QueryStatistics> {
+        match self {
+            VerificationResult::Success(statistics) => Some(statistics),
+            VerificationResult::Failure(failure) => Some(failure.get_statistics()),
+            VerificationResult::Cancelled => None,
+        }
+    }
+}
+
+/// The cost of a single `check_sat`.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct QueryStatistics {
+    /// Wall-clock time spent in `check_sat`.
+    pub duration: Duration,
+    /// The statistics reported by the solver.
+    pub solver: Statistics,
+}
+
+fn check_sat_with_statistics<S: SmtSolver>(
+    solver: &mut S,
+) -> VerifierResult<(SatResult, QueryStatistics)> {
+    let start = Instant::now();
+    let result = solver.check_sat()?;
+    let duration = start.elapsed();
+    let statistics = QueryStatistics {
+        duration,
+        solver: solver.get_statistics()?,
+    };
+    Ok((result, statistics))
+}
+
+/// Solver settings for a single verification query.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QueryConfiguration {
+    /// Options set before the query. `None` keeps the options the solver was
+    /// configured with.
+    pub profile: Option<OptionProfile>,
+    /// Whether to set the logic of quantifier-free queries.
+    pub infer_logic: bool,
 }
 
 #[derive(Debug, Error)]
 pub enum VerifierError {
     #[error("smt solver error")]
     SmtSolverError(#[from] SmtSolverError),
+    #[error("verification was cancelled")]
+    Cancelled,
 }
 
 pub type VerifierResult<T = ()> = Result<T, VerifierError>;
@@ -21,28 +73,74 @@ pub fn verify<S: SmtSolver, C: ast::Context>(
     context: &C,
     declarations: &ast::Declarations,
     assertions: &[ast::Expression],
+) -> VerifierResult<VerificationResult<S>> {
+    verify_cancellable(context, declarations, assertions, &CancellationToken::new())
+}
+
+/// Same as [`verify`], but stops early with `VerificationResult::Cancelled`
+/// once `cancellation` is cancelled.
+pub fn verify_cancellable<S: SmtSolver, C: ast::Context>(
+    context: &C,
+    declarations: &ast::Declarations,
+    assertions: &[ast::Expression],
+    cancellation: &CancellationToken,
 ) -> VerifierResult<VerificationResult<S>> {
     let solver = S::default()?;
-    verify_with_solver(solver, context, declarations, assertions)
+    verify_with_solver(
+        solver,
+        context,
+        declarations,
+        assertions,
+        &QueryConfiguration::default(),
+        cancellation,
+    )
+}
+
+/// Same as [`verify_cancellable`], but takes the solver from `pool`, so that
+/// consecutive runs reuse the solvers.
+pub fn verify_with_pool<S: SmtSolver + 'static, C: ast::Context>(
+    pool: &SolverPool<S>,
+    context: &C,
+    declarations: &ast::Declarations,
+    assertions: &[ast::Expression],
+    configuration: &QueryConfiguration,
+    cancellation: &CancellationToken,
+) -> VerifierResult<VerificationResult<PooledSolver<S>>> {
+    let solver = pool.acquire()?;
+    verify_with_solver(
+        solver,
+        context,
+        declarations,
+        assertions,
+        configuration,
+        cancellation,
+    )
 }
 
 #[derive(Debug)]
 pub struct VerificationFailure<S: SmtSolver> {
     investigator: VerificationFailureInvestigator<S>,
+    statistics: QueryStatistics,
 }
 
 impl<S: SmtSolver> VerificationFailure<S> {
     pub fn get_investigator(&mut self) -> &mut VerificationFailureInvestigator<S> {
         &mut self.investigator
     }
+    /// The statistics of the verification query that failed.
+    pub fn get_statistics(&self) -> &QueryStatistics {
+        &self.statistics
+    }
 }
 
 /// A struct that allows investigating a verification failure by checking the
 /// state with different labels and obtaining models.
 pub struct VerificationFailureInvestigator<S: SmtSolver> {
     solver: S,
+    cancellation: CancellationToken,
     labels: Option<Vec<ast::LabelSymbol>>,
     model: Option<ast::Model>,
+    queries: Vec<QueryStatistics>,
 }
 
 impl<S: SmtSolver> std::fmt::Debug for VerificationFailureInvestigator<S> {
@@ -52,23 +150,43 @@ impl<S: SmtSolver> std::fmt::Debug for VerificationFailureInvestigator<S> {
 }
 
 impl<S: SmtSolver> VerificationFailureInvestigator<S> {
-    fn new(solver: S) -> Self {
+    fn new(solver: S, cancellation: CancellationToken) -> Self {
         Self {
             solver,
+            cancellation,
             labels: None,
             model: None,
+            queries: Vec::new(),
         }
     }
+    /// The statistics of all queries made with `check_with`.
+    pub fn get_query_statistics(&self) -> &[QueryStatistics] {
+        &self.queries
+    }
     pub fn check_with(
         &mut self,
         assertions: &[ast::Expression],
         context: &impl ast::Context,
     ) -> VerifierResult<SatResult> {
+        if self.cancellation.is_cancelled() {
+            return Err(VerifierError::Cancelled);
+        }
         self.solver.push()?;
         for assertion in assertions {
             self.solver.assert(assertion, context)?;
         }
-        let result = self.solver.check_sat()?;
+        let query = {
+            let _guard = self
+                .solver
+                .interrupter()
+                .map(|interrupter| self.cancellation.on_cancel(interrupter));
+            check_sat_with_statistics(&mut self.solver)
+        };
+        if self.cancellation.is_cancelled() {
+            return Err(VerifierError::Cancelled);
+        }
+        let (result, statistics) = query?;
+        self.queries.push(statistics);
         if result != SatResult::Unsat {
             self.labels = Some(self.solver.get_labels(context)?);
             self.model = Some(self.solver.get_model(context)?);
@@ -95,17 +213,77 @@ impl<S: SmtSolver> VerificationFailureInvestigator<S> {
     }
 }
 
-fn verify_with_solver<S: SmtSolver, C: ast::Context>(
+/// Same as [`verify_cancellable`], but uses the given `solver` instead of a
+/// default one.
+pub fn verify_with_solver<S: SmtSolver, C: ast::Context>(
     mut solver: S,
     context: &C,
     declarations: &ast::Declarations,
     assertions: &[ast::Expression],
+    configuration: &QueryConfiguration,
+    cancellation: &CancellationToken,
 ) -> VerifierResult<VerificationResult<S>> {
+    if cancellation.is_cancelled() {
+        return Ok(VerificationResult::Cancelled);
+    }
+    let query = {
+        let _guard = solver
+            .interrupter()
+            .map(|interrupter| cancellation.on_cancel(interrupter));
+        check_query(
+            &mut solver,
+            context,
+            declarations,
+            assertions,
+            configuration,
+        )
+    };
+    // An interrupted solver may report an error or a bogus result.
+    if cancellation.is_cancelled() {
+        return Ok(VerificationResult::Cancelled);
+    }
+    let (sat_result, statistics) = query?;
+    if sat_result == SatResult::Unsat {
+        Ok(VerificationResult::Success(statistics))
+    } else {
+        let investigator = VerificationFailureInvestigator::new(solver, cancellation.clone());
+        Ok(VerificationResult::Failure(VerificationFailure {
+            investigator,
+            statistics,
+        }))
+    }
+}
+
+fn check_query<S: SmtSolver, C: ast::Context>(
+    solver: &mut S,
+    context: &C,
+    declarations: &ast::Declarations,
+    assertions: &[ast::Expression],
+    configuration: &QueryConfiguration,
+) -> VerifierResult<(SatResult, QueryStatistics)> {
+    if let Some(profile) = &configuration.profile {
+        for (option, value) in &profile.options {
+            solver.set_option(option, value)?;
+        }
+    }
+    if configuration.infer_logic {
+        if let Some(logic) = solvers::infer_logic(declarations, assertions) {
+            solver.set_logic(logic)?;
+        }
+    }
     for sort in &declarations.sorts {
         solver.declare_sort(sort, context)?;
     }
-    for function in &declarations.functions {
-        solver.declare_function(function, context)?;
+    if !declarations.adts.is_empty() {
+        solver.declare_adts(&declarations.adts, context)?;
+    }
+    for group in functions::function_groups(&declarations.functions) {
+        if group.recursive {
+            let functions: Vec<_> = group.functions.into_iter().cloned().collect();
+            solver.define_recursive_functions(&functions, context)?;
+        } else {
+            solver.declare_function(group.functions[0], context)?;
+        }
     }
     for label in &declarations.labels {
         solver.declare_label(label, context)?;
@@ -117,13 +295,5 @@ fn verify_with_solver<S: SmtSolver, C: ast::Context>(
     for assertion in assertions {
         solver.assert(assertion, context)?;
     }
-    let sat_result = solver.check_sat()?;
-    if sat_result == SatResult::Unsat {
-        Ok(VerificationResult::Success)
-    } else {
-        let investigator = VerificationFailureInvestigator::new(solver);
-        Ok(VerificationResult::Failure(VerificationFailure {
-            investigator,
-        }))
-    }
+    check_sat_with_statistics(solver)
 }
@@ -0,0 +1,65 @@
+use svirpti_smt::solvers::OptionProfile;
+use svirpti_smt::QueryConfiguration;
+use svirpti_vir::high;
+
+/// Settings for verifying a program fragment.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VerificationConfig {
+    /// The option profile for the procedure. `None` keeps the options the
+    /// solver was configured with.
+    pub procedure_profile: Option<OptionProfile>,
+    /// Option profiles that override the procedure profile if the procedure
+    /// contains an assertion with the given label.
+    ///
+    /// All assertions of a procedure are checked in a single query, so a
+    /// profile applies to the whole procedure, not only to its assertion. The
+    /// profiles are merged into the procedure profile in the order of the
+    /// assertions.
+    pub procedure_profile_overrides: Vec<(high::LabelSymbol, OptionProfile)>,
+    /// Whether to set the logic of quantifier-free queries. Off by default,
+    /// because a solver rejects a query that does not fit the set logic.
+    pub infer_logic: bool,
+    /// How many times recursive functions may be unfolded. `None` defines
+    /// them with `define-funs-rec` instead, which leaves the unfolding to the
+    /// solver.
+    pub recursive_function_fuel: Option<u32>,
+    /// Whether to simplify the expressions when lowering and encoding the
+    /// program.
+    pub simplify: bool,
+}
+
+impl VerificationConfig {
+    pub(crate) fn query_configuration(
+        &self,
+        program: &high::ProgramFragment,
+    ) -> QueryConfiguration {
+        let mut profile = self.procedure_profile.clone();
+        let labels = program
+            .procedure
+            .basic_blocks
+            .iter()
+            .flat_map(|block| block.statements.iter())
+            .filter_map(|statement| match statement {
+                high::Statement::Assert(high::Assert {
+                    label: Some(label), ..
+                }) => Some(label),
+                _ => None,
+            });
+        for label in labels {
+            for (_, assertion_profile) in self
+                .procedure_profile_overrides
+                .iter()
+                .filter(|(profile_label, _)| profile_label == label)
+            {
+                profile = Some(match profile {
+                    Some(profile) => profile.merge(assertion_profile),
+                    None => assertion_profile.clone(),
+                });
+            }
+        }
+        QueryConfiguration {
+            profile,
+            infer_logic: self.infer_logic,
+        }
+    }
+}
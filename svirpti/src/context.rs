@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use svirpti_vir::common::position::Position;
+use svirpti_vir::common::symbol::Symbol;
 use svirpti_vir::{high, low, smt};
 
 pub trait Context {
@@ -10,24 +13,34 @@ pub trait Context {
         &mut self,
         name: &high::UninterpretedSortSymbol,
     ) -> low::UninterpretedSortSymbol;
-    fn lower_reference_name(&mut self, name: &high::AdtNameSymbol) -> low::UninterpretedSortSymbol;
+    fn lower_reference_name(&mut self, name: &high::AdtNameSymbol) -> low::AdtNameSymbol;
     fn lower_label(&mut self, label: &high::LabelSymbol) -> low::LabelSymbol;
+    /// The label under which a failure at `position` is reported.
+    fn create_label_for_position(&mut self, position: Position) -> low::LabelSymbol;
     fn convert_variable_name_to_smt(&mut self, name: &low::VariableSymbol) -> smt::VariableSymbol;
     fn convert_uninterpreted_sort_to_smt(
         &mut self,
         name: &low::UninterpretedSortSymbol,
     ) -> smt::UninterpretedSortSymbol;
+    fn convert_adt_name_to_smt(&mut self, name: &low::AdtNameSymbol) -> smt::AdtNameSymbol;
     fn convert_function_name_to_smt(&mut self, name: &low::FunctionSymbol) -> smt::FunctionSymbol;
     fn convert_label_name_to_smt(&mut self, name: &low::LabelSymbol) -> smt::LabelSymbol;
     fn convert_known_label_name_to_smt(&self, name: &low::LabelSymbol) -> smt::LabelSymbol;
     fn create_label_for_basic_block(&mut self, id: low::BasicBlockId) -> smt::VariableSymbol;
     fn resolve_high_label(&self, name: &low::LabelSymbol) -> high::LabelSymbol;
+    /// The position of a label created by `create_label_for_position`.
+    fn resolve_position(&self, name: &low::LabelSymbol) -> Option<Position>;
     fn resolve_high_variable(&self, name: &low::VariableSymbol) -> high::VariableSymbol;
     fn resolve_low_label(&self, name: &smt::LabelSymbol) -> low::LabelSymbol;
     fn resolve_low_variable(&self, name: &smt::VariableSymbol) -> low::VariableSymbol;
 }
 
-pub struct StringContext {}
+#[derive(Default)]
+pub struct StringContext {
+    /// The versioned variables created so far, so that lowering does not format
+    /// and intern the same name again for every occurrence of a variable.
+    versioned_variables: HashMap<(Symbol, usize), low::VariableSymbol>,
+}
 
 impl Context for StringContext {
     fn create_versioned_variable_symbol(
@@ -35,54 +48,66 @@ impl Context for StringContext {
         name: &high::VariableSymbol,
         version: usize,
     ) -> low::VariableSymbol {
-        format!("{}@{}", name, version).into()
+        self.versioned_variables
+            .entry((name.symbol(), version))
+            .or_insert_with(|| format!("{}@{}", name, version).into())
+            .clone()
     }
     fn lower_domain_name(
         &mut self,
         name: &high::UninterpretedSortSymbol,
     ) -> low::UninterpretedSortSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
-    fn lower_reference_name(&mut self, name: &high::AdtNameSymbol) -> low::UninterpretedSortSymbol {
-        name.as_string().into()
+    fn lower_reference_name(&mut self, name: &high::AdtNameSymbol) -> low::AdtNameSymbol {
+        name.symbol().into()
     }
     fn lower_label(&mut self, label: &high::LabelSymbol) -> low::LabelSymbol {
-        label.as_string().into()
+        label.symbol().into()
+    }
+    fn create_label_for_position(&mut self, position: Position) -> low::LabelSymbol {
+        format!("pos{}", position).into()
+    }
+    fn convert_adt_name_to_smt(&mut self, name: &low::AdtNameSymbol) -> smt::AdtNameSymbol {
+        name.symbol().into()
     }
     fn convert_function_name_to_smt(&mut self, name: &low::FunctionSymbol) -> smt::FunctionSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
     fn convert_label_name_to_smt(&mut self, name: &low::LabelSymbol) -> smt::LabelSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
     fn convert_known_label_name_to_smt(&self, name: &low::LabelSymbol) -> smt::LabelSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
     fn convert_uninterpreted_sort_to_smt(
         &mut self,
         name: &low::UninterpretedSortSymbol,
     ) -> smt::UninterpretedSortSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
     fn convert_variable_name_to_smt(&mut self, name: &low::VariableSymbol) -> smt::VariableSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
     fn create_label_for_basic_block(&mut self, id: low::BasicBlockId) -> smt::VariableSymbol {
         format!("BB@{}", id.index()).into()
     }
     fn resolve_high_label(&self, name: &low::LabelSymbol) -> high::LabelSymbol {
-        name.as_string().into()
+        name.symbol().into()
+    }
+    fn resolve_position(&self, name: &low::LabelSymbol) -> Option<Position> {
+        let id = name.as_str().strip_prefix("pos@")?.parse().ok()?;
+        Some(Position::new(id))
     }
     fn resolve_high_variable(&self, name: &low::VariableSymbol) -> high::VariableSymbol {
-        let mut s = name.as_string();
+        let s = name.as_str();
         let index = s.rfind('@').unwrap();
-        s.truncate(index);
-        s.into()
+        s[..index].into()
     }
     fn resolve_low_label(&self, name: &smt::LabelSymbol) -> low::LabelSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
     fn resolve_low_variable(&self, name: &smt::VariableSymbol) -> low::VariableSymbol {
-        name.as_string().into()
+        name.symbol().into()
     }
 }
@@ -11,4 +11,11 @@ impl From<svirpti_smt::VerifierError> for SvirptiError {
     }
 }
 
+impl SvirptiError {
+    /// Whether the error was caused by cancelling the verification.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.verifier_error, svirpti_smt::VerifierError::Cancelled)
+    }
+}
+
 pub type SvirptiResult<T = ()> = Result<T, SvirptiError>;
@@ -1,13 +1,18 @@
 use self::lower::SmtContext;
 pub use errors::{SvirptiError, SvirptiResult};
+use svirpti_smt::functions::unfold_recursive_functions;
+use svirpti_smt::solvers::pool::{PooledSolver, SolverPool};
 use svirpti_smt::solvers::SmtSolver;
+pub use svirpti_smt::CancellationToken;
 use svirpti_vir::{high, smt};
 
+pub use self::config::VerificationConfig;
 pub use self::context::Context;
-pub use self::lower::lower;
-pub use self::wp::encode;
+pub use self::lower::{lower, lower_with_config};
+pub use self::wp::{encode, encode_with_config};
 pub use verification_result::{VerificationError, VerificationFailure, VerificationResult};
 
+mod config;
 pub mod context;
 mod errors;
 mod lower;
@@ -18,34 +23,133 @@ pub fn verify<'a, S: SmtSolver, C: Context>(
     context: &'a mut C,
     program: &high::ProgramFragment,
 ) -> Result<VerificationResult<'a, C, S>, SvirptiError> {
-    let lowered_vir = lower(&program, context)?;
+    verify_cancellable(context, program, &CancellationToken::new())
+}
+
+/// Same as [`verify`], but stops early with `VerificationResult::Cancelled`
+/// once `cancellation` is cancelled.
+pub fn verify_cancellable<'a, S: SmtSolver, C: Context>(
+    context: &'a mut C,
+    program: &high::ProgramFragment,
+    cancellation: &CancellationToken,
+) -> Result<VerificationResult<'a, C, S>, SvirptiError> {
+    verify_with_config(
+        context,
+        program,
+        &VerificationConfig::default(),
+        cancellation,
+    )
+}
+
+/// Same as [`verify_cancellable`], but with the solver settings from
+/// `config`.
+pub fn verify_with_config<'a, S: SmtSolver, C: Context>(
+    context: &'a mut C,
+    program: &high::ProgramFragment,
+    config: &VerificationConfig,
+    cancellation: &CancellationToken,
+) -> Result<VerificationResult<'a, C, S>, SvirptiError> {
+    let solver = S::default().map_err(svirpti_smt::VerifierError::from)?;
+    verify_with_solver(solver, context, program, config, cancellation)
+}
+
+/// Same as [`verify_with_config`], but takes the solver from `pool`, so that
+/// consecutive runs reuse the solvers.
+pub fn verify_with_pool<'a, S: SmtSolver + 'static, C: Context>(
+    pool: &SolverPool<S>,
+    context: &'a mut C,
+    program: &high::ProgramFragment,
+    config: &VerificationConfig,
+    cancellation: &CancellationToken,
+) -> Result<VerificationResult<'a, C, PooledSolver<S>>, SvirptiError> {
+    let solver = pool.acquire().map_err(svirpti_smt::VerifierError::from)?;
+    verify_with_solver(solver, context, program, config, cancellation)
+}
+
+/// Same as [`verify_with_config`], but uses the given `solver` instead of a
+/// default one.
+pub fn verify_with_solver<'a, S: SmtSolver, C: Context>(
+    solver: S,
+    context: &'a mut C,
+    program: &high::ProgramFragment,
+    config: &VerificationConfig,
+    cancellation: &CancellationToken,
+) -> Result<VerificationResult<'a, C, S>, SvirptiError> {
+    // Lowering and encoding cannot be interrupted, so check in between.
+    if cancellation.is_cancelled() {
+        return Ok(VerificationResult::Cancelled);
+    }
+    let query_configuration = config.query_configuration(program);
+    let lowered_vir = lower_with_config(&program, context, config)?;
+    if cancellation.is_cancelled() {
+        return Ok(VerificationResult::Cancelled);
+    }
     let smt::Query {
-        declarations,
-        assertions,
-    } = encode(&lowered_vir, context)?;
+        mut declarations,
+        mut assertions,
+    } = encode_with_config(&lowered_vir, context, config)?;
+    if let Some(fuel) = config.recursive_function_fuel {
+        assertions.extend(unfold_recursive_functions(&mut declarations, fuel));
+    }
     let smt_context = SmtContext {
         context,
         vir: lowered_vir,
+        // The labels are Boolean variables that the error reporting asserts.
         variable_sorts: declarations
             .variables
             .iter()
             .map(|variable| (variable.name.clone(), variable.sort.clone()))
+            .chain(
+                declarations
+                    .labels
+                    .iter()
+                    .map(|label| (label.name.clone(), smt::Sort::Bool)),
+            )
             .collect(),
         function_sorts: declarations
             .functions
             .iter()
             .map(|function| (function.name.clone(), function.return_sort.clone()))
             .collect(),
+        field_sorts: declarations
+            .adts
+            .iter()
+            .flat_map(|adt| &adt.constructors)
+            .flat_map(|constructor| &constructor.fields)
+            .map(|field| (field.name.clone(), field.sort.clone()))
+            .collect(),
+        constructor_fields: declarations
+            .adts
+            .iter()
+            .flat_map(|adt| &adt.constructors)
+            .map(|constructor| (constructor.name.clone(), constructor.fields.clone()))
+            .collect(),
+        adts: declarations
+            .adts
+            .iter()
+            .map(|adt| adt.name.clone())
+            .collect(),
+        bound_variables: Default::default(),
     };
-    let result = svirpti_smt::verify(&smt_context, &declarations, &assertions)?;
+    let result = svirpti_smt::verify_with_solver(
+        solver,
+        &smt_context,
+        &declarations,
+        &assertions,
+        &query_configuration,
+        cancellation,
+    )?;
     let result = match result {
-        svirpti_smt::VerificationResult::Success => VerificationResult::Success,
+        svirpti_smt::VerificationResult::Success(statistics) => {
+            VerificationResult::Success(statistics)
+        }
         svirpti_smt::VerificationResult::Failure(failure) => {
             VerificationResult::Failure(VerificationFailure {
                 smt_context,
                 smt_failure: failure,
             })
         }
+        svirpti_smt::VerificationResult::Cancelled => VerificationResult::Cancelled,
     };
     Ok(result)
 }
@@ -1,9 +1,12 @@
-use super::generic_expression::{Lowerable, Lowerer};
+use super::program::lower_type;
 use crate::context::Context;
-use crate::errors::SvirptiResult;
+use crate::errors::{SvirptiError, SvirptiResult};
 use std::collections::HashMap;
+use svirpti_vir::common::expression::LabelledExpressionHelpers;
+use svirpti_vir::low::lowering::{default_lower_adt_match_case, default_lower_quantifier, Lowerer};
 use svirpti_vir::{high, low};
 
+/// Lowers `expression`, dropping the positions of its subexpressions.
 pub(crate) fn lower_expression<C: Context>(
     context: &mut C,
     variable_versions: &HashMap<high::VariableSymbol, usize>,
@@ -12,35 +15,144 @@ pub(crate) fn lower_expression<C: Context>(
     let mut lowerer = ExpressionLowerer {
         context,
         variable_versions,
+        scopes: Vec::new(),
+        label_positions: false,
     };
-    Lowerable::lower(expression, &mut lowerer)
+    lowerer.lower_expression(expression)
+}
+
+/// Lowers an asserted `expression`. Positioned subexpressions that are not
+/// under a quantifier are labelled, so that a failing subexpression can be
+/// reported at its position.
+pub(crate) fn lower_assertion<C: Context>(
+    context: &mut C,
+    variable_versions: &HashMap<high::VariableSymbol, usize>,
+    expression: &high::Expression,
+) -> SvirptiResult<low::Expression> {
+    let mut lowerer = ExpressionLowerer {
+        context,
+        variable_versions,
+        scopes: Vec::new(),
+        label_positions: true,
+    };
+    lowerer.lower_expression(expression)
 }
 
 struct ExpressionLowerer<'a, C: Context> {
     context: &'a mut C,
     variable_versions: &'a HashMap<high::VariableSymbol, usize>,
+    /// The variables bound by the enclosing quantifiers, let bindings, and
+    /// match cases.
+    scopes: Vec<Vec<high::VariableSymbol>>,
+    /// Whether to label the positioned subexpressions.
+    label_positions: bool,
+}
+
+impl<'a, C: Context> ExpressionLowerer<'a, C> {
+    /// Binds `variables` until the matching `exit_scope`. Bound variables
+    /// shadow the variables of the program.
+    fn enter_scope(&mut self, variables: Vec<high::VariableSymbol>) {
+        self.scopes.push(variables);
+    }
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
 }
 
 impl<'a, C: Context> Lowerer for ExpressionLowerer<'a, C> {
+    type Error = SvirptiError;
     fn lower_function_symbol(
         &mut self,
         symbol: &high::FunctionSymbol,
     ) -> SvirptiResult<low::FunctionSymbol> {
-        Ok(symbol.as_string().into())
+        Ok(symbol.symbol().into())
     }
     fn lower_variable_symbol(
         &mut self,
         symbol: &high::VariableSymbol,
     ) -> SvirptiResult<low::VariableSymbol> {
-        let version = self.variable_versions[symbol];
-        Ok(self
-            .context
-            .create_versioned_variable_symbol(symbol, version))
+        if self.scopes.iter().any(|scope| scope.contains(symbol)) {
+            // Variables bound inside the expression are not versioned.
+            Ok(symbol.symbol().into())
+        } else if let Some(version) = self.variable_versions.get(symbol) {
+            Ok(self
+                .context
+                .create_versioned_variable_symbol(symbol, *version))
+        } else {
+            Ok(symbol.symbol().into())
+        }
+    }
+    fn lower_adt_name_symbol(
+        &mut self,
+        symbol: &high::AdtNameSymbol,
+    ) -> SvirptiResult<low::AdtNameSymbol> {
+        Ok(self.context.lower_reference_name(symbol))
+    }
+    fn lower_adt_constructor_symbol(
+        &mut self,
+        symbol: &high::AdtConstructorSymbol,
+    ) -> SvirptiResult<low::AdtConstructorSymbol> {
+        Ok(symbol.symbol().into())
+    }
+    fn lower_field_name_symbol(
+        &mut self,
+        symbol: &high::FieldNameSymbol,
+    ) -> SvirptiResult<low::FieldNameSymbol> {
+        Ok(symbol.symbol().into())
+    }
+    fn lower_sort(&mut self, sort: &high::Sort) -> SvirptiResult<low::Sort> {
+        Ok(lower_type(self.context, sort))
+    }
+    fn lower_adt_match_case(
+        &mut self,
+        case: &high::AdtMatchCase,
+    ) -> SvirptiResult<low::AdtMatchCase> {
+        self.enter_scope(case.binders.clone());
+        let case = default_lower_adt_match_case(self, case);
+        self.exit_scope();
+        case
+    }
+    fn lower_let(&mut self, binding: &high::Let) -> SvirptiResult<low::Let> {
+        // The binding is not visible in the value.
+        let value = self.lower_expression(&binding.value)?;
+        self.enter_scope(vec![binding.variable.clone()]);
+        let variable = self.lower_variable_symbol(&binding.variable);
+        let body = self.lower_expression(&binding.body);
+        self.exit_scope();
+        Ok(low::Let {
+            variable: variable?,
+            value: Box::new(value),
+            body: Box::new(body?),
+        })
+    }
+    fn lower_quantifier(
+        &mut self,
+        quantifier: &high::Quantifier,
+    ) -> SvirptiResult<low::Quantifier> {
+        self.enter_scope(
+            quantifier
+                .variables
+                .iter()
+                .map(|variable| variable.name.clone())
+                .collect(),
+        );
+        // The solver does not report labels under quantifiers.
+        let label_positions = std::mem::replace(&mut self.label_positions, false);
+        let quantifier = default_lower_quantifier(self, quantifier);
+        self.label_positions = label_positions;
+        self.exit_scope();
+        quantifier
     }
-    fn lower_sort_symbol(
+    fn lower_positioned_expression(
         &mut self,
-        symbol: &high::UninterpretedSortSymbol,
-    ) -> SvirptiResult<low::UninterpretedSortSymbol> {
-        Ok(symbol.as_string().into())
+        positioned: &high::PositionedExpression,
+    ) -> SvirptiResult<low::Expression> {
+        let expression = self.lower_expression(&positioned.expression)?;
+        if self.label_positions {
+            let label = self.context.create_label_for_position(positioned.position);
+            Ok(low::Expression::label_negative(label, expression))
+        } else {
+            Ok(expression)
+        }
     }
 }
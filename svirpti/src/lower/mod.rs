@@ -1,7 +1,7 @@
 mod expression;
-mod generic_expression;
 mod program;
 mod smt_context;
+mod well_definedness;
 
-pub use self::program::lower_program as lower;
+pub use self::program::{lower_program as lower, lower_program_with_config as lower_with_config};
 pub(crate) use self::smt_context::SmtContext;
@@ -1,12 +1,18 @@
-use super::expression::lower_expression;
+use super::expression::{lower_assertion, lower_expression};
+use super::well_definedness::{precondition_function_name, well_definedness_condition};
+use crate::config::VerificationConfig;
 use crate::context::Context;
 use crate::errors::SvirptiResult;
 use index_vec::IndexVec;
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 use svirpti_vir::{
     common::{
         cfg::Cfg,
-        expression::{BinaryOperationHelpers, SyntacticEvaluation, VariableHelpers},
+        expression::{
+            BinaryOperationHelpers, ConstantHelpers, ExpressionIterator, Simplification,
+            SyntacticEvaluation, VariableHelpers,
+        },
+        position::Position,
         statement::AssumeAssertHelpers,
     },
     high, low,
@@ -15,9 +21,31 @@ use svirpti_vir::{
 pub fn lower_program<C: Context>(
     program: &high::ProgramFragment,
     context: &mut C,
+) -> SvirptiResult<low::ProgramFragment> {
+    lower_program_with_config(program, context, &VerificationConfig::default())
+}
+
+/// Same as [`lower_program`], but simplifies the lowered expressions if
+/// `config.simplify` is set.
+pub fn lower_program_with_config<C: Context>(
+    program: &high::ProgramFragment,
+    context: &mut C,
+    config: &VerificationConfig,
 ) -> SvirptiResult<low::ProgramFragment> {
     // TODO: program.validate()?;
     program.procedure.validate();
+    let adts = program
+        .adts
+        .iter()
+        .map(|adt| lower_adt(context, adt))
+        .collect();
+    let functions = lower_functions(context, &program.functions)?;
+    let functions_with_preconditions: HashSet<_> = program
+        .functions
+        .iter()
+        .filter(|function| !function.preconditions.is_empty())
+        .map(|function| function.name.clone())
+        .collect();
     let mut basic_blocks = IndexVec::new();
     let predecessors = program.procedure.compute_predecessors();
     let mut variable_versions_after_block: HashMap<
@@ -91,7 +119,13 @@ pub fn lower_program<C: Context>(
             merged_variables
         };
 
+        let mut well_definedness = WellDefinednessChecks {
+            functions: &functions_with_preconditions,
+            block: &block.label,
+            count: 0,
+        };
         if !block.guard.is_true() {
+            statements.extend(well_definedness.check(context, &variables, &block.guard, None)?);
             let lowered_guard = lower_expression(context, &variables, &block.guard)?;
             statements.push(low::Statement::assume_with_label(
                 lowered_guard,
@@ -103,25 +137,55 @@ pub fn lower_program<C: Context>(
             eprintln!("high_statement: {:?}", high_statement);
             match high_statement {
                 high::Statement::Assert(statement) => {
-                    let label = statement
-                        .label
-                        .as_ref()
-                        .map(|label| context.lower_label(label));
-                    let assertion = lower_expression(context, &variables, &statement.assertion)?;
-                    statements.push(low::Statement::Assert(low::Assert { assertion, label }));
+                    // An assertion with a position is labelled, so that its
+                    // failure can be reported.
+                    let label = match (&statement.label, statement.position) {
+                        (Some(label), _) => Some(context.lower_label(label)),
+                        (None, Some(position)) => Some(context.create_label_for_position(position)),
+                        (None, None) => None,
+                    };
+                    statements.extend(well_definedness.check(
+                        context,
+                        &variables,
+                        &statement.assertion,
+                        statement.position,
+                    )?);
+                    let assertion = lower_assertion(context, &variables, &statement.assertion)?;
+                    statements.push(low::Statement::Assert(low::Assert {
+                        assertion,
+                        label,
+                        position: statement.position,
+                    }));
                 }
                 high::Statement::Assume(statement) => {
                     let label = statement
                         .label
                         .as_ref()
                         .map(|label| context.lower_label(label));
+                    statements.extend(well_definedness.check(
+                        context,
+                        &variables,
+                        &statement.assertion,
+                        statement.position,
+                    )?);
                     let assertion = lower_expression(context, &variables, &statement.assertion)?;
-                    statements.push(low::Statement::Assume(low::Assume { assertion, label }));
+                    statements.push(low::Statement::Assume(low::Assume {
+                        assertion,
+                        label,
+                        position: statement.position,
+                    }));
                 }
                 high::Statement::Havoc(_statement) => {
                     unimplemented!("havoc");
                 }
                 high::Statement::Assign(statement) => {
+                    // The expression is evaluated before the assignment.
+                    statements.extend(well_definedness.check(
+                        context,
+                        &variables,
+                        &statement.expression,
+                        statement.position,
+                    )?);
                     let variable = inc_var_version(
                         context,
                         &statement.variable,
@@ -150,13 +214,124 @@ pub fn lower_program<C: Context>(
         });
         variable_versions_after_block.insert(id, variables);
     }
-    Ok(low::ProgramFragment {
+    let mut lowered = low::ProgramFragment {
         uninterpreted_sorts: Vec::new(), // TODO
+        adts,
         variables: all_variables,
-        functions: Vec::new(), // TODO
-        axioms: Vec::new(),    // TODO
+        functions,
+        axioms: Vec::new(), // TODO
         basic_blocks,
-    })
+    };
+    if config.simplify {
+        simplify_program(&mut lowered);
+    }
+    Ok(lowered)
+}
+
+/// Simplifies all expressions of the lowered program.
+fn simplify_program(program: &mut low::ProgramFragment) {
+    let functions = program.functions.iter_mut().flat_map(|function| {
+        function
+            .preconditions
+            .iter_mut()
+            .chain(function.body.iter_mut())
+    });
+    let axioms = program.axioms.iter_mut().map(|axiom| &mut axiom.body);
+    let statements = program
+        .basic_blocks
+        .iter_mut()
+        .flat_map(|block| block.statements.iter_mut())
+        .map(|statement| match statement {
+            low::Statement::Assert(low::Assert { assertion, .. })
+            | low::Statement::Assume(low::Assume { assertion, .. }) => assertion,
+        });
+    for expression in functions.chain(axioms).chain(statements) {
+        let original = std::mem::replace(expression, low::Expression::bool(true));
+        *expression = original.simplify();
+    }
+}
+
+/// Lowers the function declarations. For every function with preconditions,
+/// it also generates the Boolean function that checks them.
+fn lower_functions<C: Context>(
+    context: &mut C,
+    functions: &[high::FunctionDeclaration],
+) -> SvirptiResult<Vec<low::FunctionDeclaration>> {
+    // The parameters are not versioned.
+    let parameter_versions = HashMap::new();
+    let mut lowered_functions = Vec::new();
+    for function in functions {
+        let parameters: Vec<_> = function
+            .parameters
+            .iter()
+            .map(|parameter| low::VariableDeclaration {
+                name: parameter.name.symbol().into(),
+                sort: lower_type(context, &parameter.sort),
+            })
+            .collect();
+        let preconditions = function
+            .preconditions
+            .iter()
+            .map(|precondition| lower_expression(context, &parameter_versions, precondition))
+            .collect::<SvirptiResult<Vec<_>>>()?;
+        if !preconditions.is_empty() {
+            lowered_functions.push(low::FunctionDeclaration {
+                name: precondition_function_name(&function.name).symbol().into(),
+                parameters: parameters.clone(),
+                return_sort: low::Sort::Bool,
+                preconditions: Vec::new(),
+                body: Some(preconditions.iter().cloned().conjoin()),
+            });
+        }
+        let body = function
+            .body
+            .as_ref()
+            .map(|body| lower_expression(context, &parameter_versions, body))
+            .transpose()?;
+        lowered_functions.push(low::FunctionDeclaration {
+            name: function.name.symbol().into(),
+            parameters,
+            return_sort: lower_type(context, &function.return_sort),
+            preconditions,
+            body,
+        });
+    }
+    Ok(lowered_functions)
+}
+
+/// Emits the well-definedness checks of the expressions in a basic block.
+struct WellDefinednessChecks<'a> {
+    /// The functions that have preconditions.
+    functions: &'a HashSet<high::FunctionSymbol>,
+    block: &'a high::LabelSymbol,
+    /// The number of checks emitted so far, which makes their labels unique.
+    count: usize,
+}
+
+impl WellDefinednessChecks<'_> {
+    /// The assertion that `expression` is well-defined, which has to precede
+    /// its evaluation. The assertion is labelled with `{block}%wd{n}` and has
+    /// the `position` of the statement that evaluates `expression`.
+    fn check<C: Context>(
+        &mut self,
+        context: &mut C,
+        variable_versions: &HashMap<high::VariableSymbol, usize>,
+        expression: &high::Expression,
+        position: Option<Position>,
+    ) -> SvirptiResult<Option<low::Statement>> {
+        let condition = well_definedness_condition(self.functions, expression);
+        if condition.is_true() {
+            return Ok(None);
+        }
+        let label: high::LabelSymbol = format!("{}%wd{}", self.block, self.count).into();
+        self.count += 1;
+        let assertion = lower_assertion(context, variable_versions, &condition)?;
+        Ok(Some(low::Statement::Assert(low::Assert {
+            assertion,
+            label: Some(context.lower_label(&label)),
+            position,
+        })))
+    }
 }
 
 fn inc_var_version<C: Context>(
@@ -200,13 +375,57 @@ fn assume_var_eq<C: Context>(
     ))
 }
 
-fn lower_type<C: Context>(context: &mut C, typ: &high::Type) -> low::Sort {
+fn lower_adt<C: Context>(context: &mut C, adt: &high::AdtDeclaration) -> low::AdtDeclaration {
+    low::AdtDeclaration {
+        name: context.lower_reference_name(&adt.name),
+        constructors: adt
+            .constructors
+            .iter()
+            .map(|constructor| low::AdtConstructorDeclaration {
+                name: constructor.name.symbol().into(),
+                fields: constructor
+                    .fields
+                    .iter()
+                    .map(|field| low::AdtFieldDeclaration {
+                        name: field.name.symbol().into(),
+                        sort: lower_type(context, &field.sort),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+pub(super) fn lower_type<C: Context>(context: &mut C, typ: &high::Type) -> low::Sort {
     match typ {
         high::Type::Int => low::Sort::Int,
         high::Type::Bool => low::Sort::Bool,
         high::Type::Real => low::Sort::Real,
+        high::Type::BitVector(high::BitVectorType { width }) => {
+            low::Sort::BitVector { width: *width }
+        }
+        high::Type::Float(high::FloatType {
+            exponent,
+            significand,
+        }) => low::Sort::Float {
+            exponent: *exponent,
+            significand: *significand,
+        },
+        high::Type::Array(high::ArrayType { index, element }) => low::Sort::Array {
+            index: Box::new(lower_type(context, index)),
+            element: Box::new(lower_type(context, element)),
+        },
+        high::Type::Seq(high::SeqType { element }) => low::Sort::Seq {
+            element: Box::new(lower_type(context, element)),
+        },
+        high::Type::Set(high::SetType { element }) => low::Sort::Set {
+            element: Box::new(lower_type(context, element)),
+        },
         high::Type::Domain(high::DomainType { name }) => low::Sort::Uninterpreted {
             name: context.lower_domain_name(name),
         },
+        high::Type::Reference(high::ReferenceType { name }) => low::Sort::Adt {
+            name: context.lower_reference_name(name),
+        },
     }
 }
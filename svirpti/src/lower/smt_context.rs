@@ -1,5 +1,5 @@
 use crate::context::Context;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use svirpti_vir::{low, smt};
 
 pub(crate) struct SmtContext<'a, C: Context> {
@@ -7,6 +7,11 @@ pub(crate) struct SmtContext<'a, C: Context> {
     pub(crate) vir: low::ProgramFragment,
     pub(crate) variable_sorts: HashMap<smt::VariableSymbol, smt::Sort>,
     pub(crate) function_sorts: HashMap<smt::FunctionSymbol, smt::Sort>,
+    pub(crate) field_sorts: HashMap<smt::FieldNameSymbol, smt::Sort>,
+    pub(crate) constructor_fields:
+        HashMap<smt::AdtConstructorSymbol, Vec<smt::AdtFieldDeclaration>>,
+    pub(crate) adts: HashSet<smt::AdtNameSymbol>,
+    pub(crate) bound_variables: smt::BoundVariables,
 }
 
 impl<'c, C: Context> smt::Context for SmtContext<'c, C> {
@@ -40,7 +45,22 @@ impl<'c, C: Context> smt::Context for SmtContext<'c, C> {
     fn get_function_sort<'a>(&'a self, function: &'a smt::FunctionSymbol) -> &'a smt::Sort {
         &self.function_sorts[function]
     }
+    fn get_field_sort<'a>(&'a self, field: &'a smt::FieldNameSymbol) -> &'a smt::Sort {
+        &self.field_sorts[field]
+    }
+    fn get_constructor_fields<'a>(
+        &'a self,
+        constructor: &'a smt::AdtConstructorSymbol,
+    ) -> &'a [smt::AdtFieldDeclaration] {
+        &self.constructor_fields[constructor]
+    }
+    fn is_adt(&self, name: &smt::AdtNameSymbol) -> bool {
+        self.adts.contains(name)
+    }
     fn resolve_ident(&self, ident: &str) -> rsmt2::SmtRes<smt::IdentSymbol> {
         Ok(ident.into())
     }
+    fn bound_variables(&self) -> &smt::BoundVariables {
+        &self.bound_variables
+    }
 }
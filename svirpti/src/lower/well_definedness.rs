@@ -0,0 +1,186 @@
+//! Well-definedness conditions, which check the preconditions of the called
+//! functions at the call sites.
+
+use std::collections::HashSet;
+use svirpti_vir::{
+    common::expression::{
+        BinaryOperationHelpers, FunctionApplicationHelpers, LetHelpers,
+        PositionedExpressionHelpers, QuantifierHelpers, SyntacticEvaluation, UnaryOperationHelpers,
+    },
+    high,
+};
+
+/// The generated Boolean function that holds if the preconditions of
+/// `function` hold for its arguments.
+pub(super) fn precondition_function_name(function: &high::FunctionSymbol) -> high::FunctionSymbol {
+    format!("{}%pre", function).into()
+}
+
+/// A condition under which every function application in `expression`
+/// satisfies the preconditions of the called function. `functions` are the
+/// functions that have preconditions.
+///
+/// Like Boolean operators and conditionals, the condition short-circuits: the
+/// right operand of `a && b` needs to be well-defined only if `a` holds.
+pub(super) fn well_definedness_condition(
+    functions: &HashSet<high::FunctionSymbol>,
+    expression: &high::Expression,
+) -> high::Expression {
+    WellDefinedness { functions }.condition(expression)
+}
+
+struct WellDefinedness<'a> {
+    functions: &'a HashSet<high::FunctionSymbol>,
+}
+
+impl WellDefinedness<'_> {
+    fn condition(&self, expression: &high::Expression) -> high::Expression {
+        match expression {
+            high::Expression::Variable(_)
+            | high::Expression::Constant(_)
+            | high::Expression::EmptySeq(_)
+            | high::Expression::EmptySet(_) => true.into(),
+            high::Expression::UnaryOperation(operation) => self.condition(&operation.arg),
+            high::Expression::BinaryOperation(operation) => {
+                let left = self.condition(&operation.left);
+                let right = self.condition(&operation.right);
+                let right = match operation.kind {
+                    high::BinaryOperationKind::And | high::BinaryOperationKind::Implies => {
+                        guarded((*operation.left).clone(), right)
+                    }
+                    high::BinaryOperationKind::Or => {
+                        guarded(high::Expression::not((*operation.left).clone()), right)
+                    }
+                    _ => right,
+                };
+                conjoin(left, right)
+            }
+            high::Expression::BitVectorConversion(conversion) => self.condition(&conversion.arg),
+            high::Expression::FloatOperation(operation) => self.all(&operation.args),
+            high::Expression::FloatConversion(conversion) => self.condition(&conversion.arg),
+            high::Expression::ArraySelect(select) => {
+                conjoin(self.condition(&select.array), self.condition(&select.index))
+            }
+            high::Expression::ArrayStore(store) => conjoin(
+                conjoin(self.condition(&store.array), self.condition(&store.index)),
+                self.condition(&store.value),
+            ),
+            high::Expression::ConstantArray(array) => self.condition(&array.value),
+            high::Expression::SeqOperation(operation) => self.all(&operation.args),
+            high::Expression::SetOperation(operation) => self.all(&operation.args),
+            high::Expression::AdtConstructor(constructor) => self.all(&constructor.args),
+            high::Expression::AdtFieldAccess(access) => self.condition(&access.base),
+            high::Expression::AdtTester(tester) => self.condition(&tester.base),
+            high::Expression::AdtMatch(adt_match) => {
+                let cases: Vec<_> = adt_match
+                    .cases
+                    .iter()
+                    .map(|case| high::AdtMatchCase {
+                        constructor: case.constructor.clone(),
+                        binders: case.binders.clone(),
+                        body: Box::new(self.condition(&case.body)),
+                    })
+                    .collect();
+                let base = self.condition(&adt_match.base);
+                if cases.iter().all(|case| case.body.is_true()) {
+                    return base;
+                }
+                conjoin(
+                    base,
+                    high::Expression::AdtMatch(high::AdtMatch {
+                        adt: adt_match.adt.clone(),
+                        base: adt_match.base.clone(),
+                        cases,
+                    }),
+                )
+            }
+            high::Expression::Conditional(conditional) => {
+                let guard = self.condition(&conditional.guard);
+                let then_expr = self.condition(&conditional.then_expr);
+                let else_expr = self.condition(&conditional.else_expr);
+                if then_expr.is_true() && else_expr.is_true() {
+                    return guard;
+                }
+                conjoin(
+                    guard,
+                    high::Expression::Conditional(high::Conditional {
+                        guard: conditional.guard.clone(),
+                        then_expr: Box::new(then_expr),
+                        else_expr: Box::new(else_expr),
+                    }),
+                )
+            }
+            high::Expression::Let(binding) => {
+                let value = self.condition(&binding.value);
+                let body = self.condition(&binding.body);
+                if body.is_true() {
+                    return value;
+                }
+                conjoin(
+                    value,
+                    high::Expression::let_in(
+                        binding.variable.clone(),
+                        (*binding.value).clone(),
+                        body,
+                    ),
+                )
+            }
+            high::Expression::PositionedExpression(positioned) => {
+                // A failing check is reported at the position of the
+                // expression.
+                let condition = self.condition(&positioned.expression);
+                if condition.is_true() {
+                    return condition;
+                }
+                high::Expression::positioned(positioned.position, condition)
+            }
+            high::Expression::Quantifier(quantifier) => {
+                // The body has to be well-defined for all values, also for an
+                // existential quantifier.
+                let body = self.condition(&quantifier.body);
+                if body.is_true() {
+                    return body;
+                }
+                high::Expression::forall(quantifier.variables.clone(), Vec::new(), body)
+            }
+            high::Expression::FunctionApplication(application) => {
+                let args = self.all(&application.args);
+                if !self.functions.contains(&application.function) {
+                    return args;
+                }
+                conjoin(
+                    args,
+                    high::Expression::call(
+                        precondition_function_name(&application.function),
+                        application.args.clone(),
+                    ),
+                )
+            }
+        }
+    }
+
+    fn all(&self, expressions: &[high::Expression]) -> high::Expression {
+        expressions
+            .iter()
+            .map(|expression| self.condition(expression))
+            .fold(true.into(), conjoin)
+    }
+}
+
+fn conjoin(left: high::Expression, right: high::Expression) -> high::Expression {
+    if left.is_true() {
+        right
+    } else if right.is_true() {
+        left
+    } else {
+        high::Expression::and(left, right)
+    }
+}
+
+fn guarded(guard: high::Expression, condition: high::Expression) -> high::Expression {
+    if condition.is_true() {
+        condition
+    } else {
+        high::Expression::implies(guard, condition)
+    }
+}
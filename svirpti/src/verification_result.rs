@@ -1,7 +1,8 @@
 use crate::wp;
 use crate::{context::Context, lower::SmtContext, SvirptiResult};
 use std::collections::BTreeMap;
-use svirpti_smt::solvers::SmtSolver;
+use svirpti_smt::{solvers::SmtSolver, QueryStatistics};
+use svirpti_vir::common::position::Position;
 use svirpti_vir::{high, low, smt};
 
 #[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -31,6 +32,13 @@ pub struct VerificationError {
     pub labels: Vec<high::LabelSymbol>,
     /// The model returned by the SMT solver.
     pub model: Model,
+    /// The position of the failing assertion, if the frontend provided one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+    /// The positions of the subexpressions of the failing assertion that
+    /// failed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failing_positions: Vec<Position>,
 }
 
 impl VerificationError {
@@ -46,21 +54,36 @@ impl VerificationError {
 }
 
 pub enum VerificationResult<'a, C: Context, S: SmtSolver> {
-    Success,
+    Success(QueryStatistics),
     Failure(VerificationFailure<'a, C, S>),
+    /// The cancellation token was cancelled before the verification
+    /// finished.
+    Cancelled,
 }
 
 impl<'a, C: Context, S: SmtSolver> VerificationResult<'a, C, S> {
     pub fn is_success(&self) -> bool {
-        matches!(self, VerificationResult::Success)
+        matches!(self, VerificationResult::Success(_))
+    }
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, VerificationResult::Cancelled)
+    }
+    /// The statistics of the verification query, unless it was cancelled.
+    pub fn get_statistics(&self) -> Option<&QueryStatistics> {
+        match self {
+            VerificationResult::Success(statistics) => Some(statistics),
+            VerificationResult::Failure(failure) => Some(failure.get_statistics()),
+            VerificationResult::Cancelled => None,
+        }
     }
 }
 
 impl<'a, C: Context, S: SmtSolver> std::fmt::Debug for VerificationResult<'a, C, S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            VerificationResult::Success => write!(f, "Success"),
+            VerificationResult::Success(_) => write!(f, "Success"),
             VerificationResult::Failure(_) => write!(f, "Failure"),
+            VerificationResult::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
@@ -71,10 +94,22 @@ pub struct VerificationFailure<'a, C: Context, S: SmtSolver> {
 }
 
 impl<'a, C: Context, S: SmtSolver> VerificationFailure<'a, C, S> {
+    /// The statistics of the verification query that failed.
+    pub fn get_statistics(&self) -> &QueryStatistics {
+        self.smt_failure.get_statistics()
+    }
     pub fn get_all_errors(self) -> SvirptiResult<Vec<VerificationError>> {
+        Ok(self.get_all_errors_with_statistics()?.0)
+    }
+    /// Same as `get_all_errors`, but also returns the statistics of all
+    /// queries: the verification query followed by the queries made while
+    /// searching for errors.
+    pub fn get_all_errors_with_statistics(
+        mut self,
+    ) -> SvirptiResult<(Vec<VerificationError>, Vec<QueryStatistics>)> {
         let mut errors = Vec::new();
         let context = self.smt_context.context;
-        for error in crate::wp::get_all_errors(self.smt_failure, &self.smt_context)? {
+        for error in crate::wp::get_all_errors(&mut self.smt_failure, &self.smt_context)? {
             eprintln!("error: {:?}", error);
             errors.push(VerificationError {
                 failing_assertion: context.resolve_high_label(&error.failing_assertion),
@@ -85,8 +120,16 @@ impl<'a, C: Context, S: SmtSolver> VerificationFailure<'a, C, S> {
                     .collect(),
                 trace: error.trace.iter().map(|id| id.index().into()).collect(),
                 model: Model::new(context, &error.model),
+                position: error.position,
+                failing_positions: error
+                    .failing_subexpressions
+                    .iter()
+                    .filter_map(|label| context.resolve_position(label))
+                    .collect(),
             });
         }
-        Ok(errors)
+        let mut statistics = vec![self.smt_failure.get_statistics().clone()];
+        statistics.extend_from_slice(self.smt_failure.get_investigator().get_query_statistics());
+        Ok((errors, statistics))
     }
 }
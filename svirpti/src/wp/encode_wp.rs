@@ -1,12 +1,16 @@
-use super::expression;
+use super::sharing::share_repeated_subterms;
+use crate::config::VerificationConfig;
 use crate::context::Context;
-use crate::errors::SvirptiResult;
+use crate::errors::{SvirptiError, SvirptiResult};
 use std::collections::HashMap;
 use svirpti_vir::common::cfg::Cfg;
 use svirpti_vir::common::expression::{
-    BinaryOperationHelpers, ExpressionIterator, LabelledExpressionHelpers, UnaryOperationHelpers,
-    VariableHelpers,
+    BinaryOperationHelpers, ExpressionIterator, LabelledExpressionHelpers, Simplification,
+    UnaryOperationHelpers, VariableHelpers,
 };
+use svirpti_vir::low::expression::default_walk_labelled_expression;
+use svirpti_vir::low::ExpressionWalker;
+use svirpti_vir::smt::lowering::Lowerer;
 use svirpti_vir::{low, smt};
 
 fn convert_sort_to_smt<C: Context>(context: &mut C, sort: &low::Sort) -> smt::Sort {
@@ -14,15 +18,107 @@ fn convert_sort_to_smt<C: Context>(context: &mut C, sort: &low::Sort) -> smt::So
         low::Sort::Bool => smt::Sort::Bool,
         low::Sort::Int => smt::Sort::Int,
         low::Sort::Real => smt::Sort::Real,
+        low::Sort::BitVector { width } => smt::Sort::BitVector { width: *width },
+        low::Sort::Float {
+            exponent,
+            significand,
+        } => smt::Sort::Float {
+            exponent: *exponent,
+            significand: *significand,
+        },
+        low::Sort::Array { index, element } => smt::Sort::Array {
+            index: Box::new(convert_sort_to_smt(context, index)),
+            element: Box::new(convert_sort_to_smt(context, element)),
+        },
+        low::Sort::Seq { element } => smt::Sort::Seq {
+            element: Box::new(convert_sort_to_smt(context, element)),
+        },
+        low::Sort::Set { element } => smt::Sort::Set {
+            element: Box::new(convert_sort_to_smt(context, element)),
+        },
         low::Sort::Uninterpreted { name } => smt::Sort::Uninterpreted {
             name: context.convert_uninterpreted_sort_to_smt(name),
         },
+        low::Sort::Adt { name } => smt::Sort::Adt {
+            name: context.convert_adt_name_to_smt(name),
+        },
     }
 }
 
+fn convert_adt_to_smt<C: Context>(
+    context: &mut C,
+    adt: &low::AdtDeclaration,
+) -> smt::AdtDeclaration {
+    smt::AdtDeclaration {
+        name: context.convert_adt_name_to_smt(&adt.name),
+        constructors: adt
+            .constructors
+            .iter()
+            .map(|constructor| smt::AdtConstructorDeclaration {
+                name: constructor.name.symbol().into(),
+                fields: constructor
+                    .fields
+                    .iter()
+                    .map(|field| smt::AdtFieldDeclaration {
+                        name: field.name.symbol().into(),
+                        sort: convert_sort_to_smt(context, &field.sort),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn convert_function_to_smt<C: Context>(
+    context: &mut C,
+    function: &low::FunctionDeclaration,
+    simplify: bool,
+) -> SvirptiResult<smt::FunctionDeclaration> {
+    let lower_expression = |context: &mut C, expression| {
+        let expression = lower_expression(context, expression)?;
+        Ok(if simplify {
+            expression.simplify()
+        } else {
+            expression
+        })
+    };
+    Ok(smt::FunctionDeclaration {
+        name: context.convert_function_name_to_smt(&function.name),
+        parameters: function
+            .parameters
+            .iter()
+            .map(|parameter| smt::VariableDeclaration {
+                name: context.convert_variable_name_to_smt(&parameter.name),
+                sort: convert_sort_to_smt(context, &parameter.sort),
+            })
+            .collect(),
+        return_sort: convert_sort_to_smt(context, &function.return_sort),
+        preconditions: function
+            .preconditions
+            .iter()
+            .map(|precondition| lower_expression(context, precondition))
+            .collect::<SvirptiResult<_>>()?,
+        body: function
+            .body
+            .as_ref()
+            .map(|body| lower_expression(context, body))
+            .transpose()?,
+    })
+}
+
 pub fn encode<C: Context>(
     program: &low::ProgramFragment,
     context: &mut C,
+) -> SvirptiResult<smt::Query> {
+    encode_with_config(program, context, &VerificationConfig::default())
+}
+
+/// Same as [`encode`], but simplifies the weakest preconditions and the
+/// function declarations if `config.simplify` is set. Labels are preserved.
+pub fn encode_with_config<C: Context>(
+    program: &low::ProgramFragment,
+    context: &mut C,
+    config: &VerificationConfig,
 ) -> SvirptiResult<smt::Query> {
     // TODO: program.validate();
     let mut assertions = Vec::new();
@@ -36,6 +132,7 @@ pub fn encode<C: Context>(
         .collect();
     let mut labels = Vec::new();
     let mut basic_block_wps: HashMap<low::BasicBlockId, smt::Expression> = HashMap::new();
+    let mut shared_subterms = 0;
     for (id, block) in program.reverse_walk() {
         let mut wp = block
             .successors
@@ -45,7 +142,10 @@ pub fn encode<C: Context>(
         for statement in block.statements.iter().rev() {
             eprintln!("     current wp: {}", wp);
             match statement {
-                low::Statement::Assert(low::Assert { assertion, label }) => {
+                low::Statement::Assert(low::Assert {
+                    assertion, label, ..
+                }) => {
+                    declare_nested_labels(context, assertion, &mut labels);
                     if let Some(label) = label {
                         let name = context.convert_label_name_to_smt(&label);
                         labels.push(smt::LabelDeclaration { name: name.clone() });
@@ -58,7 +158,10 @@ pub fn encode<C: Context>(
                         wp = smt::Expression::and(lower_expression(context, assertion)?, wp);
                     }
                 }
-                low::Statement::Assume(low::Assume { assertion, label }) => {
+                low::Statement::Assume(low::Assume {
+                    assertion, label, ..
+                }) => {
+                    declare_nested_labels(context, assertion, &mut labels);
                     if let Some(label) = label {
                         let name = context.convert_label_name_to_smt(&label);
                         labels.push(smt::LabelDeclaration { name: name.clone() });
@@ -73,6 +176,10 @@ pub fn encode<C: Context>(
                 }
             }
         }
+        if config.simplify {
+            wp = wp.simplify();
+        }
+        let wp = share_repeated_subterms(wp, &mut shared_subterms);
         let basic_block_label = context.create_label_for_basic_block(id);
         variables.push(smt::VariableDeclaration {
             name: basic_block_label.clone(),
@@ -86,9 +193,20 @@ pub fn encode<C: Context>(
     assertions.push(smt::Expression::not(smt::Expression::variable(
         context.create_label_for_basic_block(0.into()),
     )));
+    let adts = program
+        .adts
+        .iter()
+        .map(|adt| convert_adt_to_smt(context, adt))
+        .collect();
+    let functions = program
+        .functions
+        .iter()
+        .map(|function| convert_function_to_smt(context, function, config.simplify))
+        .collect::<SvirptiResult<_>>()?;
     let declarations = smt::Declarations {
         sorts: Vec::new(),
-        functions: Vec::new(),
+        adts,
+        functions,
         labels,
         variables,
     };
@@ -102,7 +220,8 @@ struct ExpressionLowerer<'a, C: Context> {
     context: &'a mut C,
 }
 
-impl<'a, C: Context> expression::Lowerer for ExpressionLowerer<'a, C> {
+impl<'a, C: Context> Lowerer for ExpressionLowerer<'a, C> {
+    type Error = SvirptiError;
     fn lower_variable_symbol(
         &mut self,
         symbol: &low::VariableSymbol,
@@ -115,12 +234,65 @@ impl<'a, C: Context> expression::Lowerer for ExpressionLowerer<'a, C> {
     ) -> SvirptiResult<smt::FunctionSymbol> {
         Ok(self.context.convert_function_name_to_smt(symbol))
     }
-    fn lower_sort_symbol(
+    fn lower_uninterpreted_sort_symbol(
         &mut self,
         symbol: &low::UninterpretedSortSymbol,
     ) -> SvirptiResult<smt::UninterpretedSortSymbol> {
         Ok(self.context.convert_uninterpreted_sort_to_smt(symbol))
     }
+    fn lower_adt_name_symbol(
+        &mut self,
+        symbol: &low::AdtNameSymbol,
+    ) -> SvirptiResult<smt::AdtNameSymbol> {
+        Ok(self.context.convert_adt_name_to_smt(symbol))
+    }
+    fn lower_adt_constructor_symbol(
+        &mut self,
+        symbol: &low::AdtConstructorSymbol,
+    ) -> SvirptiResult<smt::AdtConstructorSymbol> {
+        Ok(symbol.symbol().into())
+    }
+    fn lower_field_name_symbol(
+        &mut self,
+        symbol: &low::FieldNameSymbol,
+    ) -> SvirptiResult<smt::FieldNameSymbol> {
+        Ok(symbol.symbol().into())
+    }
+    fn lower_label_symbol(&mut self, symbol: &low::LabelSymbol) -> SvirptiResult<smt::LabelSymbol> {
+        Ok(self.context.convert_label_name_to_smt(symbol))
+    }
+}
+
+/// The labels of the subexpressions of `expression`, which mark the
+/// positions of the frontend.
+pub(super) fn nested_labels(expression: &low::Expression) -> Vec<low::LabelSymbol> {
+    struct LabelCollector {
+        labels: Vec<low::LabelSymbol>,
+    }
+    impl low::ExpressionWalker for LabelCollector {
+        fn walk_labelled_expression(&mut self, labelled: &low::LabelledExpression) {
+            self.labels.push(labelled.name.clone());
+            default_walk_labelled_expression(self, labelled);
+        }
+    }
+    let mut collector = LabelCollector { labels: Vec::new() };
+    collector.walk_expression(expression);
+    collector.labels
+}
+
+/// Declares the labels of the subexpressions of `assertion` that are not
+/// declared yet.
+fn declare_nested_labels<C: Context>(
+    context: &mut C,
+    assertion: &low::Expression,
+    labels: &mut Vec<smt::LabelDeclaration>,
+) {
+    for label in nested_labels(assertion) {
+        let name = context.convert_label_name_to_smt(&label);
+        if labels.iter().all(|declaration| declaration.name != name) {
+            labels.push(smt::LabelDeclaration { name });
+        }
+    }
 }
 
 fn lower_expression<C: Context>(
@@ -128,5 +300,5 @@ fn lower_expression<C: Context>(
     expression: &low::Expression,
 ) -> SvirptiResult<smt::Expression> {
     let mut lowerer = ExpressionLowerer { context };
-    expression::Lowerable::lower(expression, &mut lowerer)
+    lowerer.lower_expression(expression)
 }
@@ -1,6 +1,6 @@
 mod encode_wp;
-mod expression;
+mod sharing;
 mod verification_result;
 
-pub use self::encode_wp::encode;
+pub use self::encode_wp::{encode, encode_with_config};
 pub(crate) use self::verification_result::{get_all_errors, Model};
@@ -0,0 +1,251 @@
+//! Names the subterms that occur more than once in a weakest precondition, so
+//! that the solver gets each of them only once.
+//!
+//! Only subterms outside of binders are shared, so that they cannot refer to
+//! bound variables. Subterms that contain labels are not shared, so that
+//! every label stays at its own position.
+
+use std::collections::HashMap;
+use svirpti_vir::common::expression::{LetHelpers, VariableHelpers};
+use svirpti_vir::smt;
+
+/// Binds the maximal compound subterms that occur more than once in
+/// `expression` with `let` expressions around it. `next_name` numbers the
+/// bound variables.
+pub(super) fn share_repeated_subterms(
+    expression: smt::Expression,
+    next_name: &mut usize,
+) -> smt::Expression {
+    let mut counter = Counter::default();
+    counter.count(&expression);
+    let Counter { occurrences, order } = counter;
+    let shared: Vec<&smt::Expression> = order
+        .into_iter()
+        .filter(|subterm| occurrences[subterm] > 1)
+        .collect();
+    if shared.is_empty() {
+        return expression;
+    }
+    let mut names = HashMap::new();
+    let mut bindings = Vec::new();
+    // A subterm comes after the shared subterms it contains, so their
+    // variables are bound when its value is built.
+    for subterm in shared {
+        let value = map_children(subterm, &mut |child| replace(child, &names));
+        let name: smt::VariableSymbol = format!("shared%{}", next_name).into();
+        *next_name += 1;
+        names.insert(subterm, name.clone());
+        bindings.push((name, value));
+    }
+    let body = replace(&expression, &names);
+    bindings
+        .into_iter()
+        .rev()
+        .fold(body, |body, (name, value)| {
+            smt::Expression::let_in(name, value, body)
+        })
+}
+
+#[derive(Default)]
+struct Counter<'a> {
+    occurrences: HashMap<&'a smt::Expression, usize>,
+    /// The candidate subterms in the order in which their first occurrence was
+    /// completed.
+    order: Vec<&'a smt::Expression>,
+}
+
+impl<'a> Counter<'a> {
+    /// Counts the candidate subterms of `expression` and returns whether it
+    /// contains a label. The children of a repeated occurrence are not
+    /// counted again, so that only the maximal repeated subterms are shared.
+    fn count(&mut self, expression: &'a smt::Expression) -> bool {
+        if let Some(occurrences) = self.occurrences.get_mut(expression) {
+            *occurrences += 1;
+            return false;
+        }
+        let mut labelled = matches!(expression, smt::Expression::LabelledExpression(_));
+        for child in children(expression) {
+            labelled |= self.count(child);
+        }
+        if !labelled && is_compound(expression) {
+            self.occurrences.insert(expression, 1);
+            self.order.push(expression);
+        }
+        labelled
+    }
+}
+
+fn is_compound(expression: &smt::Expression) -> bool {
+    !matches!(
+        expression,
+        smt::Expression::Variable(_)
+            | smt::Expression::Constant(_)
+            | smt::Expression::EmptySeq(_)
+            | smt::Expression::EmptySet(_)
+    )
+}
+
+fn replace(
+    expression: &smt::Expression,
+    names: &HashMap<&smt::Expression, smt::VariableSymbol>,
+) -> smt::Expression {
+    if let Some(name) = names.get(expression) {
+        smt::Expression::variable(name.clone())
+    } else {
+        map_children(expression, &mut |child| replace(child, names))
+    }
+}
+
+/// The subexpressions of `expression` that are not in the scope of a binder
+/// of `expression`.
+fn children(expression: &smt::Expression) -> Vec<&smt::Expression> {
+    match expression {
+        smt::Expression::Variable(_)
+        | smt::Expression::Constant(_)
+        | smt::Expression::EmptySeq(_)
+        | smt::Expression::EmptySet(_)
+        | smt::Expression::Quantifier(_) => Vec::new(),
+        smt::Expression::UnaryOperation(operation) => vec![&operation.arg],
+        smt::Expression::BinaryOperation(operation) => vec![&operation.left, &operation.right],
+        smt::Expression::BitVectorConversion(conversion) => vec![&conversion.arg],
+        smt::Expression::FloatOperation(operation) => operation.args.iter().collect(),
+        smt::Expression::FloatConversion(conversion) => vec![&conversion.arg],
+        smt::Expression::ArraySelect(select) => vec![&select.array, &select.index],
+        smt::Expression::ArrayStore(store) => vec![&store.array, &store.index, &store.value],
+        smt::Expression::ConstantArray(array) => vec![&array.value],
+        smt::Expression::SeqOperation(operation) => operation.args.iter().collect(),
+        smt::Expression::SetOperation(operation) => operation.args.iter().collect(),
+        smt::Expression::AdtConstructor(constructor) => constructor.args.iter().collect(),
+        smt::Expression::AdtFieldAccess(access) => vec![&access.base],
+        smt::Expression::AdtTester(tester) => vec![&tester.base],
+        smt::Expression::AdtMatch(adt_match) => vec![&adt_match.base],
+        smt::Expression::Conditional(conditional) => vec![
+            &conditional.guard,
+            &conditional.then_expr,
+            &conditional.else_expr,
+        ],
+        smt::Expression::Let(binding) => vec![&binding.value],
+        smt::Expression::FunctionApplication(application) => application.args.iter().collect(),
+        smt::Expression::LabelledExpression(labelled) => vec![&labelled.expression],
+    }
+}
+
+/// Rebuilds `expression` with `map` applied to its [`children`].
+fn map_children(
+    expression: &smt::Expression,
+    map: &mut impl FnMut(&smt::Expression) -> smt::Expression,
+) -> smt::Expression {
+    let mut map_box = |expression: &smt::Expression| Box::new(map(expression));
+    match expression {
+        smt::Expression::Variable(_)
+        | smt::Expression::Constant(_)
+        | smt::Expression::EmptySeq(_)
+        | smt::Expression::EmptySet(_)
+        | smt::Expression::Quantifier(_) => expression.clone(),
+        smt::Expression::UnaryOperation(operation) => {
+            smt::Expression::UnaryOperation(smt::UnaryOperation {
+                kind: operation.kind,
+                arg: map_box(&operation.arg),
+            })
+        }
+        smt::Expression::BinaryOperation(operation) => {
+            smt::Expression::BinaryOperation(smt::BinaryOperation {
+                kind: operation.kind,
+                left: map_box(&operation.left),
+                right: map_box(&operation.right),
+            })
+        }
+        smt::Expression::BitVectorConversion(conversion) => {
+            smt::Expression::BitVectorConversion(smt::BitVectorConversion {
+                kind: conversion.kind,
+                arg: map_box(&conversion.arg),
+            })
+        }
+        smt::Expression::FloatOperation(operation) => {
+            smt::Expression::FloatOperation(smt::FloatOperation {
+                kind: operation.kind,
+                args: operation.args.iter().map(map).collect(),
+            })
+        }
+        smt::Expression::FloatConversion(conversion) => {
+            smt::Expression::FloatConversion(smt::FloatConversion {
+                kind: conversion.kind,
+                arg: map_box(&conversion.arg),
+            })
+        }
+        smt::Expression::ArraySelect(select) => smt::Expression::ArraySelect(smt::ArraySelect {
+            array: map_box(&select.array),
+            index: map_box(&select.index),
+        }),
+        smt::Expression::ArrayStore(store) => smt::Expression::ArrayStore(smt::ArrayStore {
+            array: map_box(&store.array),
+            index: map_box(&store.index),
+            value: map_box(&store.value),
+        }),
+        smt::Expression::ConstantArray(array) => {
+            smt::Expression::ConstantArray(smt::ConstantArray {
+                index_sort: array.index_sort.clone(),
+                value: map_box(&array.value),
+            })
+        }
+        smt::Expression::SeqOperation(operation) => {
+            smt::Expression::SeqOperation(smt::SeqOperation {
+                kind: operation.kind,
+                args: operation.args.iter().map(map).collect(),
+            })
+        }
+        smt::Expression::SetOperation(operation) => {
+            smt::Expression::SetOperation(smt::SetOperation {
+                kind: operation.kind,
+                args: operation.args.iter().map(map).collect(),
+            })
+        }
+        smt::Expression::AdtConstructor(constructor) => {
+            smt::Expression::AdtConstructor(smt::AdtConstructor {
+                adt: constructor.adt.clone(),
+                constructor: constructor.constructor.clone(),
+                args: constructor.args.iter().map(map).collect(),
+            })
+        }
+        smt::Expression::AdtFieldAccess(access) => {
+            smt::Expression::AdtFieldAccess(smt::AdtFieldAccess {
+                base: map_box(&access.base),
+                field: access.field.clone(),
+            })
+        }
+        smt::Expression::AdtTester(tester) => smt::Expression::AdtTester(smt::AdtTester {
+            base: map_box(&tester.base),
+            constructor: tester.constructor.clone(),
+        }),
+        smt::Expression::AdtMatch(adt_match) => smt::Expression::AdtMatch(smt::AdtMatch {
+            adt: adt_match.adt.clone(),
+            base: map_box(&adt_match.base),
+            cases: adt_match.cases.clone(),
+        }),
+        smt::Expression::Conditional(conditional) => {
+            smt::Expression::Conditional(smt::Conditional {
+                guard: map_box(&conditional.guard),
+                then_expr: map_box(&conditional.then_expr),
+                else_expr: map_box(&conditional.else_expr),
+            })
+        }
+        smt::Expression::Let(binding) => smt::Expression::Let(smt::Let {
+            variable: binding.variable.clone(),
+            value: map_box(&binding.value),
+            body: binding.body.clone(),
+        }),
+        smt::Expression::FunctionApplication(application) => {
+            smt::Expression::FunctionApplication(smt::FunctionApplication {
+                function: application.function.clone(),
+                args: application.args.iter().map(map).collect(),
+            })
+        }
+        smt::Expression::LabelledExpression(labelled) => {
+            smt::Expression::LabelledExpression(smt::LabelledExpression {
+                name: labelled.name.clone(),
+                positivity: labelled.positivity.clone(),
+                expression: map_box(&labelled.expression),
+            })
+        }
+    }
+}
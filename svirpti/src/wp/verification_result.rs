@@ -1,9 +1,11 @@
+use super::encode_wp::nested_labels;
 use crate::context::Context;
 use crate::{errors::SvirptiResult, SmtContext};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use svirpti_smt::solvers::{SatResult, SmtSolver};
 use svirpti_vir::common::cfg::Cfg;
 use svirpti_vir::common::expression::{UnaryOperationHelpers, VariableHelpers};
+use svirpti_vir::common::position::Position;
 use svirpti_vir::{low, smt};
 
 #[derive(Debug)]
@@ -48,6 +50,10 @@ pub struct VerificationError {
     pub trace: Vec<low::BasicBlockId>,
     /// The labels reported by the SMT solver.
     pub labels: Vec<low::LabelSymbol>,
+    /// The labels of the subexpressions of the failing assertion that failed.
+    pub failing_subexpressions: Vec<low::LabelSymbol>,
+    /// The position of the failing assertion.
+    pub position: Option<Position>,
     /// The model returned by the SMT solver.
     pub model: Model,
 }
@@ -57,9 +63,14 @@ impl VerificationError {
         smt_context: &SmtContext<C>,
         failing_assertion: smt::LabelSymbol,
         trace: Vec<smt::LabelSymbol>,
+        failing_subexpressions: Vec<smt::LabelSymbol>,
         model: &smt::Model,
     ) -> Self {
         let failing_assertion = smt_context.context.resolve_low_label(&failing_assertion);
+        let failing_subexpressions = failing_subexpressions
+            .into_iter()
+            .map(|label| smt_context.context.resolve_low_label(&label))
+            .collect();
         let trace: Vec<_> = trace
             .into_iter()
             .map(|label| smt_context.context.resolve_low_label(&label))
@@ -105,12 +116,21 @@ impl VerificationError {
             basic_block_trace.push(current_block);
         }
         basic_block_trace.reverse();
+        let position = smt_context
+            .vir
+            .basic_blocks
+            .iter()
+            .flat_map(|block| &block.statements)
+            .find(|statement| statement.get_label() == Some(&failing_assertion))
+            .and_then(|statement| statement.get_position());
 
         Self {
             failing_assertion,
             model: Model::new(smt_context, model),
             trace: basic_block_trace,
             labels: trace,
+            failing_subexpressions,
+            position,
         }
     }
 }
@@ -120,21 +140,37 @@ impl VerificationError {
 /// counterexamples](https://www.microsoft.com/en-us/research/wp-content/uploads/2016/12/krml120.pdf)
 /// to generate all errors.
 pub(crate) fn get_all_errors<S: SmtSolver, C: Context>(
-    mut failure: svirpti_smt::VerificationFailure<S>,
+    failure: &mut svirpti_smt::VerificationFailure<S>,
     smt_context: &SmtContext<C>,
 ) -> SvirptiResult<Vec<VerificationError>> {
     let investigator = failure.get_investigator();
-    let labels = investigator.get_labels(smt_context)?;
+    let reported_labels = investigator.get_labels(smt_context)?;
     let mut model = investigator.get_model(smt_context)?;
-    eprintln!("Labels: {:?} model: {:?}", labels, model);
+    eprintln!("Labels: {:?} model: {:?}", reported_labels, model);
 
-    // Get all labels from the program fragment sorted in a topological order.
+    // Get all labels from the program fragment sorted in a topological order
+    // and the labels of the subexpressions with the labels of their
+    // statements.
     let mut all_labels = Vec::new();
+    let mut nested_label_statements = HashMap::new();
     for (_, block) in smt_context.vir.walk() {
         for statement in &block.statements {
-            if let Some(label) = statement.get_label() {
-                all_labels.push(smt_context.context.convert_known_label_name_to_smt(label));
+            let label = statement
+                .get_label()
+                .map(|label| smt_context.context.convert_known_label_name_to_smt(label));
+            let assertion = match statement {
+                low::Statement::Assert(low::Assert { assertion, .. })
+                | low::Statement::Assume(low::Assume { assertion, .. }) => assertion,
+            };
+            for nested_label in nested_labels(assertion) {
+                nested_label_statements.insert(
+                    smt_context
+                        .context
+                        .convert_known_label_name_to_smt(&nested_label),
+                    label.clone(),
+                );
             }
+            all_labels.extend(label);
         }
     }
     eprintln!("all labels: {:?}", all_labels);
@@ -151,6 +187,28 @@ pub(crate) fn get_all_errors<S: SmtSolver, C: Context>(
         labels.max_by_key(|label| label_ids[label]).unwrap().clone()
     }
 
+    /// Splits the reported labels into the labels of the statements and the
+    /// labels of the subexpressions.
+    fn split_nested_labels(
+        nested_label_statements: &HashMap<smt::LabelSymbol, Option<smt::LabelSymbol>>,
+        labels: Vec<smt::LabelSymbol>,
+    ) -> (Vec<smt::LabelSymbol>, Vec<smt::LabelSymbol>) {
+        labels
+            .into_iter()
+            .partition(|label| !nested_label_statements.contains_key(label))
+    }
+
+    fn failing_subexpressions(
+        nested_label_statements: &HashMap<smt::LabelSymbol, Option<smt::LabelSymbol>>,
+        reported_nested_labels: Vec<smt::LabelSymbol>,
+        failing_assertion: &smt::LabelSymbol,
+    ) -> Vec<smt::LabelSymbol> {
+        reported_nested_labels
+            .into_iter()
+            .filter(|label| nested_label_statements[label].as_ref() == Some(failing_assertion))
+            .collect()
+    }
+
     fn compute_failing_trace(
         label_ids: &HashMap<smt::LabelSymbol, usize>,
         labels: &[smt::LabelSymbol],
@@ -165,12 +223,20 @@ pub(crate) fn get_all_errors<S: SmtSolver, C: Context>(
         trace
     }
 
+    let (labels, reported_nested_labels) =
+        split_nested_labels(&nested_label_statements, reported_labels);
     let failing_assertion = find_last_label(&label_ids, labels.iter());
     let failing_trace = compute_failing_trace(&label_ids, &labels, &failing_assertion);
+    let failing_nested_labels = failing_subexpressions(
+        &nested_label_statements,
+        reported_nested_labels,
+        &failing_assertion,
+    );
     let mut errors = vec![VerificationError::new(
         smt_context,
         failing_assertion,
         failing_trace,
+        failing_nested_labels,
         &model,
     )];
 
@@ -235,7 +301,10 @@ pub(crate) fn get_all_errors<S: SmtSolver, C: Context>(
         eprintln!("result: {:?}", result);
         if result != SatResult::Unsat {
             // We got a new failing trace.
-            let new_labels = investigator.get_labels(smt_context)?;
+            let (new_labels, new_nested_labels) = split_nested_labels(
+                &nested_label_statements,
+                investigator.get_labels(smt_context)?,
+            );
             let new_model = investigator.get_model(smt_context)?;
             eprintln!("new labels = {:?} model = {:?}", new_labels, new_model);
 
@@ -258,10 +327,16 @@ pub(crate) fn get_all_errors<S: SmtSolver, C: Context>(
 
             let failing_assertion = find_last_label(&label_ids, new_labels.iter());
             let failing_trace = compute_failing_trace(&label_ids, &new_labels, &failing_assertion);
+            let failing_nested_labels = failing_subexpressions(
+                &nested_label_statements,
+                new_nested_labels,
+                &failing_assertion,
+            );
             errors.push(VerificationError::new(
                 smt_context,
                 failing_assertion,
                 failing_trace,
+                failing_nested_labels,
                 &new_model,
             ));
 
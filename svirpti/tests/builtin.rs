@@ -0,0 +1,284 @@
+//! End-to-end checks with the built-in solver. They do not need Z3.
+
+use svirpti::{
+    context::StringContext, verify, verify_cancellable, verify_with_pool, verify_with_solver,
+    CancellationToken, VerificationConfig,
+};
+use svirpti_smt::solvers::builtin::BuiltinSmtSolver;
+use svirpti_smt::solvers::pool::SolverPool;
+use svirpti_smt::solvers::recording::{read_recording, RecordingSolver, SolverEvent};
+use svirpti_smt::solvers::{Logic, OptionProfile, SmtSolver};
+use svirpti_vir::common::expression::{
+    BinaryOperationHelpers, ConstantHelpers, PositionedExpressionHelpers, VariableHelpers,
+};
+use svirpti_vir::common::position::Position;
+use svirpti_vir::high;
+use svirpti_vir_derive::vir_high;
+
+#[test]
+fn check_success() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+                y: Int,
+            }
+            bb1 {
+                guard true;
+                assume l0 x > 0;
+                assign y = x + 1;
+                assert l1 y > 1;
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    let result = verify::<BuiltinSmtSolver, _>(&mut context, &program).unwrap();
+    assert!(result.is_success());
+    assert!(result
+        .get_statistics()
+        .unwrap()
+        .solver
+        .get("conflicts")
+        .is_some());
+}
+
+#[test]
+fn check_multiple_errors() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard true;
+                goto { bb2, bb3 }
+            }
+            bb2 {
+                guard x > 0;
+                assert l1 x == 2;
+                goto { bb4 }
+            }
+            bb3 {
+                guard !(x > 0);
+                assert l2 x < 0;
+                goto { bb4 }
+            }
+            bb4 {
+                guard true;
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    match verify::<BuiltinSmtSolver, _>(&mut context, &program).unwrap() {
+        svirpti::VerificationResult::Failure(failure) => {
+            let (errors, statistics) = failure.get_all_errors_with_statistics().unwrap();
+            // The verification query and at least one query per additional
+            // error.
+            assert!(statistics.len() >= errors.len());
+            let mut failing: Vec<_> = errors
+                .into_iter()
+                .map(|error| error.failing_assertion.to_string())
+                .collect();
+            failing.sort();
+            failing.dedup();
+            assert_eq!(failing, vec!["l1", "l2"]);
+        }
+        x => unreachable!("{:?}", x),
+    }
+}
+
+#[test]
+fn check_cancelled() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard true;
+                assert l1 x > 0;
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    let cancellation = CancellationToken::new();
+    cancellation.cancel();
+    let result =
+        verify_cancellable::<BuiltinSmtSolver, _>(&mut context, &program, &cancellation).unwrap();
+    assert!(result.is_cancelled());
+    assert!(result.get_statistics().is_none());
+}
+
+#[test]
+fn check_timeout() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard true;
+                assert l1 x > 0;
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    let cancellation = CancellationToken::with_timeout(std::time::Duration::from_secs(0));
+    let result =
+        verify_cancellable::<BuiltinSmtSolver, _>(&mut context, &program, &cancellation).unwrap();
+    assert!(result.is_cancelled());
+}
+
+#[test]
+fn check_pooled_solver() {
+    let pool = SolverPool::<BuiltinSmtSolver>::new(Default::default());
+    for _ in 0..2 {
+        let program = vir_high! {
+            procedure {
+                locals {
+                    x: Int,
+                }
+                bb1 {
+                    guard true;
+                    assume l0 x > 1;
+                    assert l1 x > 0;
+                    goto { exit }
+                }
+            }
+        };
+        let mut context = StringContext::default();
+        let result = verify_with_pool(
+            &pool,
+            &mut context,
+            &program,
+            &VerificationConfig::default(),
+            &CancellationToken::new(),
+        )
+        .unwrap();
+        assert!(result.is_success());
+        assert_eq!(pool.idle_count(), 1);
+    }
+}
+
+#[test]
+fn check_option_profiles() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard true;
+                assume l0 x > 1;
+                assert l1 x > 0;
+                goto { exit }
+            }
+        }
+    };
+    let config = VerificationConfig {
+        procedure_profile: Some(OptionProfile::fast()),
+        procedure_profile_overrides: vec![
+            ("l1".into(), OptionProfile::nonlinear()),
+            ("l2".into(), OptionProfile::quantifier_heavy()),
+        ],
+        infer_logic: true,
+        recursive_function_fuel: None,
+        simplify: false,
+    };
+    let path = std::env::temp_dir().join(format!("svirpti-profiles-{}.jsonl", std::process::id()));
+    let solver =
+        RecordingSolver::wrap(BuiltinSmtSolver::default().unwrap(), Some(path.clone())).unwrap();
+    let mut context = StringContext::default();
+    let result = verify_with_solver(
+        solver,
+        &mut context,
+        &program,
+        &config,
+        &CancellationToken::new(),
+    )
+    .unwrap();
+    assert!(result.is_success());
+    let events = read_recording(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let option = |name: &str, value: &str| SolverEvent::SetOption(name.into(), value.into());
+    // `l2` does not occur in the procedure.
+    assert!(events.contains(&option(":timeout", "2000")));
+    assert!(events.contains(&option(":smt.arith.nl", "true")));
+    assert!(!events.contains(&option(":smt.qi.max_multi_patterns", "1000")));
+    assert!(events.contains(&SolverEvent::SetLogic(Logic::QF_LIA)));
+}
+
+#[test]
+fn check_let() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+                y: Int,
+            }
+            bb1 {
+                guard true;
+                assume l0 x > 0;
+                assign y = x + 1;
+                assert l1 (let x = y + 1 in x > y) && y + 1 > x;
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    let result = verify::<BuiltinSmtSolver, _>(&mut context, &program).unwrap();
+    assert!(result.is_success());
+}
+
+#[test]
+fn check_positions() {
+    let mut program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard true;
+                assume l0 x > 0;
+                assert l1 true;
+                goto { exit }
+            }
+        }
+    };
+    let var = || high::Expression::variable("x".into());
+    for block in program.procedure.basic_blocks.iter_mut() {
+        for statement in &mut block.statements {
+            if let high::Statement::Assert(assert) = statement {
+                // @2: x > 0 && @3: x > 5
+                assert.assertion = high::Expression::and(
+                    high::Expression::positioned(
+                        Position::new(2),
+                        high::Expression::greater_than(var(), high::Expression::int(0)),
+                    ),
+                    high::Expression::positioned(
+                        Position::new(3),
+                        high::Expression::greater_than(var(), high::Expression::int(5)),
+                    ),
+                );
+                assert.position = Some(Position::new(1));
+            }
+        }
+    }
+    let mut context = StringContext::default();
+    match verify::<BuiltinSmtSolver, _>(&mut context, &program).unwrap() {
+        svirpti::VerificationResult::Failure(failure) => {
+            let errors = failure.get_all_errors().unwrap();
+            assert!(!errors.is_empty());
+            for error in errors {
+                assert_eq!(error.failing_assertion.to_string(), "l1");
+                assert_eq!(error.position, Some(Position::new(1)));
+                assert_eq!(error.failing_positions, vec![Position::new(3)]);
+            }
+        }
+        x => unreachable!("{:?}", x),
+    }
+}
@@ -0,0 +1,28 @@
+//! Helpers shared by the integration tests.
+
+use std::path::Path;
+use svirpti::{CancellationToken, Context, SvirptiError, VerificationConfig, VerificationResult};
+use svirpti_smt::solvers::{recording::RecordedSolver, z3_smt2::Z3SmtSolver};
+use svirpti_vir::high;
+
+pub type RecordedZ3 = RecordedSolver<Z3SmtSolver>;
+
+/// Verifies `program` by replaying `tests/recordings/<name>.jsonl`. If
+/// `SVIRPTI_RERECORD` is set, runs Z3 and records it instead.
+pub fn verify<'a, C: Context>(
+    name: &str,
+    context: &'a mut C,
+    program: &high::ProgramFragment,
+) -> Result<VerificationResult<'a, C, RecordedZ3>, SvirptiError> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/recordings")
+        .join(format!("{}.jsonl", name));
+    let solver = RecordedZ3::open(path).map_err(svirpti_smt::VerifierError::from)?;
+    svirpti::verify_with_solver(
+        solver,
+        context,
+        program,
+        &VerificationConfig::default(),
+        &CancellationToken::new(),
+    )
+}
@@ -1,8 +1,9 @@
 //! Examples from papers and tutorials to check that all errors are reported as
 //! expected.
 
-use svirpti::{context::StringContext, lower, verify};
-use svirpti_smt::solvers::z3_smt2::Z3SmtSolver;
+mod common;
+
+use svirpti::{context::StringContext, lower};
 use svirpti_vir_derive::vir_high;
 
 /// An example from
@@ -36,9 +37,9 @@ fn labels_paper_example() {
             }
         }
     };
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     insta::assert_display_snapshot!(lower(&program, &mut context).unwrap());
-    match verify::<Z3SmtSolver, _>(&mut context, &program).unwrap() {
+    match common::verify("labels_paper_example", &mut context, &program).unwrap() {
         svirpti::VerificationResult::Failure(failure) => {
             let mut errors = failure.get_all_errors().unwrap();
             assert_eq!(errors.len(), 1);
@@ -0,0 +1,233 @@
+//! Checks of the run-time parser for the textual representation of high VIR.
+
+use svirpti_vir::high;
+use svirpti_vir_derive::vir_high;
+
+#[test]
+fn check_same_as_macro() {
+    let expected = vir_high! {
+        sort Loc;
+        adt List {
+            Nil,
+            Cons(head: Int, tail: Adt<List>),
+        }
+        fn len(list: Adt<List>) -> Int {
+            match list {
+                List::Nil => 0,
+                List::Cons(head, tail) => 1 + len(tail),
+            }
+        }
+        axiom positive_len {
+            forall(|l: Adt<List>| len(l) >= 0, [(len(l))])
+        }
+        procedure {
+            locals {
+                x: Int,
+                b: BV8,
+                r: Real,
+                f: F32,
+                a: Array<Int, Loc>,
+            }
+            bb1 {
+                guard true;
+                assume l0 x > 123456789012345678901234567890 && b == 5bv8;
+                assign r = 1.25 + to_real(x);
+                havoc x;
+                goto { bb2, exit }
+            }
+            bb2 {
+                guard x < 0 || fp_is_nan(f) -> r == 0.5;
+                assert l1 if x > 0 { a[x := a[0]] == a } else { 1.5f32 == f };
+                goto { exit }
+            }
+        }
+    };
+    let parsed = high::ProgramFragment::parse_str(
+        r#"
+        sort Loc;
+        adt List {
+            Nil,
+            Cons(head: Int, tail: Adt<List>),
+        }
+        fn len(list: Adt<List>) -> Int {
+            match list {
+                List::Nil => 0,
+                List::Cons(head, tail) => 1 + len(tail),
+            }
+        }
+        axiom positive_len {
+            forall(|l: Adt<List>| len(l) >= 0, [(len(l))])
+        }
+        procedure {
+            locals {
+                x: Int,
+                b: BV8,
+                r: Real,
+                f: F32,
+                a: Array<Int, Loc>,
+            }
+            bb1 {
+                guard true;
+                assume l0 x > 123456789012345678901234567890 && b == 5bv8;
+                assign r = 1.25 + to_real(x);
+                havoc x;
+                goto { bb2, exit }
+            }
+            bb2 {
+                guard x < 0 || fp_is_nan(f) -> r == 0.5;
+                assert l1 if x > 0 { a[x := a[0]] == a } else { 1.5f32 == f };
+                goto { exit }
+            }
+        }
+        "#,
+    )
+    .unwrap();
+    assert_eq!(parsed, expected);
+}
+
+#[test]
+fn check_errors() {
+    let error = high::ProgramFragment::parse_str(
+        "procedure {\n  locals {}\n  bb1 {\n    guard true;\n    goto { bb2 }\n  }\n}",
+    )
+    .unwrap_err();
+    assert_eq!((error.line, error.column), (5, 12));
+    assert_eq!(error.message, "no block with this label");
+    let error = high::ProgramFragment::parse_str(
+        "procedure {\n  locals {}\n  bb1 {\n    guard #guard;\n    goto { exit }\n  }\n}",
+    )
+    .unwrap_err();
+    assert_eq!((error.line, error.column), (4, 12));
+    assert_eq!(
+        error.to_string(),
+        "line 4, column 12: `#guard` can only be filled in by `vir_high!`"
+    );
+    let error = high::ProgramFragment::parse_str(
+        "procedure {\n  locals {\n    f: Float<100, 100>,\n  }\n  bb1 {\n    guard true;\n    goto { exit }\n  }\n}",
+    )
+    .unwrap_err();
+    assert_eq!((error.line, error.column), (3, 14));
+    assert_eq!(
+        error.message,
+        "Expected at least 2 exponent and 2 significand bits, and at most 128 bits in total"
+    );
+    let error = high::ProgramFragment::parse_str("sort Loc;").unwrap_err();
+    assert_eq!(error.message, "missing procedure");
+}
+
+fn check_round_trip(source: &str) -> String {
+    let program = high::ProgramFragment::parse_str(source).unwrap();
+    let printed = program.to_string();
+    let reparsed = high::ProgramFragment::parse_str(&printed)
+        .unwrap_or_else(|error| panic!("{}\n{}", error, printed));
+    assert_eq!(reparsed, program, "{}", printed);
+    assert_eq!(reparsed.to_string(), printed);
+    printed
+}
+
+#[test]
+fn check_print() {
+    let printed = check_round_trip(
+        r#"
+        sort Loc;
+        fn inc(x: Int) -> Int requires x >= 0 && (x < 10 || x == 20) { x + 1 }
+        fn opaque(l: Loc) -> Bool;
+        axiom inc_grows { forall(|x: Int| inc(x) > x, [(inc(x))]) }
+        procedure {
+            locals { x: Int, b: Bool, }
+            start {
+                guard true;
+                assume l0 (x > 0 || b) && x - (x - 1) == 1;
+                assert l1 !(x > 0) -> x <= 0;
+                assign x = (let y = x in y * 2) + 1;
+                goto { next, exit }
+            }
+            next {
+                guard b;
+                havoc x;
+                goto { exit }
+            }
+        }
+        "#,
+    );
+    let expected = r#"sort Loc;
+
+fn inc(x: Int) -> Int
+    requires x >= 0 && (x < 10 || x == 20)
+{
+    x + 1
+}
+
+fn opaque(l: Loc) -> Bool;
+
+axiom inc_grows {
+    forall(|x: Int| inc(x) > x, [(inc(x))])
+}
+
+procedure {
+    locals {
+        x: Int,
+        b: Bool,
+    }
+    start {
+        guard true;
+        assume l0 (x > 0 || b) && x - (x - 1) == 1;
+        assert l1 !(x > 0) -> x <= 0;
+        assign x = (let y = x in y * 2) + 1;
+        goto { next, exit }
+    }
+    next {
+        guard b;
+        havoc x;
+        goto { exit }
+    }
+}
+"#;
+    assert_eq!(printed, expected);
+    let mut program = high::ProgramFragment::parse_str(&printed).unwrap();
+    program.axioms[0].name = None;
+    if let high::statement::Statement::Assert(assert) =
+        &mut program.procedure.basic_blocks.raw[1].statements[1]
+    {
+        assert.label = None;
+    }
+    let printed = program.to_string();
+    assert!(printed.contains("axiom axiom_0 {"), "{}", printed);
+    assert!(printed.contains("assert start_1 !(x > 0)"), "{}", printed);
+}
+
+#[test]
+fn check_print_round_trip() {
+    check_round_trip(
+        r#"
+        adt List {
+            Nil,
+            Cons(head: Int, tail: Adt<List>),
+        }
+        procedure {
+            locals {
+                l: Adt<List>,
+                v: BV8,
+                f: F32,
+                r: Real,
+                a: Array<Int, Int>,
+                s: Seq<Int>,
+                t: Set<Int>,
+            }
+            bb1 {
+                guard (if is_Cons(l) { l } else { List::Cons(1, List::Nil) }).head == 1;
+                assume a1 match l { List::Nil => true, List::Cons(h, t) => h - -1 > 0 };
+                assume a2 sdiv(v, 3bv8) == extract(zero_extend(v, 8), 7, 0) & (v | 1bv8) << 1bv8;
+                assume a3 fp_add(RNE, f, 1.5f32) == to_fp(RTZ, r, F32) && fp_is_nan(f);
+                assume a4 fp_to_sbv(RNE, f, 32) == int2bv(bv2int(v), 32);
+                assume a5 (a[0 := 1])[1] == const_array<Int>(0)[2];
+                assume a6 seq_len(seq_concat(s, seq_unit(1))) == 2 && set_contains(t, 1);
+                assume a7 set_union(t, set_empty<Int>()) == t && s != seq_empty<Int>();
+                assume a8 (1.0 / 3.0 + to_real(2)) * 1.25 > r;
+                assume a9 exists(|x: Int, y: Int| x < y, []) -> (r > 0.0 -> false);
+                goto { exit }
+            }
+        }
+        "#,
+    );
+}
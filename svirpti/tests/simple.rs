@@ -1,5 +1,6 @@
-use svirpti::{context::StringContext, encode, lower, verify};
-use svirpti_smt::solvers::z3_smt2::Z3SmtSolver;
+mod common;
+
+use svirpti::{context::StringContext, encode, lower};
 use svirpti_vir::common::{expression::VariableHelpers, statement::AssumeAssertHelpers};
 use svirpti_vir::high;
 use svirpti_vir_derive::vir_high;
@@ -7,6 +8,7 @@ use svirpti_vir_derive::vir_high;
 fn trivial_fail() -> high::ProgramFragment {
     high::ProgramFragment {
         sorts: vec![],
+        adts: vec![],
         axioms: vec![],
         functions: vec![],
         procedure: high::ProcedureDeclaration {
@@ -66,7 +68,7 @@ fn trivial_fail_macro_test() {
 fn lower_trivial_fail() {
     let program = trivial_fail();
     insta::assert_yaml_snapshot!(program);
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     let lowered = lower(&program, &mut context).unwrap();
     insta::assert_yaml_snapshot!(lowered);
 }
@@ -74,7 +76,7 @@ fn lower_trivial_fail() {
 #[test]
 fn encode_trivial_fail() {
     let program = trivial_fail();
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     let lowered = lower(&program, &mut context).unwrap();
     let encoded = encode(&lowered, &mut context).unwrap();
     insta::assert_yaml_snapshot!(encoded);
@@ -83,8 +85,8 @@ fn encode_trivial_fail() {
 #[test]
 fn verify_trivial_fail() {
     let program = trivial_fail();
-    let mut context = StringContext {};
-    match verify::<Z3SmtSolver, _>(&mut context, &program).unwrap() {
+    let mut context = StringContext::default();
+    match common::verify("verify_trivial_fail", &mut context, &program).unwrap() {
         svirpti::VerificationResult::Failure(failure) => {
             let errors = failure.get_all_errors().unwrap();
             insta::assert_yaml_snapshot!(errors);
@@ -110,15 +112,17 @@ fn check_assume_encoding() {
         }
     };
     insta::assert_yaml_snapshot!(program);
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     insta::assert_yaml_snapshot!(lower(&program, &mut context).unwrap());
     let lowered = lower(&program, &mut context).unwrap();
     insta::assert_display_snapshot!(lowered);
     let encoded = encode(&lowered, &mut context).unwrap();
     insta::assert_display_snapshot!(encoded);
-    assert!(verify::<Z3SmtSolver, _>(&mut context, &program)
-        .unwrap()
-        .is_success());
+    assert!(
+        common::verify("check_assume_encoding", &mut context, &program)
+            .unwrap()
+            .is_success()
+    );
 }
 
 #[test]
@@ -144,15 +148,17 @@ fn check_sequence_encoding() {
         }
     };
     insta::assert_yaml_snapshot!(program);
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     insta::assert_yaml_snapshot!(lower(&program, &mut context).unwrap());
     let lowered = lower(&program, &mut context).unwrap();
     insta::assert_display_snapshot!(lowered);
     let encoded = encode(&lowered, &mut context).unwrap();
     insta::assert_display_snapshot!(encoded);
-    assert!(verify::<Z3SmtSolver, _>(&mut context, &program)
-        .unwrap()
-        .is_success());
+    assert!(
+        common::verify("check_sequence_encoding", &mut context, &program)
+            .unwrap()
+            .is_success()
+    );
 }
 
 #[test]
@@ -186,15 +192,19 @@ fn check_non_deterministic_choice_encoding() {
         }
     };
     insta::assert_yaml_snapshot!(program);
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     insta::assert_yaml_snapshot!(lower(&program, &mut context).unwrap());
     let lowered = lower(&program, &mut context).unwrap();
     insta::assert_display_snapshot!(lowered);
     let encoded = encode(&lowered, &mut context).unwrap();
     insta::assert_display_snapshot!(encoded);
-    assert!(verify::<Z3SmtSolver, _>(&mut context, &program)
-        .unwrap()
-        .is_success());
+    assert!(common::verify(
+        "check_non_deterministic_choice_encoding",
+        &mut context,
+        &program
+    )
+    .unwrap()
+    .is_success());
 }
 
 #[test]
@@ -227,15 +237,19 @@ fn check_non_deterministic_choice_encoding2() {
         }
     };
     insta::assert_yaml_snapshot!(program);
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     insta::assert_yaml_snapshot!(lower(&program, &mut context).unwrap());
     let lowered = lower(&program, &mut context).unwrap();
     insta::assert_display_snapshot!(lowered);
     let encoded = encode(&lowered, &mut context).unwrap();
     insta::assert_display_snapshot!(encoded);
-    assert!(verify::<Z3SmtSolver, _>(&mut context, &program)
-        .unwrap()
-        .is_success());
+    assert!(common::verify(
+        "check_non_deterministic_choice_encoding2",
+        &mut context,
+        &program
+    )
+    .unwrap()
+    .is_success());
 }
 
 #[test]
@@ -259,15 +273,17 @@ fn check_assign_encoding() {
         }
     };
     insta::assert_yaml_snapshot!(program);
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     insta::assert_yaml_snapshot!(lower(&program, &mut context).unwrap());
     let lowered = lower(&program, &mut context).unwrap();
     insta::assert_display_snapshot!(lowered);
     let encoded = encode(&lowered, &mut context).unwrap();
     insta::assert_display_snapshot!(encoded);
-    assert!(verify::<Z3SmtSolver, _>(&mut context, &program)
-        .unwrap()
-        .is_success());
+    assert!(
+        common::verify("check_assign_encoding", &mut context, &program)
+            .unwrap()
+            .is_success()
+    );
 }
 
 #[test]
@@ -299,12 +315,12 @@ fn check_multiple_errors() {
         }
     };
     insta::assert_display_snapshot!(program);
-    let mut context = StringContext {};
+    let mut context = StringContext::default();
     let lowered = lower(&program, &mut context).unwrap();
     insta::assert_display_snapshot!(lowered);
     let encoded = encode(&lowered, &mut context).unwrap();
     insta::assert_display_snapshot!(encoded);
-    match verify::<Z3SmtSolver, _>(&mut context, &program).unwrap() {
+    match common::verify("check_multiple_errors", &mut context, &program).unwrap() {
         svirpti::VerificationResult::Failure(failure) => {
             let errors = failure.get_all_errors().unwrap();
             insta::assert_yaml_snapshot!(errors);
@@ -312,3 +328,126 @@ fn check_multiple_errors() {
         x => unreachable!("{:?}", x),
     }
 }
+
+#[test]
+fn lower_function_preconditions() {
+    let program = vir_high! {
+        fn sum(n: Int) -> Int requires n >= 0 { if n == 0 { 0 } else { n + sum(n - 1) } }
+        procedure {
+            locals {
+                x: Int,
+                y: Int,
+            }
+            bb1 {
+                guard x > 0 -> sum(x) > 0;
+                assign y = sum(x);
+                assert l1 y >= x;
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    let lowered = lower(&program, &mut context).unwrap();
+    insta::assert_display_snapshot!(lowered);
+    let encoded = encode(&lowered, &mut context).unwrap();
+    insta::assert_yaml_snapshot!(encoded);
+}
+
+#[test]
+fn verify_match_binder_arithmetic() {
+    let program = vir_high! {
+        adt List {
+            Nil,
+            Cons(head: Int, tail: Adt<List>),
+        }
+        procedure {
+            locals {
+                l: Adt<List>,
+            }
+            bb1 {
+                guard is_Cons(l) && l.head > 0;
+                assert l1 match l { List::Nil => false, List::Cons(h, t) => h - 1 >= 0 };
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    assert!(
+        common::verify("verify_match_binder_arithmetic", &mut context, &program)
+            .unwrap()
+            .is_success()
+    );
+}
+
+#[test]
+fn verify_functions_with_same_parameter_names() {
+    let program = vir_high! {
+        fn inc(x: Int) -> Int { x + 1 }
+        fn positive(x: Real) -> Bool { x > 0.0 }
+        procedure {
+            locals {
+                y: Int,
+                r: Real,
+            }
+            bb1 {
+                guard r > 1.0;
+                assert l1 inc(y) > y && positive(r);
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    assert!(common::verify(
+        "verify_functions_with_same_parameter_names",
+        &mut context,
+        &program
+    )
+    .unwrap()
+    .is_success());
+}
+
+#[test]
+fn lower_let_expressions() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+                y: Int,
+            }
+            bb1 {
+                guard true;
+                assign y = x + 1;
+                assert l1 (let x = y + 1 in x > y) && y + 1 > x;
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    let lowered = lower(&program, &mut context).unwrap();
+    insta::assert_display_snapshot!(lowered);
+    let encoded = encode(&lowered, &mut context).unwrap();
+    insta::assert_display_snapshot!(encoded);
+}
+
+#[test]
+fn verify_scoped_let_expressions() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard true;
+                assert l1 forall(|i: Int| let y = i + 1 in y > i, []);
+                assert l2 (let y = x > 0 in y || !y) && (let y = x + 1 in y > x);
+                goto { exit }
+            }
+        }
+    };
+    let mut context = StringContext::default();
+    assert!(
+        common::verify("verify_scoped_let_expressions", &mut context, &program)
+            .unwrap()
+            .is_success()
+    );
+}
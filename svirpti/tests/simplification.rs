@@ -0,0 +1,79 @@
+//! Checks of the expression simplifier.
+
+use svirpti_vir::common::expression::{
+    BinaryOperationHelpers, ConstantHelpers, ExpressionIterator, LabelledExpressionHelpers,
+    Simplification, UnaryOperationHelpers, VariableHelpers,
+};
+use svirpti_vir::{high, smt};
+
+fn var(name: &str) -> high::Expression {
+    high::Expression::variable(name.into())
+}
+
+#[test]
+fn check_constant_folding() {
+    // 1 + 2 > 2
+    let expression = high::Expression::greater_than(
+        high::Expression::add(high::Expression::int(1), high::Expression::int(2)),
+        high::Expression::int(2),
+    );
+    assert_eq!(expression.simplify(), high::Expression::bool(true));
+    let expression = high::Expression::minus(high::Expression::int(3));
+    assert_eq!(expression.simplify(), high::Expression::int(-3));
+}
+
+#[test]
+fn check_boolean_simplification() {
+    // !!p
+    let expression = high::Expression::not(high::Expression::not(var("p")));
+    assert_eq!(expression.simplify(), var("p"));
+    // x + 1 == x + 1
+    let expression = high::Expression::equals(
+        high::Expression::add(var("x"), high::Expression::int(1)),
+        high::Expression::add(var("x"), high::Expression::int(1)),
+    );
+    assert_eq!(expression.simplify(), high::Expression::bool(true));
+    // p || !p
+    let expression = high::Expression::or(var("p"), high::Expression::not(var("p")));
+    assert_eq!(expression.simplify(), high::Expression::bool(true));
+}
+
+#[test]
+fn check_flattening_and_absorption() {
+    // (a && (b && a)) && (a || c)
+    let expression = high::Expression::and(
+        high::Expression::and(var("a"), high::Expression::and(var("b"), var("a"))),
+        high::Expression::or(var("a"), var("c")),
+    );
+    assert_eq!(
+        expression.simplify(),
+        high::Expression::and(var("a"), var("b"))
+    );
+    // (true && a) || (false || b)
+    let expression = high::Expression::or(
+        high::Expression::and(high::Expression::bool(true), var("a")),
+        high::Expression::or(high::Expression::bool(false), var("b")),
+    );
+    assert_eq!(
+        expression.simplify(),
+        high::Expression::or(var("a"), var("b"))
+    );
+}
+
+#[test]
+fn check_disjoin() {
+    let disjunction = vec![var("a"), var("b")].into_iter().disjoin();
+    assert_eq!(disjunction, high::Expression::or(var("a"), var("b")));
+}
+
+#[test]
+fn check_labels_are_preserved() {
+    let labelled =
+        smt::Expression::label_negative("l".into(), smt::Expression::variable("p".into()));
+    // false && (! l: p)
+    let expression = smt::Expression::and(smt::Expression::bool(false), labelled.clone());
+    assert_eq!(
+        expression.simplify(),
+        smt::Expression::and(labelled, smt::Expression::bool(false))
+    );
+}
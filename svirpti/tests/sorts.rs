@@ -0,0 +1,138 @@
+//! Checks of the sort inference for high and low expressions.
+
+use svirpti::{context::StringContext, lower};
+use svirpti_vir::high::{self, InferSort};
+use svirpti_vir::low::{self, InferSort as _};
+use svirpti_vir_derive::vir_high;
+
+fn program() -> high::ProgramFragment {
+    vir_high! {
+        adt List {
+            Nil,
+            Cons(head: Int, tail: Adt<List>),
+        }
+        fn len(list: Adt<List>) -> Int {
+            match list {
+                List::Nil => 0,
+                List::Cons(head, tail) => 1 + len(tail),
+            }
+        }
+        procedure {
+            locals {
+                x: Int,
+                b: BV8,
+                f: F32,
+                a: Array<Int, Real>,
+                s: Seq<Int>,
+                l: Adt<List>,
+            }
+            bb1 {
+                guard x > 0 && is_Cons(l);
+                assume l0 l.head == x && len(l.tail) >= 0;
+                assume l1 concat(b, extract(b, 3, 0)) == zero_extend(b, 4);
+                assume l2 fp_add(RNE, f, 1.5f32) < f || fp_to_real(f) == a[x];
+                assume l3 seq_index(seq_concat(s, seq_unit(x)), 0) == x;
+                assume l4 forall(|y: Int| let z = y + x in a[z := 1.5][y] > 0.0, [(a[y])]);
+                assign x = if b == 5bv8 { bv2int(b) } else { seq_len(s) };
+                goto { exit }
+            }
+        }
+    }
+}
+
+fn expression(source: &str) -> high::Expression {
+    let program = high::ProgramFragment::parse_str(&format!(
+        "sort Loc; procedure {{ locals {{}} bb1 {{ guard {}; goto {{ exit }} }} }}",
+        source
+    ))
+    .unwrap();
+    program.procedure.basic_blocks.raw[1].guard.clone()
+}
+
+#[test]
+fn check_high_sorts() {
+    let program = program();
+    let symbols = program.symbol_table();
+    for basic_block in &program.procedure.basic_blocks {
+        assert_eq!(basic_block.guard.infer_sort(&symbols), Ok(high::Type::Bool));
+        for statement in &basic_block.statements {
+            match statement {
+                high::statement::Statement::Assume(assume) => {
+                    assert_eq!(assume.assertion.infer_sort(&symbols), Ok(high::Type::Bool));
+                }
+                high::statement::Statement::Assign(assign) => {
+                    assert_eq!(assign.expression.infer_sort(&symbols), Ok(high::Type::Int));
+                }
+                _ => {}
+            }
+        }
+    }
+    let body = program.functions[0].body.as_ref().unwrap();
+    let mut function_symbols = symbols.clone();
+    function_symbols.declare_variable(&program.functions[0].parameters[0]);
+    assert_eq!(body.infer_sort(&function_symbols), Ok(high::Type::Int));
+    assert_eq!(
+        expression("const_array<Int>(seq_empty<Loc>())").infer_sort(&symbols),
+        Ok(high::Type::array(
+            high::Type::Int,
+            high::Type::seq(high::Type::Domain(high::DomainType { name: "Loc".into() })),
+        ))
+    );
+}
+
+#[test]
+fn check_high_sort_errors() {
+    let symbols = program().symbol_table();
+    let error = |source: &str| {
+        expression(source)
+            .infer_sort(&symbols)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(error("x + b"), "`+` expects `Int`, but got `BV8`");
+    assert_eq!(error("y > 0"), "unknown variable `y`");
+    assert_eq!(
+        error("forall(|y: Int| y, [])"),
+        "`forall` expects `Bool`, but got `Int`"
+    );
+    assert_eq!(error("x && x"), "`&&` expects Booleans, but got `Int`");
+    assert_eq!(
+        error("List::Cons(x, l, l) == l"),
+        "`List::Cons` takes 2 arguments, but 3 were given"
+    );
+    assert_eq!(error("l.size"), "`Adt<List>` has no field `size`");
+    assert_eq!(error("List::Snoc(x)"), "`List` has no constructor `Snoc`");
+    assert_eq!(
+        error("extract(b, 8, 0) == b"),
+        "`extract` is invalid: bits 8 to 0 of a bit-vector of width 8"
+    );
+    assert_eq!(error("a[b]"), "`select` expects `Int`, but got `BV8`");
+    assert_eq!(
+        error("if x > 0 { 1 } else { 1.5 }"),
+        "`if` expects `Int`, but got `Real`"
+    );
+}
+
+#[test]
+fn check_low_sorts() {
+    let program = program();
+    let mut context = StringContext::default();
+    let lowered = lower(&program, &mut context).unwrap();
+    let symbols = lowered.symbol_table();
+    for basic_block in &lowered.basic_blocks {
+        for statement in &basic_block.statements {
+            let assertion = match statement {
+                low::Statement::Assume(assume) => &assume.assertion,
+                low::Statement::Assert(assert) => &assert.assertion,
+            };
+            assert_eq!(assertion.infer_sort(&symbols), Ok(low::Sort::Bool));
+        }
+    }
+    let unknown = low::Expression::Variable(low::Variable {
+        name: "undeclared".into(),
+    });
+    assert_eq!(
+        unknown.infer_sort(&symbols),
+        Err(low::SortError::UnknownVariable("undeclared".into()))
+    );
+}
@@ -0,0 +1,129 @@
+//! Checks of substitution and renaming on expressions.
+
+use std::collections::{BTreeSet, HashMap};
+use svirpti_vir::common::expression::{
+    BinaryOperationHelpers, ConstantHelpers, LetHelpers, QuantifierHelpers, SubstitutionHelpers,
+    VariableHelpers,
+};
+use svirpti_vir::{high, smt};
+
+fn var(name: &str) -> high::Expression {
+    high::Expression::variable(name.into())
+}
+
+fn int_variable(name: &str) -> high::BoundedVariableDecl {
+    high::BoundedVariableDecl {
+        name: name.into(),
+        sort: high::Type::Int,
+    }
+}
+
+#[test]
+fn check_free_variables() {
+    // (forall x: Int :: x > y) && (let z = x in z + w > 0)
+    let expression = high::Expression::and(
+        high::Expression::forall(
+            vec![int_variable("x")],
+            Vec::new(),
+            high::Expression::greater_than(var("x"), var("y")),
+        ),
+        high::Expression::greater_than(
+            high::Expression::let_in(
+                "z".into(),
+                var("x"),
+                high::Expression::add(var("z"), var("w")),
+            ),
+            high::Expression::int(0),
+        ),
+    );
+    let expected: BTreeSet<high::VariableSymbol> = vec!["w".into(), "x".into(), "y".into()]
+        .into_iter()
+        .collect();
+    assert_eq!(expression.free_variables(), expected);
+}
+
+#[test]
+fn check_substitution_avoids_capture() {
+    // forall x: Int :: x > y
+    let expression = high::Expression::forall(
+        vec![int_variable("x")],
+        Vec::new(),
+        high::Expression::greater_than(var("x"), var("y")),
+    );
+    let substitution: HashMap<_, _> = vec![(
+        "y".into(),
+        high::Expression::add(var("x"), high::Expression::int(1)),
+    )]
+    .into_iter()
+    .collect();
+    let substituted = expression.substitute(&substitution);
+    let expected = high::Expression::forall(
+        vec![int_variable("x%0")],
+        Vec::new(),
+        high::Expression::greater_than(
+            var("x%0"),
+            high::Expression::add(var("x"), high::Expression::int(1)),
+        ),
+    );
+    assert_eq!(substituted, expected);
+}
+
+#[test]
+fn check_substitution_respects_shadowing() {
+    // let y = y + 1 in y
+    let expression = high::Expression::let_in(
+        "y".into(),
+        high::Expression::add(var("y"), high::Expression::int(1)),
+        var("y"),
+    );
+    let substitution: HashMap<_, _> = vec![("y".into(), high::Expression::int(5))]
+        .into_iter()
+        .collect();
+    let expected = high::Expression::let_in(
+        "y".into(),
+        high::Expression::add(high::Expression::int(5), high::Expression::int(1)),
+        var("y"),
+    );
+    assert_eq!(expression.substitute(&substitution), expected);
+}
+
+#[test]
+fn check_renaming() {
+    // forall x: Int :: x > y
+    let expression = high::Expression::forall(
+        vec![int_variable("x")],
+        Vec::new(),
+        high::Expression::greater_than(var("x"), var("y")),
+    );
+    let renaming: HashMap<_, _> = vec![("y".into(), "z".into())].into_iter().collect();
+    let renamed = expression.rename_free_variables(&renaming);
+    let mut counter = 0;
+    let renamed = renamed.rename_bound_variables(&mut |variable| {
+        counter += 1;
+        format!("{}_{}", variable, counter).into()
+    });
+    let expected = high::Expression::forall(
+        vec![int_variable("x_1")],
+        Vec::new(),
+        high::Expression::greater_than(var("x_1"), var("z")),
+    );
+    assert_eq!(renamed, expected);
+}
+
+#[test]
+fn check_smt_substitution() {
+    let var = |name: &str| smt::Expression::variable(name.into());
+    // let x = y in x + y
+    let expression = smt::Expression::let_in(
+        "x".into(),
+        var("y"),
+        smt::Expression::add(var("x"), var("y")),
+    );
+    let substitution: HashMap<_, _> = vec![("y".into(), var("x"))].into_iter().collect();
+    let expected = smt::Expression::let_in(
+        "x%0".into(),
+        var("x"),
+        smt::Expression::add(var("x%0"), var("x")),
+    );
+    assert_eq!(expression.substitute(&substitution), expected);
+}
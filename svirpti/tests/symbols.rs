@@ -0,0 +1,54 @@
+//! Checks of the interned symbols.
+
+use std::collections::BTreeSet;
+use svirpti::context::{Context, StringContext};
+use svirpti_vir::common::symbol::Symbol;
+use svirpti_vir::{high, low, smt};
+
+#[test]
+fn check_interning() {
+    let symbol = Symbol::intern("x");
+    assert_eq!(symbol, Symbol::from(String::from("x")));
+    assert!(std::ptr::eq(symbol.as_str(), Symbol::intern("x").as_str()));
+    assert_ne!(symbol, Symbol::intern("y"));
+    let variable: high::VariableSymbol = "x".into();
+    assert_eq!(variable.symbol(), symbol);
+    let lowered: low::VariableSymbol = variable.symbol().into();
+    assert_eq!(lowered, "x".into());
+    assert_eq!(lowered.to_string(), "x");
+    assert_eq!(format!("{:?}", lowered), "VariableSymbol(\"x\")");
+}
+
+#[test]
+fn check_ordering() {
+    // Intern in reverse order so that the order of the ids differs from the
+    // order of the strings.
+    let names = ["order_c", "order_b", "order_a"];
+    let symbols: BTreeSet<smt::IdentSymbol> = names.iter().map(|name| (*name).into()).collect();
+    let sorted: Vec<_> = symbols.iter().map(|symbol| symbol.as_str()).collect();
+    assert_eq!(sorted, ["order_a", "order_b", "order_c"]);
+}
+
+#[test]
+fn check_serialization() {
+    let variable: high::VariableSymbol = "serialized".into();
+    let json = serde_json::to_string(&variable).unwrap();
+    assert_eq!(json, "\"serialized\"");
+    let deserialized: high::VariableSymbol = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, variable);
+    let fresh: low::LabelSymbol = serde_json::from_str("\"never_interned\"").unwrap();
+    assert_eq!(fresh, "never_interned".into());
+}
+
+#[test]
+fn check_versioned_variables() {
+    let mut context = StringContext::default();
+    let name: high::VariableSymbol = "v".into();
+    let first = context.create_versioned_variable_symbol(&name, 3);
+    assert_eq!(first.as_str(), "v@3");
+    assert!(std::ptr::eq(
+        first.as_str(),
+        context.create_versioned_variable_symbol(&name, 3).as_str()
+    ));
+    assert_eq!(context.resolve_high_variable(&first), name);
+}
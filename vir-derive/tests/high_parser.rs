@@ -96,3 +96,191 @@ fn quantifiers() {
     insta::assert_display_snapshot!(program);
     insta::assert_yaml_snapshot!(program);
 }
+
+#[test]
+fn bit_vectors() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                a: BV8,
+                b: BV8,
+                c: Int,
+            }
+            bb1 {
+                guard a & 15bv8 == b | 1bv8 ^ a;
+                assume label1 a << 1bv8 >> b + 1bv8 < b;
+                assume label2 slt(ashr(a, 1bv8), sdiv(b, srem(a, b)));
+                assume label3 extract(concat(a, b), 11, 4) == zero_extend(extract(a, 3, 0), 4);
+                assume label4 bv2int(sign_extend(a, 8)) == c && int2bv(c, 8) == -!a;
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn big_integers() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                i: Int,
+            }
+            bb1 {
+                guard i <= 18446744073709551615 && i >= -170141183460469231731687303715884105728;
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn reals() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Real,
+                i: Int,
+            }
+            bb1 {
+                guard x / 2.0 < 0.125 && to_real(i) == -1.5;
+                assume label1 is_int(x) -> to_real(to_int(x)) == x;
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn arrays() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                heap: Array<Int, Array<Int, Bool>>,
+                counts: Array<BV8, Int>,
+                i: Int,
+            }
+            bb1 {
+                guard heap[i][i + 1] && counts[3bv8] > 0;
+                assume label1 heap[i := const_array<Int>(false)][i][0] == false;
+                assume label2 counts == const_array<BV8>(0)[1bv8 := i][2bv8 := i];
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn adts() {
+    let program = vir_high! {
+        adt List {
+            Nil,
+            Cons(head: Int, tail: Adt<List>),
+        }
+        procedure {
+            locals {
+                list: Adt<List>,
+                n: Int,
+            }
+            bb1 {
+                guard is_Cons(list) && list.head > 0;
+                assume label1 list == List::Cons(n, List::Nil);
+                assume label2 match list.tail { List::Nil => n == 0, List::Cons(h, t) => h == n };
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn sequences_and_sets() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                s: Seq<Int>,
+                seen: Set<Int>,
+                i: Int,
+            }
+            bb1 {
+                guard 0 <= i && i < seq_len(s) && set_contains(seen, seq_index(s, i));
+                assume label1 seq_concat(seq_sub(s, 0, i), seq_unit(i)) == seq_update(s, i, i);
+                assume label2 set_subset(set_intersection(seen, set_singleton(i)), set_union(seen, set_empty<Int>()));
+                assume label3 seq_empty<Int>() != s;
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn floats() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: F32,
+                y: F32,
+                d: F64,
+                h: Float<4, 12>,
+                b: BV32,
+            }
+            bb1 {
+                guard x + 1.5f32 < y * 2.0f32 && fp_eq(fp_add(RNE, x, y), fp_sqrt(RTZ, x));
+                assume label1 to_fp(RNE, d, F32) == fp_from_bits(b, F32) && fp_to_sbv(RTZ, x, 32) == b;
+                assume label2 !fp_is_nan(x) && fp_to_real(y) < 2.5 && d == -0.5f64;
+                assume label3 fp_is_zero(to_fp(RTN, x, Float<4, 12>)) == fp_is_zero(h);
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn functions() {
+    let program = vir_high! {
+        fn sum(n: Int) -> Int requires n >= 0 { if n == 0 { 0 } else { n + sum(n - 1) } }
+        fn pick(x: Int) -> Bool;
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard x >= 0 && pick(x);
+                assume label1 sum(x) >= x;
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
+
+#[test]
+fn lets() {
+    let program = vir_high! {
+        procedure {
+            locals {
+                x: Int,
+            }
+            bb1 {
+                guard let y = x + 1 in y > x;
+                assume label1 (let x = x * 2 in let y = x + x in y >= x) && x >= 0;
+                goto { exit }
+            }
+        }
+    };
+    insta::assert_display_snapshot!(program);
+    insta::assert_yaml_snapshot!(program);
+}
@@ -28,3 +28,21 @@ pub(crate) struct RawBlock {
     /// The contents of the block.
     pub(crate) content: Vec<syn::Item>,
 }
+
+/// vir_walkers!
+pub(crate) struct Walkers {
+    /// The enum whose traversal traits are generated.
+    pub(crate) root: syn::Ident,
+}
+
+/// vir_lowering!
+pub(crate) struct Lowering {
+    /// The IR from which this IR is lowered.
+    pub(crate) source: syn::Ident,
+    /// The types from which the lowering starts, given by their paths relative
+    /// to the roots of the IRs. The other types are looked up in their modules.
+    pub(crate) roots: Vec<syn::Path>,
+    /// The types that are not shared by the two IRs, which the implementor of
+    /// the generated trait has to lower by hand.
+    pub(crate) mapped_types: Vec<syn::Ident>,
+}
@@ -0,0 +1,594 @@
+//! Generation of the lowering from one IR to another.
+//!
+//! For
+//!
+//! ```ignore
+//! vir_lowering! { high =>
+//!     use expression::Expression;
+//!     map VariableSymbol, Sort;
+//! }
+//! ```
+//!
+//! a trait `Lowerer` is generated with a method for every type that is
+//! reachable from `Expression` and is defined in the module `expression` of
+//! both IRs. Such a shared type is lowered field by field: the fields of
+//! shared and mapped types with their methods, and the other fields by
+//! cloning them. The mapped types, typically the symbols, get required
+//! methods that the implementor of the trait maps by hand.
+//!
+//! As for the walkers, a method of a shared type defaults to the function
+//! with the same name prefixed with `default_`, so that an overriding method,
+//! for example one that tracks the variables bound by a quantifier, can fall
+//! back to it.
+//!
+//! A variant of a shared enum whose payload is defined only in the source IR,
+//! such as a positioned expression of `high`, gets a required method that
+//! lowers the payload to the enum of the target IR.
+
+use super::walkers::{parameter_name, snake_case};
+use crate::ast::Lowering;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// How a field is lowered.
+enum Conversion {
+    /// With the method of a shared or a mapped type.
+    Method(syn::Ident),
+    Box(Box<Conversion>),
+    Vec(Box<Conversion>),
+    Option(Box<Conversion>),
+    /// A type that is the same in both IRs.
+    Clone,
+}
+
+impl Conversion {
+    fn is_clone(&self) -> bool {
+        matches!(self, Conversion::Clone)
+    }
+}
+
+/// The value that a conversion lowers.
+enum Value {
+    /// A place expression such as a field access.
+    Place(TokenStream),
+    /// A reference such as a binding of a pattern.
+    Reference(TokenStream),
+}
+
+impl Value {
+    fn reference(&self) -> TokenStream {
+        match self {
+            Value::Place(place) => quote! { &#place },
+            Value::Reference(reference) => reference.clone(),
+        }
+    }
+    /// The expression on which methods are called.
+    fn receiver(&self) -> &TokenStream {
+        match self {
+            Value::Place(tokens) | Value::Reference(tokens) => tokens,
+        }
+    }
+}
+
+/// A module that contains shared types.
+struct Module<'a> {
+    path: Vec<syn::Ident>,
+    source_items: &'a [syn::Item],
+    target_items: &'a [syn::Item],
+}
+
+/// A type that is defined in both IRs.
+struct SharedType<'a> {
+    name: &'a syn::Ident,
+    module: usize,
+    item: &'a syn::Item,
+}
+
+/// The payload of an enum variant that is defined only in the source IR.
+struct SourceOnlyVariant<'a> {
+    payload: &'a syn::Ident,
+    module: usize,
+    /// The shared enum that has the variant.
+    enum_name: &'a syn::Ident,
+}
+
+struct Generator<'a> {
+    mapped_types: &'a [syn::Ident],
+    modules: Vec<Module<'a>>,
+    shared_types: Vec<SharedType<'a>>,
+    source_only_variants: Vec<SourceOnlyVariant<'a>>,
+    /// The path of the source IR relative to the module of the lowering.
+    source_root: TokenStream,
+    /// The path of the target IR relative to the module of the lowering.
+    target_root: TokenStream,
+}
+
+/// Generates the lowering from `source_ir` to `target_ir`. `depth` is the
+/// depth of the module of the lowering in `target_ir`.
+pub(crate) fn generate_lowering(
+    lowering: &Lowering,
+    depth: usize,
+    source_ir: &syn::ItemMod,
+    target_ir: &syn::ItemMod,
+) -> syn::Result<TokenStream> {
+    let source = &lowering.source;
+    let supers: Vec<_> = (0..depth).map(|_| quote! { super:: }).collect();
+    let mut generator = Generator {
+        mapped_types: &lowering.mapped_types,
+        modules: Vec::new(),
+        shared_types: Vec::new(),
+        source_only_variants: Vec::new(),
+        source_root: quote! { #(#supers)* super::#source:: },
+        target_root: quote! { #(#supers)* },
+    };
+    let mut roots = Vec::new();
+    for root in &lowering.roots {
+        let mut path: Vec<_> = root.segments.iter().map(|segment| &segment.ident).collect();
+        let name = path.pop().unwrap();
+        let module = generator.add_module(path, source_ir, target_ir)?;
+        roots.push((name, module));
+    }
+    for (name, module) in roots {
+        if generator.find_shared_type(name).is_none() {
+            let item = find_type(generator.modules[module].source_items, name)
+                .ok_or_else(|| syn::Error::new(name.span(), format!("not found {}", name)))?;
+            generator
+                .shared_types
+                .push(SharedType { name, module, item });
+        }
+    }
+    generator.compute_shared_types()?;
+    Ok(generator.generate(source))
+}
+
+impl<'a> Generator<'a> {
+    fn add_module(
+        &mut self,
+        path: Vec<&'a syn::Ident>,
+        source_ir: &'a syn::ItemMod,
+        target_ir: &'a syn::ItemMod,
+    ) -> syn::Result<usize> {
+        if let Some(index) = self
+            .modules
+            .iter()
+            .position(|module| module.path.iter().eq(path.iter().copied()))
+        {
+            return Ok(index);
+        }
+        self.modules.push(Module {
+            source_items: find_module(source_ir, &path)?,
+            target_items: find_module(target_ir, &path)?,
+            path: path.into_iter().cloned().collect(),
+        });
+        Ok(self.modules.len() - 1)
+    }
+
+    /// Adds the shared types that are reachable from the roots.
+    fn compute_shared_types(&mut self) -> syn::Result<()> {
+        let mut index = 0;
+        while index < self.shared_types.len() {
+            let SharedType {
+                name: enum_name,
+                module,
+                item,
+            } = self.shared_types[index];
+            let mut field_types = Vec::new();
+            match item {
+                syn::Item::Enum(item) => {
+                    for variant in &item.variants {
+                        if let Some(payload) = source_only_payload(&self.modules[module], variant) {
+                            self.source_only_variants.push(SourceOnlyVariant {
+                                payload,
+                                module,
+                                enum_name,
+                            });
+                        } else {
+                            for field in &variant.fields {
+                                collect_type_idents(&field.ty, &mut field_types);
+                            }
+                        }
+                    }
+                }
+                item => for_each_field(item, |field| {
+                    collect_type_idents(&field.ty, &mut field_types)
+                }),
+            }
+            for name in field_types {
+                if self.mapped_types.contains(name) || self.find_shared_type(name).is_some() {
+                    continue;
+                }
+                let found = self.modules.iter().enumerate().find_map(|(module, items)| {
+                    find_type(items.source_items, name).map(|item| (module, item))
+                });
+                if let Some((module, item)) = found {
+                    self.shared_types.push(SharedType { name, module, item });
+                }
+            }
+            index += 1;
+        }
+        for shared_type in &self.shared_types {
+            let module = &self.modules[shared_type.module];
+            if find_type(module.target_items, shared_type.name).is_none() {
+                return Err(syn::Error::new(
+                    shared_type.name.span(),
+                    format!("{} is not defined in the target IR", shared_type.name),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn find_shared_type(&self, name: &syn::Ident) -> Option<&SharedType<'a>> {
+        self.shared_types
+            .iter()
+            .find(|shared_type| shared_type.name == name)
+    }
+
+    fn find_source_only_variant(
+        &self,
+        enum_name: &syn::Ident,
+        variant: &syn::Variant,
+    ) -> Option<&SourceOnlyVariant<'a>> {
+        let shared_type = self.find_shared_type(enum_name)?;
+        let payload = source_only_payload(&self.modules[shared_type.module], variant)?;
+        self.source_only_variants.iter().find(|source_only| {
+            source_only.enum_name == enum_name && source_only.payload == payload
+        })
+    }
+
+    fn conversion(&self, ty: &syn::Type) -> Conversion {
+        let path = match ty {
+            syn::Type::Path(syn::TypePath { qself: None, path }) if path.segments.len() == 1 => {
+                path
+            }
+            _ => return Conversion::Clone,
+        };
+        let segment = &path.segments[0];
+        if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(argument)) = arguments.args.first() {
+                let argument = self.conversion(argument);
+                if argument.is_clone() {
+                    return Conversion::Clone;
+                }
+                return match segment.ident.to_string().as_str() {
+                    "Box" => Conversion::Box(Box::new(argument)),
+                    "Vec" => Conversion::Vec(Box::new(argument)),
+                    "Option" => Conversion::Option(Box::new(argument)),
+                    _ => Conversion::Clone,
+                };
+            }
+        }
+        if self.mapped_types.contains(&segment.ident)
+            || self.find_shared_type(&segment.ident).is_some()
+        {
+            Conversion::Method(segment.ident.clone())
+        } else {
+            Conversion::Clone
+        }
+    }
+
+    fn source_path(&self, name: &syn::Ident) -> TokenStream {
+        let source_root = &self.source_root;
+        match self.find_shared_type(name) {
+            Some(shared_type) => {
+                let module = &self.modules[shared_type.module].path;
+                quote! { #source_root #(#module::)* #name }
+            }
+            None => quote! { #source_root #name },
+        }
+    }
+
+    fn target_path(&self, name: &syn::Ident) -> TokenStream {
+        let target_root = &self.target_root;
+        match self.find_shared_type(name) {
+            Some(shared_type) => {
+                let module = &self.modules[shared_type.module].path;
+                quote! { #target_root #(#module::)* #name }
+            }
+            None => quote! { #target_root #name },
+        }
+    }
+
+    fn generate(&self, source: &syn::Ident) -> TokenStream {
+        let doc = format!("Lowers the `{}` IR to this IR.", source);
+        let mut methods = TokenStream::new();
+        for name in self.mapped_types {
+            let snake_name = snake_case(&name.to_string());
+            let method = format_ident!("lower_{}", snake_name);
+            let parameter = parameter_name(&snake_name);
+            let source_path = self.source_path(name);
+            let target_path = self.target_path(name);
+            methods.extend(quote! {
+                fn #method(
+                    &mut self,
+                    #parameter: &#source_path,
+                ) -> Result<#target_path, Self::Error>;
+            });
+        }
+        for variant in &self.source_only_variants {
+            let snake_name = snake_case(&variant.payload.to_string());
+            let method = format_ident!("lower_{}", snake_name);
+            let parameter = parameter_name(&snake_name);
+            let source_root = &self.source_root;
+            let module = &self.modules[variant.module].path;
+            let payload = variant.payload;
+            let target_path = self.target_path(variant.enum_name);
+            methods.extend(quote! {
+                fn #method(
+                    &mut self,
+                    #parameter: &#source_root #(#module::)* #payload,
+                ) -> Result<#target_path, Self::Error>;
+            });
+        }
+        let mut functions = TokenStream::new();
+        for shared_type in &self.shared_types {
+            let snake_name = snake_case(&shared_type.name.to_string());
+            let method = format_ident!("lower_{}", snake_name);
+            let default_function = format_ident!("default_lower_{}", snake_name);
+            let parameter = parameter_name(&snake_name);
+            let source_path = self.source_path(shared_type.name);
+            let target_path = self.target_path(shared_type.name);
+            methods.extend(quote! {
+                fn #method(
+                    &mut self,
+                    #parameter: &#source_path,
+                ) -> Result<#target_path, Self::Error> {
+                    #default_function(self, #parameter)
+                }
+            });
+            let (body, uses_this) = match shared_type.item {
+                syn::Item::Struct(item) => self.generate_struct_body(item, &parameter),
+                syn::Item::Enum(item) => self.generate_enum_body(item, &parameter),
+                _ => unreachable!(),
+            };
+            let this = if uses_this {
+                format_ident!("this")
+            } else {
+                format_ident!("_this")
+            };
+            functions.extend(quote! {
+                pub fn #default_function<T: Lowerer + ?Sized>(
+                    #this: &mut T,
+                    #parameter: &#source_path,
+                ) -> Result<#target_path, T::Error> {
+                    #body
+                }
+            });
+        }
+        quote! {
+            #[doc = #doc]
+            pub trait Lowerer {
+                type Error;
+                #methods
+            }
+            #functions
+        }
+    }
+
+    /// The body of the default function of a struct and whether it uses the
+    /// lowerer.
+    fn generate_struct_body(
+        &self,
+        item: &syn::ItemStruct,
+        parameter: &syn::Ident,
+    ) -> (TokenStream, bool) {
+        let target_path = self.target_path(&item.ident);
+        let mut uses_this = false;
+        let values: Vec<_> = item
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let conversion = self.conversion(&field.ty);
+                uses_this |= !conversion.is_clone();
+                let member = field_member(field, index);
+                let value = self
+                    .generate_conversion(&conversion, Value::Place(quote! { #parameter.#member }));
+                quote! { #member: #value }
+            })
+            .collect();
+        (quote! { Ok(#target_path { #(#values),* }) }, uses_this)
+    }
+
+    /// The body of the default function of an enum and whether it uses the
+    /// lowerer.
+    fn generate_enum_body(
+        &self,
+        item: &syn::ItemEnum,
+        parameter: &syn::Ident,
+    ) -> (TokenStream, bool) {
+        let source_path = self.source_path(&item.ident);
+        let target_path = self.target_path(&item.ident);
+        let mut uses_this = false;
+        let arms = item.variants.iter().map(|variant| {
+            let variant_name = &variant.ident;
+            if let Some(source_only) = self.find_source_only_variant(&item.ident, variant) {
+                uses_this = true;
+                let method =
+                    format_ident!("lower_{}", snake_case(&source_only.payload.to_string()));
+                return quote! {
+                    #source_path::#variant_name(payload) => return this.#method(payload),
+                };
+            }
+            let mut bindings = Vec::new();
+            let mut values = Vec::new();
+            for (index, field) in variant.fields.iter().enumerate() {
+                let conversion = self.conversion(&field.ty);
+                uses_this |= !conversion.is_clone();
+                let binding = field
+                    .ident
+                    .clone()
+                    .unwrap_or_else(|| format_ident!("field{}", index));
+                let value =
+                    self.generate_conversion(&conversion, Value::Reference(quote! { #binding }));
+                values.push(match &field.ident {
+                    Some(name) => quote! { #name: #value },
+                    None => value,
+                });
+                bindings.push(binding);
+            }
+            match &variant.fields {
+                syn::Fields::Named(_) => quote! {
+                    #source_path::#variant_name { #(#bindings),* } =>
+                        #target_path::#variant_name { #(#values),* },
+                },
+                syn::Fields::Unnamed(_) => quote! {
+                    #source_path::#variant_name(#(#bindings),*) =>
+                        #target_path::#variant_name(#(#values),*),
+                },
+                syn::Fields::Unit => quote! {
+                    #source_path::#variant_name => #target_path::#variant_name,
+                },
+            }
+        });
+        let body = quote! {
+            Ok(match #parameter {
+                #(#arms)*
+            })
+        };
+        (body, uses_this)
+    }
+
+    /// Lowers `value` with `conversion`.
+    fn generate_conversion(&self, conversion: &Conversion, value: Value) -> TokenStream {
+        match conversion {
+            Conversion::Method(name) => {
+                let method = format_ident!("lower_{}", snake_case(&name.to_string()));
+                let reference = value.reference();
+                quote! { this.#method(#reference)? }
+            }
+            // References to boxes are coerced to references to their contents.
+            Conversion::Box(inner) => {
+                let inner = self.generate_conversion(inner, value);
+                quote! { Box::new(#inner) }
+            }
+            Conversion::Vec(inner) => {
+                let receiver = value.receiver();
+                let map = self.generate_item_conversion(inner);
+                quote! { #receiver.iter().map(#map).collect::<Result<_, _>>()? }
+            }
+            Conversion::Option(inner) => {
+                let receiver = value.receiver();
+                let map = self.generate_item_conversion(inner);
+                quote! { #receiver.as_ref().map(#map).transpose()? }
+            }
+            Conversion::Clone => {
+                let reference = value.reference();
+                quote! { ::std::clone::Clone::clone(#reference) }
+            }
+        }
+    }
+
+    /// A closure that lowers a reference to an element of a collection.
+    fn generate_item_conversion(&self, conversion: &Conversion) -> TokenStream {
+        match conversion {
+            Conversion::Method(name) => {
+                let method = format_ident!("lower_{}", snake_case(&name.to_string()));
+                quote! { |item| this.#method(item) }
+            }
+            _ => {
+                let value = self.generate_conversion(conversion, Value::Reference(quote! { item }));
+                quote! { |item| -> Result<_, T::Error> { Ok(#value) } }
+            }
+        }
+    }
+}
+
+fn find_module<'a>(ir: &'a syn::ItemMod, path: &[&syn::Ident]) -> syn::Result<&'a [syn::Item]> {
+    let mut current_mod = ir;
+    for segment in path {
+        let (_, content) = current_mod
+            .content
+            .as_ref()
+            .expect("bug: the parser did not expand all modules");
+        current_mod = content
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Mod(module) if &module.ident == *segment => Some(module),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                syn::Error::new(
+                    segment.span(),
+                    format!("not found module {} in {}", segment, ir.ident),
+                )
+            })?;
+    }
+    let (_, content) = current_mod
+        .content
+        .as_ref()
+        .expect("bug (2): the parser did not expand all modules");
+    Ok(content)
+}
+
+/// The struct or enum `name` in `items`.
+fn find_type<'a>(items: &'a [syn::Item], name: &syn::Ident) -> Option<&'a syn::Item> {
+    items.iter().find(|item| match item {
+        syn::Item::Struct(item) => &item.ident == name,
+        syn::Item::Enum(item) => &item.ident == name,
+        _ => false,
+    })
+}
+
+/// The payload of `variant` if it is a single type that is defined in the
+/// source but not in the target module.
+fn source_only_payload<'a>(
+    module: &Module<'a>,
+    variant: &'a syn::Variant,
+) -> Option<&'a syn::Ident> {
+    let fields = match &variant.fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields,
+        _ => return None,
+    };
+    let payload = match &fields.unnamed[0].ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.get_ident()?,
+        _ => return None,
+    };
+    if find_type(module.source_items, payload).is_some()
+        && find_type(module.target_items, payload).is_none()
+    {
+        Some(payload)
+    } else {
+        None
+    }
+}
+
+fn for_each_field<'a>(item: &'a syn::Item, mut f: impl FnMut(&'a syn::Field)) {
+    match item {
+        syn::Item::Struct(item) => item.fields.iter().for_each(f),
+        syn::Item::Enum(item) => {
+            for variant in &item.variants {
+                variant.fields.iter().for_each(&mut f);
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// The identifiers of the single-segment paths in `ty`, including those in
+/// generic arguments.
+fn collect_type_idents<'a>(ty: &'a syn::Type, idents: &mut Vec<&'a syn::Ident>) {
+    if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+        if path.segments.len() != 1 {
+            return;
+        }
+        let segment = &path.segments[0];
+        match &segment.arguments {
+            syn::PathArguments::AngleBracketed(arguments) => {
+                for argument in &arguments.args {
+                    if let syn::GenericArgument::Type(argument) = argument {
+                        collect_type_idents(argument, idents);
+                    }
+                }
+            }
+            _ => idents.push(&segment.ident),
+        }
+    }
+}
+
+fn field_member(field: &syn::Field, index: usize) -> syn::Member {
+    match &field.ident {
+        Some(name) => syn::Member::Named(name.clone()),
+        None => syn::Member::Unnamed(index.into()),
+    }
+}
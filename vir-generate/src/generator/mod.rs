@@ -2,6 +2,12 @@ use crate::ast::*;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
+mod lowering;
+mod walkers;
+
+pub(crate) use lowering::generate_lowering;
+pub(crate) use walkers::generate_walkers;
+
 impl ToTokens for Declarations {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         for ir in &self.irs {
@@ -0,0 +1,463 @@
+//! Generation of the traversal traits of an enum such as `Expression`.
+//!
+//! For `vir_walkers! { Expression }`, three traits are generated from the
+//! definitions in the same module:
+//!
+//! * `ExpressionWalker` visits an expression by reference;
+//! * `ExpressionWalkerMut` visits an expression by mutable reference;
+//! * `ExpressionFolder` consumes an expression and builds a new one.
+//!
+//! Every trait has a method for the enum, for every variant payload, and for
+//! every other struct that contains expressions. A method defaults to the
+//! function with the same name prefixed with `default_`, which traverses the
+//! fields that contain expressions, so that an overriding method can fall
+//! back to it.
+
+use crate::ast::Walkers;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::{HashMap, HashSet};
+
+/// The type of a field, as far as the traversal is concerned.
+enum FieldType {
+    /// The enum itself.
+    Root,
+    /// A struct that contains the enum.
+    Struct(syn::Ident),
+    Box(Box<FieldType>),
+    Vec(Box<FieldType>),
+    Option(Box<FieldType>),
+    /// A type that does not contain the enum.
+    Leaf,
+}
+
+impl FieldType {
+    fn is_leaf(&self) -> bool {
+        matches!(self, FieldType::Leaf)
+    }
+}
+
+/// The traversal mode of the generated trait.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Walk,
+    WalkMut,
+    Fold,
+}
+
+impl Mode {
+    fn method(self, snake_name: &str) -> syn::Ident {
+        match self {
+            Mode::Walk => format_ident!("walk_{}", snake_name),
+            Mode::WalkMut => format_ident!("walk_{}_mut", snake_name),
+            Mode::Fold => format_ident!("fold_{}", snake_name),
+        }
+    }
+    fn default_function(self, snake_name: &str) -> syn::Ident {
+        format_ident!("default_{}", self.method(snake_name))
+    }
+    fn reference(self, ty: &syn::Ident) -> TokenStream {
+        match self {
+            Mode::Walk => quote! { &#ty },
+            Mode::WalkMut => quote! { &mut #ty },
+            Mode::Fold => quote! { #ty },
+        }
+    }
+}
+
+struct Generator<'a> {
+    root: &'a syn::Ident,
+    trait_names: [(Mode, syn::Ident); 3],
+    /// The variants of the root enum with the types of their payloads.
+    variants: Vec<(&'a syn::Ident, &'a syn::Ident)>,
+    structs: HashMap<&'a syn::Ident, &'a syn::ItemStruct>,
+    /// The structs that contain the root enum.
+    containing: HashSet<&'a syn::Ident>,
+}
+
+pub(crate) fn generate_walkers(walkers: &Walkers, items: &[syn::Item]) -> syn::Result<TokenStream> {
+    let root = &walkers.root;
+    let root_enum = items
+        .iter()
+        .find_map(|item| match item {
+            syn::Item::Enum(item) if &item.ident == root => Some(item),
+            _ => None,
+        })
+        .ok_or_else(|| syn::Error::new(root.span(), format!("not found enum {}", root)))?;
+    let mut variants = Vec::new();
+    for variant in &root_enum.variants {
+        let payload = match &variant.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                type_ident(&fields.unnamed[0].ty)
+            }
+            _ => None,
+        };
+        let payload = payload.ok_or_else(|| {
+            syn::Error::new(
+                variant.ident.span(),
+                "a variant must have exactly one field whose type is a struct or an enum",
+            )
+        })?;
+        variants.push((&variant.ident, payload));
+    }
+    let structs: HashMap<_, _> = items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) => Some((&item.ident, item)),
+            _ => None,
+        })
+        .collect();
+    let mut generator = Generator {
+        root,
+        trait_names: [
+            (Mode::Walk, format_ident!("{}Walker", root)),
+            (Mode::WalkMut, format_ident!("{}WalkerMut", root)),
+            (Mode::Fold, format_ident!("{}Folder", root)),
+        ],
+        variants,
+        structs,
+        containing: HashSet::new(),
+    };
+    generator.compute_containing();
+    generator.check_enums(items)?;
+    let mut tokens = TokenStream::new();
+    for (mode, trait_name) in &generator.trait_names {
+        tokens.extend(generator.generate_trait(*mode, trait_name));
+    }
+    Ok(tokens)
+}
+
+impl<'a> Generator<'a> {
+    fn compute_containing(&mut self) {
+        loop {
+            let new: Vec<_> = self
+                .structs
+                .iter()
+                .filter(|(ident, item)| {
+                    !self.containing.contains(*ident)
+                        && item
+                            .fields
+                            .iter()
+                            .any(|field| !self.field_type(&field.ty).is_leaf())
+                })
+                .map(|(ident, _)| *ident)
+                .collect();
+            if new.is_empty() {
+                break;
+            }
+            self.containing.extend(new);
+        }
+    }
+
+    /// Payloads that are enums are traversed as leaves, so they must not
+    /// contain the root enum.
+    fn check_enums(&self, items: &[syn::Item]) -> syn::Result<()> {
+        for item in items {
+            if let syn::Item::Enum(item) = item {
+                if &item.ident == self.root {
+                    continue;
+                }
+                for variant in &item.variants {
+                    for field in &variant.fields {
+                        if !self.field_type(&field.ty).is_leaf() {
+                            return Err(syn::Error::new(
+                                item.ident.span(),
+                                format!("enums that contain {} are not supported", self.root),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn field_type(&self, ty: &syn::Type) -> FieldType {
+        let segment = match ty {
+            syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last().unwrap(),
+            _ => return FieldType::Leaf,
+        };
+        if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(argument)) = arguments.args.first() {
+                let argument = self.field_type(argument);
+                if argument.is_leaf() {
+                    return FieldType::Leaf;
+                }
+                return match segment.ident.to_string().as_str() {
+                    "Box" => FieldType::Box(Box::new(argument)),
+                    "Vec" => FieldType::Vec(Box::new(argument)),
+                    "Option" => FieldType::Option(Box::new(argument)),
+                    _ => FieldType::Leaf,
+                };
+            }
+        }
+        if &segment.ident == self.root {
+            FieldType::Root
+        } else if self.containing.contains(&segment.ident) {
+            FieldType::Struct(segment.ident.clone())
+        } else {
+            FieldType::Leaf
+        }
+    }
+
+    /// The types that get a method: the root, the variant payloads, and the
+    /// other structs that contain the root.
+    fn methods(&self) -> Vec<&'a syn::Ident> {
+        let mut types = vec![self.root];
+        for (_, payload) in &self.variants {
+            if !types.contains(payload) {
+                types.push(payload);
+            }
+        }
+        let mut others: Vec<_> = self
+            .containing
+            .iter()
+            .copied()
+            .filter(|ident| !types.contains(ident))
+            .collect();
+        others.sort();
+        types.extend(others);
+        types
+    }
+
+    fn is_payload(&self, ty: &syn::Ident) -> bool {
+        self.variants.iter().any(|(_, payload)| *payload == ty)
+    }
+
+    fn generate_trait(&self, mode: Mode, trait_name: &syn::Ident) -> TokenStream {
+        let root = self.root;
+        let doc = match mode {
+            Mode::Walk => format!("Visits [`{}`]s by reference.", root),
+            Mode::WalkMut => format!("Visits [`{}`]s by mutable reference.", root),
+            Mode::Fold => format!("Consumes [`{}`]s and builds new ones.", root),
+        };
+        let mut methods = TokenStream::new();
+        let mut functions = TokenStream::new();
+        for ty in self.methods() {
+            let snake_name = snake_case(&ty.to_string());
+            let method = mode.method(&snake_name);
+            let default_function = mode.default_function(&snake_name);
+            let parameter = parameter_name(&snake_name);
+            let parameter_type = mode.reference(ty);
+            let (method_result, method_body) = if mode == Mode::Fold && self.is_payload(ty) {
+                // A payload is folded into an expression, so that a folder can
+                // replace it with an expression of another variant.
+                let variant = self
+                    .variants
+                    .iter()
+                    .find(|(_, payload)| *payload == ty)
+                    .unwrap()
+                    .0;
+                (
+                    quote! { -> #root },
+                    quote! { #root::#variant(#default_function(self, #parameter)) },
+                )
+            } else if mode == Mode::Fold {
+                (
+                    quote! { -> #ty },
+                    quote! { #default_function(self, #parameter) },
+                )
+            } else {
+                (quote! {}, quote! { #default_function(self, #parameter) })
+            };
+            methods.extend(quote! {
+                fn #method(&mut self, #parameter: #parameter_type) #method_result {
+                    #method_body
+                }
+            });
+            let function_result = if mode == Mode::Fold {
+                quote! { -> #ty }
+            } else {
+                quote! {}
+            };
+            let body = if ty == self.root {
+                self.generate_root_body(mode, &parameter)
+            } else {
+                self.generate_struct_body(mode, ty, &parameter)
+            };
+            let (this, parameter) = if body.is_empty() {
+                (format_ident!("_this"), format_ident!("_{}", snake_name))
+            } else {
+                (format_ident!("this"), parameter)
+            };
+            let body = if body.is_empty() && mode == Mode::Fold {
+                quote! { #parameter }
+            } else {
+                body
+            };
+            functions.extend(quote! {
+                pub fn #default_function<T: #trait_name + ?Sized>(
+                    #this: &mut T,
+                    #parameter: #parameter_type,
+                ) #function_result {
+                    #body
+                }
+            });
+        }
+        quote! {
+            #[doc = #doc]
+            pub trait #trait_name {
+                #methods
+            }
+            #functions
+        }
+    }
+
+    fn generate_root_body(&self, mode: Mode, parameter: &syn::Ident) -> TokenStream {
+        let root = self.root;
+        let arms = self.variants.iter().map(|(variant, payload)| {
+            let method = mode.method(&snake_case(&payload.to_string()));
+            quote! { #root::#variant(node) => this.#method(node), }
+        });
+        quote! {
+            match #parameter {
+                #(#arms)*
+            }
+        }
+    }
+
+    /// The body of the default function of a struct, or nothing if the
+    /// struct does not contain the root.
+    fn generate_struct_body(
+        &self,
+        mode: Mode,
+        ty: &syn::Ident,
+        parameter: &syn::Ident,
+    ) -> TokenStream {
+        let item = match self.structs.get(ty) {
+            Some(item) if self.containing.contains(ty) => item,
+            _ => return TokenStream::new(),
+        };
+        let fields: Vec<_> = item
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field
+                        .ident
+                        .as_ref()
+                        .expect("tuple structs are not supported"),
+                    self.field_type(&field.ty),
+                )
+            })
+            .collect();
+        if mode == Mode::Fold {
+            let names = fields.iter().map(|(name, _)| name);
+            let values = fields.iter().map(|(name, field_type)| {
+                if field_type.is_leaf() {
+                    quote! { #name }
+                } else {
+                    let value = self.generate_fold(field_type, quote! { #name });
+                    quote! { #name: #value }
+                }
+            });
+            quote! {
+                let #ty { #(#names),* } = #parameter;
+                #ty { #(#values),* }
+            }
+        } else {
+            let borrow = if mode == Mode::Walk {
+                quote! { & }
+            } else {
+                quote! { &mut }
+            };
+            fields
+                .iter()
+                .filter(|(_, field_type)| !field_type.is_leaf())
+                .map(|(name, field_type)| {
+                    self.generate_walk(mode, field_type, quote! { #borrow #parameter.#name })
+                })
+                .collect()
+        }
+    }
+
+    /// Visits `reference`, a reference to a value of `field_type`.
+    fn generate_walk(
+        &self,
+        mode: Mode,
+        field_type: &FieldType,
+        reference: TokenStream,
+    ) -> TokenStream {
+        match field_type {
+            FieldType::Root => {
+                let method = mode.method(&snake_case(&self.root.to_string()));
+                quote! { this.#method(#reference); }
+            }
+            FieldType::Struct(ty) => {
+                let method = mode.method(&snake_case(&ty.to_string()));
+                quote! { this.#method(#reference); }
+            }
+            // References to boxes are coerced to references to their contents.
+            FieldType::Box(inner) => self.generate_walk(mode, inner, reference),
+            FieldType::Vec(inner) => {
+                let body = self.generate_walk(mode, inner, quote! { item });
+                quote! { for item in #reference { #body } }
+            }
+            FieldType::Option(inner) => {
+                let body = self.generate_walk(mode, inner, quote! { item });
+                quote! { if let Some(item) = #reference { #body } }
+            }
+            FieldType::Leaf => TokenStream::new(),
+        }
+    }
+
+    /// Folds `value`, a value of `field_type`.
+    fn generate_fold(&self, field_type: &FieldType, value: TokenStream) -> TokenStream {
+        match field_type {
+            FieldType::Root => {
+                let method = Mode::Fold.method(&snake_case(&self.root.to_string()));
+                quote! { this.#method(#value) }
+            }
+            FieldType::Struct(ty) => {
+                let method = Mode::Fold.method(&snake_case(&ty.to_string()));
+                quote! { this.#method(#value) }
+            }
+            FieldType::Box(inner) => {
+                let inner = self.generate_fold(inner, quote! { *#value });
+                quote! { Box::new(#inner) }
+            }
+            FieldType::Vec(inner) => {
+                let inner = self.generate_fold(inner, quote! { item });
+                quote! { #value.into_iter().map(|item| #inner).collect() }
+            }
+            FieldType::Option(inner) => {
+                let inner = self.generate_fold(inner, quote! { item });
+                quote! { #value.map(|item| #inner) }
+            }
+            FieldType::Leaf => value,
+        }
+    }
+}
+
+/// The identifier of a type that is given by a path without generic
+/// arguments.
+fn type_ident(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.get_ident(),
+        _ => None,
+    }
+}
+
+pub(super) fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous_lowercase = false;
+    for character in name.chars() {
+        if character.is_uppercase() {
+            if previous_lowercase {
+                result.push('_');
+            }
+            result.extend(character.to_lowercase());
+            previous_lowercase = false;
+        } else {
+            result.push(character);
+            previous_lowercase = character.is_lowercase() || character.is_numeric();
+        }
+    }
+    result
+}
+
+/// The snake-case name, or the raw identifier if it is a keyword such as
+/// `let`.
+pub(super) fn parameter_name(snake_name: &str) -> syn::Ident {
+    syn::parse_str(snake_name).unwrap_or_else(|_| format_ident!("r#{}", snake_name))
+}
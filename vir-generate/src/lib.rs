@@ -39,6 +39,9 @@ pub fn define_vir(input: TokenStream, source_file: &std::path::Path) -> TokenStr
         }
         mem::swap(ir, &mut sentinel_item);
     }
+    for error in resolver::expand_lowerings(&mut declarations.irs) {
+        error_tokens.extend(error.to_compile_error());
+    }
 
     quote! { #declarations #error_tokens }
 }
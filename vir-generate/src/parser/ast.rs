@@ -7,6 +7,7 @@ use crate::ast::*;
 
 mod kw {
     syn::custom_keyword!(derive);
+    syn::custom_keyword!(map);
 }
 
 impl Parse for Declarations {
@@ -80,3 +81,42 @@ impl Parse for RawBlock {
         Ok(Self { name, content })
     }
 }
+
+impl Parse for Walkers {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let root = input.parse()?;
+        Ok(Self { root })
+    }
+}
+
+impl Parse for Lowering {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let source = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let mut roots = Vec::new();
+        let mut mapped_types = Vec::new();
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(Token![use]) {
+                input.parse::<Token![use]>()?;
+                roots.push(input.parse()?);
+                input.parse::<Token![;]>()?;
+            } else if lookahead.peek(kw::map) {
+                input.parse::<kw::map>()?;
+                mapped_types.push(input.parse()?);
+                while input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                    mapped_types.push(input.parse()?);
+                }
+                input.parse::<Token![;]>()?;
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+        Ok(Self {
+            source,
+            roots,
+            mapped_types,
+        })
+    }
+}
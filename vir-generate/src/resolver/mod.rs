@@ -1,4 +1,5 @@
-use crate::ast::{Include, RawBlock};
+use crate::ast::{Include, Lowering, RawBlock, Walkers};
+use crate::generator::{generate_lowering, generate_walkers};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::spanned::Spanned;
@@ -16,6 +17,59 @@ pub(crate) fn expand(
     (expanded_ir, expander.errors)
 }
 
+/// Expands the `vir_lowering!` directives. They need the expanded items of two
+/// IRs, so they are expanded after all IRs.
+pub(crate) fn expand_lowerings(irs: &mut [syn::ItemMod]) -> Vec<syn::Error> {
+    let expanded_irs = irs.to_vec();
+    let mut errors = Vec::new();
+    for (ir, expanded_ir) in irs.iter_mut().zip(&expanded_irs) {
+        if let Some((_, content)) = &mut ir.content {
+            expand_lowerings_in(content, 0, expanded_ir, &expanded_irs, &mut errors);
+        }
+    }
+    errors
+}
+
+fn expand_lowerings_in(
+    items: &mut Vec<syn::Item>,
+    depth: usize,
+    target_ir: &syn::ItemMod,
+    irs: &[syn::ItemMod],
+    errors: &mut Vec<syn::Error>,
+) {
+    for item in std::mem::take(items) {
+        match item {
+            syn::Item::Macro(macro_item) if macro_item.mac.path.is_ident("vir_lowering") => {
+                let generated = syn::parse2::<Lowering>(macro_item.mac.tokens)
+                    .and_then(|lowering| {
+                        let source_ir = irs
+                            .iter()
+                            .find(|ir| ir.ident == lowering.source)
+                            .ok_or_else(|| {
+                                syn::Error::new(
+                                    lowering.source.span(),
+                                    format!("not found IR {}", lowering.source),
+                                )
+                            })?;
+                        generate_lowering(&lowering, depth, source_ir, target_ir)
+                    })
+                    .and_then(syn::parse2::<syn::File>);
+                match generated {
+                    Ok(file) => items.extend(file.items),
+                    Err(error) => errors.push(error),
+                }
+            }
+            syn::Item::Mod(mut module) => {
+                if let Some((_, content)) = &mut module.content {
+                    expand_lowerings_in(content, depth + 1, target_ir, irs, errors);
+                }
+                items.push(syn::Item::Mod(module));
+            }
+            _ => items.push(item),
+        }
+    }
+}
+
 struct Expander<'a> {
     components: &'a syn::ItemMod,
     errors: Vec<syn::Error>,
@@ -144,8 +198,17 @@ impl<'a> Fold for Expander<'a> {
     fn fold_item_mod(&mut self, mut item_mod: syn::ItemMod) -> syn::ItemMod {
         if let Some((brace, content)) = item_mod.content {
             let mut new_content = Vec::new();
+            let mut walkers = Vec::new();
             for item in content {
                 match item {
+                    // The walkers are generated from the expanded items, so
+                    // they are generated last.
+                    syn::Item::Macro(macro_item) if macro_item.mac.path.is_ident("vir_walkers") => {
+                        match syn::parse2::<Walkers>(macro_item.mac.tokens) {
+                            Ok(walker) => walkers.push(walker),
+                            Err(error) => self.errors.push(error),
+                        }
+                    }
                     syn::Item::Macro(macro_item) if macro_item.mac.path.is_ident("vir_include") => {
                         match syn::parse2::<Include>(macro_item.mac.tokens) {
                             Ok(include) => {
@@ -163,6 +226,12 @@ impl<'a> Fold for Expander<'a> {
                     }
                 }
             }
+            for walker in walkers {
+                match generate_walkers(&walker, &new_content).and_then(syn::parse2::<syn::File>) {
+                    Ok(file) => new_content.extend(file.items),
+                    Err(error) => self.errors.push(error),
+                }
+            }
             item_mod.content = Some((brace, new_content));
         }
         item_mod
@@ -19,7 +19,14 @@ vir_raw_block! { FunctionDeclaration =>
             for parameter in &self.parameters {
                 write!(f, "{},", parameter)?;
             }
-            write!(f, ") -> {}", self.return_sort)
+            write!(f, ") -> {}", self.return_sort)?;
+            for precondition in &self.preconditions {
+                write!(f, " requires {}", precondition)?;
+            }
+            if let Some(body) = &self.body {
+                write!(f, " {{ {} }}", body)?;
+            }
+            Ok(())
         }
     }
 }
@@ -40,4 +47,40 @@ vir_raw_block! { AxiomDeclaration =>
             write!(f, "{{ {} }}", self.body)
         }
     }
-}
\ No newline at end of file
+}
+vir_raw_block! { AdtDeclaration =>
+    impl std::fmt::Display for AdtDeclaration {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "adt {} {{", self.name)?;
+            for constructor in &self.constructors {
+                write!(f, " {},", constructor)?;
+            }
+            write!(f, " }}")
+        }
+    }
+}
+vir_raw_block! { AdtConstructorDeclaration =>
+    impl std::fmt::Display for AdtConstructorDeclaration {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.name)?;
+            if !self.fields.is_empty() {
+                write!(f, "(")?;
+                for (i, field) in self.fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", field)?;
+                }
+                write!(f, ")")?;
+            }
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { AdtFieldDeclaration =>
+    impl std::fmt::Display for AdtFieldDeclaration {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}: {}", self.name, self.sort)
+        }
+    }
+}
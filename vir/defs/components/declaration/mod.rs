@@ -9,6 +9,7 @@ trait Interface {
     type AxiomNameSymbol;
     type FieldNameSymbol;
     type AdtNameSymbol;
+    type AdtConstructorSymbol;
 }
 
 mod parse;
@@ -29,6 +30,12 @@ pub struct FunctionDeclaration {
     pub name: FunctionSymbol,
     pub parameters: Vec<VariableDeclaration>,
     pub return_sort: Sort,
+    /// The conditions under which the function may be called. They do not
+    /// restrict the definition, but are checked at the call sites.
+    pub preconditions: Vec<Expression>,
+    /// The definition of the function over its parameters. Functions without
+    /// a body are uninterpreted.
+    pub body: Option<Expression>,
 }
 
 pub struct LabelDeclaration {
@@ -39,3 +46,22 @@ pub struct AxiomDeclaration {
     pub name: Option<AxiomNameSymbol>,
     pub body: Expression,
 }
+
+/// An algebraic datatype. Datatypes that are declared together may refer to
+/// each other.
+pub struct AdtDeclaration {
+    pub name: AdtNameSymbol,
+    pub constructors: Vec<AdtConstructorDeclaration>,
+}
+
+pub struct AdtConstructorDeclaration {
+    pub name: AdtConstructorSymbol,
+    /// The names of the fields are also the names of their selectors, so they
+    /// must be unique among all datatypes.
+    pub fields: Vec<AdtFieldDeclaration>,
+}
+
+pub struct AdtFieldDeclaration {
+    pub name: FieldNameSymbol,
+    pub sort: Sort,
+}
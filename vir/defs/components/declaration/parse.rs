@@ -2,6 +2,8 @@ vir_raw_block! { kw =>
     pub mod kw {
         syn::custom_keyword!(sort);
         syn::custom_keyword!(axiom);
+        syn::custom_keyword!(adt);
+        syn::custom_keyword!(requires);
     }
 }
 
@@ -61,11 +63,29 @@ vir_raw_block! { FunctionDeclaration =>
             let parameters = punctuated.into_iter().collect();
             input.parse::<syn::Token![->]>()?;
             let return_sort = input.parse()?;
-            input.parse::<syn::Token![;]>()?;
+            let mut preconditions = Vec::new();
+            while input.peek(kw::requires) {
+                input.parse::<kw::requires>()?;
+                preconditions.push(input.parse()?);
+            }
+            let body = if input.peek(syn::token::Brace) {
+                let content;
+                syn::braced!(content in input);
+                let body = content.parse()?;
+                if !content.is_empty() {
+                    return Err(syn::Error::new(content.span(), "unexpected tokens"));
+                }
+                Some(body)
+            } else {
+                input.parse::<syn::Token![;]>()?;
+                None
+            };
             Ok(Self {
                 name,
                 parameters,
                 return_sort,
+                preconditions,
+                body,
             })
         }
     }
@@ -77,11 +97,18 @@ vir_raw_block! { FunctionDeclaration =>
                 parameter_tokens.extend(quote::quote! { #parameter, });
             }
             let sort = &self.return_sort;
+            let preconditions = &self.preconditions;
+            let body = match &self.body {
+                Some(body) => quote::quote! { Some(#body) },
+                None => quote::quote! { None },
+            };
             tokens.extend(quote::quote! {
                 FunctionDeclaration {
                     name: #name.into(),
                     parameters: vec![#parameter_tokens],
                     return_sort: #sort,
+                    preconditions: vec![#(#preconditions),*],
+                    body: #body,
                 }
             });
         }
@@ -115,3 +142,89 @@ vir_raw_block! { AxiomDeclaration =>
         }
     }
 }
+
+vir_raw_block! { AdtDeclaration =>
+    impl syn::parse::Parse for AdtDeclaration {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            input.parse::<kw::adt>()?;
+            let name = input.parse()?;
+            let content;
+            syn::braced!(content in input);
+            let punctuated: syn::punctuated::Punctuated<_, syn::Token![,]> =
+                content.parse_terminated(AdtConstructorDeclaration::parse)?;
+            let constructors = punctuated.into_iter().collect();
+            Ok(Self { name, constructors })
+        }
+    }
+    impl quote::ToTokens for AdtDeclaration {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let name = format!("{}", self.name);
+            let mut constructor_tokens = proc_macro2::TokenStream::new();
+            for constructor in &self.constructors {
+                constructor_tokens.extend(quote::quote! { #constructor, });
+            }
+            tokens.extend(quote::quote! {
+                AdtDeclaration {
+                    name: #name.into(),
+                    constructors: vec![#constructor_tokens],
+                }
+            });
+        }
+    }
+}
+
+vir_raw_block! { AdtConstructorDeclaration =>
+    impl syn::parse::Parse for AdtConstructorDeclaration {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let name = input.parse()?;
+            let fields = if input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in input);
+                let punctuated: syn::punctuated::Punctuated<_, syn::Token![,]> =
+                    content.parse_terminated(AdtFieldDeclaration::parse)?;
+                punctuated.into_iter().collect()
+            } else {
+                Vec::new()
+            };
+            Ok(Self { name, fields })
+        }
+    }
+    impl quote::ToTokens for AdtConstructorDeclaration {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let name = format!("{}", self.name);
+            let mut field_tokens = proc_macro2::TokenStream::new();
+            for field in &self.fields {
+                field_tokens.extend(quote::quote! { #field, });
+            }
+            tokens.extend(quote::quote! {
+                AdtConstructorDeclaration {
+                    name: #name.into(),
+                    fields: vec![#field_tokens],
+                }
+            });
+        }
+    }
+}
+
+vir_raw_block! { AdtFieldDeclaration =>
+    impl syn::parse::Parse for AdtFieldDeclaration {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let name = input.parse()?;
+            input.parse::<syn::Token![:]>()?;
+            let sort = input.parse()?;
+            Ok(Self { name, sort })
+        }
+    }
+    impl quote::ToTokens for AdtFieldDeclaration {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let name = format!("{}", self.name);
+            let sort = &self.sort;
+            tokens.extend(quote::quote! {
+                AdtFieldDeclaration {
+                    name: #name.into(),
+                    sort: #sort,
+                }
+            });
+        }
+    }
+}
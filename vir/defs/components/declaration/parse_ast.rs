@@ -16,9 +16,26 @@ pub struct FunctionDeclaration {
     pub name: syn::Ident,
     pub parameters: Vec<VariableDeclaration>,
     pub return_sort: Sort,
+    pub preconditions: Vec<Expression>,
+    pub body: Option<Expression>,
 }
 
 pub struct AxiomDeclaration {
     pub name: syn::Ident,
     pub body: Expression,
 }
+
+pub struct AdtDeclaration {
+    pub name: syn::Ident,
+    pub constructors: Vec<AdtConstructorDeclaration>,
+}
+
+pub struct AdtConstructorDeclaration {
+    pub name: syn::Ident,
+    pub fields: Vec<AdtFieldDeclaration>,
+}
+
+pub struct AdtFieldDeclaration {
+    pub name: syn::Ident,
+    pub sort: Sort,
+}
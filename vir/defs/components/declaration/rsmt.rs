@@ -15,3 +15,130 @@ vir_raw_block! { UninterpretedSortDeclaration =>
         }
     }
 }
+
+vir_raw_block! { AdtDeclaration =>
+    impl AdtDeclaration {
+        /// Writes a `declare-datatypes` command that declares `adts` together.
+        pub fn write_declare_datatypes<Writer: std::io::Write, C: Context>(
+            adts: &[AdtDeclaration],
+            writer: &mut Writer,
+            context: &C,
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(declare-datatypes (")?;
+            for (i, adt) in adts.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, " ")?;
+                }
+                write!(writer, "(")?;
+                ::rsmt2::print::Sym2Smt::sym_to_smt2(&adt.name, writer, context)?;
+                write!(writer, " 0)")?;
+            }
+            write!(writer, ") (")?;
+            for (i, adt) in adts.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, " ")?;
+                }
+                write!(writer, "(")?;
+                for (j, constructor) in adt.constructors.iter().enumerate() {
+                    if j > 0 {
+                        write!(writer, " ")?;
+                    }
+                    write!(writer, "(")?;
+                    ::rsmt2::print::Sym2Smt::sym_to_smt2(&constructor.name, writer, context)?;
+                    for field in &constructor.fields {
+                        write!(writer, " (")?;
+                        ::rsmt2::print::Sym2Smt::sym_to_smt2(&field.name, writer, context)?;
+                        write!(writer, " ")?;
+                        ::rsmt2::print::Sort2Smt::sort_to_smt2(&field.sort, writer, context)?;
+                        write!(writer, ")")?;
+                    }
+                    write!(writer, ")")?;
+                }
+                write!(writer, ")")?;
+            }
+            write!(writer, "))")?;
+            Ok(())
+        }
+    }
+}
+
+vir_raw_block! { FunctionDeclaration =>
+    impl FunctionDeclaration {
+        /// Writes a `define-fun` command. The function must have a body.
+        pub fn write_define_fun<Writer: std::io::Write, C: Context>(
+            &self,
+            writer: &mut Writer,
+            context: &C,
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(define-fun ")?;
+            self.write_signature(writer, context)?;
+            write!(writer, " ")?;
+            self.write_body(writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+        /// Writes a `define-funs-rec` command that defines `functions`
+        /// together, so that they may call each other. All functions must
+        /// have a body.
+        pub fn write_define_funs_rec<Writer: std::io::Write, C: Context>(
+            functions: &[FunctionDeclaration],
+            writer: &mut Writer,
+            context: &C,
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(define-funs-rec (")?;
+            for (i, function) in functions.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, " ")?;
+                }
+                write!(writer, "(")?;
+                function.write_signature(writer, context)?;
+                write!(writer, ")")?;
+            }
+            write!(writer, ") (")?;
+            for (i, function) in functions.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, " ")?;
+                }
+                function.write_body(writer, context)?;
+            }
+            write!(writer, "))")?;
+            Ok(())
+        }
+        fn write_signature<Writer: std::io::Write, C: Context>(
+            &self,
+            writer: &mut Writer,
+            context: &C,
+        ) -> ::rsmt2::SmtRes<()> {
+            ::rsmt2::print::Sym2Smt::sym_to_smt2(&self.name, writer, context)?;
+            write!(writer, " (")?;
+            for (i, parameter) in self.parameters.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, " ")?;
+                }
+                write!(writer, "(")?;
+                context.write_variable_symbol(writer, &parameter.name)?;
+                write!(writer, " ")?;
+                ::rsmt2::print::Sort2Smt::sort_to_smt2(&parameter.sort, writer, context)?;
+                write!(writer, ")")?;
+            }
+            write!(writer, ") ")?;
+            ::rsmt2::print::Sort2Smt::sort_to_smt2(&self.return_sort, writer, context)?;
+            Ok(())
+        }
+        fn write_body<Writer: std::io::Write, C: Context>(
+            &self,
+            writer: &mut Writer,
+            context: &C,
+        ) -> ::rsmt2::SmtRes<()> {
+            let body = self.body.as_ref().expect("the function has no body");
+            let parameters = self
+                .parameters
+                .iter()
+                .map(|parameter| (parameter.name.clone(), parameter.sort.clone()));
+            context.bound_variables().bind(parameters, || {
+                ::rsmt2::print::Expr2Smt::expr_to_smt2(body, writer, context)
+            })?;
+            Ok(())
+        }
+    }
+}
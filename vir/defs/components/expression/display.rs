@@ -11,6 +11,13 @@ vir_raw_block! { Constant =>
             match self {
                 Constant::Bool(value) => value.fmt(f),
                 Constant::Int(value) => value.fmt(f),
+                Constant::BitVector { value, width } => write!(f, "{}bv{}", value, width),
+                Constant::Real { numerator, denominator } => {
+                    crate::common::real::write_rational(f, numerator, denominator)
+                }
+                Constant::Float { bits, exponent, significand } => {
+                    crate::common::float::write_literal(f, *bits, *exponent, *significand)
+                }
             }
         }
     }
@@ -19,17 +26,33 @@ vir_raw_block! { UnaryOperation =>
     impl std::fmt::Display for UnaryOperation {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self.kind {
-                UnaryOperationKind::Not => write!(f, "!")?,
-                UnaryOperationKind::Minus => write!(f, "-")?,
+                UnaryOperationKind::Not => write!(f, "!{}", self.arg),
+                UnaryOperationKind::Minus => write!(f, "-{}", self.arg),
+                UnaryOperationKind::ToReal => write!(f, "to_real({})", self.arg),
+                UnaryOperationKind::ToInt => write!(f, "to_int({})", self.arg),
+                UnaryOperationKind::IsInt => write!(f, "is_int({})", self.arg),
             }
-            write!(f, "{}", self.arg)
         }
     }
 }
 vir_raw_block! { BinaryOperation =>
     impl std::fmt::Display for BinaryOperation {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "({}{}{})", self.left, self.kind, self.right)
+            match self.kind {
+                // Operations without operator syntax are printed as in
+                // `vir_high!`.
+                BinaryOperationKind::AShr
+                | BinaryOperationKind::SDiv
+                | BinaryOperationKind::SRem
+                | BinaryOperationKind::SGtCmp
+                | BinaryOperationKind::SGeCmp
+                | BinaryOperationKind::SLtCmp
+                | BinaryOperationKind::SLeCmp
+                | BinaryOperationKind::Concat => {
+                    write!(f, "{}({}, {})", self.kind, self.left, self.right)
+                }
+                _ => write!(f, "({}{}{})", self.left, self.kind, self.right),
+            }
         }
     }
 }
@@ -51,10 +74,267 @@ vir_raw_block! { BinaryOperationKind =>
                 BinaryOperationKind::And => write!(f, "&&"),
                 BinaryOperationKind::Or => write!(f, "||"),
                 BinaryOperationKind::Implies => write!(f, "==>"),
+                BinaryOperationKind::BitAnd => write!(f, "&"),
+                BinaryOperationKind::BitOr => write!(f, "|"),
+                BinaryOperationKind::BitXor => write!(f, "^"),
+                BinaryOperationKind::Shl => write!(f, "<<"),
+                BinaryOperationKind::LShr => write!(f, ">>"),
+                BinaryOperationKind::AShr => write!(f, "ashr"),
+                BinaryOperationKind::SDiv => write!(f, "sdiv"),
+                BinaryOperationKind::SRem => write!(f, "srem"),
+                BinaryOperationKind::SGtCmp => write!(f, "sgt"),
+                BinaryOperationKind::SGeCmp => write!(f, "sge"),
+                BinaryOperationKind::SLtCmp => write!(f, "slt"),
+                BinaryOperationKind::SLeCmp => write!(f, "sle"),
+                BinaryOperationKind::Concat => write!(f, "concat"),
+            }
+        }
+    }
+}
+vir_raw_block! { BitVectorConversion =>
+    impl std::fmt::Display for BitVectorConversion {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self.kind {
+                BitVectorConversionKind::Extract { high, low } => {
+                    write!(f, "extract({}, {}, {})", self.arg, high, low)
+                }
+                BitVectorConversionKind::ZeroExtend { bits } => {
+                    write!(f, "zero_extend({}, {})", self.arg, bits)
+                }
+                BitVectorConversionKind::SignExtend { bits } => {
+                    write!(f, "sign_extend({}, {})", self.arg, bits)
+                }
+                BitVectorConversionKind::FromInt { width } => {
+                    write!(f, "int2bv({}, {})", self.arg, width)
+                }
+                BitVectorConversionKind::ToInt => write!(f, "bv2int({})", self.arg),
+            }
+        }
+    }
+}
+vir_raw_block! { FloatOperation =>
+    impl std::fmt::Display for FloatOperation {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}(", self.kind)?;
+            if let Some(rounding_mode) = self.kind.rounding_mode() {
+                write!(f, "{}", rounding_mode)?;
+                if !self.args.is_empty() {
+                    write!(f, ", ")?;
+                }
+            }
+            for (i, arg) in self.args.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", arg)?;
+            }
+            write!(f, ")")
+        }
+    }
+}
+vir_raw_block! { FloatOperationKind =>
+    impl std::fmt::Display for FloatOperationKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let name = match self {
+                FloatOperationKind::Add(_) => "fp_add",
+                FloatOperationKind::Sub(_) => "fp_sub",
+                FloatOperationKind::Mul(_) => "fp_mul",
+                FloatOperationKind::Div(_) => "fp_div",
+                FloatOperationKind::Fma(_) => "fp_fma",
+                FloatOperationKind::Sqrt(_) => "fp_sqrt",
+                FloatOperationKind::RoundToIntegral(_) => "fp_round",
+                FloatOperationKind::Rem => "fp_rem",
+                FloatOperationKind::Abs => "fp_abs",
+                FloatOperationKind::Min => "fp_min",
+                FloatOperationKind::Max => "fp_max",
+                FloatOperationKind::Eq => "fp_eq",
+                FloatOperationKind::IsNormal => "fp_is_normal",
+                FloatOperationKind::IsSubnormal => "fp_is_subnormal",
+                FloatOperationKind::IsZero => "fp_is_zero",
+                FloatOperationKind::IsInfinite => "fp_is_infinite",
+                FloatOperationKind::IsNaN => "fp_is_nan",
+                FloatOperationKind::IsNegative => "fp_is_negative",
+                FloatOperationKind::IsPositive => "fp_is_positive",
+            };
+            write!(f, "{}", name)
+        }
+    }
+}
+vir_raw_block! { FloatConversion =>
+    impl std::fmt::Display for FloatConversion {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            use crate::common::float::write_format;
+            match &self.kind {
+                FloatConversionKind::ToFloat { rounding_mode, exponent, significand } => {
+                    write!(f, "to_fp({}, {}, ", rounding_mode, self.arg)?;
+                    write_format(f, *exponent, *significand)?;
+                    write!(f, ")")
+                }
+                FloatConversionKind::UnsignedToFloat { rounding_mode, exponent, significand } => {
+                    write!(f, "to_fp_unsigned({}, {}, ", rounding_mode, self.arg)?;
+                    write_format(f, *exponent, *significand)?;
+                    write!(f, ")")
+                }
+                FloatConversionKind::FromBits { exponent, significand } => {
+                    write!(f, "fp_from_bits({}, ", self.arg)?;
+                    write_format(f, *exponent, *significand)?;
+                    write!(f, ")")
+                }
+                FloatConversionKind::ToReal => write!(f, "fp_to_real({})", self.arg),
+                FloatConversionKind::ToSigned { rounding_mode, width } => {
+                    write!(f, "fp_to_sbv({}, {}, {})", rounding_mode, self.arg, width)
+                }
+                FloatConversionKind::ToUnsigned { rounding_mode, width } => {
+                    write!(f, "fp_to_ubv({}, {}, {})", rounding_mode, self.arg, width)
+                }
+            }
+        }
+    }
+}
+vir_raw_block! { ArraySelect =>
+    impl std::fmt::Display for ArraySelect {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}[{}]", self.array, self.index)
+        }
+    }
+}
+vir_raw_block! { ArrayStore =>
+    impl std::fmt::Display for ArrayStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}[{} := {}]", self.array, self.index, self.value)
+        }
+    }
+}
+vir_raw_block! { ConstantArray =>
+    impl std::fmt::Display for ConstantArray {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "const_array<{}>({})", self.index_sort, self.value)
+        }
+    }
+}
+vir_raw_block! { SeqOperation =>
+    impl std::fmt::Display for SeqOperation {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}(", self.kind)?;
+            for (i, arg) in self.args.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", arg)?;
+            }
+            write!(f, ")")
+        }
+    }
+}
+vir_raw_block! { SeqOperationKind =>
+    impl std::fmt::Display for SeqOperationKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SeqOperationKind::Unit => write!(f, "seq_unit"),
+                SeqOperationKind::Length => write!(f, "seq_len"),
+                SeqOperationKind::Index => write!(f, "seq_index"),
+                SeqOperationKind::Concat => write!(f, "seq_concat"),
+                SeqOperationKind::Subsequence => write!(f, "seq_sub"),
+                SeqOperationKind::Update => write!(f, "seq_update"),
             }
         }
     }
 }
+vir_raw_block! { EmptySeq =>
+    impl std::fmt::Display for EmptySeq {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "seq_empty<{}>()", self.element_sort)
+        }
+    }
+}
+vir_raw_block! { SetOperation =>
+    impl std::fmt::Display for SetOperation {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}(", self.kind)?;
+            for (i, arg) in self.args.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", arg)?;
+            }
+            write!(f, ")")
+        }
+    }
+}
+vir_raw_block! { SetOperationKind =>
+    impl std::fmt::Display for SetOperationKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SetOperationKind::Singleton => write!(f, "set_singleton"),
+                SetOperationKind::Contains => write!(f, "set_contains"),
+                SetOperationKind::Union => write!(f, "set_union"),
+                SetOperationKind::Intersection => write!(f, "set_intersection"),
+                SetOperationKind::Subset => write!(f, "set_subset"),
+            }
+        }
+    }
+}
+vir_raw_block! { EmptySet =>
+    impl std::fmt::Display for EmptySet {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "set_empty<{}>()", self.element_sort)
+        }
+    }
+}
+vir_raw_block! { AdtConstructor =>
+    impl std::fmt::Display for AdtConstructor {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}::{}", self.adt, self.constructor)?;
+            if !self.args.is_empty() {
+                write!(f, "(")?;
+                for (i, arg) in self.args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")?;
+            }
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { AdtFieldAccess =>
+    impl std::fmt::Display for AdtFieldAccess {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}.{}", self.base, self.field)
+        }
+    }
+}
+vir_raw_block! { AdtTester =>
+    impl std::fmt::Display for AdtTester {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "is_{}({})", self.constructor, self.base)
+        }
+    }
+}
+vir_raw_block! { AdtMatch =>
+    impl std::fmt::Display for AdtMatch {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "match {} {{", self.base)?;
+            for case in &self.cases {
+                write!(f, " {}::{}", self.adt, case.constructor)?;
+                if !case.binders.is_empty() {
+                    write!(f, "(")?;
+                    for (i, binder) in case.binders.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", binder)?;
+                    }
+                    write!(f, ")")?;
+                }
+                write!(f, " => {},", case.body)?;
+            }
+            write!(f, " }}")
+        }
+    }
+}
 vir_raw_block! { Conditional =>
     impl std::fmt::Display for Conditional {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -66,6 +346,13 @@ vir_raw_block! { Conditional =>
         }
     }
 }
+vir_raw_block! { Let =>
+    impl std::fmt::Display for Let {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "(let {} = {} in {})", self.variable, self.value, self.body)
+        }
+    }
+}
 vir_raw_block! { Quantifier =>
     impl std::fmt::Display for Quantifier {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -130,3 +417,10 @@ vir_raw_block! { LabelledExpression =>
         }
     }
 }
+vir_raw_block! { PositionedExpression =>
+    impl std::fmt::Display for PositionedExpression {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "({} {})", self.position, self.expression)
+        }
+    }
+}
@@ -15,13 +15,19 @@ vir_raw_block! { Constant =>
         fn is_true(&self) -> bool {
             match self {
                 Constant::Bool(value) => *value,
-                Constant::Int(_) => false,
+                Constant::Int(_)
+                | Constant::BitVector { .. }
+                | Constant::Real { .. }
+                | Constant::Float { .. } => false,
             }
         }
         fn is_false(&self) -> bool {
             match self {
                 Constant::Bool(value) => !*value,
-                Constant::Int(_) => false,
+                Constant::Int(_)
+                | Constant::BitVector { .. }
+                | Constant::Real { .. }
+                | Constant::Float { .. } => false,
             }
         }
     }
@@ -31,13 +37,19 @@ vir_raw_block! { UnaryOperation =>
         fn is_true(&self) -> bool {
             match self.kind {
                 UnaryOperationKind::Not => self.arg.is_false(),
-                UnaryOperationKind::Minus => unreachable!(),
+                UnaryOperationKind::IsInt
+                | UnaryOperationKind::Minus
+                | UnaryOperationKind::ToReal
+                | UnaryOperationKind::ToInt => false,
             }
         }
         fn is_false(&self) -> bool {
             match self.kind {
                 UnaryOperationKind::Not => self.arg.is_true(),
-                UnaryOperationKind::Minus => unreachable!(),
+                UnaryOperationKind::IsInt
+                | UnaryOperationKind::Minus
+                | UnaryOperationKind::ToReal
+                | UnaryOperationKind::ToInt => false,
             }
         }
     }
@@ -51,7 +63,11 @@ vir_raw_block! { BinaryOperation =>
                 | BinaryOperationKind::GtCmp
                 | BinaryOperationKind::GeCmp
                 | BinaryOperationKind::LtCmp
-                | BinaryOperationKind::LeCmp => false,
+                | BinaryOperationKind::LeCmp
+                | BinaryOperationKind::SGtCmp
+                | BinaryOperationKind::SGeCmp
+                | BinaryOperationKind::SLtCmp
+                | BinaryOperationKind::SLeCmp => false,
                 BinaryOperationKind::And => {
                     self.left.is_true() && self.right.is_true()
                 }
@@ -65,7 +81,16 @@ vir_raw_block! { BinaryOperation =>
                 | BinaryOperationKind::Sub
                 | BinaryOperationKind::Mul
                 | BinaryOperationKind::Div
-                | BinaryOperationKind::Mod => unreachable!(),
+                | BinaryOperationKind::Mod
+                | BinaryOperationKind::BitAnd
+                | BinaryOperationKind::BitOr
+                | BinaryOperationKind::BitXor
+                | BinaryOperationKind::Shl
+                | BinaryOperationKind::LShr
+                | BinaryOperationKind::AShr
+                | BinaryOperationKind::SDiv
+                | BinaryOperationKind::SRem
+                | BinaryOperationKind::Concat => false,
             }
         }
         fn is_false(&self) -> bool {
@@ -75,7 +100,11 @@ vir_raw_block! { BinaryOperation =>
                 | BinaryOperationKind::GtCmp
                 | BinaryOperationKind::GeCmp
                 | BinaryOperationKind::LtCmp
-                | BinaryOperationKind::LeCmp => false,
+                | BinaryOperationKind::LeCmp
+                | BinaryOperationKind::SGtCmp
+                | BinaryOperationKind::SGeCmp
+                | BinaryOperationKind::SLtCmp
+                | BinaryOperationKind::SLeCmp => false,
                 BinaryOperationKind::And => {
                     self.left.is_false() || self.right.is_false()
                 }
@@ -89,11 +118,160 @@ vir_raw_block! { BinaryOperation =>
                 | BinaryOperationKind::Sub
                 | BinaryOperationKind::Mul
                 | BinaryOperationKind::Div
-                | BinaryOperationKind::Mod => unreachable!(),
+                | BinaryOperationKind::Mod
+                | BinaryOperationKind::BitAnd
+                | BinaryOperationKind::BitOr
+                | BinaryOperationKind::BitXor
+                | BinaryOperationKind::Shl
+                | BinaryOperationKind::LShr
+                | BinaryOperationKind::AShr
+                | BinaryOperationKind::SDiv
+                | BinaryOperationKind::SRem
+                | BinaryOperationKind::Concat => false,
             }
         }
     }
 }
+vir_raw_block! { BitVectorConversion =>
+    impl crate::common::expression::SyntacticEvaluation for BitVectorConversion {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { ArraySelect =>
+    impl crate::common::expression::SyntacticEvaluation for ArraySelect {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { ArrayStore =>
+    impl crate::common::expression::SyntacticEvaluation for ArrayStore {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { ConstantArray =>
+    impl crate::common::expression::SyntacticEvaluation for ConstantArray {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { FloatOperation =>
+    impl crate::common::expression::SyntacticEvaluation for FloatOperation {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { FloatConversion =>
+    impl crate::common::expression::SyntacticEvaluation for FloatConversion {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { SeqOperation =>
+    impl crate::common::expression::SyntacticEvaluation for SeqOperation {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { EmptySeq =>
+    impl crate::common::expression::SyntacticEvaluation for EmptySeq {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { SetOperation =>
+    impl crate::common::expression::SyntacticEvaluation for SetOperation {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { EmptySet =>
+    impl crate::common::expression::SyntacticEvaluation for EmptySet {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { AdtConstructor =>
+    impl crate::common::expression::SyntacticEvaluation for AdtConstructor {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { AdtFieldAccess =>
+    impl crate::common::expression::SyntacticEvaluation for AdtFieldAccess {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { AdtTester =>
+    impl crate::common::expression::SyntacticEvaluation for AdtTester {
+        fn is_true(&self) -> bool {
+            false
+        }
+        fn is_false(&self) -> bool {
+            false
+        }
+    }
+}
+vir_raw_block! { AdtMatch =>
+    impl crate::common::expression::SyntacticEvaluation for AdtMatch {
+        fn is_true(&self) -> bool {
+            self.cases.iter().all(|case| case.body.is_true())
+        }
+        fn is_false(&self) -> bool {
+            self.cases.iter().all(|case| case.body.is_false())
+        }
+    }
+}
 vir_raw_block! { Conditional =>
     impl crate::common::expression::SyntacticEvaluation for Conditional {
         fn is_true(&self) -> bool {
@@ -104,6 +282,16 @@ vir_raw_block! { Conditional =>
         }
     }
 }
+vir_raw_block! { Let =>
+    impl crate::common::expression::SyntacticEvaluation for Let {
+        fn is_true(&self) -> bool {
+            self.body.is_true()
+        }
+        fn is_false(&self) -> bool {
+            self.body.is_false()
+        }
+    }
+}
 vir_raw_block! { Quantifier =>
     impl crate::common::expression::SyntacticEvaluation for Quantifier {
         fn is_true(&self) -> bool {
@@ -134,3 +322,13 @@ vir_raw_block! { LabelledExpression =>
         }
     }
 }
+vir_raw_block! { PositionedExpression =>
+    impl crate::common::expression::SyntacticEvaluation for PositionedExpression {
+        fn is_true(&self) -> bool {
+            self.expression.is_true()
+        }
+        fn is_false(&self) -> bool {
+            self.expression.is_false()
+        }
+    }
+}
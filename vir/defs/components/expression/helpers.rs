@@ -24,7 +24,26 @@ vir_raw_block! { ConstantHelpers =>
             Expression::constant(Constant::Bool(value))
         }
         fn int(value: i64) -> Expression {
-            Expression::constant(Constant::Int(value))
+            Expression::constant(Constant::Int(value.into()))
+        }
+        fn bit_vector(value: u128, width: u32) -> Expression {
+            Expression::constant(Constant::BitVector { value, width })
+        }
+        fn real(numerator: i64, denominator: i64) -> Expression {
+            let (numerator, denominator) =
+                crate::common::real::normalize(numerator.into(), denominator.into())
+                    .expect("invalid rational");
+            Expression::constant(Constant::Real { numerator, denominator })
+        }
+        fn f32(value: f32) -> Expression {
+            let (exponent, significand) = crate::common::float::F32;
+            let bits = value.to_bits().into();
+            Expression::constant(Constant::Float { bits, exponent, significand })
+        }
+        fn f64(value: f64) -> Expression {
+            let (exponent, significand) = crate::common::float::F64;
+            let bits = value.to_bits().into();
+            Expression::constant(Constant::Float { bits, exponent, significand })
         }
     }
     impl From<bool> for Constant {
@@ -34,7 +53,7 @@ vir_raw_block! { ConstantHelpers =>
     }
     impl From<i64> for Constant {
         fn from(value: i64) -> Self {
-            Constant::Int(value)
+            Constant::Int(value.into())
         }
     }
     impl From<bool> for Expression {
@@ -69,6 +88,15 @@ vir_raw_block! { UnaryOperationHelpers =>
         fn minus(arg: Expression) -> Expression {
             Expression::unary_operation(UnaryOperationKind::Minus, arg)
         }
+        fn to_real(arg: Expression) -> Expression {
+            Expression::unary_operation(UnaryOperationKind::ToReal, arg)
+        }
+        fn to_int(arg: Expression) -> Expression {
+            Expression::unary_operation(UnaryOperationKind::ToInt, arg)
+        }
+        fn is_int(arg: Expression) -> Expression {
+            Expression::unary_operation(UnaryOperationKind::IsInt, arg)
+        }
     }
     impl From<UnaryOperation> for Expression {
         fn from(operation: UnaryOperation) -> Self {
@@ -137,6 +165,293 @@ vir_raw_block! { BinaryOperationHelpers =>
     }
 }
 
+vir_raw_block! { BitVectorConversionHelpers =>
+    impl crate::common::expression::BitVectorConversionHelpers for Expression {
+        type BitVectorConversionKind = BitVectorConversionKind;
+        fn bit_vector_conversion(kind: BitVectorConversionKind, arg: Expression) -> Expression {
+            Expression::BitVectorConversion(BitVectorConversion {
+                kind,
+                arg: Box::new(arg),
+            })
+        }
+        fn extract(arg: Expression, high: u32, low: u32) -> Expression {
+            Self::bit_vector_conversion(BitVectorConversionKind::Extract { high, low }, arg)
+        }
+        fn zero_extend(arg: Expression, bits: u32) -> Expression {
+            Self::bit_vector_conversion(BitVectorConversionKind::ZeroExtend { bits }, arg)
+        }
+        fn sign_extend(arg: Expression, bits: u32) -> Expression {
+            Self::bit_vector_conversion(BitVectorConversionKind::SignExtend { bits }, arg)
+        }
+        fn int_to_bit_vector(arg: Expression, width: u32) -> Expression {
+            Self::bit_vector_conversion(BitVectorConversionKind::FromInt { width }, arg)
+        }
+        fn bit_vector_to_int(arg: Expression) -> Expression {
+            Self::bit_vector_conversion(BitVectorConversionKind::ToInt, arg)
+        }
+    }
+    impl From<BitVectorConversion> for Expression {
+        fn from(conversion: BitVectorConversion) -> Self {
+            Self::BitVectorConversion(conversion)
+        }
+    }
+}
+
+vir_raw_block! { FloatHelpers =>
+    impl crate::common::expression::FloatHelpers for Expression {
+        type FloatOperationKind = FloatOperationKind;
+        type FloatConversionKind = FloatConversionKind;
+        fn float_operation(kind: FloatOperationKind, args: Vec<Expression>) -> Expression {
+            Expression::FloatOperation(FloatOperation { kind, args })
+        }
+        fn float_conversion(kind: FloatConversionKind, arg: Expression) -> Expression {
+            Expression::FloatConversion(FloatConversion { kind, arg: Box::new(arg) })
+        }
+        fn fp_add(
+            rounding_mode: crate::common::float::RoundingMode,
+            left: Expression,
+            right: Expression,
+        ) -> Expression {
+            Self::float_operation(FloatOperationKind::Add(rounding_mode), vec![left, right])
+        }
+        fn fp_sub(
+            rounding_mode: crate::common::float::RoundingMode,
+            left: Expression,
+            right: Expression,
+        ) -> Expression {
+            Self::float_operation(FloatOperationKind::Sub(rounding_mode), vec![left, right])
+        }
+        fn fp_mul(
+            rounding_mode: crate::common::float::RoundingMode,
+            left: Expression,
+            right: Expression,
+        ) -> Expression {
+            Self::float_operation(FloatOperationKind::Mul(rounding_mode), vec![left, right])
+        }
+        fn fp_div(
+            rounding_mode: crate::common::float::RoundingMode,
+            left: Expression,
+            right: Expression,
+        ) -> Expression {
+            Self::float_operation(FloatOperationKind::Div(rounding_mode), vec![left, right])
+        }
+        fn fp_sqrt(
+            rounding_mode: crate::common::float::RoundingMode,
+            arg: Expression,
+        ) -> Expression {
+            Self::float_operation(FloatOperationKind::Sqrt(rounding_mode), vec![arg])
+        }
+        fn fp_eq(left: Expression, right: Expression) -> Expression {
+            Self::float_operation(FloatOperationKind::Eq, vec![left, right])
+        }
+        fn fp_is_nan(arg: Expression) -> Expression {
+            Self::float_operation(FloatOperationKind::IsNaN, vec![arg])
+        }
+        fn fp_is_infinite(arg: Expression) -> Expression {
+            Self::float_operation(FloatOperationKind::IsInfinite, vec![arg])
+        }
+        fn to_float(
+            rounding_mode: crate::common::float::RoundingMode,
+            arg: Expression,
+            exponent: u32,
+            significand: u32,
+        ) -> Expression {
+            let kind = FloatConversionKind::ToFloat { rounding_mode, exponent, significand };
+            Self::float_conversion(kind, arg)
+        }
+        fn float_to_real(arg: Expression) -> Expression {
+            Self::float_conversion(FloatConversionKind::ToReal, arg)
+        }
+        fn float_to_signed(
+            rounding_mode: crate::common::float::RoundingMode,
+            arg: Expression,
+            width: u32,
+        ) -> Expression {
+            Self::float_conversion(FloatConversionKind::ToSigned { rounding_mode, width }, arg)
+        }
+    }
+    impl From<FloatOperation> for Expression {
+        fn from(operation: FloatOperation) -> Self {
+            Self::FloatOperation(operation)
+        }
+    }
+    impl From<FloatConversion> for Expression {
+        fn from(conversion: FloatConversion) -> Self {
+            Self::FloatConversion(conversion)
+        }
+    }
+}
+
+vir_raw_block! { ArrayHelpers =>
+    impl crate::common::expression::ArrayHelpers for Expression {
+        type Sort = Sort;
+        fn select(array: Expression, index: Expression) -> Expression {
+            Expression::ArraySelect(ArraySelect {
+                array: Box::new(array),
+                index: Box::new(index),
+            })
+        }
+        fn store(array: Expression, index: Expression, value: Expression) -> Expression {
+            Expression::ArrayStore(ArrayStore {
+                array: Box::new(array),
+                index: Box::new(index),
+                value: Box::new(value),
+            })
+        }
+        fn constant_array(index_sort: Sort, value: Expression) -> Expression {
+            Expression::ConstantArray(ConstantArray {
+                index_sort,
+                value: Box::new(value),
+            })
+        }
+    }
+    impl From<ArraySelect> for Expression {
+        fn from(select: ArraySelect) -> Self {
+            Self::ArraySelect(select)
+        }
+    }
+    impl From<ArrayStore> for Expression {
+        fn from(store: ArrayStore) -> Self {
+            Self::ArrayStore(store)
+        }
+    }
+    impl From<ConstantArray> for Expression {
+        fn from(array: ConstantArray) -> Self {
+            Self::ConstantArray(array)
+        }
+    }
+}
+
+vir_raw_block! { SeqHelpers =>
+    impl crate::common::expression::SeqHelpers for Expression {
+        type Sort = Sort;
+        type SeqOperationKind = SeqOperationKind;
+        fn seq_operation(kind: SeqOperationKind, args: Vec<Expression>) -> Expression {
+            Expression::SeqOperation(SeqOperation { kind, args })
+        }
+        fn empty_seq(element_sort: Sort) -> Expression {
+            Expression::EmptySeq(EmptySeq { element_sort })
+        }
+        fn seq_unit(value: Expression) -> Expression {
+            Self::seq_operation(SeqOperationKind::Unit, vec![value])
+        }
+        fn seq_length(seq: Expression) -> Expression {
+            Self::seq_operation(SeqOperationKind::Length, vec![seq])
+        }
+        fn seq_index(seq: Expression, index: Expression) -> Expression {
+            Self::seq_operation(SeqOperationKind::Index, vec![seq, index])
+        }
+        fn seq_concat(left: Expression, right: Expression) -> Expression {
+            Self::seq_operation(SeqOperationKind::Concat, vec![left, right])
+        }
+        fn seq_subsequence(seq: Expression, offset: Expression, length: Expression) -> Expression {
+            Self::seq_operation(SeqOperationKind::Subsequence, vec![seq, offset, length])
+        }
+        fn seq_update(seq: Expression, index: Expression, value: Expression) -> Expression {
+            Self::seq_operation(SeqOperationKind::Update, vec![seq, index, value])
+        }
+    }
+    impl From<SeqOperation> for Expression {
+        fn from(operation: SeqOperation) -> Self {
+            Self::SeqOperation(operation)
+        }
+    }
+    impl From<EmptySeq> for Expression {
+        fn from(seq: EmptySeq) -> Self {
+            Self::EmptySeq(seq)
+        }
+    }
+}
+
+vir_raw_block! { SetHelpers =>
+    impl crate::common::expression::SetHelpers for Expression {
+        type Sort = Sort;
+        type SetOperationKind = SetOperationKind;
+        fn set_operation(kind: SetOperationKind, args: Vec<Expression>) -> Expression {
+            Expression::SetOperation(SetOperation { kind, args })
+        }
+        fn empty_set(element_sort: Sort) -> Expression {
+            Expression::EmptySet(EmptySet { element_sort })
+        }
+        fn set_singleton(value: Expression) -> Expression {
+            Self::set_operation(SetOperationKind::Singleton, vec![value])
+        }
+        fn set_contains(set: Expression, value: Expression) -> Expression {
+            Self::set_operation(SetOperationKind::Contains, vec![set, value])
+        }
+        fn set_union(left: Expression, right: Expression) -> Expression {
+            Self::set_operation(SetOperationKind::Union, vec![left, right])
+        }
+        fn set_intersection(left: Expression, right: Expression) -> Expression {
+            Self::set_operation(SetOperationKind::Intersection, vec![left, right])
+        }
+        fn set_subset(left: Expression, right: Expression) -> Expression {
+            Self::set_operation(SetOperationKind::Subset, vec![left, right])
+        }
+    }
+    impl From<SetOperation> for Expression {
+        fn from(operation: SetOperation) -> Self {
+            Self::SetOperation(operation)
+        }
+    }
+    impl From<EmptySet> for Expression {
+        fn from(set: EmptySet) -> Self {
+            Self::EmptySet(set)
+        }
+    }
+}
+
+vir_raw_block! { AdtHelpers =>
+    impl crate::common::expression::AdtHelpers for Expression {
+        type AdtNameSymbol = AdtNameSymbol;
+        type AdtConstructorSymbol = AdtConstructorSymbol;
+        type FieldNameSymbol = FieldNameSymbol;
+        fn construct(
+            adt: AdtNameSymbol,
+            constructor: AdtConstructorSymbol,
+            args: Vec<Expression>,
+        ) -> Expression {
+            Expression::AdtConstructor(AdtConstructor {
+                adt,
+                constructor,
+                args,
+            })
+        }
+        fn field(base: Expression, field: FieldNameSymbol) -> Expression {
+            Expression::AdtFieldAccess(AdtFieldAccess {
+                base: Box::new(base),
+                field,
+            })
+        }
+        fn is_constructor(base: Expression, constructor: AdtConstructorSymbol) -> Expression {
+            Expression::AdtTester(AdtTester {
+                base: Box::new(base),
+                constructor,
+            })
+        }
+    }
+    impl From<AdtConstructor> for Expression {
+        fn from(constructor: AdtConstructor) -> Self {
+            Self::AdtConstructor(constructor)
+        }
+    }
+    impl From<AdtFieldAccess> for Expression {
+        fn from(access: AdtFieldAccess) -> Self {
+            Self::AdtFieldAccess(access)
+        }
+    }
+    impl From<AdtTester> for Expression {
+        fn from(tester: AdtTester) -> Self {
+            Self::AdtTester(tester)
+        }
+    }
+    impl From<AdtMatch> for Expression {
+        fn from(adt_match: AdtMatch) -> Self {
+            Self::AdtMatch(adt_match)
+        }
+    }
+}
+
 vir_raw_block! { QuantifierHelpers =>
     impl crate::common::expression::QuantifierHelpers for Expression {
         type QuantifierKind = QuantifierKind;
@@ -177,6 +492,23 @@ vir_raw_block! { QuantifierHelpers =>
     }
 }
 
+vir_raw_block! { LetHelpers =>
+    impl crate::common::expression::LetHelpers for Expression {
+        fn let_in(variable: VariableSymbol, value: Expression, body: Expression) -> Expression {
+            Expression::Let(Let {
+                variable,
+                value: Box::new(value),
+                body: Box::new(body),
+            })
+        }
+    }
+    impl From<Let> for Expression {
+        fn from(binding: Let) -> Self {
+            Self::Let(binding)
+        }
+    }
+}
+
 vir_raw_block! { FunctionApplicationHelpers =>
     impl crate::common::expression::FunctionApplicationHelpers for Expression {
         type FunctionSymbol = FunctionSymbol;
@@ -222,3 +554,22 @@ vir_raw_block! { LabelledExpressionHelpers =>
         }
     }
 }
+
+vir_raw_block! { PositionedExpressionHelpers =>
+    impl crate::common::expression::PositionedExpressionHelpers for Expression {
+        fn positioned(
+            position: crate::common::position::Position,
+            expression: Expression,
+        ) -> Expression {
+            Expression::PositionedExpression(PositionedExpression {
+                position,
+                expression: Box::new(expression),
+            })
+        }
+    }
+    impl From<PositionedExpression> for Expression {
+        fn from(expression: PositionedExpression) -> Self {
+            Self::PositionedExpression(expression)
+        }
+    }
+}
@@ -4,6 +4,9 @@ trait Interface {
     type Sort;
     type FunctionSymbol;
     type LabelSymbol;
+    type AdtNameSymbol;
+    type AdtConstructorSymbol;
+    type FieldNameSymbol;
 }
 
 mod display;
@@ -12,7 +15,10 @@ mod helpers;
 mod parse;
 mod parse_ast;
 mod rsmt;
+mod simplification;
 mod sort;
+mod sort_inference;
+mod substitution;
 
 pub struct Variable {
     pub name: VariableSymbol,
@@ -20,7 +26,26 @@ pub struct Variable {
 
 pub enum Constant {
     Bool(bool),
-    Int(i64),
+    Int(#[serde(with = "crate::common::integer")] crate::common::integer::BigInt),
+    /// A bit-vector literal. Only the lowest `width` bits of `value` may be
+    /// set.
+    BitVector {
+        value: u128,
+        width: u32,
+    },
+    /// A rational number in lowest terms with a positive denominator.
+    Real {
+        #[serde(with = "crate::common::integer")]
+        numerator: crate::common::integer::BigInt,
+        #[serde(with = "crate::common::integer")]
+        denominator: crate::common::integer::BigInt,
+    },
+    /// A floating-point number given by its bits.
+    Float {
+        bits: u128,
+        exponent: u32,
+        significand: u32,
+    },
 }
 
 pub struct UnaryOperation {
@@ -31,6 +56,12 @@ pub struct UnaryOperation {
 pub enum UnaryOperationKind {
     Not,
     Minus,
+    /// Converts an integer to a real.
+    ToReal,
+    /// Rounds a real down to the nearest integer.
+    ToInt,
+    /// Whether a real is an integer.
+    IsInt,
 }
 
 pub struct BinaryOperation {
@@ -39,6 +70,9 @@ pub struct BinaryOperation {
     pub right: Box<Expression>,
 }
 
+/// Arithmetic operations and comparisons on bit-vectors are unsigned; the
+/// `S*` variants are their signed counterparts. Equality on arrays is
+/// extensional.
 pub enum BinaryOperationKind {
     EqCmp,
     NeCmp,
@@ -54,6 +88,256 @@ pub enum BinaryOperationKind {
     And,
     Or,
     Implies,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    /// Logical (unsigned) shift right.
+    LShr,
+    /// Arithmetic (signed) shift right.
+    AShr,
+    SDiv,
+    SRem,
+    SGtCmp,
+    SGeCmp,
+    SLtCmp,
+    SLeCmp,
+    /// Bit-vector concatenation; `left` provides the most significant bits.
+    Concat,
+}
+
+pub struct BitVectorConversion {
+    pub kind: BitVectorConversionKind,
+    pub arg: Box<Expression>,
+}
+
+pub enum BitVectorConversionKind {
+    /// The bits from `high` down to `low`, both inclusive.
+    Extract {
+        high: u32,
+        low: u32,
+    },
+    ZeroExtend {
+        bits: u32,
+    },
+    SignExtend {
+        bits: u32,
+    },
+    /// Converts an integer to a bit-vector of the given width, wrapping
+    /// around on overflow.
+    FromInt {
+        width: u32,
+    },
+    /// Converts a bit-vector to a non-negative integer.
+    ToInt,
+}
+
+/// An IEEE 754 floating-point operation. The arguments are given by `kind`.
+/// The arithmetic operators and comparisons on floats are also supported by
+/// `BinaryOperation`, which rounds to nearest, ties to even, as Rust does.
+pub struct FloatOperation {
+    pub kind: FloatOperationKind,
+    pub args: Vec<Expression>,
+}
+
+pub enum FloatOperationKind {
+    Add(crate::common::float::RoundingMode),
+    Sub(crate::common::float::RoundingMode),
+    Mul(crate::common::float::RoundingMode),
+    Div(crate::common::float::RoundingMode),
+    /// `fp_fma(rm, a, b, c)`: `a * b + c` with a single rounding.
+    Fma(crate::common::float::RoundingMode),
+    Sqrt(crate::common::float::RoundingMode),
+    /// Rounds to an integral float.
+    RoundToIntegral(crate::common::float::RoundingMode),
+    /// The IEEE remainder.
+    Rem,
+    Abs,
+    Min,
+    Max,
+    /// IEEE equality, under which NaN is not equal to itself and the two
+    /// zeros are equal.
+    Eq,
+    IsNormal,
+    IsSubnormal,
+    IsZero,
+    IsInfinite,
+    IsNaN,
+    IsNegative,
+    IsPositive,
+}
+
+impl FloatOperationKind {
+    /// The rounding mode of operations that round their result.
+    pub fn rounding_mode(&self) -> Option<crate::common::float::RoundingMode> {
+        match self {
+            FloatOperationKind::Add(rounding_mode)
+            | FloatOperationKind::Sub(rounding_mode)
+            | FloatOperationKind::Mul(rounding_mode)
+            | FloatOperationKind::Div(rounding_mode)
+            | FloatOperationKind::Fma(rounding_mode)
+            | FloatOperationKind::Sqrt(rounding_mode)
+            | FloatOperationKind::RoundToIntegral(rounding_mode) => Some(*rounding_mode),
+            _ => None,
+        }
+    }
+    /// Whether the operation is a predicate.
+    pub fn is_predicate(&self) -> bool {
+        std::matches!(
+            self,
+            FloatOperationKind::Eq
+                | FloatOperationKind::IsNormal
+                | FloatOperationKind::IsSubnormal
+                | FloatOperationKind::IsZero
+                | FloatOperationKind::IsInfinite
+                | FloatOperationKind::IsNaN
+                | FloatOperationKind::IsNegative
+                | FloatOperationKind::IsPositive
+        )
+    }
+}
+
+pub struct FloatConversion {
+    pub kind: FloatConversionKind,
+    pub arg: Box<Expression>,
+}
+
+pub enum FloatConversionKind {
+    /// Converts a float, a real, or a signed bit-vector to a float.
+    ToFloat {
+        rounding_mode: crate::common::float::RoundingMode,
+        exponent: u32,
+        significand: u32,
+    },
+    /// Converts an unsigned bit-vector to a float.
+    UnsignedToFloat {
+        rounding_mode: crate::common::float::RoundingMode,
+        exponent: u32,
+        significand: u32,
+    },
+    /// Reinterprets a bit-vector as a float.
+    FromBits { exponent: u32, significand: u32 },
+    /// Converts a finite float to a real.
+    ToReal,
+    /// Converts a float to a signed bit-vector. The value is unspecified if
+    /// the result does not fit.
+    ToSigned {
+        rounding_mode: crate::common::float::RoundingMode,
+        width: u32,
+    },
+    /// Converts a float to an unsigned bit-vector. The value is unspecified if
+    /// the result does not fit.
+    ToUnsigned {
+        rounding_mode: crate::common::float::RoundingMode,
+        width: u32,
+    },
+}
+
+pub struct ArraySelect {
+    pub array: Box<Expression>,
+    pub index: Box<Expression>,
+}
+
+/// The array `array` with the element at `index` replaced by `value`.
+pub struct ArrayStore {
+    pub array: Box<Expression>,
+    pub index: Box<Expression>,
+    pub value: Box<Expression>,
+}
+
+/// An array that maps every index of sort `index_sort` to `value`.
+pub struct ConstantArray {
+    pub index_sort: Sort,
+    pub value: Box<Expression>,
+}
+
+/// An operation on sequences. The arguments are given by `kind`.
+pub struct SeqOperation {
+    pub kind: SeqOperationKind,
+    pub args: Vec<Expression>,
+}
+
+pub enum SeqOperationKind {
+    /// `seq_unit(v)`: the sequence that contains only `v`.
+    Unit,
+    /// `seq_len(s)`.
+    Length,
+    /// `seq_index(s, i)`: the element of `s` at `i`. The value is unspecified
+    /// if `i` is out of bounds.
+    Index,
+    /// `seq_concat(s, t)`.
+    Concat,
+    /// `seq_sub(s, offset, length)`: the at most `length` elements of `s`
+    /// that start at `offset`.
+    Subsequence,
+    /// `seq_update(s, i, v)`: `s` with the element at `i` replaced by `v`, or
+    /// `s` if `i` is out of bounds.
+    Update,
+}
+
+/// The empty sequence of elements of sort `element_sort`.
+pub struct EmptySeq {
+    pub element_sort: Sort,
+}
+
+/// An operation on sets. The arguments are given by `kind`.
+pub struct SetOperation {
+    pub kind: SetOperationKind,
+    pub args: Vec<Expression>,
+}
+
+pub enum SetOperationKind {
+    /// `set_singleton(v)`: the set that contains only `v`.
+    Singleton,
+    /// `set_contains(s, v)`: whether `v` is an element of `s`.
+    Contains,
+    /// `set_union(s, t)`.
+    Union,
+    /// `set_intersection(s, t)`.
+    Intersection,
+    /// `set_subset(s, t)`: whether every element of `s` is in `t`.
+    Subset,
+}
+
+/// The empty set of elements of sort `element_sort`.
+pub struct EmptySet {
+    pub element_sort: Sort,
+}
+
+/// An application of the constructor `constructor` of the algebraic datatype
+/// `adt`.
+pub struct AdtConstructor {
+    pub adt: AdtNameSymbol,
+    pub constructor: AdtConstructorSymbol,
+    pub args: Vec<Expression>,
+}
+
+/// The field `field` of `base`. The value is unspecified if `base` was not
+/// built with the constructor that declares `field`.
+pub struct AdtFieldAccess {
+    pub base: Box<Expression>,
+    pub field: FieldNameSymbol,
+}
+
+/// Whether `base` was built with `constructor`.
+pub struct AdtTester {
+    pub base: Box<Expression>,
+    pub constructor: AdtConstructorSymbol,
+}
+
+/// A case analysis on the constructor of `base`, a value of the algebraic
+/// datatype `adt`. Every constructor must be covered by exactly one case.
+pub struct AdtMatch {
+    pub adt: AdtNameSymbol,
+    pub base: Box<Expression>,
+    pub cases: Vec<AdtMatchCase>,
+}
+
+/// A case that binds the fields of `constructor`, in order, to `binders`.
+pub struct AdtMatchCase {
+    pub constructor: AdtConstructorSymbol,
+    pub binders: Vec<VariableSymbol>,
+    pub body: Box<Expression>,
 }
 
 pub struct Conditional {
@@ -62,6 +346,14 @@ pub struct Conditional {
     pub else_expr: Box<Expression>,
 }
 
+/// `body` with `variable` bound to the value of `value`. The binding is not
+/// visible in `value`.
+pub struct Let {
+    pub variable: VariableSymbol,
+    pub value: Box<Expression>,
+    pub body: Box<Expression>,
+}
+
 pub struct Quantifier {
     pub kind: QuantifierKind,
     /// Bounded variables.
@@ -101,3 +393,11 @@ pub enum LabelPositivity {
     Positive,
     Negative,
 }
+
+/// An expression together with its position in the source of the frontend.
+/// If the expression is a part of an assertion that fails, the position is
+/// reported in the verification error.
+pub struct PositionedExpression {
+    pub position: crate::common::position::Position,
+    pub expression: Box<Expression>,
+}
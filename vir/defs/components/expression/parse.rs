@@ -7,6 +7,9 @@ vir_raw_block! { kw =>
     pub mod kw {
         syn::custom_keyword!(forall);
         syn::custom_keyword!(exists);
+        syn::custom_keyword!(const_array);
+        syn::custom_keyword!(seq_empty);
+        syn::custom_keyword!(set_empty);
     }
 }
 
@@ -30,17 +33,79 @@ vir_raw_block! { Variable =>
 vir_raw_block! { Constant =>
     impl syn::parse::Parse for Constant {
         fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-            Ok(Self {
-                literal: input.parse()?,
-            })
+            let literal = input.parse()?;
+            if let syn::Lit::Float(float) = &literal {
+                let is_float = float.suffix() == "f32" || float.suffix() == "f64";
+                let is_real = crate::common::real::parse_decimal(float.base10_digits()).is_some()
+                    && float.suffix().is_empty();
+                if !is_float && !is_real {
+                    return Err(syn::Error::new(float.span(), "invalid real literal"));
+                }
+            }
+            Ok(Self { literal })
         }
     }
     impl quote::ToTokens for Constant {
         fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
             let literal = &self.literal;
-            tokens.extend(quote::quote! {
-                #literal.into()
-            })
+            match literal {
+                // Bit-vector literals are written with a width suffix: `5bv32`.
+                syn::Lit::Int(int) if int.suffix().starts_with("bv") => {
+                    let value: u128 = int.base10_parse().unwrap();
+                    let width: u32 = int.suffix()["bv".len()..].parse().unwrap();
+                    tokens.extend(quote::quote! {
+                        Constant::BitVector { value: #value, width: #width }
+                    })
+                }
+                // Float literals are written with a Rust suffix: `1.5f32`.
+                syn::Lit::Float(float) if float.suffix() == "f32" => {
+                    let bits = u128::from(float.base10_parse::<f32>().unwrap().to_bits());
+                    let (exponent, significand) = crate::common::float::F32;
+                    tokens.extend(quote::quote! {
+                        Constant::Float {
+                            bits: #bits,
+                            exponent: #exponent,
+                            significand: #significand,
+                        }
+                    })
+                }
+                syn::Lit::Float(float) if float.suffix() == "f64" => {
+                    let bits = u128::from(float.base10_parse::<f64>().unwrap().to_bits());
+                    let (exponent, significand) = crate::common::float::F64;
+                    tokens.extend(quote::quote! {
+                        Constant::Float {
+                            bits: #bits,
+                            exponent: #exponent,
+                            significand: #significand,
+                        }
+                    })
+                }
+                // Real literals are written in decimal notation: `1.25`.
+                syn::Lit::Float(float) => {
+                    let (numerator, denominator) =
+                        crate::common::real::parse_decimal(float.base10_digits()).unwrap();
+                    let numerator = numerator.to_string();
+                    let denominator = denominator.to_string();
+                    tokens.extend(quote::quote! {
+                        Constant::Real {
+                            numerator: #numerator.parse().unwrap(),
+                            denominator: #denominator.parse().unwrap(),
+                        }
+                    })
+                }
+                // Integers that do not fit into an `i64` are parsed at runtime.
+                syn::Lit::Int(int) if int.base10_parse::<i64>().is_err() => {
+                    let digits = int.base10_digits();
+                    tokens.extend(quote::quote! {
+                        Constant::Int(
+                            #digits.parse::<svirpti_vir::common::integer::BigInt>().unwrap()
+                        )
+                    })
+                }
+                _ => tokens.extend(quote::quote! {
+                    #literal.into()
+                }),
+            }
         }
     }
 }
@@ -84,6 +149,9 @@ vir_raw_block! { UnaryOperationKind =>
             match self {
                 UnaryOperationKind::Not => tokens.extend(quote::quote! {UnaryOperationKind::Not}),
                 UnaryOperationKind::Minus => tokens.extend(quote::quote! {UnaryOperationKind::Minus}),
+                UnaryOperationKind::ToReal => tokens.extend(quote::quote! {UnaryOperationKind::ToReal}),
+                UnaryOperationKind::ToInt => tokens.extend(quote::quote! {UnaryOperationKind::ToInt}),
+                UnaryOperationKind::IsInt => tokens.extend(quote::quote! {UnaryOperationKind::IsInt}),
             }
         }
     }
@@ -113,12 +181,18 @@ vir_raw_block! { BinaryOperationKind =>
             } else if input.peek(syn::Token![!=]) {
                 input.parse::<syn::Token![!=]>()?;
                 Ok(BinaryOperationKind::NeCmp)
+            } else if input.peek(syn::Token![>>]) {
+                input.parse::<syn::Token![>>]>()?;
+                Ok(BinaryOperationKind::LShr)
             } else if input.peek(syn::Token![>=]) {
                 input.parse::<syn::Token![>=]>()?;
                 Ok(BinaryOperationKind::GeCmp)
             } else if input.peek(syn::Token![>]) {
                 input.parse::<syn::Token![>]>()?;
                 Ok(BinaryOperationKind::GtCmp)
+            } else if input.peek(syn::Token![<<]) {
+                input.parse::<syn::Token![<<]>()?;
+                Ok(BinaryOperationKind::Shl)
             } else if input.peek(syn::Token![<=]) {
                 input.parse::<syn::Token![<=]>()?;
                 Ok(BinaryOperationKind::LeCmp)
@@ -131,6 +205,15 @@ vir_raw_block! { BinaryOperationKind =>
             } else if input.peek(syn::Token![||]) {
                 input.parse::<syn::Token![||]>()?;
                 Ok(BinaryOperationKind::Or)
+            } else if input.peek(syn::Token![&]) {
+                input.parse::<syn::Token![&]>()?;
+                Ok(BinaryOperationKind::BitAnd)
+            } else if input.peek(syn::Token![|]) {
+                input.parse::<syn::Token![|]>()?;
+                Ok(BinaryOperationKind::BitOr)
+            } else if input.peek(syn::Token![^]) {
+                input.parse::<syn::Token![^]>()?;
+                Ok(BinaryOperationKind::BitXor)
             } else if input.peek(syn::Token![->]) {
                 input.parse::<syn::Token![->]>()?;
                 Ok(BinaryOperationKind::Implies)
@@ -169,11 +252,750 @@ vir_raw_block! { BinaryOperationKind =>
                 BinaryOperationKind::And => tokens.extend(quote::quote! {BinaryOperationKind::And}),
                 BinaryOperationKind::Or => tokens.extend(quote::quote! {BinaryOperationKind::Or}),
                 BinaryOperationKind::Implies => tokens.extend(quote::quote! {BinaryOperationKind::Implies}),
+                BinaryOperationKind::BitAnd => tokens.extend(quote::quote! {BinaryOperationKind::BitAnd}),
+                BinaryOperationKind::BitOr => tokens.extend(quote::quote! {BinaryOperationKind::BitOr}),
+                BinaryOperationKind::BitXor => tokens.extend(quote::quote! {BinaryOperationKind::BitXor}),
+                BinaryOperationKind::Shl => tokens.extend(quote::quote! {BinaryOperationKind::Shl}),
+                BinaryOperationKind::LShr => tokens.extend(quote::quote! {BinaryOperationKind::LShr}),
+                BinaryOperationKind::AShr => tokens.extend(quote::quote! {BinaryOperationKind::AShr}),
+                BinaryOperationKind::SDiv => tokens.extend(quote::quote! {BinaryOperationKind::SDiv}),
+                BinaryOperationKind::SRem => tokens.extend(quote::quote! {BinaryOperationKind::SRem}),
+                BinaryOperationKind::SGtCmp => tokens.extend(quote::quote! {BinaryOperationKind::SGtCmp}),
+                BinaryOperationKind::SGeCmp => tokens.extend(quote::quote! {BinaryOperationKind::SGeCmp}),
+                BinaryOperationKind::SLtCmp => tokens.extend(quote::quote! {BinaryOperationKind::SLtCmp}),
+                BinaryOperationKind::SLeCmp => tokens.extend(quote::quote! {BinaryOperationKind::SLeCmp}),
+                BinaryOperationKind::Concat => tokens.extend(quote::quote! {BinaryOperationKind::Concat}),
+            }
+        }
+    }
+}
+
+vir_raw_block! { BitVectorConversion =>
+    impl syn::parse::Parse for BitVectorConversion {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let name: syn::Ident = input.parse()?;
+            let content;
+            syn::parenthesized!(content in input);
+            let arg = content.parse()?;
+            let parse_index = || -> syn::Result<u32> {
+                content.parse::<syn::Token![,]>()?;
+                content.parse::<syn::LitInt>()?.base10_parse()
+            };
+            let kind = match name.to_string().as_str() {
+                "extract" => BitVectorConversionKind::Extract {
+                    high: parse_index()?,
+                    low: parse_index()?,
+                },
+                "zero_extend" => BitVectorConversionKind::ZeroExtend { bits: parse_index()? },
+                "sign_extend" => BitVectorConversionKind::SignExtend { bits: parse_index()? },
+                "int2bv" => BitVectorConversionKind::FromInt { width: parse_index()? },
+                "bv2int" => BitVectorConversionKind::ToInt,
+                _ => return Err(syn::Error::new(name.span(), "Unknown bit-vector conversion")),
+            };
+            if !content.is_empty() {
+                return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+            }
+            Ok(Self { kind, arg })
+        }
+    }
+    impl quote::ToTokens for BitVectorConversion {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let kind = &self.kind;
+            let arg = &self.arg;
+            tokens.extend(quote::quote! {
+                BitVectorConversion {
+                    kind: #kind, arg: Box::new(#arg)
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { BitVectorConversionKind =>
+    impl quote::ToTokens for BitVectorConversionKind {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            match self {
+                BitVectorConversionKind::Extract { high, low } => tokens.extend(
+                    quote::quote! {BitVectorConversionKind::Extract { high: #high, low: #low }}
+                ),
+                BitVectorConversionKind::ZeroExtend { bits } => tokens.extend(
+                    quote::quote! {BitVectorConversionKind::ZeroExtend { bits: #bits }}
+                ),
+                BitVectorConversionKind::SignExtend { bits } => tokens.extend(
+                    quote::quote! {BitVectorConversionKind::SignExtend { bits: #bits }}
+                ),
+                BitVectorConversionKind::FromInt { width } => tokens.extend(
+                    quote::quote! {BitVectorConversionKind::FromInt { width: #width }}
+                ),
+                BitVectorConversionKind::ToInt => tokens.extend(
+                    quote::quote! {BitVectorConversionKind::ToInt}
+                ),
+            }
+        }
+    }
+}
+
+vir_raw_block! { ArraySelect =>
+    impl quote::ToTokens for ArraySelect {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let array = &self.array;
+            let index = &self.index;
+            tokens.extend(quote::quote! {
+                ArraySelect {
+                    array: Box::new(#array),
+                    index: Box::new(#index),
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { ArrayStore =>
+    impl quote::ToTokens for ArrayStore {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let array = &self.array;
+            let index = &self.index;
+            let value = &self.value;
+            tokens.extend(quote::quote! {
+                ArrayStore {
+                    array: Box::new(#array),
+                    index: Box::new(#index),
+                    value: Box::new(#value),
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { ConstantArray =>
+    impl syn::parse::Parse for ConstantArray {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            input.parse::<kw::const_array>()?;
+            input.parse::<syn::Token![<]>()?;
+            let index_sort = input.parse()?;
+            input.parse::<syn::Token![>]>()?;
+            let content;
+            syn::parenthesized!(content in input);
+            let value = content.parse()?;
+            if !content.is_empty() {
+                return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+            }
+            Ok(Self { index_sort, value })
+        }
+    }
+    impl quote::ToTokens for ConstantArray {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let index_sort = &self.index_sort;
+            let value = &self.value;
+            tokens.extend(quote::quote! {
+                ConstantArray {
+                    index_sort: #index_sort,
+                    value: Box::new(#value),
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { FloatOperation =>
+    /// Parses a rounding mode such as `RNE` followed by a comma.
+    fn parse_rounding_mode(
+        input: syn::parse::ParseStream,
+    ) -> syn::Result<crate::common::float::RoundingMode> {
+        let name: syn::Ident = input.parse()?;
+        let rounding_mode = crate::common::float::RoundingMode::from_abbreviation(
+            &name.to_string(),
+        )
+        .ok_or_else(|| syn::Error::new(name.span(), "Unknown rounding mode"))?;
+        input.parse::<syn::Token![,]>()?;
+        Ok(rounding_mode)
+    }
+    fn rounding_mode_tokens(
+        rounding_mode: &crate::common::float::RoundingMode,
+    ) -> proc_macro2::TokenStream {
+        let variant = syn::Ident::new(
+            &format!("{:?}", rounding_mode),
+            proc_macro2::Span::call_site(),
+        );
+        quote::quote! { svirpti_vir::common::float::RoundingMode::#variant }
+    }
+    /// Parses the float format of a sort such as `F32`.
+    fn parse_float_format(input: syn::parse::ParseStream) -> syn::Result<(u32, u32)> {
+        let span = input.span();
+        match input.parse()? {
+            Sort::Float { exponent, significand } => Ok((exponent, significand)),
+            _ => Err(syn::Error::new(span, "Expected a floating-point sort")),
+        }
+    }
+    impl syn::parse::Parse for FloatOperation {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let name: syn::Ident = input.parse()?;
+            let content;
+            syn::parenthesized!(content in input);
+            let (kind, arity) = match name.to_string().as_str() {
+                "fp_add" => (FloatOperationKind::Add(parse_rounding_mode(&content)?), 2),
+                "fp_sub" => (FloatOperationKind::Sub(parse_rounding_mode(&content)?), 2),
+                "fp_mul" => (FloatOperationKind::Mul(parse_rounding_mode(&content)?), 2),
+                "fp_div" => (FloatOperationKind::Div(parse_rounding_mode(&content)?), 2),
+                "fp_fma" => (FloatOperationKind::Fma(parse_rounding_mode(&content)?), 3),
+                "fp_sqrt" => (FloatOperationKind::Sqrt(parse_rounding_mode(&content)?), 1),
+                "fp_round" => {
+                    (FloatOperationKind::RoundToIntegral(parse_rounding_mode(&content)?), 1)
+                }
+                "fp_rem" => (FloatOperationKind::Rem, 2),
+                "fp_abs" => (FloatOperationKind::Abs, 1),
+                "fp_min" => (FloatOperationKind::Min, 2),
+                "fp_max" => (FloatOperationKind::Max, 2),
+                "fp_eq" => (FloatOperationKind::Eq, 2),
+                "fp_is_normal" => (FloatOperationKind::IsNormal, 1),
+                "fp_is_subnormal" => (FloatOperationKind::IsSubnormal, 1),
+                "fp_is_zero" => (FloatOperationKind::IsZero, 1),
+                "fp_is_infinite" => (FloatOperationKind::IsInfinite, 1),
+                "fp_is_nan" => (FloatOperationKind::IsNaN, 1),
+                "fp_is_negative" => (FloatOperationKind::IsNegative, 1),
+                "fp_is_positive" => (FloatOperationKind::IsPositive, 1),
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "Unknown floating-point operation",
+                    ))
+                }
+            };
+            let punctuated: syn::punctuated::Punctuated<_, syn::Token![,]> =
+                content.parse_terminated(Expression::parse)?;
+            if punctuated.len() != arity {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Expected {} arguments", arity),
+                ));
+            }
+            Ok(Self { kind, args: punctuated.into_iter().collect() })
+        }
+    }
+    impl quote::ToTokens for FloatOperation {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let kind = &self.kind;
+            let mut arg_tokens = proc_macro2::TokenStream::new();
+            for arg in &self.args {
+                arg_tokens.extend(quote::quote! { #arg, });
+            }
+            tokens.extend(quote::quote! {
+                FloatOperation {
+                    kind: #kind,
+                    args: vec![#arg_tokens],
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { FloatOperationKind =>
+    impl quote::ToTokens for FloatOperationKind {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let (variant, rounding_mode) = match self {
+                FloatOperationKind::Add(rounding_mode) => ("Add", Some(rounding_mode)),
+                FloatOperationKind::Sub(rounding_mode) => ("Sub", Some(rounding_mode)),
+                FloatOperationKind::Mul(rounding_mode) => ("Mul", Some(rounding_mode)),
+                FloatOperationKind::Div(rounding_mode) => ("Div", Some(rounding_mode)),
+                FloatOperationKind::Fma(rounding_mode) => ("Fma", Some(rounding_mode)),
+                FloatOperationKind::Sqrt(rounding_mode) => ("Sqrt", Some(rounding_mode)),
+                FloatOperationKind::RoundToIntegral(rounding_mode) => {
+                    ("RoundToIntegral", Some(rounding_mode))
+                }
+                FloatOperationKind::Rem => ("Rem", None),
+                FloatOperationKind::Abs => ("Abs", None),
+                FloatOperationKind::Min => ("Min", None),
+                FloatOperationKind::Max => ("Max", None),
+                FloatOperationKind::Eq => ("Eq", None),
+                FloatOperationKind::IsNormal => ("IsNormal", None),
+                FloatOperationKind::IsSubnormal => ("IsSubnormal", None),
+                FloatOperationKind::IsZero => ("IsZero", None),
+                FloatOperationKind::IsInfinite => ("IsInfinite", None),
+                FloatOperationKind::IsNaN => ("IsNaN", None),
+                FloatOperationKind::IsNegative => ("IsNegative", None),
+                FloatOperationKind::IsPositive => ("IsPositive", None),
+            };
+            let variant = syn::Ident::new(variant, proc_macro2::Span::call_site());
+            match rounding_mode {
+                Some(rounding_mode) => {
+                    let rounding_mode = rounding_mode_tokens(rounding_mode);
+                    tokens.extend(quote::quote! {FloatOperationKind::#variant(#rounding_mode)})
+                }
+                None => tokens.extend(quote::quote! {FloatOperationKind::#variant}),
+            }
+        }
+    }
+}
+
+vir_raw_block! { FloatConversion =>
+    impl syn::parse::Parse for FloatConversion {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let name: syn::Ident = input.parse()?;
+            let content;
+            syn::parenthesized!(content in input);
+            let (kind, arg) = match name.to_string().as_str() {
+                "to_fp" | "to_fp_unsigned" => {
+                    let rounding_mode = parse_rounding_mode(&content)?;
+                    let arg = content.parse()?;
+                    content.parse::<syn::Token![,]>()?;
+                    let (exponent, significand) = parse_float_format(&content)?;
+                    let kind = if name == "to_fp" {
+                        FloatConversionKind::ToFloat { rounding_mode, exponent, significand }
+                    } else {
+                        FloatConversionKind::UnsignedToFloat {
+                            rounding_mode,
+                            exponent,
+                            significand,
+                        }
+                    };
+                    (kind, arg)
+                }
+                "fp_from_bits" => {
+                    let arg = content.parse()?;
+                    content.parse::<syn::Token![,]>()?;
+                    let (exponent, significand) = parse_float_format(&content)?;
+                    (FloatConversionKind::FromBits { exponent, significand }, arg)
+                }
+                "fp_to_real" => (FloatConversionKind::ToReal, content.parse()?),
+                "fp_to_sbv" | "fp_to_ubv" => {
+                    let rounding_mode = parse_rounding_mode(&content)?;
+                    let arg = content.parse()?;
+                    content.parse::<syn::Token![,]>()?;
+                    let width: syn::LitInt = content.parse()?;
+                    let width = width.base10_parse()?;
+                    let kind = if name == "fp_to_sbv" {
+                        FloatConversionKind::ToSigned { rounding_mode, width }
+                    } else {
+                        FloatConversionKind::ToUnsigned { rounding_mode, width }
+                    };
+                    (kind, arg)
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "Unknown floating-point conversion",
+                    ))
+                }
+            };
+            if !content.is_empty() {
+                return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+            }
+            Ok(Self { kind, arg: Box::new(arg) })
+        }
+    }
+    impl quote::ToTokens for FloatConversion {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let kind = &self.kind;
+            let arg = &self.arg;
+            tokens.extend(quote::quote! {
+                FloatConversion {
+                    kind: #kind,
+                    arg: Box::new(#arg),
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { FloatConversionKind =>
+    impl quote::ToTokens for FloatConversionKind {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            match self {
+                FloatConversionKind::ToFloat { rounding_mode, exponent, significand } => {
+                    let rounding_mode = rounding_mode_tokens(rounding_mode);
+                    tokens.extend(quote::quote! {
+                        FloatConversionKind::ToFloat {
+                            rounding_mode: #rounding_mode,
+                            exponent: #exponent,
+                            significand: #significand,
+                        }
+                    })
+                }
+                FloatConversionKind::UnsignedToFloat { rounding_mode, exponent, significand } => {
+                    let rounding_mode = rounding_mode_tokens(rounding_mode);
+                    tokens.extend(quote::quote! {
+                        FloatConversionKind::UnsignedToFloat {
+                            rounding_mode: #rounding_mode,
+                            exponent: #exponent,
+                            significand: #significand,
+                        }
+                    })
+                }
+                FloatConversionKind::FromBits { exponent, significand } => {
+                    tokens.extend(quote::quote! {
+                        FloatConversionKind::FromBits {
+                            exponent: #exponent,
+                            significand: #significand,
+                        }
+                    })
+                }
+                FloatConversionKind::ToReal => {
+                    tokens.extend(quote::quote! {FloatConversionKind::ToReal})
+                }
+                FloatConversionKind::ToSigned { rounding_mode, width } => {
+                    let rounding_mode = rounding_mode_tokens(rounding_mode);
+                    tokens.extend(quote::quote! {
+                        FloatConversionKind::ToSigned {
+                            rounding_mode: #rounding_mode,
+                            width: #width,
+                        }
+                    })
+                }
+                FloatConversionKind::ToUnsigned { rounding_mode, width } => {
+                    let rounding_mode = rounding_mode_tokens(rounding_mode);
+                    tokens.extend(quote::quote! {
+                        FloatConversionKind::ToUnsigned {
+                            rounding_mode: #rounding_mode,
+                            width: #width,
+                        }
+                    })
+                }
             }
         }
     }
 }
 
+vir_raw_block! { SeqOperation =>
+    impl syn::parse::Parse for SeqOperation {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let name: syn::Ident = input.parse()?;
+            let (kind, arity) = match name.to_string().as_str() {
+                "seq_unit" => (SeqOperationKind::Unit, 1),
+                "seq_len" => (SeqOperationKind::Length, 1),
+                "seq_index" => (SeqOperationKind::Index, 2),
+                "seq_concat" => (SeqOperationKind::Concat, 2),
+                "seq_sub" => (SeqOperationKind::Subsequence, 3),
+                "seq_update" => (SeqOperationKind::Update, 3),
+                _ => return Err(syn::Error::new(name.span(), "Unknown sequence operation")),
+            };
+            let content;
+            syn::parenthesized!(content in input);
+            let punctuated: syn::punctuated::Punctuated<_, syn::Token![,]> =
+                content.parse_terminated(Expression::parse)?;
+            if punctuated.len() != arity {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Expected {} arguments", arity),
+                ));
+            }
+            Ok(Self { kind, args: punctuated.into_iter().collect() })
+        }
+    }
+    impl quote::ToTokens for SeqOperation {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let kind = &self.kind;
+            let mut arg_tokens = proc_macro2::TokenStream::new();
+            for arg in &self.args {
+                arg_tokens.extend(quote::quote! { #arg, });
+            }
+            tokens.extend(quote::quote! {
+                SeqOperation {
+                    kind: #kind,
+                    args: vec![#arg_tokens],
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { SeqOperationKind =>
+    impl quote::ToTokens for SeqOperationKind {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            match self {
+                SeqOperationKind::Unit => tokens.extend(quote::quote! {SeqOperationKind::Unit}),
+                SeqOperationKind::Length => {
+                    tokens.extend(quote::quote! {SeqOperationKind::Length})
+                }
+                SeqOperationKind::Index => tokens.extend(quote::quote! {SeqOperationKind::Index}),
+                SeqOperationKind::Concat => {
+                    tokens.extend(quote::quote! {SeqOperationKind::Concat})
+                }
+                SeqOperationKind::Subsequence => {
+                    tokens.extend(quote::quote! {SeqOperationKind::Subsequence})
+                }
+                SeqOperationKind::Update => {
+                    tokens.extend(quote::quote! {SeqOperationKind::Update})
+                }
+            }
+        }
+    }
+}
+
+vir_raw_block! { EmptySeq =>
+    impl syn::parse::Parse for EmptySeq {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            input.parse::<kw::seq_empty>()?;
+            input.parse::<syn::Token![<]>()?;
+            let element_sort = input.parse()?;
+            input.parse::<syn::Token![>]>()?;
+            let content;
+            syn::parenthesized!(content in input);
+            if !content.is_empty() {
+                return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+            }
+            Ok(Self { element_sort })
+        }
+    }
+    impl quote::ToTokens for EmptySeq {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let element_sort = &self.element_sort;
+            tokens.extend(quote::quote! {
+                EmptySeq {
+                    element_sort: #element_sort,
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { SetOperation =>
+    impl syn::parse::Parse for SetOperation {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let name: syn::Ident = input.parse()?;
+            let (kind, arity) = match name.to_string().as_str() {
+                "set_singleton" => (SetOperationKind::Singleton, 1),
+                "set_contains" => (SetOperationKind::Contains, 2),
+                "set_union" => (SetOperationKind::Union, 2),
+                "set_intersection" => (SetOperationKind::Intersection, 2),
+                "set_subset" => (SetOperationKind::Subset, 2),
+                _ => return Err(syn::Error::new(name.span(), "Unknown set operation")),
+            };
+            let content;
+            syn::parenthesized!(content in input);
+            let punctuated: syn::punctuated::Punctuated<_, syn::Token![,]> =
+                content.parse_terminated(Expression::parse)?;
+            if punctuated.len() != arity {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Expected {} arguments", arity),
+                ));
+            }
+            Ok(Self { kind, args: punctuated.into_iter().collect() })
+        }
+    }
+    impl quote::ToTokens for SetOperation {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let kind = &self.kind;
+            let mut arg_tokens = proc_macro2::TokenStream::new();
+            for arg in &self.args {
+                arg_tokens.extend(quote::quote! { #arg, });
+            }
+            tokens.extend(quote::quote! {
+                SetOperation {
+                    kind: #kind,
+                    args: vec![#arg_tokens],
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { SetOperationKind =>
+    impl quote::ToTokens for SetOperationKind {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            match self {
+                SetOperationKind::Singleton => {
+                    tokens.extend(quote::quote! {SetOperationKind::Singleton})
+                }
+                SetOperationKind::Contains => {
+                    tokens.extend(quote::quote! {SetOperationKind::Contains})
+                }
+                SetOperationKind::Union => tokens.extend(quote::quote! {SetOperationKind::Union}),
+                SetOperationKind::Intersection => {
+                    tokens.extend(quote::quote! {SetOperationKind::Intersection})
+                }
+                SetOperationKind::Subset => {
+                    tokens.extend(quote::quote! {SetOperationKind::Subset})
+                }
+            }
+        }
+    }
+}
+
+vir_raw_block! { EmptySet =>
+    impl syn::parse::Parse for EmptySet {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            input.parse::<kw::set_empty>()?;
+            input.parse::<syn::Token![<]>()?;
+            let element_sort = input.parse()?;
+            input.parse::<syn::Token![>]>()?;
+            let content;
+            syn::parenthesized!(content in input);
+            if !content.is_empty() {
+                return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+            }
+            Ok(Self { element_sort })
+        }
+    }
+    impl quote::ToTokens for EmptySet {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let element_sort = &self.element_sort;
+            tokens.extend(quote::quote! {
+                EmptySet {
+                    element_sort: #element_sort,
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { AdtConstructor =>
+    impl syn::parse::Parse for AdtConstructor {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let adt = input.parse()?;
+            input.parse::<syn::Token![::]>()?;
+            let constructor = input.parse()?;
+            let args = if input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in input);
+                let punctuated: syn::punctuated::Punctuated<_, syn::Token![,]> =
+                    content.parse_terminated(Expression::parse)?;
+                punctuated.into_iter().collect()
+            } else {
+                Vec::new()
+            };
+            Ok(Self { adt, constructor, args })
+        }
+    }
+    impl quote::ToTokens for AdtConstructor {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let adt = self.adt.to_string();
+            let constructor = self.constructor.to_string();
+            let mut arg_tokens = proc_macro2::TokenStream::new();
+            for arg in &self.args {
+                arg_tokens.extend(quote::quote! { #arg, });
+            }
+            tokens.extend(quote::quote! {
+                AdtConstructor {
+                    adt: #adt.into(),
+                    constructor: #constructor.into(),
+                    args: vec![#arg_tokens],
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { AdtFieldAccess =>
+    impl quote::ToTokens for AdtFieldAccess {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let base = &self.base;
+            let field = self.field.to_string();
+            tokens.extend(quote::quote! {
+                AdtFieldAccess {
+                    base: Box::new(#base),
+                    field: #field.into(),
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { AdtTester =>
+    impl quote::ToTokens for AdtTester {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let base = &self.base;
+            let constructor = self.constructor.to_string();
+            tokens.extend(quote::quote! {
+                AdtTester {
+                    base: Box::new(#base),
+                    constructor: #constructor.into(),
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { AdtMatch =>
+    impl syn::parse::Parse for AdtMatch {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let match_token = input.parse::<syn::Token![match]>()?;
+            let base = input.parse()?;
+            let content;
+            syn::braced!(content in input);
+            let mut adt: Option<syn::Ident> = None;
+            let mut cases = Vec::new();
+            while !content.is_empty() {
+                let case_adt: syn::Ident = content.parse()?;
+                match &adt {
+                    Some(adt) if adt != &case_adt => {
+                        return Err(syn::Error::new(
+                            case_adt.span(),
+                            format!("expected a constructor of `{}`", adt),
+                        ));
+                    }
+                    _ => adt = Some(case_adt),
+                }
+                content.parse::<syn::Token![::]>()?;
+                let constructor = content.parse()?;
+                let binders = if content.peek(syn::token::Paren) {
+                    let binders;
+                    syn::parenthesized!(binders in content);
+                    let punctuated: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]> =
+                        binders.parse_terminated(syn::Ident::parse)?;
+                    punctuated.into_iter().collect()
+                } else {
+                    Vec::new()
+                };
+                content.parse::<syn::Token![=>]>()?;
+                let body = content.parse()?;
+                cases.push(AdtMatchCase {
+                    constructor,
+                    binders,
+                    body: Box::new(body),
+                });
+                if !content.is_empty() {
+                    content.parse::<syn::Token![,]>()?;
+                }
+            }
+            let adt = match adt {
+                Some(adt) => adt,
+                None => {
+                    return Err(syn::Error::new(
+                        match_token.span,
+                        "a match must have at least one case",
+                    ))
+                }
+            };
+            Ok(Self { adt, base, cases })
+        }
+    }
+    impl quote::ToTokens for AdtMatch {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let adt = self.adt.to_string();
+            let base = &self.base;
+            let mut case_tokens = proc_macro2::TokenStream::new();
+            for case in &self.cases {
+                case_tokens.extend(quote::quote! { #case, });
+            }
+            tokens.extend(quote::quote! {
+                AdtMatch {
+                    adt: #adt.into(),
+                    base: Box::new(#base),
+                    cases: vec![#case_tokens],
+                }
+            })
+        }
+    }
+}
+
+vir_raw_block! { AdtMatchCase =>
+    impl quote::ToTokens for AdtMatchCase {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let constructor = self.constructor.to_string();
+            let binders = self.binders.iter().map(|binder| binder.to_string());
+            let body = &self.body;
+            tokens.extend(quote::quote! {
+                AdtMatchCase {
+                    constructor: #constructor.into(),
+                    binders: vec![#(#binders.into()),*],
+                    body: Box::new(#body),
+                }
+            })
+        }
+    }
+}
+
 vir_raw_block! { Conditional =>
     impl syn::parse::Parse for Conditional {
         fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -215,6 +1037,38 @@ vir_raw_block! { Conditional =>
     }
 }
 
+vir_raw_block! { Let =>
+    impl syn::parse::Parse for Let {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            input.parse::<syn::Token![let]>()?;
+            let variable = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let value = input.parse()?;
+            input.parse::<syn::Token![in]>()?;
+            let body = input.parse()?;
+            Ok(Self {
+                variable,
+                value,
+                body,
+            })
+        }
+    }
+    impl quote::ToTokens for Let {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let variable = self.variable.to_string();
+            let value = &self.value;
+            let body = &self.body;
+            tokens.extend(quote::quote! {
+                Let {
+                    variable: #variable.into(),
+                    value: Box::new(#value),
+                    body: Box::new(#body),
+                }
+            })
+        }
+    }
+}
+
 vir_raw_block! { Quantifier =>
     impl syn::parse::Parse for Quantifier {
         fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -361,19 +1215,15 @@ vir_raw_block! { FunctionApplication =>
     }
     impl quote::ToTokens for FunctionApplication {
         fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-            let function = &self.function;
+            let function = self.function.to_string();
             let mut arg_tokens = proc_macro2::TokenStream::new();
             for arg in &self.args {
                 arg_tokens.extend(quote::quote! { #arg, });
             }
             tokens.extend(quote::quote! {
-                {
-                    let function = #function;
-                    let args = vec![#arg_tokens];
-                    Quantifier {
-                        function,
-                        args,
-                    }
+                FunctionApplication {
+                    function: #function.into(),
+                    args: vec![#arg_tokens],
                 }
             });
         }
@@ -387,6 +1237,10 @@ vir_raw_block! { Expression =>
         Implies,
         And,
         Compare,
+        BitOr,
+        BitXor,
+        BitAnd,
+        Shift,
         Add,
         Mul,
     }
@@ -407,12 +1261,205 @@ vir_raw_block! { Expression =>
                 BinaryOperationKind::And => Precedence::And,
                 BinaryOperationKind::Or => Precedence::And,
                 BinaryOperationKind::Implies => Precedence::Implies,
+                BinaryOperationKind::BitAnd => Precedence::BitAnd,
+                BinaryOperationKind::BitOr => Precedence::BitOr,
+                BinaryOperationKind::BitXor => Precedence::BitXor,
+                BinaryOperationKind::Shl => Precedence::Shift,
+                BinaryOperationKind::LShr => Precedence::Shift,
+                BinaryOperationKind::AShr => Precedence::Shift,
+                BinaryOperationKind::SDiv => Precedence::Mul,
+                BinaryOperationKind::SRem => Precedence::Mul,
+                BinaryOperationKind::SGtCmp => Precedence::Compare,
+                BinaryOperationKind::SGeCmp => Precedence::Compare,
+                BinaryOperationKind::SLtCmp => Precedence::Compare,
+                BinaryOperationKind::SLeCmp => Precedence::Compare,
+                BinaryOperationKind::Concat => Precedence::Add,
+            }
+        }
+    }
+    /// Parses the bit-vector operations that have no operator syntax and are
+    /// written as function applications instead, for example `slt(a, b)` or
+    /// `extract(a, 7, 0)`.
+    fn parse_bit_vector_function(input: syn::parse::ParseStream) -> syn::Result<Option<Expression>> {
+        let name = match input.fork().parse::<syn::Ident>() {
+            Ok(name) => name.to_string(),
+            Err(_) => return Ok(None),
+        };
+        let kind = match name.as_str() {
+            "ashr" => BinaryOperationKind::AShr,
+            "sdiv" => BinaryOperationKind::SDiv,
+            "srem" => BinaryOperationKind::SRem,
+            "sgt" => BinaryOperationKind::SGtCmp,
+            "sge" => BinaryOperationKind::SGeCmp,
+            "slt" => BinaryOperationKind::SLtCmp,
+            "sle" => BinaryOperationKind::SLeCmp,
+            "concat" => BinaryOperationKind::Concat,
+            "extract" | "zero_extend" | "sign_extend" | "int2bv" | "bv2int" => {
+                return Ok(Some(Expression::BitVectorConversion(input.parse()?)));
+            }
+            _ => return Ok(None),
+        };
+        input.parse::<syn::Ident>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let left = content.parse()?;
+        content.parse::<syn::Token![,]>()?;
+        let right = content.parse()?;
+        if !content.is_empty() {
+            return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+        }
+        Ok(Some(Expression::BinaryOperation(BinaryOperation { kind, left, right })))
+    }
+    /// Parses the conversions between integers and reals, which are written
+    /// as function applications: `to_real(i)`, `to_int(r)` and `is_int(r)`.
+    fn parse_real_function(input: syn::parse::ParseStream) -> syn::Result<Option<Expression>> {
+        let name = match input.fork().parse::<syn::Ident>() {
+            Ok(name) => name.to_string(),
+            Err(_) => return Ok(None),
+        };
+        let kind = match name.as_str() {
+            "to_real" => UnaryOperationKind::ToReal,
+            "to_int" => UnaryOperationKind::ToInt,
+            "is_int" => UnaryOperationKind::IsInt,
+            _ => return Ok(None),
+        };
+        input.parse::<syn::Ident>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let arg = content.parse()?;
+        if !content.is_empty() {
+            return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+        }
+        Ok(Some(Expression::UnaryOperation(UnaryOperation { kind, arg })))
+    }
+    /// Parses array accesses `a[i]`, array updates `a[i := v]`, and field
+    /// accesses `a.f` that follow `base`.
+    fn parse_trailers(
+        input: syn::parse::ParseStream,
+        mut base: Expression,
+    ) -> syn::Result<Expression> {
+        loop {
+            if input.peek(syn::Token![.]) {
+                input.parse::<syn::Token![.]>()?;
+                base = Expression::AdtFieldAccess(AdtFieldAccess {
+                    base: Box::new(base),
+                    field: input.parse()?,
+                });
+            } else if input.peek(syn::token::Bracket) {
+                base = parse_array_access(input, base)?;
+            } else {
+                break;
             }
         }
+        Ok(base)
+    }
+    fn parse_array_access(
+        input: syn::parse::ParseStream,
+        base: Expression,
+    ) -> syn::Result<Expression> {
+        let content;
+        syn::bracketed!(content in input);
+        let index = content.parse()?;
+        let access = if content.peek(syn::Token![:]) {
+            content.parse::<syn::Token![:]>()?;
+            content.parse::<syn::Token![=]>()?;
+            let value = content.parse()?;
+            Expression::ArrayStore(ArrayStore {
+                array: Box::new(base),
+                index,
+                value,
+            })
+        } else {
+            Expression::ArraySelect(ArraySelect {
+                array: Box::new(base),
+                index,
+            })
+        };
+        if !content.is_empty() {
+            return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+        }
+        Ok(access)
+    }
+    /// Parses a tester `is_Ctor(base)`. Only the functions whose name
+    /// continues with an uppercase letter after `is_` are testers.
+    fn parse_adt_tester(input: syn::parse::ParseStream) -> syn::Result<Option<Expression>> {
+        let name = match input.fork().parse::<syn::Ident>() {
+            Ok(name) => name,
+            Err(_) => return Ok(None),
+        };
+        let constructor = match name.to_string().strip_prefix("is_") {
+            Some(constructor) if constructor.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                syn::Ident::new(constructor, name.span())
+            }
+            _ => return Ok(None),
+        };
+        input.parse::<syn::Ident>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let base = content.parse()?;
+        if !content.is_empty() {
+            return Err(syn::Error::new(content.span(), "Unexpected tokens"));
+        }
+        Ok(Some(Expression::AdtTester(AdtTester { base, constructor })))
+    }
+    /// Parses the floating-point operations and conversions, which are written
+    /// as function applications.
+    fn parse_float_function(input: syn::parse::ParseStream) -> syn::Result<Option<Expression>> {
+        let name = match input.fork().parse::<syn::Ident>() {
+            Ok(name) => name.to_string(),
+            Err(_) => return Ok(None),
+        };
+        match name.as_str() {
+            "to_fp" | "to_fp_unsigned" | "fp_from_bits" | "fp_to_real" | "fp_to_sbv"
+            | "fp_to_ubv" => Ok(Some(Expression::FloatConversion(input.parse()?))),
+            _ if name.starts_with("fp_") => Ok(Some(Expression::FloatOperation(input.parse()?))),
+            _ => Ok(None),
+        }
+    }
+    /// Parses the sequence and set operations, which are written as function
+    /// applications with the prefixes `seq_` and `set_`.
+    fn parse_collection_function(
+        input: syn::parse::ParseStream,
+    ) -> syn::Result<Option<Expression>> {
+        let name = match input.fork().parse::<syn::Ident>() {
+            Ok(name) => name.to_string(),
+            Err(_) => return Ok(None),
+        };
+        if name.starts_with("seq_") {
+            Ok(Some(Expression::SeqOperation(input.parse()?)))
+        } else if name.starts_with("set_") {
+            Ok(Some(Expression::SetOperation(input.parse()?)))
+        } else {
+            Ok(None)
+        }
     }
     fn trailer_expression(input: syn::parse::ParseStream) -> syn::Result<Expression> {
-        if input.peek2(syn::token::Paren) {
-            parse_function_like(input)
+        let expression = primary_expression(input)?;
+        parse_trailers(input, expression)
+    }
+    fn primary_expression(input: syn::parse::ParseStream) -> syn::Result<Expression> {
+        if input.peek(kw::const_array) {
+            Ok(Expression::ConstantArray(input.parse()?))
+        } else if input.peek(kw::seq_empty) {
+            Ok(Expression::EmptySeq(input.parse()?))
+        } else if input.peek(kw::set_empty) {
+            Ok(Expression::EmptySet(input.parse()?))
+        } else if input.peek(syn::Ident) && input.peek2(syn::Token![::]) {
+            Ok(Expression::AdtConstructor(input.parse()?))
+        } else if input.peek2(syn::token::Paren) {
+            if let Some(expression) = parse_adt_tester(input)? {
+                Ok(expression)
+            } else if let Some(expression) = parse_bit_vector_function(input)? {
+                Ok(expression)
+            } else if let Some(expression) = parse_real_function(input)? {
+                Ok(expression)
+            } else if let Some(expression) = parse_float_function(input)? {
+                Ok(expression)
+            } else if let Some(expression) = parse_collection_function(input)? {
+                Ok(expression)
+            } else {
+                parse_function_like(input)
+            }
         } else if input.peek(syn::Lit) {
             // Must be a literal.
             Ok(Expression::Constant(input.parse()?))
@@ -430,6 +1477,10 @@ vir_raw_block! { Expression =>
     fn unary_expression(input: syn::parse::ParseStream) -> syn::Result<Expression> {
         if input.peek(syn::Token![if]) {
             Ok(Expression::Conditional(input.parse()?))
+        } else if input.peek(syn::Token![match]) {
+            Ok(Expression::AdtMatch(input.parse()?))
+        } else if input.peek(syn::Token![let]) {
+            Ok(Expression::Let(input.parse()?))
         } else if input.peek(syn::Token![!]) || input.peek(syn::Token![-]) {
             Ok(Expression::UnaryOperation(UnaryOperation {
                 kind: input.parse()?,
@@ -17,6 +17,9 @@ pub struct UnaryOperation {
 pub enum UnaryOperationKind {
     Not,
     Minus,
+    ToReal,
+    ToInt,
+    IsInt,
 }
 
 pub struct BinaryOperation {
@@ -25,6 +28,9 @@ pub struct BinaryOperation {
     pub right: Box<Expression>,
 }
 
+/// Arithmetic operations and comparisons on bit-vectors are unsigned; the
+/// `S*` variants are their signed counterparts. Equality on arrays is
+/// extensional.
 pub enum BinaryOperationKind {
     EqCmp,
     NeCmp,
@@ -40,6 +46,196 @@ pub enum BinaryOperationKind {
     And,
     Or,
     Implies,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    /// Logical (unsigned) shift right.
+    LShr,
+    /// Arithmetic (signed) shift right.
+    AShr,
+    SDiv,
+    SRem,
+    SGtCmp,
+    SGeCmp,
+    SLtCmp,
+    SLeCmp,
+    /// Bit-vector concatenation; `left` provides the most significant bits.
+    Concat,
+}
+
+pub struct BitVectorConversion {
+    pub kind: BitVectorConversionKind,
+    pub arg: Box<Expression>,
+}
+
+pub enum BitVectorConversionKind {
+    /// The bits from `high` down to `low`, both inclusive.
+    Extract {
+        high: u32,
+        low: u32,
+    },
+    ZeroExtend {
+        bits: u32,
+    },
+    SignExtend {
+        bits: u32,
+    },
+    /// Converts an integer to a bit-vector of the given width, wrapping
+    /// around on overflow.
+    FromInt {
+        width: u32,
+    },
+    /// Converts a bit-vector to a non-negative integer.
+    ToInt,
+}
+
+pub struct ArraySelect {
+    pub array: Box<Expression>,
+    pub index: Box<Expression>,
+}
+
+/// The array `array` with the element at `index` replaced by `value`.
+pub struct ArrayStore {
+    pub array: Box<Expression>,
+    pub index: Box<Expression>,
+    pub value: Box<Expression>,
+}
+
+/// An array that maps every index of sort `index_sort` to `value`.
+pub struct ConstantArray {
+    pub index_sort: Sort,
+    pub value: Box<Expression>,
+}
+
+/// Written as a function application, for example `fp_add(RNE, x, y)`.
+pub struct FloatOperation {
+    pub kind: FloatOperationKind,
+    pub args: Vec<Expression>,
+}
+
+pub enum FloatOperationKind {
+    Add(crate::common::float::RoundingMode),
+    Sub(crate::common::float::RoundingMode),
+    Mul(crate::common::float::RoundingMode),
+    Div(crate::common::float::RoundingMode),
+    Fma(crate::common::float::RoundingMode),
+    Sqrt(crate::common::float::RoundingMode),
+    RoundToIntegral(crate::common::float::RoundingMode),
+    Rem,
+    Abs,
+    Min,
+    Max,
+    Eq,
+    IsNormal,
+    IsSubnormal,
+    IsZero,
+    IsInfinite,
+    IsNaN,
+    IsNegative,
+    IsPositive,
+}
+
+/// Written as `to_fp(RNE, x, F32)`, `to_fp_unsigned(RNE, x, F32)`,
+/// `fp_from_bits(x, F32)`, `fp_to_real(x)`, `fp_to_sbv(RNE, x, 32)`, or
+/// `fp_to_ubv(RNE, x, 32)`.
+pub struct FloatConversion {
+    pub kind: FloatConversionKind,
+    pub arg: Box<Expression>,
+}
+
+pub enum FloatConversionKind {
+    ToFloat {
+        rounding_mode: crate::common::float::RoundingMode,
+        exponent: u32,
+        significand: u32,
+    },
+    UnsignedToFloat {
+        rounding_mode: crate::common::float::RoundingMode,
+        exponent: u32,
+        significand: u32,
+    },
+    FromBits {
+        exponent: u32,
+        significand: u32,
+    },
+    ToReal,
+    ToSigned {
+        rounding_mode: crate::common::float::RoundingMode,
+        width: u32,
+    },
+    ToUnsigned {
+        rounding_mode: crate::common::float::RoundingMode,
+        width: u32,
+    },
+}
+
+/// Written as a function application, for example `seq_len(s)`.
+pub struct SeqOperation {
+    pub kind: SeqOperationKind,
+    pub args: Vec<Expression>,
+}
+
+pub enum SeqOperationKind {
+    Unit,
+    Length,
+    Index,
+    Concat,
+    Subsequence,
+    Update,
+}
+
+/// Written as `seq_empty<Element>()`.
+pub struct EmptySeq {
+    pub element_sort: Sort,
+}
+
+/// Written as a function application, for example `set_union(s, t)`.
+pub struct SetOperation {
+    pub kind: SetOperationKind,
+    pub args: Vec<Expression>,
+}
+
+pub enum SetOperationKind {
+    Singleton,
+    Contains,
+    Union,
+    Intersection,
+    Subset,
+}
+
+/// Written as `set_empty<Element>()`.
+pub struct EmptySet {
+    pub element_sort: Sort,
+}
+
+pub struct AdtConstructor {
+    pub adt: syn::Ident,
+    pub constructor: syn::Ident,
+    pub args: Vec<Expression>,
+}
+
+pub struct AdtFieldAccess {
+    pub base: Box<Expression>,
+    pub field: syn::Ident,
+}
+
+/// Written as `is_Ctor(base)`.
+pub struct AdtTester {
+    pub base: Box<Expression>,
+    pub constructor: syn::Ident,
+}
+
+pub struct AdtMatch {
+    pub adt: syn::Ident,
+    pub base: Box<Expression>,
+    pub cases: Vec<AdtMatchCase>,
+}
+
+pub struct AdtMatchCase {
+    pub constructor: syn::Ident,
+    pub binders: Vec<syn::Ident>,
+    pub body: Box<Expression>,
 }
 
 pub struct Conditional {
@@ -48,6 +244,13 @@ pub struct Conditional {
     pub else_expr: Box<Expression>,
 }
 
+/// Written as `let x = value in body`.
+pub struct Let {
+    pub variable: syn::Ident,
+    pub value: Box<Expression>,
+    pub body: Box<Expression>,
+}
+
 pub struct Quantifier {
     pub kind: QuantifierKind,
     /// Bounded variables.
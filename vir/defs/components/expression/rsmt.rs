@@ -26,8 +26,24 @@ vir_raw_block! { Constant =>
         ) -> ::rsmt2::SmtRes<()> {
             match self {
                 Constant::Bool(true) => write!(writer, "true")?,
-                Constant::Bool(false) => write!(writer, "true")?,
+                Constant::Bool(false) => write!(writer, "false")?,
                 Constant::Int(value) => write!(writer, "{}", value)?,
+                Constant::BitVector { value, width } => {
+                    write!(writer, "(_ bv{} {})", value, width)?
+                }
+                Constant::Float { bits, exponent, significand } => {
+                    crate::common::float::write_smt(writer, *bits, *exponent, *significand)?
+                }
+                Constant::Real { numerator, denominator } => {
+                    // Negative rationals are written as `(- 1.5)`.
+                    let magnitude = numerator.magnitude();
+                    let (open, close) = if *numerator < 0.into() { ("(- ", ")") } else { ("", "") };
+                    if *denominator == 1.into() {
+                        write!(writer, "{}{}.0{}", open, magnitude, close)?
+                    } else {
+                        write!(writer, "{}(/ {}.0 {}.0){}", open, magnitude, denominator, close)?
+                    }
+                }
             }
             Ok(())
         }
@@ -40,13 +56,17 @@ vir_raw_block! { UnaryOperation =>
             writer: &mut Writer,
             context: &'a C
         ) -> ::rsmt2::SmtRes<()> {
+            let sort = self.arg.sort(context);
+            let bit_vector = sort.is_bit_vector();
             match self.kind {
-                UnaryOperationKind::Not => {
-                    write!(writer, "(not ")?;
-                }
-                UnaryOperationKind::Minus => {
-                    write!(writer, "(- ")?;
-                }
+                UnaryOperationKind::Not if bit_vector => write!(writer, "(bvnot ")?,
+                UnaryOperationKind::Not => write!(writer, "(not ")?,
+                UnaryOperationKind::Minus if bit_vector => write!(writer, "(bvneg ")?,
+                UnaryOperationKind::Minus if sort.is_float() => write!(writer, "(fp.neg ")?,
+                UnaryOperationKind::Minus => write!(writer, "(- ")?,
+                UnaryOperationKind::ToReal => write!(writer, "(to_real ")?,
+                UnaryOperationKind::ToInt => write!(writer, "(to_int ")?,
+                UnaryOperationKind::IsInt => write!(writer, "(is_int ")?,
             }
             self.arg.expr_to_smt2(writer, context)?;
             write!(writer, " )")?;
@@ -62,30 +82,64 @@ vir_raw_block! { BinaryOperation =>
             context: &'a C
         ) -> ::rsmt2::SmtRes<()> {
             write!(writer, "(")?;
-            match self.kind {
-                BinaryOperationKind::EqCmp => write!(writer, "=")?,
-                BinaryOperationKind::NeCmp => {
-                    write!(writer, "not (= ")?;
-                }
-                BinaryOperationKind::GtCmp => write!(writer, ">")?,
-                BinaryOperationKind::GeCmp => write!(writer, ">=")?,
-                BinaryOperationKind::LtCmp => write!(writer, "<")?,
-                BinaryOperationKind::LeCmp => write!(writer, "<=")?,
-                BinaryOperationKind::Add => write!(writer, "+")?,
-                BinaryOperationKind::Sub => write!(writer, "-")?,
-                BinaryOperationKind::Mul => write!(writer, "*")?,
-                BinaryOperationKind::Div => {
-                    if self.left.sort(context).is_integer() {
-                        write!(writer, "div")?
-                    } else {
-                        write!(writer, "/")?
+            let operator = match self.kind {
+                BinaryOperationKind::EqCmp => "=",
+                BinaryOperationKind::NeCmp => "not (=",
+                BinaryOperationKind::And => "and",
+                BinaryOperationKind::Or => "or",
+                BinaryOperationKind::Implies => "=>",
+                BinaryOperationKind::BitAnd => "bvand",
+                BinaryOperationKind::BitOr => "bvor",
+                BinaryOperationKind::BitXor => "bvxor",
+                BinaryOperationKind::Shl => "bvshl",
+                BinaryOperationKind::LShr => "bvlshr",
+                BinaryOperationKind::AShr => "bvashr",
+                BinaryOperationKind::SDiv => "bvsdiv",
+                BinaryOperationKind::SRem => "bvsrem",
+                BinaryOperationKind::SGtCmp => "bvsgt",
+                BinaryOperationKind::SGeCmp => "bvsge",
+                BinaryOperationKind::SLtCmp => "bvslt",
+                BinaryOperationKind::SLeCmp => "bvsle",
+                BinaryOperationKind::Concat => "concat",
+                kind => {
+                    // Arithmetic operations and comparisons are overloaded
+                    // on the sort of their operands. Float arithmetic rounds
+                    // to nearest, ties to even.
+                    let sort = self.left.sort(context);
+                    match kind {
+                        BinaryOperationKind::GtCmp if sort.is_float() => "fp.gt",
+                        BinaryOperationKind::GeCmp if sort.is_float() => "fp.geq",
+                        BinaryOperationKind::LtCmp if sort.is_float() => "fp.lt",
+                        BinaryOperationKind::LeCmp if sort.is_float() => "fp.leq",
+                        BinaryOperationKind::Add if sort.is_float() => "fp.add RNE",
+                        BinaryOperationKind::Sub if sort.is_float() => "fp.sub RNE",
+                        BinaryOperationKind::Mul if sort.is_float() => "fp.mul RNE",
+                        BinaryOperationKind::Div if sort.is_float() => "fp.div RNE",
+                        BinaryOperationKind::Mod if sort.is_float() => "fp.rem",
+                        BinaryOperationKind::GtCmp if sort.is_bit_vector() => "bvugt",
+                        BinaryOperationKind::GtCmp => ">",
+                        BinaryOperationKind::GeCmp if sort.is_bit_vector() => "bvuge",
+                        BinaryOperationKind::GeCmp => ">=",
+                        BinaryOperationKind::LtCmp if sort.is_bit_vector() => "bvult",
+                        BinaryOperationKind::LtCmp => "<",
+                        BinaryOperationKind::LeCmp if sort.is_bit_vector() => "bvule",
+                        BinaryOperationKind::LeCmp => "<=",
+                        BinaryOperationKind::Add if sort.is_bit_vector() => "bvadd",
+                        BinaryOperationKind::Add => "+",
+                        BinaryOperationKind::Sub if sort.is_bit_vector() => "bvsub",
+                        BinaryOperationKind::Sub => "-",
+                        BinaryOperationKind::Mul if sort.is_bit_vector() => "bvmul",
+                        BinaryOperationKind::Mul => "*",
+                        BinaryOperationKind::Div if sort.is_bit_vector() => "bvudiv",
+                        BinaryOperationKind::Div if sort.is_real() => "/",
+                        BinaryOperationKind::Div => "div",
+                        BinaryOperationKind::Mod if sort.is_bit_vector() => "bvurem",
+                        BinaryOperationKind::Mod => "mod",
+                        _ => unreachable!(),
                     }
                 }
-                BinaryOperationKind::Mod => write!(writer, "mod")?,
-                BinaryOperationKind::And => write!(writer, "and")?,
-                BinaryOperationKind::Or => write!(writer, "or")?,
-                BinaryOperationKind::Implies => write!(writer, "=>")?,
-            }
+            };
+            write!(writer, "{}", operator)?;
             write!(writer, " ")?;
             self.left.expr_to_smt2(writer, context)?;
             write!(writer, " ")?;
@@ -98,6 +152,363 @@ vir_raw_block! { BinaryOperation =>
         }
     }
 }
+vir_raw_block! { BitVectorConversion =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for BitVectorConversion {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            match self.kind {
+                BitVectorConversionKind::Extract { high, low } => {
+                    write!(writer, "((_ extract {} {}) ", high, low)?
+                }
+                BitVectorConversionKind::ZeroExtend { bits } => {
+                    write!(writer, "((_ zero_extend {}) ", bits)?
+                }
+                BitVectorConversionKind::SignExtend { bits } => {
+                    write!(writer, "((_ sign_extend {}) ", bits)?
+                }
+                BitVectorConversionKind::FromInt { width } => {
+                    write!(writer, "((_ int2bv {}) ", width)?
+                }
+                BitVectorConversionKind::ToInt => write!(writer, "(bv2nat ")?,
+            }
+            self.arg.expr_to_smt2(writer, context)?;
+            write!(writer, " )")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { FloatOperation =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for FloatOperation {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            let operator = match self.kind {
+                FloatOperationKind::Add(_) => "fp.add",
+                FloatOperationKind::Sub(_) => "fp.sub",
+                FloatOperationKind::Mul(_) => "fp.mul",
+                FloatOperationKind::Div(_) => "fp.div",
+                FloatOperationKind::Fma(_) => "fp.fma",
+                FloatOperationKind::Sqrt(_) => "fp.sqrt",
+                FloatOperationKind::RoundToIntegral(_) => "fp.roundToIntegral",
+                FloatOperationKind::Rem => "fp.rem",
+                FloatOperationKind::Abs => "fp.abs",
+                FloatOperationKind::Min => "fp.min",
+                FloatOperationKind::Max => "fp.max",
+                FloatOperationKind::Eq => "fp.eq",
+                FloatOperationKind::IsNormal => "fp.isNormal",
+                FloatOperationKind::IsSubnormal => "fp.isSubnormal",
+                FloatOperationKind::IsZero => "fp.isZero",
+                FloatOperationKind::IsInfinite => "fp.isInfinite",
+                FloatOperationKind::IsNaN => "fp.isNaN",
+                FloatOperationKind::IsNegative => "fp.isNegative",
+                FloatOperationKind::IsPositive => "fp.isPositive",
+            };
+            write!(writer, "({}", operator)?;
+            if let Some(rounding_mode) = self.kind.rounding_mode() {
+                write!(writer, " {}", rounding_mode)?;
+            }
+            for arg in &self.args {
+                write!(writer, " ")?;
+                arg.expr_to_smt2(writer, context)?;
+            }
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { FloatConversion =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for FloatConversion {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            match self.kind {
+                FloatConversionKind::ToFloat { rounding_mode, exponent, significand } => {
+                    write!(writer, "((_ to_fp {} {}) {} ", exponent, significand, rounding_mode)?
+                }
+                FloatConversionKind::UnsignedToFloat { rounding_mode, exponent, significand } => {
+                    write!(
+                        writer,
+                        "((_ to_fp_unsigned {} {}) {} ",
+                        exponent, significand, rounding_mode
+                    )?
+                }
+                FloatConversionKind::FromBits { exponent, significand } => {
+                    write!(writer, "((_ to_fp {} {}) ", exponent, significand)?
+                }
+                FloatConversionKind::ToReal => write!(writer, "(fp.to_real ")?,
+                FloatConversionKind::ToSigned { rounding_mode, width } => {
+                    write!(writer, "((_ fp.to_sbv {}) {} ", width, rounding_mode)?
+                }
+                FloatConversionKind::ToUnsigned { rounding_mode, width } => {
+                    write!(writer, "((_ fp.to_ubv {}) {} ", width, rounding_mode)?
+                }
+            }
+            self.arg.expr_to_smt2(writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { ArraySelect =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for ArraySelect {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(select ")?;
+            self.array.expr_to_smt2(writer, context)?;
+            write!(writer, " ")?;
+            self.index.expr_to_smt2(writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { ArrayStore =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for ArrayStore {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(store ")?;
+            self.array.expr_to_smt2(writer, context)?;
+            write!(writer, " ")?;
+            self.index.expr_to_smt2(writer, context)?;
+            write!(writer, " ")?;
+            self.value.expr_to_smt2(writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { ConstantArray =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for ConstantArray {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "((as const ")?;
+            ::rsmt2::print::Sort2Smt::sort_to_smt2(&self.sort(context), writer, context)?;
+            write!(writer, ") ")?;
+            self.value.expr_to_smt2(writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { SeqOperation =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for SeqOperation {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            let function = match self.kind {
+                SeqOperationKind::Unit => "seq.unit",
+                SeqOperationKind::Length => "seq.len",
+                SeqOperationKind::Index => "seq.nth",
+                SeqOperationKind::Concat => "seq.++",
+                SeqOperationKind::Subsequence => "seq.extract",
+                SeqOperationKind::Update => "seq.update",
+            };
+            write!(writer, "({}", function)?;
+            for (i, arg) in self.args.iter().enumerate() {
+                write!(writer, " ")?;
+                // `seq.update` replaces a subsequence.
+                if self.kind == SeqOperationKind::Update && i == 2 {
+                    write!(writer, "(seq.unit ")?;
+                    arg.expr_to_smt2(writer, context)?;
+                    write!(writer, ")")?;
+                } else {
+                    arg.expr_to_smt2(writer, context)?;
+                }
+            }
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { EmptySeq =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for EmptySeq {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(as seq.empty ")?;
+            ::rsmt2::print::Sort2Smt::sort_to_smt2(&self.sort(context), writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { SetOperation =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for SetOperation {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            // Sets are arrays from the elements to `Bool`.
+            match self.kind {
+                SetOperationKind::Singleton => {
+                    write!(writer, "(store ((as const ")?;
+                    ::rsmt2::print::Sort2Smt::sort_to_smt2(&self.sort(context), writer, context)?;
+                    write!(writer, ") false) ")?;
+                    self.args[0].expr_to_smt2(writer, context)?;
+                    write!(writer, " true)")?;
+                }
+                SetOperationKind::Contains => {
+                    write!(writer, "(select ")?;
+                    self.args[0].expr_to_smt2(writer, context)?;
+                    write!(writer, " ")?;
+                    self.args[1].expr_to_smt2(writer, context)?;
+                    write!(writer, ")")?;
+                }
+                SetOperationKind::Union | SetOperationKind::Intersection => {
+                    let function = if self.kind == SetOperationKind::Union { "or" } else { "and" };
+                    write!(writer, "((_ map {}) ", function)?;
+                    self.args[0].expr_to_smt2(writer, context)?;
+                    write!(writer, " ")?;
+                    self.args[1].expr_to_smt2(writer, context)?;
+                    write!(writer, ")")?;
+                }
+                SetOperationKind::Subset => {
+                    // `s` is a subset of `t` if their intersection is `s`.
+                    write!(writer, "(= ((_ map and) ")?;
+                    self.args[0].expr_to_smt2(writer, context)?;
+                    write!(writer, " ")?;
+                    self.args[1].expr_to_smt2(writer, context)?;
+                    write!(writer, ") ")?;
+                    self.args[0].expr_to_smt2(writer, context)?;
+                    write!(writer, ")")?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { EmptySet =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for EmptySet {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "((as const ")?;
+            ::rsmt2::print::Sort2Smt::sort_to_smt2(&self.sort(context), writer, context)?;
+            write!(writer, ") false)")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { AdtConstructor =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for AdtConstructor {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            if self.args.is_empty() {
+                self.constructor.expr_to_smt2(writer, context)?;
+            } else {
+                write!(writer, "(")?;
+                self.constructor.expr_to_smt2(writer, context)?;
+                for arg in &self.args {
+                    write!(writer, " ")?;
+                    arg.expr_to_smt2(writer, context)?;
+                }
+                write!(writer, ")")?;
+            }
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { AdtFieldAccess =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for AdtFieldAccess {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(")?;
+            self.field.expr_to_smt2(writer, context)?;
+            write!(writer, " ")?;
+            self.base.expr_to_smt2(writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { AdtTester =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for AdtTester {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "((_ is ")?;
+            self.constructor.expr_to_smt2(writer, context)?;
+            write!(writer, ") ")?;
+            self.base.expr_to_smt2(writer, context)?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
+vir_raw_block! { AdtMatch =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for AdtMatch {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(match ")?;
+            self.base.expr_to_smt2(writer, context)?;
+            write!(writer, " (")?;
+            for (i, case) in self.cases.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, " ")?;
+                }
+                write!(writer, "(")?;
+                if case.binders.is_empty() {
+                    case.constructor.expr_to_smt2(writer, context)?;
+                } else {
+                    write!(writer, "(")?;
+                    case.constructor.expr_to_smt2(writer, context)?;
+                    for binder in &case.binders {
+                        write!(writer, " ")?;
+                        context.write_variable_symbol(writer, binder)?;
+                    }
+                    write!(writer, ")")?;
+                }
+                write!(writer, " ")?;
+                let fields = context.get_constructor_fields(&case.constructor);
+                let binders = case
+                    .binders
+                    .iter()
+                    .zip(fields)
+                    .map(|(binder, field)| (binder.clone(), field.sort.clone()));
+                context
+                    .bound_variables()
+                    .bind(binders, || case.body.expr_to_smt2(writer, context))?;
+                write!(writer, ")")?;
+            }
+            write!(writer, "))")?;
+            Ok(())
+        }
+    }
+}
 vir_raw_block! { Conditional =>
     impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for Conditional {
         fn expr_to_smt2<Writer: std::io::Write>(
@@ -116,6 +527,27 @@ vir_raw_block! { Conditional =>
         }
     }
 }
+vir_raw_block! { Let =>
+    impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for Let {
+        fn expr_to_smt2<Writer: std::io::Write>(
+            &self,
+            writer: &mut Writer,
+            context: &'a C
+        ) -> ::rsmt2::SmtRes<()> {
+            write!(writer, "(let ((")?;
+            context.write_variable_symbol(writer, &self.variable)?;
+            write!(writer, " ")?;
+            self.value.expr_to_smt2(writer, context)?;
+            write!(writer, ")) ")?;
+            let variable = (self.variable.clone(), self.value.sort(context));
+            context
+                .bound_variables()
+                .bind([variable], || self.body.expr_to_smt2(writer, context))?;
+            write!(writer, ")")?;
+            Ok(())
+        }
+    }
+}
 vir_raw_block! { Quantifier =>
     impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for Quantifier {
         fn expr_to_smt2<Writer: std::io::Write>(
@@ -135,10 +567,17 @@ vir_raw_block! { Quantifier =>
                 write!(writer, ")")?;
             }
             write!(writer, ") (! ")?;
-            self.body.expr_to_smt2(writer, context)?;
-            for trigger in &self.triggers {
-                trigger.expr_to_smt2(writer, context)?;
-            }
+            let variables = self
+                .variables
+                .iter()
+                .map(|variable| (variable.name.clone(), variable.sort.clone()));
+            context.bound_variables().bind(variables, || -> ::rsmt2::SmtRes<()> {
+                self.body.expr_to_smt2(writer, context)?;
+                for trigger in &self.triggers {
+                    trigger.expr_to_smt2(writer, context)?;
+                }
+                Ok(())
+            })?;
             write!(writer, " ))")?;
             Ok(())
         }
@@ -0,0 +1,293 @@
+vir_raw_block! { Simplification =>
+    impl crate::common::expression::Simplification for Expression {
+        fn simplify(self) -> Expression {
+            Simplifier.fold_expression(self)
+        }
+    }
+
+    /// Simplifies expressions bottom-up. A subterm is dropped only if
+    /// `Simplifier::is_preserved` allows it.
+    struct Simplifier;
+
+    impl ExpressionFolder for Simplifier {
+        fn fold_unary_operation(&mut self, operation: UnaryOperation) -> Expression {
+            let operation = default_fold_unary_operation(self, operation);
+            Simplifier::simplify_unary_operation(operation)
+        }
+        fn fold_binary_operation(&mut self, operation: BinaryOperation) -> Expression {
+            let operation = default_fold_binary_operation(self, operation);
+            Simplifier::simplify_binary_operation(operation)
+        }
+        fn fold_conditional(&mut self, conditional: Conditional) -> Expression {
+            let conditional = default_fold_conditional(self, conditional);
+            Simplifier::simplify_conditional(conditional)
+        }
+    }
+
+    impl Simplifier {
+        fn simplify_unary_operation(operation: UnaryOperation) -> Expression {
+            match (operation.kind, *operation.arg) {
+                (UnaryOperationKind::Not, Expression::Constant(Constant::Bool(value))) => {
+                    Expression::bool(!value)
+                }
+                (
+                    UnaryOperationKind::Not,
+                    Expression::UnaryOperation(UnaryOperation {
+                        kind: UnaryOperationKind::Not,
+                        arg,
+                    }),
+                ) => *arg,
+                (UnaryOperationKind::Minus, Expression::Constant(Constant::Int(value))) => {
+                    Expression::constant(Constant::Int(-value))
+                }
+                (kind, arg) => Expression::unary_operation(kind, arg),
+            }
+        }
+        fn simplify_binary_operation(operation: BinaryOperation) -> Expression {
+            let BinaryOperation { kind, left, right } = operation;
+            if kind == BinaryOperationKind::And || kind == BinaryOperationKind::Or {
+                return Simplifier::simplify_junction(kind, *left, *right);
+            }
+            if let (Expression::Constant(left), Expression::Constant(right)) = (&*left, &*right) {
+                if let Some(result) = Simplifier::evaluate(&kind, left, right) {
+                    return result;
+                }
+            }
+            let droppable = !Simplifier::is_preserved(&left) && !Simplifier::is_preserved(&right);
+            match kind {
+                BinaryOperationKind::EqCmp if droppable && left == right => Expression::bool(true),
+                BinaryOperationKind::NeCmp if droppable && left == right => Expression::bool(false),
+                BinaryOperationKind::Implies => match (*left, *right) {
+                    (Expression::Constant(Constant::Bool(true)), right) => right,
+                    (Expression::Constant(Constant::Bool(false)), right)
+                        if !Simplifier::is_preserved(&right) =>
+                    {
+                        Expression::bool(true)
+                    }
+                    (left, Expression::Constant(Constant::Bool(true)))
+                        if !Simplifier::is_preserved(&left) =>
+                    {
+                        Expression::bool(true)
+                    }
+                    (left, Expression::Constant(Constant::Bool(false))) => {
+                        Simplifier::simplify_unary_operation(UnaryOperation {
+                            kind: UnaryOperationKind::Not,
+                            arg: Box::new(left),
+                        })
+                    }
+                    (left, right) if droppable && left == right => Expression::bool(true),
+                    (left, right) => Expression::implies(left, right),
+                },
+                kind => Expression::binary_operation(kind, *left, *right),
+            }
+        }
+        /// Evaluates operations on constants. Returns `None` if the result is
+        /// not known.
+        fn evaluate(
+            kind: &BinaryOperationKind,
+            left: &Constant,
+            right: &Constant,
+        ) -> Option<Expression> {
+            match (kind, left, right) {
+                // Floats are excluded because different bits may denote the
+                // same NaN.
+                (BinaryOperationKind::EqCmp, _, _) | (BinaryOperationKind::NeCmp, _, _)
+                    if std::matches!(
+                        (left, right),
+                        (Constant::Bool(_), Constant::Bool(_))
+                            | (Constant::Int(_), Constant::Int(_))
+                            | (Constant::BitVector { .. }, Constant::BitVector { .. })
+                            | (Constant::Real { .. }, Constant::Real { .. })
+                    ) =>
+                {
+                    let equal = left == right;
+                    Some(Expression::bool(if *kind == BinaryOperationKind::EqCmp {
+                        equal
+                    } else {
+                        !equal
+                    }))
+                }
+                (kind, Constant::Int(left), Constant::Int(right)) => {
+                    let result = match kind {
+                        BinaryOperationKind::Add => Constant::Int(left + right),
+                        BinaryOperationKind::Sub => Constant::Int(left - right),
+                        BinaryOperationKind::Mul => Constant::Int(left * right),
+                        BinaryOperationKind::GtCmp => Constant::Bool(left > right),
+                        BinaryOperationKind::GeCmp => Constant::Bool(left >= right),
+                        BinaryOperationKind::LtCmp => Constant::Bool(left < right),
+                        BinaryOperationKind::LeCmp => Constant::Bool(left <= right),
+                        _ => return None,
+                    };
+                    Some(Expression::constant(result))
+                }
+                _ => None,
+            }
+        }
+        /// Simplifies the conjunction or disjunction (`kind`) of `left` and
+        /// `right`. Nested operations of the same kind are flattened, and the
+        /// result is nested to the left.
+        fn simplify_junction(
+            kind: BinaryOperationKind,
+            left: Expression,
+            right: Expression,
+        ) -> Expression {
+            // `unit && x` is `x`, and `zero && x` is `zero`.
+            let unit = kind == BinaryOperationKind::And;
+            let mut flattened = Vec::new();
+            Simplifier::flatten(&kind, left, &mut flattened);
+            Simplifier::flatten(&kind, right, &mut flattened);
+            let mut operands: Vec<Expression> = Vec::new();
+            let mut is_zero = false;
+            for operand in flattened {
+                match operand {
+                    Expression::Constant(Constant::Bool(value)) if value == unit => {}
+                    Expression::Constant(Constant::Bool(_)) => is_zero = true,
+                    operand => {
+                        if Simplifier::is_preserved(&operand) || !operands.contains(&operand) {
+                            operands.push(operand);
+                        }
+                    }
+                }
+            }
+            let droppable = operands
+                .iter()
+                .all(|operand| !Simplifier::is_preserved(operand));
+            if droppable {
+                let has_complement = operands.iter().any(|operand| match operand {
+                    Expression::UnaryOperation(UnaryOperation {
+                        kind: UnaryOperationKind::Not,
+                        arg,
+                    }) => operands.contains(arg),
+                    _ => false,
+                });
+                if is_zero || has_complement {
+                    return Expression::bool(!unit);
+                }
+            } else if is_zero {
+                operands.push(Expression::bool(!unit));
+            }
+            // Absorption: `a && (a || b)` is `a`.
+            let dual = if unit {
+                BinaryOperationKind::Or
+            } else {
+                BinaryOperationKind::And
+            };
+            let absorbed: Vec<bool> = operands
+                .iter()
+                .map(|operand| {
+                    if Simplifier::is_preserved(operand) {
+                        return false;
+                    }
+                    let mut dual_operands = Vec::new();
+                    Simplifier::flatten(&dual, operand.clone(), &mut dual_operands);
+                    dual_operands.len() > 1
+                        && operands
+                            .iter()
+                            .any(|other| other != operand && dual_operands.contains(other))
+                })
+                .collect();
+            let mut operands = operands
+                .into_iter()
+                .zip(absorbed)
+                .filter(|(_, absorbed)| !absorbed)
+                .map(|(operand, _)| operand);
+            let first = match operands.next() {
+                Some(first) => first,
+                None => return Expression::bool(unit),
+            };
+            operands.fold(first, |result, operand| {
+                if unit {
+                    Expression::and(result, operand)
+                } else {
+                    Expression::or(result, operand)
+                }
+            })
+        }
+        /// Pushes the operands of the nested operations of `kind` in
+        /// `expression` to `operands`.
+        fn flatten(
+            kind: &BinaryOperationKind,
+            expression: Expression,
+            operands: &mut Vec<Expression>,
+        ) {
+            match expression {
+                Expression::BinaryOperation(operation) if operation.kind == *kind => {
+                    Simplifier::flatten(kind, *operation.left, operands);
+                    Simplifier::flatten(kind, *operation.right, operands);
+                }
+                expression => operands.push(expression),
+            }
+        }
+        fn simplify_conditional(conditional: Conditional) -> Expression {
+            let Conditional {
+                guard,
+                then_expr,
+                else_expr,
+            } = conditional;
+            match *guard {
+                Expression::Constant(Constant::Bool(true))
+                    if !Simplifier::is_preserved(&else_expr) =>
+                {
+                    *then_expr
+                }
+                Expression::Constant(Constant::Bool(false))
+                    if !Simplifier::is_preserved(&then_expr) =>
+                {
+                    *else_expr
+                }
+                guard if then_expr == else_expr && !Simplifier::is_preserved(&guard) => *then_expr,
+                guard => Expression::Conditional(Conditional {
+                    guard: Box::new(guard),
+                    then_expr,
+                    else_expr,
+                }),
+            }
+        }
+    }
+}
+
+vir_raw_block! { LabelledSimplification =>
+    impl Simplifier {
+        /// Whether dropping `expression` changes the meaning of the
+        /// expression beyond its value, which is the case if it contains a
+        /// label.
+        fn is_preserved(expression: &Expression) -> bool {
+            let mut finder = LabelFinder { found: false };
+            finder.walk_expression(expression);
+            finder.found
+        }
+    }
+
+    struct LabelFinder {
+        found: bool,
+    }
+
+    impl ExpressionWalker for LabelFinder {
+        fn walk_labelled_expression(&mut self, _labelled_expression: &LabelledExpression) {
+            self.found = true;
+        }
+    }
+}
+
+vir_raw_block! { PositionedSimplification =>
+    impl Simplifier {
+        /// Whether dropping `expression` changes the meaning of the
+        /// expression beyond its value, which is the case if it contains a
+        /// position that may be reported in a verification error.
+        fn is_preserved(expression: &Expression) -> bool {
+            let mut finder = PositionFinder { found: false };
+            finder.walk_expression(expression);
+            finder.found
+        }
+    }
+
+    struct PositionFinder {
+        found: bool,
+    }
+
+    impl ExpressionWalker for PositionFinder {
+        fn walk_positioned_expression(&mut self, _positioned_expression: &PositionedExpression) {
+            self.found = true;
+        }
+    }
+}
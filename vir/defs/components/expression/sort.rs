@@ -7,31 +7,45 @@ use super::*;
 
 vir_raw_block! { Variable =>
     impl<C: Context> WithSort<C> for Variable {
-        fn sort<'a>(&'a self, context: &'a C) -> &'a Sort {
-            context.get_variable_sort(&self.name)
+        fn sort(&self, context: &C) -> Sort {
+            context
+                .bound_variables()
+                .get(&self.name)
+                .unwrap_or_else(|| context.get_variable_sort(&self.name).clone())
         }
     }
 }
 vir_raw_block! { Constant =>
     impl<C: Context> WithSort<C> for Constant {
-        fn sort<'a>(&'a self, _context: &'a C) -> &'a Sort {
+        fn sort(&self, _context: &C) -> Sort {
             match self {
-                Constant::Bool(_) => &Sort::Bool,
-                Constant::Int(_) => &Sort::Int,
+                Constant::Bool(_) => Sort::Bool,
+                Constant::Int(_) => Sort::Int,
+                Constant::BitVector { width, .. } => Sort::BitVector { width: *width },
+                Constant::Real { .. } => Sort::Real,
+                Constant::Float { exponent, significand, .. } => Sort::Float {
+                    exponent: *exponent,
+                    significand: *significand,
+                },
             }
         }
     }
 }
 vir_raw_block! { UnaryOperation =>
     impl<C: Context> WithSort<C> for UnaryOperation {
-        fn sort<'a>(&'a self, context: &'a C) -> &'a Sort {
-            self.arg.sort(context)
+        fn sort(&self, context: &C) -> Sort {
+            match self.kind {
+                UnaryOperationKind::Not | UnaryOperationKind::Minus => self.arg.sort(context),
+                UnaryOperationKind::ToReal => Sort::Real,
+                UnaryOperationKind::ToInt => Sort::Int,
+                UnaryOperationKind::IsInt => Sort::Bool,
+            }
         }
     }
 }
 vir_raw_block! { BinaryOperation =>
     impl<C: Context> WithSort<C> for BinaryOperation {
-        fn sort<'a>(&'a self, context: &'a C) -> &'a Sort {
+        fn sort(&self, context: &C) -> Sort {
             match self.kind {
                 BinaryOperationKind::EqCmp
                 | BinaryOperationKind::NeCmp
@@ -41,47 +55,231 @@ vir_raw_block! { BinaryOperation =>
                 | BinaryOperationKind::LeCmp
                 | BinaryOperationKind::And
                 | BinaryOperationKind::Or
-                | BinaryOperationKind::Implies => &Sort::Bool,
+                | BinaryOperationKind::Implies
+                | BinaryOperationKind::SGtCmp
+                | BinaryOperationKind::SGeCmp
+                | BinaryOperationKind::SLtCmp
+                | BinaryOperationKind::SLeCmp => Sort::Bool,
                 BinaryOperationKind::Add
                 | BinaryOperationKind::Sub
                 | BinaryOperationKind::Mul
                 | BinaryOperationKind::Div
-                | BinaryOperationKind::Mod => {
+                | BinaryOperationKind::Mod
+                | BinaryOperationKind::BitAnd
+                | BinaryOperationKind::BitOr
+                | BinaryOperationKind::BitXor
+                | BinaryOperationKind::Shl
+                | BinaryOperationKind::LShr
+                | BinaryOperationKind::AShr
+                | BinaryOperationKind::SDiv
+                | BinaryOperationKind::SRem => {
                     let sort = self.left.sort(context);
                     debug_assert_eq!(sort, self.right.sort(context));
                     sort
                 },
+                BinaryOperationKind::Concat => {
+                    let left = self.left.sort(context).bit_vector_width().unwrap();
+                    let right = self.right.sort(context).bit_vector_width().unwrap();
+                    Sort::BitVector { width: left + right }
+                },
+            }
+        }
+    }
+}
+vir_raw_block! { BitVectorConversion =>
+    impl<C: Context> WithSort<C> for BitVectorConversion {
+        fn sort(&self, context: &C) -> Sort {
+            let width = match self.kind {
+                BitVectorConversionKind::Extract { high, low } => high - low + 1,
+                BitVectorConversionKind::ZeroExtend { bits }
+                | BitVectorConversionKind::SignExtend { bits } => {
+                    self.arg.sort(context).bit_vector_width().unwrap() + bits
+                }
+                BitVectorConversionKind::FromInt { width } => width,
+                BitVectorConversionKind::ToInt => return Sort::Int,
+            };
+            Sort::BitVector { width }
+        }
+    }
+}
+vir_raw_block! { FloatOperation =>
+    impl<C: Context> WithSort<C> for FloatOperation {
+        fn sort(&self, context: &C) -> Sort {
+            if self.kind.is_predicate() {
+                Sort::Bool
+            } else {
+                self.args[0].sort(context)
+            }
+        }
+    }
+}
+vir_raw_block! { FloatConversion =>
+    impl<C: Context> WithSort<C> for FloatConversion {
+        fn sort(&self, _context: &C) -> Sort {
+            match self.kind {
+                FloatConversionKind::ToFloat { exponent, significand, .. }
+                | FloatConversionKind::UnsignedToFloat { exponent, significand, .. }
+                | FloatConversionKind::FromBits { exponent, significand } => {
+                    Sort::Float { exponent, significand }
+                }
+                FloatConversionKind::ToReal => Sort::Real,
+                FloatConversionKind::ToSigned { width, .. }
+                | FloatConversionKind::ToUnsigned { width, .. } => Sort::BitVector { width },
+            }
+        }
+    }
+}
+vir_raw_block! { ArraySelect =>
+    impl<C: Context> WithSort<C> for ArraySelect {
+        fn sort(&self, context: &C) -> Sort {
+            self.array.sort(context).array_element_sort().unwrap().clone()
+        }
+    }
+}
+vir_raw_block! { ArrayStore =>
+    impl<C: Context> WithSort<C> for ArrayStore {
+        fn sort(&self, context: &C) -> Sort {
+            self.array.sort(context)
+        }
+    }
+}
+vir_raw_block! { ConstantArray =>
+    impl<C: Context> WithSort<C> for ConstantArray {
+        fn sort(&self, context: &C) -> Sort {
+            Sort::Array {
+                index: Box::new(self.index_sort.clone()),
+                element: Box::new(self.value.sort(context)),
+            }
+        }
+    }
+}
+vir_raw_block! { SeqOperation =>
+    impl<C: Context> WithSort<C> for SeqOperation {
+        fn sort(&self, context: &C) -> Sort {
+            match self.kind {
+                SeqOperationKind::Unit => Sort::Seq {
+                    element: Box::new(self.args[0].sort(context)),
+                },
+                SeqOperationKind::Length => Sort::Int,
+                SeqOperationKind::Index => {
+                    self.args[0].sort(context).collection_element_sort().unwrap().clone()
+                }
+                SeqOperationKind::Concat
+                | SeqOperationKind::Subsequence
+                | SeqOperationKind::Update => self.args[0].sort(context),
+            }
+        }
+    }
+}
+vir_raw_block! { EmptySeq =>
+    impl<C: Context> WithSort<C> for EmptySeq {
+        fn sort(&self, _context: &C) -> Sort {
+            Sort::Seq {
+                element: Box::new(self.element_sort.clone()),
+            }
+        }
+    }
+}
+vir_raw_block! { SetOperation =>
+    impl<C: Context> WithSort<C> for SetOperation {
+        fn sort(&self, context: &C) -> Sort {
+            match self.kind {
+                SetOperationKind::Singleton => Sort::Set {
+                    element: Box::new(self.args[0].sort(context)),
+                },
+                SetOperationKind::Contains | SetOperationKind::Subset => Sort::Bool,
+                SetOperationKind::Union | SetOperationKind::Intersection => {
+                    self.args[0].sort(context)
+                }
+            }
+        }
+    }
+}
+vir_raw_block! { EmptySet =>
+    impl<C: Context> WithSort<C> for EmptySet {
+        fn sort(&self, _context: &C) -> Sort {
+            Sort::Set {
+                element: Box::new(self.element_sort.clone()),
             }
         }
     }
 }
+vir_raw_block! { AdtConstructor =>
+    impl<C: Context> WithSort<C> for AdtConstructor {
+        fn sort(&self, _context: &C) -> Sort {
+            Sort::Adt {
+                name: self.adt.clone(),
+            }
+        }
+    }
+}
+vir_raw_block! { AdtFieldAccess =>
+    impl<C: Context> WithSort<C> for AdtFieldAccess {
+        fn sort(&self, context: &C) -> Sort {
+            context.get_field_sort(&self.field).clone()
+        }
+    }
+}
+vir_raw_block! { AdtTester =>
+    impl<C: Context> WithSort<C> for AdtTester {
+        fn sort(&self, _context: &C) -> Sort {
+            Sort::Bool
+        }
+    }
+}
+vir_raw_block! { AdtMatch =>
+    impl<C: Context> WithSort<C> for AdtMatch {
+        fn sort(&self, context: &C) -> Sort {
+            let case = &self.cases[0];
+            let fields = context.get_constructor_fields(&case.constructor);
+            let binders = case
+                .binders
+                .iter()
+                .zip(fields)
+                .map(|(binder, field)| (binder.clone(), field.sort.clone()));
+            context
+                .bound_variables()
+                .bind(binders, || case.body.sort(context))
+        }
+    }
+}
 vir_raw_block! { Conditional =>
     impl<C: Context> WithSort<C> for Conditional {
-        fn sort<'a>(&'a self, context: &'a C) -> &'a Sort {
+        fn sort(&self, context: &C) -> Sort {
             let sort = self.then_expr.sort(context);
             debug_assert_eq!(sort, self.else_expr.sort(context));
             sort
         }
     }
 }
+vir_raw_block! { Let =>
+    impl<C: Context> WithSort<C> for Let {
+        fn sort(&self, context: &C) -> Sort {
+            let variable = (self.variable.clone(), self.value.sort(context));
+            context
+                .bound_variables()
+                .bind([variable], || self.body.sort(context))
+        }
+    }
+}
 vir_raw_block! { Quantifier =>
     impl<C: Context> WithSort<C> for Quantifier {
-        fn sort<'a>(&'a self, _context: &'a C) -> &'a Sort {
-            &Sort::Bool
+        fn sort(&self, _context: &C) -> Sort {
+            Sort::Bool
         }
     }
 }
 vir_raw_block! { FunctionApplication =>
     impl<C: Context> WithSort<C> for FunctionApplication {
-        fn sort<'a>(&'a self, context: &'a C) -> &'a Sort {
-            context.get_function_sort(&self.function)
+        fn sort(&self, context: &C) -> Sort {
+            context.get_function_sort(&self.function).clone()
         }
     }
 }
 vir_raw_block! { LabelledExpression =>
     impl<C: Context> WithSort<C> for LabelledExpression {
-        fn sort<'a>(&'a self, _context: &'a C) -> &'a Sort {
-            &Sort::Bool
+        fn sort(&self, _context: &C) -> Sort {
+            Sort::Bool
         }
     }
 }
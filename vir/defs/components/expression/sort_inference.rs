@@ -0,0 +1,725 @@
+trait Interface {
+    type Expression;
+    type Sort;
+    type VariableSymbol;
+    type FunctionSymbol;
+    type LabelSymbol;
+    type AdtNameSymbol;
+    type AdtConstructorSymbol;
+    type FieldNameSymbol;
+}
+
+use super::*;
+
+vir_raw_block! { InferSort =>
+    /// Computes the sort of an expression and checks that the sorts of its
+    /// subexpressions fit together.
+    pub trait InferSort {
+        /// Looks up the free variables, the functions and the datatypes of
+        /// `self` in `symbols`.
+        fn infer_sort(&self, symbols: &SymbolTable) -> Result<Sort, SortError> {
+            self.infer_sort_in_scope(&mut SortScope::new(symbols))
+        }
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError>;
+    }
+
+    /// The variables bound by the enclosing quantifiers, let expressions and
+    /// match cases, on top of a symbol table.
+    pub struct SortScope<'a> {
+        symbols: &'a SymbolTable,
+        bound: Vec<(VariableSymbol, Sort)>,
+    }
+
+    impl<'a> SortScope<'a> {
+        pub fn new(symbols: &'a SymbolTable) -> Self {
+            Self {
+                symbols,
+                bound: Vec::new(),
+            }
+        }
+        fn variable_sort(&self, variable: &VariableSymbol) -> Result<Sort, SortError> {
+            match self.bound.iter().rev().find(|(name, _)| name == variable) {
+                Some((_, sort)) => Ok(sort.clone()),
+                None => self
+                    .symbols
+                    .variables
+                    .get(variable)
+                    .cloned()
+                    .ok_or_else(|| SortError::UnknownVariable(variable.clone())),
+            }
+        }
+        /// Infers the sort of `expression` with `variables` bound in
+        /// addition.
+        fn infer_with_bound<E: InferSort + ?Sized>(
+            &mut self,
+            variables: impl IntoIterator<Item = (VariableSymbol, Sort)>,
+            expression: &E,
+        ) -> Result<Sort, SortError> {
+            let len = self.bound.len();
+            self.bound.extend(variables);
+            let sort = expression.infer_sort_in_scope(self);
+            self.bound.truncate(len);
+            sort
+        }
+        /// The constructors of the datatype `adt`.
+        fn constructors(
+            &self,
+            adt: &AdtNameSymbol,
+        ) -> Result<&'a [super::declaration::AdtConstructorDeclaration], SortError> {
+            let symbols: &'a SymbolTable = self.symbols;
+            match symbols.adts.get(adt) {
+                Some(declaration) => Ok(&declaration.constructors),
+                None => Err(SortError::UnknownAdt(adt.clone())),
+            }
+        }
+        fn constructor(
+            &self,
+            adt: &AdtNameSymbol,
+            constructor: &AdtConstructorSymbol,
+        ) -> Result<&'a super::declaration::AdtConstructorDeclaration, SortError> {
+            self.constructors(adt)?
+                .iter()
+                .find(|declaration| &declaration.name == constructor)
+                .ok_or_else(|| SortError::UnknownConstructor {
+                    adt: adt.clone(),
+                    constructor: constructor.clone(),
+                })
+        }
+    }
+
+    fn mismatch(operation: &str, expected: String, found: Sort) -> SortError {
+        SortError::Mismatch {
+            operation: operation.to_string(),
+            expected,
+            found,
+        }
+    }
+
+    fn expect_sort(operation: &str, expected: &Sort, found: Sort) -> Result<(), SortError> {
+        if &found == expected {
+            Ok(())
+        } else {
+            Err(mismatch(operation, format!("`{}`", expected), found))
+        }
+    }
+
+    fn expect_bit_vector(operation: &str, found: Sort) -> Result<u32, SortError> {
+        match found.bit_vector_width() {
+            Some(width) => Ok(width),
+            None => Err(mismatch(operation, "a bit-vector".to_string(), found)),
+        }
+    }
+
+    fn expect_float(operation: &str, found: Sort) -> Result<(), SortError> {
+        if found.is_float() {
+            Ok(())
+        } else {
+            Err(mismatch(operation, "a floating-point number".to_string(), found))
+        }
+    }
+
+    fn expect_argument_count(
+        operation: &str,
+        expected: usize,
+        found: usize,
+    ) -> Result<(), SortError> {
+        if found == expected {
+            Ok(())
+        } else {
+            Err(SortError::ArgumentCount {
+                operation: operation.to_string(),
+                expected,
+                found,
+            })
+        }
+    }
+
+    fn is_number(sort: &Sort) -> bool {
+        sort.is_integer() || sort.is_real() || sort.is_bit_vector() || sort.is_float()
+    }
+
+    /// Infers the sorts of `left` and `right` and checks that they are the
+    /// same.
+    fn infer_same_sort(
+        operation: &str,
+        left: &Expression,
+        right: &Expression,
+        scope: &mut SortScope<'_>,
+    ) -> Result<Sort, SortError> {
+        let sort = left.infer_sort_in_scope(scope)?;
+        expect_sort(operation, &sort, right.infer_sort_in_scope(scope)?)?;
+        Ok(sort)
+    }
+}
+vir_raw_block! { SymbolTable =>
+    /// The sorts of the variables, the signatures of the functions and the
+    /// datatypes that expressions may refer to.
+    #[derive(Debug, Clone, Default)]
+    pub struct SymbolTable {
+        variables: std::collections::HashMap<VariableSymbol, Sort>,
+        functions: std::collections::HashMap<FunctionSymbol, (Vec<Sort>, Sort)>,
+        adts: std::collections::HashMap<AdtNameSymbol, super::declaration::AdtDeclaration>,
+    }
+
+    impl SymbolTable {
+        /// Replaces the declaration of a variable with the same name, if any.
+        pub fn declare_variable(&mut self, declaration: &super::declaration::VariableDeclaration) {
+            self.variables
+                .insert(declaration.name.clone(), declaration.sort.clone());
+        }
+        pub fn declare_function(&mut self, declaration: &super::declaration::FunctionDeclaration) {
+            let parameters = declaration
+                .parameters
+                .iter()
+                .map(|parameter| parameter.sort.clone())
+                .collect();
+            self.functions.insert(
+                declaration.name.clone(),
+                (parameters, declaration.return_sort.clone()),
+            );
+        }
+        pub fn declare_adt(&mut self, declaration: &super::declaration::AdtDeclaration) {
+            self.adts.insert(declaration.name.clone(), declaration.clone());
+        }
+        pub fn variable_sort(&self, variable: &VariableSymbol) -> Option<&Sort> {
+            self.variables.get(variable)
+        }
+    }
+}
+vir_raw_block! { SortError =>
+    #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+    pub enum SortError {
+        #[error("unknown variable `{0}`")]
+        UnknownVariable(VariableSymbol),
+        #[error("unknown function `{0}`")]
+        UnknownFunction(FunctionSymbol),
+        #[error("unknown algebraic datatype `{0}`")]
+        UnknownAdt(AdtNameSymbol),
+        #[error("`{adt}` has no constructor `{constructor}`")]
+        UnknownConstructor {
+            adt: AdtNameSymbol,
+            constructor: AdtConstructorSymbol,
+        },
+        #[error("`{sort}` has no field `{field}`")]
+        UnknownField { sort: Sort, field: FieldNameSymbol },
+        #[error("`{operation}` takes {expected} arguments, but {found} were given")]
+        ArgumentCount {
+            operation: String,
+            expected: usize,
+            found: usize,
+        },
+        #[error("`{operation}` expects {expected}, but got `{found}`")]
+        Mismatch {
+            operation: String,
+            expected: String,
+            found: Sort,
+        },
+        #[error("`{operation}` is invalid: {message}")]
+        Invalid { operation: String, message: String },
+    }
+}
+vir_raw_block! { Variable =>
+    impl InferSort for Variable {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            scope.variable_sort(&self.name)
+        }
+    }
+}
+vir_raw_block! { Constant =>
+    impl InferSort for Constant {
+        fn infer_sort_in_scope(&self, _scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            Ok(match self {
+                Constant::Bool(_) => Sort::Bool,
+                Constant::Int(_) => Sort::Int,
+                Constant::BitVector { width, .. } => Sort::bit_vector(*width),
+                Constant::Real { .. } => Sort::Real,
+                Constant::Float { exponent, significand, .. } => {
+                    Sort::float(*exponent, *significand)
+                }
+            })
+        }
+    }
+}
+vir_raw_block! { UnaryOperation =>
+    impl InferSort for UnaryOperation {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.arg.infer_sort_in_scope(scope)?;
+            match self.kind {
+                UnaryOperationKind::Not => {
+                    expect_sort("!", &Sort::Bool, sort)?;
+                    Ok(Sort::Bool)
+                }
+                UnaryOperationKind::Minus => {
+                    if is_number(&sort) {
+                        Ok(sort)
+                    } else {
+                        Err(mismatch("-", "a number".to_string(), sort))
+                    }
+                }
+                UnaryOperationKind::ToReal => {
+                    expect_sort("to_real", &Sort::Int, sort)?;
+                    Ok(Sort::Real)
+                }
+                UnaryOperationKind::ToInt => {
+                    expect_sort("to_int", &Sort::Real, sort)?;
+                    Ok(Sort::Int)
+                }
+                UnaryOperationKind::IsInt => {
+                    expect_sort("is_int", &Sort::Real, sort)?;
+                    Ok(Sort::Bool)
+                }
+            }
+        }
+    }
+}
+vir_raw_block! { BinaryOperation =>
+    impl InferSort for BinaryOperation {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let operation = self.kind.to_string();
+            if self.kind == BinaryOperationKind::Concat {
+                let left = expect_bit_vector(&operation, self.left.infer_sort_in_scope(scope)?)?;
+                let right = expect_bit_vector(&operation, self.right.infer_sort_in_scope(scope)?)?;
+                return Ok(Sort::bit_vector(left + right));
+            }
+            let sort = infer_same_sort(&operation, &self.left, &self.right, scope)?;
+            let (accepted, expected) = match self.kind {
+                BinaryOperationKind::EqCmp | BinaryOperationKind::NeCmp => (true, ""),
+                BinaryOperationKind::And
+                | BinaryOperationKind::Or
+                | BinaryOperationKind::Implies => (sort.is_bool(), "Booleans"),
+                BinaryOperationKind::GtCmp
+                | BinaryOperationKind::GeCmp
+                | BinaryOperationKind::LtCmp
+                | BinaryOperationKind::LeCmp
+                | BinaryOperationKind::Add
+                | BinaryOperationKind::Sub
+                | BinaryOperationKind::Mul
+                | BinaryOperationKind::Div => (is_number(&sort), "numbers"),
+                BinaryOperationKind::Mod => (
+                    sort.is_integer() || sort.is_bit_vector() || sort.is_float(),
+                    "integers, bit-vectors or floating-point numbers",
+                ),
+                BinaryOperationKind::BitAnd
+                | BinaryOperationKind::BitOr
+                | BinaryOperationKind::BitXor
+                | BinaryOperationKind::Shl
+                | BinaryOperationKind::LShr
+                | BinaryOperationKind::AShr
+                | BinaryOperationKind::SDiv
+                | BinaryOperationKind::SRem
+                | BinaryOperationKind::SGtCmp
+                | BinaryOperationKind::SGeCmp
+                | BinaryOperationKind::SLtCmp
+                | BinaryOperationKind::SLeCmp => (sort.is_bit_vector(), "bit-vectors"),
+                BinaryOperationKind::Concat => unreachable!(),
+            };
+            if !accepted {
+                return Err(mismatch(&operation, expected.to_string(), sort));
+            }
+            match self.kind {
+                BinaryOperationKind::EqCmp
+                | BinaryOperationKind::NeCmp
+                | BinaryOperationKind::And
+                | BinaryOperationKind::Or
+                | BinaryOperationKind::Implies
+                | BinaryOperationKind::GtCmp
+                | BinaryOperationKind::GeCmp
+                | BinaryOperationKind::LtCmp
+                | BinaryOperationKind::LeCmp
+                | BinaryOperationKind::SGtCmp
+                | BinaryOperationKind::SGeCmp
+                | BinaryOperationKind::SLtCmp
+                | BinaryOperationKind::SLeCmp => Ok(Sort::Bool),
+                _ => Ok(sort),
+            }
+        }
+    }
+}
+vir_raw_block! { BitVectorConversion =>
+    impl InferSort for BitVectorConversion {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.arg.infer_sort_in_scope(scope)?;
+            match self.kind {
+                BitVectorConversionKind::Extract { high, low } => {
+                    let width = expect_bit_vector("extract", sort)?;
+                    if low > high || high >= width {
+                        return Err(SortError::Invalid {
+                            operation: "extract".to_string(),
+                            message: format!(
+                                "bits {} to {} of a bit-vector of width {}",
+                                high, low, width
+                            ),
+                        });
+                    }
+                    Ok(Sort::bit_vector(high - low + 1))
+                }
+                BitVectorConversionKind::ZeroExtend { bits } => {
+                    Ok(Sort::bit_vector(expect_bit_vector("zero_extend", sort)? + bits))
+                }
+                BitVectorConversionKind::SignExtend { bits } => {
+                    Ok(Sort::bit_vector(expect_bit_vector("sign_extend", sort)? + bits))
+                }
+                BitVectorConversionKind::FromInt { width } => {
+                    expect_sort("int2bv", &Sort::Int, sort)?;
+                    Ok(Sort::bit_vector(width))
+                }
+                BitVectorConversionKind::ToInt => {
+                    expect_bit_vector("bv2int", sort)?;
+                    Ok(Sort::Int)
+                }
+            }
+        }
+    }
+}
+vir_raw_block! { FloatOperation =>
+    impl InferSort for FloatOperation {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let operation = self.kind.to_string();
+            let arity = match self.kind {
+                FloatOperationKind::Fma(_) => 3,
+                FloatOperationKind::Add(_)
+                | FloatOperationKind::Sub(_)
+                | FloatOperationKind::Mul(_)
+                | FloatOperationKind::Div(_)
+                | FloatOperationKind::Rem
+                | FloatOperationKind::Min
+                | FloatOperationKind::Max
+                | FloatOperationKind::Eq => 2,
+                _ => 1,
+            };
+            expect_argument_count(&operation, arity, self.args.len())?;
+            let sort = self.args[0].infer_sort_in_scope(scope)?;
+            expect_float(&operation, sort.clone())?;
+            for arg in &self.args[1..] {
+                expect_sort(&operation, &sort, arg.infer_sort_in_scope(scope)?)?;
+            }
+            if self.kind.is_predicate() {
+                Ok(Sort::Bool)
+            } else {
+                Ok(sort)
+            }
+        }
+    }
+}
+vir_raw_block! { FloatConversion =>
+    impl InferSort for FloatConversion {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.arg.infer_sort_in_scope(scope)?;
+            match self.kind {
+                FloatConversionKind::ToFloat { exponent, significand, .. } => {
+                    if sort.is_real() || sort.is_float() || sort.is_bit_vector() {
+                        Ok(Sort::float(exponent, significand))
+                    } else {
+                        Err(mismatch(
+                            "to_fp",
+                            "a real, a floating-point number or a bit-vector".to_string(),
+                            sort,
+                        ))
+                    }
+                }
+                FloatConversionKind::UnsignedToFloat { exponent, significand, .. } => {
+                    expect_bit_vector("to_fp_unsigned", sort)?;
+                    Ok(Sort::float(exponent, significand))
+                }
+                FloatConversionKind::FromBits { exponent, significand } => {
+                    expect_sort("fp_from_bits", &Sort::bit_vector(exponent + significand), sort)?;
+                    Ok(Sort::float(exponent, significand))
+                }
+                FloatConversionKind::ToReal => {
+                    expect_float("fp_to_real", sort)?;
+                    Ok(Sort::Real)
+                }
+                FloatConversionKind::ToSigned { width, .. }
+                | FloatConversionKind::ToUnsigned { width, .. } => {
+                    let operation = match self.kind {
+                        FloatConversionKind::ToSigned { .. } => "fp_to_sbv",
+                        _ => "fp_to_ubv",
+                    };
+                    expect_float(operation, sort)?;
+                    Ok(Sort::bit_vector(width))
+                }
+            }
+        }
+    }
+}
+vir_raw_block! { ArraySelect =>
+    impl InferSort for ArraySelect {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.array.infer_sort_in_scope(scope)?;
+            let (index, element) = match sort.array_sorts() {
+                Some(sorts) => sorts,
+                None => return Err(mismatch("select", "an array".to_string(), sort)),
+            };
+            expect_sort("select", index, self.index.infer_sort_in_scope(scope)?)?;
+            Ok(element.clone())
+        }
+    }
+}
+vir_raw_block! { ArrayStore =>
+    impl InferSort for ArrayStore {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.array.infer_sort_in_scope(scope)?;
+            let (index, element) = match sort.array_sorts() {
+                Some(sorts) => sorts,
+                None => return Err(mismatch("store", "an array".to_string(), sort)),
+            };
+            expect_sort("store", index, self.index.infer_sort_in_scope(scope)?)?;
+            expect_sort("store", element, self.value.infer_sort_in_scope(scope)?)?;
+            Ok(sort)
+        }
+    }
+}
+vir_raw_block! { ConstantArray =>
+    impl InferSort for ConstantArray {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let element = self.value.infer_sort_in_scope(scope)?;
+            Ok(Sort::array(self.index_sort.clone(), element))
+        }
+    }
+}
+vir_raw_block! { SeqOperation =>
+    impl InferSort for SeqOperation {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let operation = self.kind.to_string();
+            let arity = match self.kind {
+                SeqOperationKind::Unit | SeqOperationKind::Length => 1,
+                SeqOperationKind::Index | SeqOperationKind::Concat => 2,
+                SeqOperationKind::Subsequence | SeqOperationKind::Update => 3,
+            };
+            expect_argument_count(&operation, arity, self.args.len())?;
+            let sort = self.args[0].infer_sort_in_scope(scope)?;
+            if self.kind == SeqOperationKind::Unit {
+                return Ok(Sort::seq(sort));
+            }
+            let element = match sort.seq_element_sort() {
+                Some(element) => element.clone(),
+                None => return Err(mismatch(&operation, "a sequence".to_string(), sort)),
+            };
+            match self.kind {
+                SeqOperationKind::Unit => unreachable!(),
+                SeqOperationKind::Length => Ok(Sort::Int),
+                SeqOperationKind::Index => {
+                    expect_sort(&operation, &Sort::Int, self.args[1].infer_sort_in_scope(scope)?)?;
+                    Ok(element)
+                }
+                SeqOperationKind::Concat => {
+                    expect_sort(&operation, &sort, self.args[1].infer_sort_in_scope(scope)?)?;
+                    Ok(sort)
+                }
+                SeqOperationKind::Subsequence => {
+                    expect_sort(&operation, &Sort::Int, self.args[1].infer_sort_in_scope(scope)?)?;
+                    expect_sort(&operation, &Sort::Int, self.args[2].infer_sort_in_scope(scope)?)?;
+                    Ok(sort)
+                }
+                SeqOperationKind::Update => {
+                    expect_sort(&operation, &Sort::Int, self.args[1].infer_sort_in_scope(scope)?)?;
+                    expect_sort(&operation, &element, self.args[2].infer_sort_in_scope(scope)?)?;
+                    Ok(sort)
+                }
+            }
+        }
+    }
+}
+vir_raw_block! { EmptySeq =>
+    impl InferSort for EmptySeq {
+        fn infer_sort_in_scope(&self, _scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            Ok(Sort::seq(self.element_sort.clone()))
+        }
+    }
+}
+vir_raw_block! { SetOperation =>
+    impl InferSort for SetOperation {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let operation = self.kind.to_string();
+            let arity = match self.kind {
+                SetOperationKind::Singleton => 1,
+                _ => 2,
+            };
+            expect_argument_count(&operation, arity, self.args.len())?;
+            let sort = self.args[0].infer_sort_in_scope(scope)?;
+            if self.kind == SetOperationKind::Singleton {
+                return Ok(Sort::set(sort));
+            }
+            let element = match sort.set_element_sort() {
+                Some(element) => element.clone(),
+                None => return Err(mismatch(&operation, "a set".to_string(), sort)),
+            };
+            let other = self.args[1].infer_sort_in_scope(scope)?;
+            match self.kind {
+                SetOperationKind::Singleton => unreachable!(),
+                SetOperationKind::Contains => {
+                    expect_sort(&operation, &element, other)?;
+                    Ok(Sort::Bool)
+                }
+                SetOperationKind::Subset => {
+                    expect_sort(&operation, &sort, other)?;
+                    Ok(Sort::Bool)
+                }
+                SetOperationKind::Union | SetOperationKind::Intersection => {
+                    expect_sort(&operation, &sort, other)?;
+                    Ok(sort)
+                }
+            }
+        }
+    }
+}
+vir_raw_block! { EmptySet =>
+    impl InferSort for EmptySet {
+        fn infer_sort_in_scope(&self, _scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            Ok(Sort::set(self.element_sort.clone()))
+        }
+    }
+}
+vir_raw_block! { AdtConstructor =>
+    impl InferSort for AdtConstructor {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let constructor = scope.constructor(&self.adt, &self.constructor)?;
+            let operation = format!("{}::{}", self.adt, self.constructor);
+            expect_argument_count(&operation, constructor.fields.len(), self.args.len())?;
+            for (field, arg) in constructor.fields.iter().zip(&self.args) {
+                expect_sort(&operation, &field.sort, arg.infer_sort_in_scope(scope)?)?;
+            }
+            Ok(Sort::adt(self.adt.clone()))
+        }
+    }
+}
+vir_raw_block! { AdtFieldAccess =>
+    impl InferSort for AdtFieldAccess {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.base.infer_sort_in_scope(scope)?;
+            let adt = match sort.adt_name() {
+                Some(adt) => adt,
+                None => {
+                    let operation = format!(".{}", self.field);
+                    return Err(mismatch(&operation, "an algebraic datatype".to_string(), sort));
+                }
+            };
+            let field = scope
+                .constructors(adt)?
+                .iter()
+                .flat_map(|constructor| &constructor.fields)
+                .find(|field| field.name == self.field);
+            match field {
+                Some(field) => Ok(field.sort.clone()),
+                None => Err(SortError::UnknownField {
+                    sort: sort.clone(),
+                    field: self.field.clone(),
+                }),
+            }
+        }
+    }
+}
+vir_raw_block! { AdtTester =>
+    impl InferSort for AdtTester {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.base.infer_sort_in_scope(scope)?;
+            match sort.adt_name() {
+                Some(adt) => {
+                    scope.constructor(adt, &self.constructor)?;
+                    Ok(Sort::Bool)
+                }
+                None => Err(mismatch(
+                    &format!("is_{}", self.constructor),
+                    "an algebraic datatype".to_string(),
+                    sort,
+                )),
+            }
+        }
+    }
+}
+vir_raw_block! { AdtMatch =>
+    impl InferSort for AdtMatch {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.base.infer_sort_in_scope(scope)?;
+            expect_sort("match", &Sort::adt(self.adt.clone()), sort)?;
+            let mut result: Option<Sort> = None;
+            for case in &self.cases {
+                let constructor = scope.constructor(&self.adt, &case.constructor)?;
+                let operation = format!("{}::{}", self.adt, case.constructor);
+                expect_argument_count(&operation, constructor.fields.len(), case.binders.len())?;
+                let binders = case
+                    .binders
+                    .iter()
+                    .cloned()
+                    .zip(constructor.fields.iter().map(|field| field.sort.clone()));
+                let sort = scope.infer_with_bound(binders, &*case.body)?;
+                match &result {
+                    Some(expected) => expect_sort("match", expected, sort)?,
+                    None => result = Some(sort),
+                }
+            }
+            result.ok_or_else(|| SortError::Invalid {
+                operation: "match".to_string(),
+                message: "there are no cases".to_string(),
+            })
+        }
+    }
+}
+vir_raw_block! { Conditional =>
+    impl InferSort for Conditional {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            expect_sort("if", &Sort::Bool, self.guard.infer_sort_in_scope(scope)?)?;
+            infer_same_sort("if", &self.then_expr, &self.else_expr, scope)
+        }
+    }
+}
+vir_raw_block! { Let =>
+    impl InferSort for Let {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.value.infer_sort_in_scope(scope)?;
+            scope.infer_with_bound(std::iter::once((self.variable.clone(), sort)), &*self.body)
+        }
+    }
+}
+vir_raw_block! { Quantifier =>
+    impl InferSort for Quantifier {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let variables: Vec<_> = self
+                .variables
+                .iter()
+                .map(|variable| (variable.name.clone(), variable.sort.clone()))
+                .collect();
+            for trigger in &self.triggers {
+                for part in &trigger.parts {
+                    scope.infer_with_bound(variables.iter().cloned(), part)?;
+                }
+            }
+            let sort = scope.infer_with_bound(variables, &*self.body)?;
+            expect_sort(&self.kind.to_string(), &Sort::Bool, sort)?;
+            Ok(Sort::Bool)
+        }
+    }
+}
+vir_raw_block! { FunctionApplication =>
+    impl InferSort for FunctionApplication {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let symbols = scope.symbols;
+            let (parameters, return_sort) = match symbols.functions.get(&self.function) {
+                Some(signature) => signature,
+                None => return Err(SortError::UnknownFunction(self.function.clone())),
+            };
+            let operation = self.function.to_string();
+            expect_argument_count(&operation, parameters.len(), self.args.len())?;
+            for (parameter, arg) in parameters.iter().zip(&self.args) {
+                expect_sort(&operation, parameter, arg.infer_sort_in_scope(scope)?)?;
+            }
+            Ok(return_sort.clone())
+        }
+    }
+}
+vir_raw_block! { LabelledExpression =>
+    impl InferSort for LabelledExpression {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            let sort = self.expression.infer_sort_in_scope(scope)?;
+            expect_sort(&format!("label {}", self.name), &Sort::Bool, sort)?;
+            Ok(Sort::Bool)
+        }
+    }
+}
+vir_raw_block! { PositionedExpression =>
+    impl InferSort for PositionedExpression {
+        fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+            self.expression.infer_sort_in_scope(scope)
+        }
+    }
+}
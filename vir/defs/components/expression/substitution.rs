@@ -0,0 +1,220 @@
+vir_raw_block! { SubstitutionHelpers =>
+    impl crate::common::expression::SubstitutionHelpers for Expression {
+        fn free_variables(&self) -> std::collections::BTreeSet<VariableSymbol> {
+            let mut collector = VariableCollector::default();
+            collector.walk_expression(self);
+            collector.free
+        }
+        fn substitute(
+            self,
+            substitution: &std::collections::HashMap<VariableSymbol, Expression>,
+        ) -> Expression {
+            if substitution.is_empty() {
+                return self;
+            }
+            let mut collector = VariableCollector::default();
+            collector.walk_expression(&self);
+            for value in substitution.values() {
+                collector.walk_expression(value);
+            }
+            let mut range_variables = std::collections::BTreeSet::new();
+            for value in substitution.values() {
+                range_variables.extend(value.free_variables());
+            }
+            let mut substituter = Substituter {
+                substitution: substitution.clone(),
+                range_variables,
+                used_names: collector.free.into_iter().chain(collector.bound).collect(),
+                fresh_name: None,
+            };
+            substituter.fold_expression(self)
+        }
+        fn rename_free_variables(
+            self,
+            renaming: &std::collections::HashMap<VariableSymbol, VariableSymbol>,
+        ) -> Expression {
+            let substitution = renaming
+                .iter()
+                .map(|(variable, name)| (variable.clone(), Expression::variable(name.clone())))
+                .collect();
+            self.substitute(&substitution)
+        }
+        fn rename_bound_variables(
+            self,
+            fresh_name: &mut dyn FnMut(&VariableSymbol) -> VariableSymbol,
+        ) -> Expression {
+            let mut substituter = Substituter {
+                substitution: std::collections::HashMap::new(),
+                range_variables: std::collections::BTreeSet::new(),
+                used_names: std::collections::BTreeSet::new(),
+                fresh_name: Some(fresh_name),
+            };
+            substituter.fold_expression(self)
+        }
+    }
+
+    /// Collects the free variables of expressions and the variables bound in
+    /// them.
+    #[derive(Default)]
+    struct VariableCollector {
+        /// The variables bound by the enclosing binders.
+        scope: Vec<VariableSymbol>,
+        free: std::collections::BTreeSet<VariableSymbol>,
+        bound: std::collections::BTreeSet<VariableSymbol>,
+    }
+
+    impl VariableCollector {
+        fn bind(&mut self, variable: &VariableSymbol) {
+            self.scope.push(variable.clone());
+            self.bound.insert(variable.clone());
+        }
+    }
+
+    impl ExpressionWalker for VariableCollector {
+        fn walk_variable(&mut self, variable: &Variable) {
+            if !self.scope.contains(&variable.name) {
+                self.free.insert(variable.name.clone());
+            }
+        }
+        fn walk_quantifier(&mut self, quantifier: &Quantifier) {
+            let scope_len = self.scope.len();
+            for variable in &quantifier.variables {
+                self.bind(&variable.name);
+            }
+            default_walk_quantifier(self, quantifier);
+            self.scope.truncate(scope_len);
+        }
+        fn walk_let(&mut self, binding: &Let) {
+            // The binding is not visible in the value.
+            self.walk_expression(&binding.value);
+            self.bind(&binding.variable);
+            self.walk_expression(&binding.body);
+            self.scope.pop();
+        }
+        fn walk_adt_match_case(&mut self, case: &AdtMatchCase) {
+            let scope_len = self.scope.len();
+            for binder in &case.binders {
+                self.bind(binder);
+            }
+            default_walk_adt_match_case(self, case);
+            self.scope.truncate(scope_len);
+        }
+    }
+
+    /// Replaces free variables with expressions and renames the bound
+    /// variables that would capture a free variable of such an expression.
+    struct Substituter<'a> {
+        /// The substitution in the current scope. A renamed bound variable is
+        /// mapped to its new name.
+        substitution: std::collections::HashMap<VariableSymbol, Expression>,
+        /// The free variables of the substituted expressions.
+        range_variables: std::collections::BTreeSet<VariableSymbol>,
+        /// The names that a fresh name must differ from.
+        used_names: std::collections::BTreeSet<VariableSymbol>,
+        /// If set, every bound variable is renamed with it.
+        fresh_name: Option<&'a mut dyn FnMut(&VariableSymbol) -> VariableSymbol>,
+    }
+
+    impl<'a> Substituter<'a> {
+        /// Binds `variable` until the matching `unbind` and returns its new
+        /// name.
+        fn bind(
+            &mut self,
+            variable: &VariableSymbol,
+            shadowed: &mut Vec<(VariableSymbol, Option<Expression>)>,
+        ) -> VariableSymbol {
+            let previous = self.substitution.remove(variable);
+            shadowed.push((variable.clone(), previous));
+            let name = if let Some(fresh_name) = &mut self.fresh_name {
+                fresh_name(variable)
+            } else if self.range_variables.contains(variable) {
+                self.generate_fresh_name(variable)
+            } else {
+                return variable.clone();
+            };
+            self.substitution
+                .insert(variable.clone(), Expression::variable(name.clone()));
+            name
+        }
+        /// Restores the substitution of the enclosing scope.
+        fn unbind(&mut self, shadowed: Vec<(VariableSymbol, Option<Expression>)>) {
+            for (variable, previous) in shadowed.into_iter().rev() {
+                match previous {
+                    Some(value) => self.substitution.insert(variable, value),
+                    None => self.substitution.remove(&variable),
+                };
+            }
+        }
+        fn generate_fresh_name(&mut self, variable: &VariableSymbol) -> VariableSymbol {
+            let mut index = 0;
+            loop {
+                let name: VariableSymbol = format!("{}%{}", variable, index).into();
+                if self.used_names.insert(name.clone()) {
+                    return name;
+                }
+                index += 1;
+            }
+        }
+    }
+
+    impl<'a> ExpressionFolder for Substituter<'a> {
+        fn fold_variable(&mut self, variable: Variable) -> Expression {
+            match self.substitution.get(&variable.name) {
+                Some(value) => value.clone(),
+                None => Expression::Variable(variable),
+            }
+        }
+        fn fold_quantifier(&mut self, quantifier: Quantifier) -> Expression {
+            let Quantifier {
+                kind,
+                variables,
+                triggers,
+                body,
+            } = quantifier;
+            let mut shadowed = Vec::new();
+            let variables = variables
+                .into_iter()
+                .map(|variable| BoundedVariableDecl {
+                    name: self.bind(&variable.name, &mut shadowed),
+                    sort: variable.sort,
+                })
+                .collect();
+            let triggers = triggers
+                .into_iter()
+                .map(|trigger| self.fold_trigger(trigger))
+                .collect();
+            let body = self.fold_expression(*body);
+            self.unbind(shadowed);
+            Expression::Quantifier(Quantifier {
+                kind,
+                variables,
+                triggers,
+                body: Box::new(body),
+            })
+        }
+        fn fold_let(&mut self, binding: Let) -> Expression {
+            // The binding is not visible in the value.
+            let value = self.fold_expression(*binding.value);
+            let mut shadowed = Vec::new();
+            let variable = self.bind(&binding.variable, &mut shadowed);
+            let body = self.fold_expression(*binding.body);
+            self.unbind(shadowed);
+            Expression::let_in(variable, value, body)
+        }
+        fn fold_adt_match_case(&mut self, case: AdtMatchCase) -> AdtMatchCase {
+            let mut shadowed = Vec::new();
+            let binders = case
+                .binders
+                .iter()
+                .map(|binder| self.bind(binder, &mut shadowed))
+                .collect();
+            let body = self.fold_expression(*case.body);
+            self.unbind(shadowed);
+            AdtMatchCase {
+                constructor: case.constructor,
+                binders,
+                body: Box::new(body),
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+vir_raw_block! { Value =>
+    impl std::fmt::Display for Value {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Value::Bool(value) => value.fmt(f),
+                Value::Int(value) => value.fmt(f),
+                Value::Real { numerator, denominator } => {
+                    crate::common::real::write_rational(f, numerator, denominator)
+                }
+                Value::BitVector { value, width } => write!(f, "{}bv{}", value, width),
+                Value::Float { bits, exponent, significand } => {
+                    crate::common::float::write_value(f, *bits, *exponent, *significand)
+                }
+                Value::Array { entries, default } => {
+                    write!(f, "[")?;
+                    for (index, value) in entries {
+                        write!(f, "{} := {}, ", index, value)?;
+                    }
+                    write!(f, "_ := {}]", default)
+                }
+                Value::Seq(elements) => {
+                    write!(f, "[")?;
+                    for (i, element) in elements.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", element)?;
+                    }
+                    write!(f, "]")
+                }
+                Value::Adt { constructor, args } => {
+                    write!(f, "{}", constructor)?;
+                    if !args.is_empty() {
+                        write!(f, "(")?;
+                        for (i, arg) in args.iter().enumerate() {
+                            if i > 0 {
+                                write!(f, ", ")?;
+                            }
+                            write!(f, "{}", arg)?;
+                        }
+                        write!(f, ")")?;
+                    }
+                    Ok(())
+                }
+                Value::Uninterpreted { value, .. } => value.fmt(f),
+                Value::Opaque(value) => value.fmt(f),
+            }
+        }
+    }
+}
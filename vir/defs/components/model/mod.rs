@@ -5,15 +5,50 @@ trait Interface {
     type IdentSymbol;
 }
 
+mod display;
+
 pub enum Value {
     Bool(bool),
-    Int(i64),
-    Real,
+    Int(#[serde(with = "crate::common::integer")] crate::common::integer::BigInt),
+    /// A rational number in lowest terms with a positive denominator.
+    Real {
+        #[serde(with = "crate::common::integer")]
+        numerator: crate::common::integer::BigInt,
+        #[serde(with = "crate::common::integer")]
+        denominator: crate::common::integer::BigInt,
+    },
+    BitVector {
+        value: u128,
+        width: u32,
+    },
+    /// A floating-point number given by its bits.
+    Float {
+        bits: u128,
+        exponent: u32,
+        significand: u32,
+    },
+    /// An array that maps the indices in `entries` to their values and all
+    /// other indices to `default`.
+    Array {
+        entries: Vec<(Value, Value)>,
+        default: Box<Value>,
+    },
+    /// A sequence with the given elements.
+    Seq(Vec<Value>),
+    /// A value of an algebraic datatype, built by applying `constructor` to
+    /// `args`.
+    Adt {
+        constructor: IdentSymbol,
+        args: Vec<Value>,
+    },
     /// A value of a user-defined uninterpreted sort.
     Uninterpreted {
         sort: UninterpretedSortSymbol,
         value: UninterpretedSortValue,
     },
+    /// A value as printed by the solver in a form that is not parsed, such as
+    /// the interpretation of a function with arguments.
+    Opaque(String),
 }
 
 pub struct ModelItemArg {
@@ -5,7 +5,15 @@ vir_raw_block! { Sort =>
                 Sort::Bool => write!(f, "Bool"),
                 Sort::Int => write!(f, "Int"),
                 Sort::Real => write!(f, "Real"),
+                Sort::BitVector { width } => write!(f, "BV{}", width),
+                Sort::Float { exponent, significand } => {
+                    crate::common::float::write_format(f, *exponent, *significand)
+                }
+                Sort::Array { index, element } => write!(f, "Array<{}, {}>", index, element),
+                Sort::Seq { element } => write!(f, "Seq<{}>", element),
+                Sort::Set { element } => write!(f, "Set<{}>", element),
                 Sort::Uninterpreted { name } => write!(f, "{}", name),
+                Sort::Adt { name } => write!(f, "{}", name),
             }
         }
     }
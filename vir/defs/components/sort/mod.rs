@@ -1,5 +1,6 @@
 trait Interface {
     type UninterpretedSortSymbol;
+    type AdtNameSymbol;
     type Context;
 }
 
@@ -12,18 +13,150 @@ pub enum Sort {
     Bool,
     Int,
     Real,
+    /// A fixed-width bit-vector.
+    BitVector {
+        width: u32,
+    },
+    /// An IEEE 754 floating-point number. `significand` includes the hidden
+    /// bit.
+    Float {
+        exponent: u32,
+        significand: u32,
+    },
+    /// An SMT array, a total map from `index` to `element`.
+    Array {
+        index: Box<Sort>,
+        element: Box<Sort>,
+    },
+    /// A finite sequence, encoded as a Z3 sequence.
+    Seq {
+        element: Box<Sort>,
+    },
+    /// A set, encoded as an array from the elements to `Bool`.
+    Set {
+        element: Box<Sort>,
+    },
     /// A user-defined uninterpreted sort.
     Uninterpreted {
         name: UninterpretedSortSymbol,
     },
+    /// A user-defined algebraic datatype.
+    Adt {
+        name: AdtNameSymbol,
+    },
 }
 
 impl Sort {
+    pub fn bit_vector(width: u32) -> Self {
+        Sort::BitVector { width }
+    }
+    pub fn float(exponent: u32, significand: u32) -> Self {
+        Sort::Float {
+            exponent,
+            significand,
+        }
+    }
+    pub fn array(index: Sort, element: Sort) -> Self {
+        Sort::Array {
+            index: Box::new(index),
+            element: Box::new(element),
+        }
+    }
+    pub fn seq(element: Sort) -> Self {
+        Sort::Seq {
+            element: Box::new(element),
+        }
+    }
+    pub fn set(element: Sort) -> Self {
+        Sort::Set {
+            element: Box::new(element),
+        }
+    }
+    pub fn adt(name: AdtNameSymbol) -> Self {
+        Sort::Adt { name }
+    }
+    pub fn is_bool(&self) -> bool {
+        std::matches!(self, Sort::Bool)
+    }
     pub fn is_integer(&self) -> bool {
         std::matches!(self, Sort::Int)
     }
+    pub fn is_real(&self) -> bool {
+        std::matches!(self, Sort::Real)
+    }
+    pub fn is_bit_vector(&self) -> bool {
+        std::matches!(self, Sort::BitVector { .. })
+    }
+    /// The width of a bit-vector sort.
+    pub fn bit_vector_width(&self) -> Option<u32> {
+        match self {
+            Sort::BitVector { width } => Some(*width),
+            _ => None,
+        }
+    }
+    pub fn is_float(&self) -> bool {
+        std::matches!(self, Sort::Float { .. })
+    }
+    /// The exponent and significand widths of a floating-point sort.
+    pub fn float_format(&self) -> Option<(u32, u32)> {
+        match self {
+            Sort::Float {
+                exponent,
+                significand,
+            } => Some((*exponent, *significand)),
+            _ => None,
+        }
+    }
+    pub fn is_adt(&self) -> bool {
+        std::matches!(self, Sort::Adt { .. })
+    }
+    pub fn is_array(&self) -> bool {
+        std::matches!(self, Sort::Array { .. })
+    }
+    /// The element sort of an array sort.
+    pub fn array_element_sort(&self) -> Option<&Sort> {
+        match self {
+            Sort::Array { element, .. } => Some(element),
+            _ => None,
+        }
+    }
+    /// The element sort of a sequence or set sort.
+    pub fn collection_element_sort(&self) -> Option<&Sort> {
+        match self {
+            Sort::Seq { element } | Sort::Set { element } => Some(element),
+            _ => None,
+        }
+    }
+    /// The index and element sorts of an array sort.
+    pub fn array_sorts(&self) -> Option<(&Sort, &Sort)> {
+        match self {
+            Sort::Array { index, element } => Some((index, element)),
+            _ => None,
+        }
+    }
+    /// The element sort of a sequence sort.
+    pub fn seq_element_sort(&self) -> Option<&Sort> {
+        match self {
+            Sort::Seq { element } => Some(element),
+            _ => None,
+        }
+    }
+    /// The element sort of a set sort.
+    pub fn set_element_sort(&self) -> Option<&Sort> {
+        match self {
+            Sort::Set { element } => Some(element),
+            _ => None,
+        }
+    }
+    /// The name of an algebraic datatype sort.
+    pub fn adt_name(&self) -> Option<&AdtNameSymbol> {
+        match self {
+            Sort::Adt { name } => Some(name),
+            _ => None,
+        }
+    }
 }
 
 pub trait WithSort<C: Context> {
-    fn sort<'a>(&'a self, context: &'a C) -> &'a Sort;
+    fn sort(&self, context: &C) -> Sort;
 }
@@ -3,6 +3,11 @@ vir_raw_block! { Sort =>
         syn::custom_keyword!(Bool);
         syn::custom_keyword!(Int);
         syn::custom_keyword!(Real);
+        syn::custom_keyword!(Float);
+        syn::custom_keyword!(Array);
+        syn::custom_keyword!(Adt);
+        syn::custom_keyword!(Seq);
+        syn::custom_keyword!(Set);
     }
     impl syn::parse::Parse for Sort {
         fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -16,8 +21,68 @@ vir_raw_block! { Sort =>
             } else if lookahead.peek(kw::Real) {
                 input.parse::<kw::Real>()?;
                 Ok(Self::Real)
+            } else if lookahead.peek(kw::Float) {
+                input.parse::<kw::Float>()?;
+                input.parse::<syn::Token![<]>()?;
+                let exponent: syn::LitInt = input.parse()?;
+                input.parse::<syn::Token![,]>()?;
+                let significand: syn::LitInt = input.parse()?;
+                input.parse::<syn::Token![>]>()?;
+                let span = exponent.span();
+                let exponent = exponent.base10_parse()?;
+                let significand = significand.base10_parse()?;
+                if !crate::common::float::is_supported_format(exponent, significand) {
+                    return Err(syn::Error::new(
+                        span,
+                        "Expected at least 2 exponent and 2 significand bits, and at most 128 bits in total",
+                    ));
+                }
+                Ok(Self::Float { exponent, significand })
+            } else if lookahead.peek(kw::Array) {
+                input.parse::<kw::Array>()?;
+                input.parse::<syn::Token![<]>()?;
+                let index = input.parse()?;
+                input.parse::<syn::Token![,]>()?;
+                let element = input.parse()?;
+                input.parse::<syn::Token![>]>()?;
+                Ok(Self::Array {
+                    index: Box::new(index),
+                    element: Box::new(element),
+                })
+            } else if lookahead.peek(kw::Seq) {
+                input.parse::<kw::Seq>()?;
+                input.parse::<syn::Token![<]>()?;
+                let element = input.parse()?;
+                input.parse::<syn::Token![>]>()?;
+                Ok(Self::Seq {
+                    element: Box::new(element),
+                })
+            } else if lookahead.peek(kw::Set) {
+                input.parse::<kw::Set>()?;
+                input.parse::<syn::Token![<]>()?;
+                let element = input.parse()?;
+                input.parse::<syn::Token![>]>()?;
+                Ok(Self::Set {
+                    element: Box::new(element),
+                })
+            } else if lookahead.peek(kw::Adt) {
+                input.parse::<kw::Adt>()?;
+                input.parse::<syn::Token![<]>()?;
+                let name = input.parse()?;
+                input.parse::<syn::Token![>]>()?;
+                Ok(Self::Adt { name })
             } else {
-                Ok(Self::Uninterpreted { name: input.parse()? })
+                let name: syn::Ident = input.parse()?;
+                let width = name
+                    .to_string()
+                    .strip_prefix("BV")
+                    .and_then(|width| width.parse().ok());
+                let format = crate::common::float::format_from_name(&name.to_string());
+                match (width, format) {
+                    (Some(width), _) => Ok(Self::BitVector { width }),
+                    (_, Some((exponent, significand))) => Ok(Self::Float { exponent, significand }),
+                    _ => Ok(Self::Uninterpreted { name }),
+                }
             }
         }
     }
@@ -33,8 +98,52 @@ vir_raw_block! { Sort =>
                 Sort::Real => {
                     tokens.extend(quote::quote! {svirpti_vir::high::Sort::Real})
                 },
+                Sort::BitVector { width } => {
+                    tokens.extend(quote::quote! {
+                        svirpti_vir::high::Sort::BitVector(svirpti_vir::high::BitVectorType { width: #width })
+                    })
+                },
+                Sort::Float { exponent, significand } => {
+                    tokens.extend(quote::quote! {
+                        svirpti_vir::high::Sort::Float(svirpti_vir::high::FloatType {
+                            exponent: #exponent,
+                            significand: #significand,
+                        })
+                    })
+                },
+                Sort::Array { index, element } => {
+                    tokens.extend(quote::quote! {
+                        svirpti_vir::high::Sort::Array(svirpti_vir::high::ArrayType {
+                            index: Box::new(#index),
+                            element: Box::new(#element),
+                        })
+                    })
+                },
+                Sort::Seq { element } => {
+                    tokens.extend(quote::quote! {
+                        svirpti_vir::high::Sort::Seq(svirpti_vir::high::SeqType {
+                            element: Box::new(#element),
+                        })
+                    })
+                },
+                Sort::Set { element } => {
+                    tokens.extend(quote::quote! {
+                        svirpti_vir::high::Sort::Set(svirpti_vir::high::SetType {
+                            element: Box::new(#element),
+                        })
+                    })
+                },
                 Sort::Uninterpreted { name } => {
-                    tokens.extend(quote::quote! {svirpti_vir::high::Sort::Uninterpreted { name: #name }})
+                    let name = name.to_string();
+                    tokens.extend(quote::quote! {
+                        svirpti_vir::high::Sort::Domain(svirpti_vir::high::DomainType { name: #name.into() })
+                    })
+                },
+                Sort::Adt { name } => {
+                    let name = name.to_string();
+                    tokens.extend(quote::quote! {
+                        svirpti_vir::high::Sort::Reference(svirpti_vir::high::ReferenceType { name: #name.into() })
+                    })
                 },
             }
         }
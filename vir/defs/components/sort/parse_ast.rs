@@ -2,8 +2,35 @@ pub enum Sort {
     Bool,
     Int,
     Real,
+    /// A fixed-width bit-vector, written as `BV<width>`, for example `BV32`.
+    BitVector {
+        width: u32,
+    },
+    /// A floating-point number, written as `F16`, `F32`, `F64`, `F128`, or
+    /// `Float<exponent, significand>`.
+    Float {
+        exponent: u32,
+        significand: u32,
+    },
+    /// An SMT array, written as `Array<Index, Element>`.
+    Array {
+        index: Box<Sort>,
+        element: Box<Sort>,
+    },
+    /// A sequence, written as `Seq<Element>`.
+    Seq {
+        element: Box<Sort>,
+    },
+    /// A set, written as `Set<Element>`.
+    Set {
+        element: Box<Sort>,
+    },
     /// A user-defined uninterpreted sort.
     Uninterpreted {
         name: syn::Ident,
     },
+    /// A user-defined algebraic datatype, written as `Adt<Name>`.
+    Adt {
+        name: syn::Ident,
+    },
 }
@@ -14,9 +14,31 @@ vir_raw_block! { Sort =>
                 Sort::Bool => write!(writer, "Bool")?,
                 Sort::Int => write!(writer, "Int")?,
                 Sort::Real => write!(writer, "Real")?,
+                Sort::BitVector { width } => write!(writer, "(_ BitVec {})", width)?,
+                Sort::Float { exponent, significand } => {
+                    write!(writer, "(_ FloatingPoint {} {})", exponent, significand)?
+                }
+                Sort::Array { index, element } => {
+                    write!(writer, "(Array ")?;
+                    index.sort_to_smt2(writer, context)?;
+                    write!(writer, " ")?;
+                    element.sort_to_smt2(writer, context)?;
+                    write!(writer, ")")?;
+                }
+                Sort::Seq { element } => {
+                    write!(writer, "(Seq ")?;
+                    element.sort_to_smt2(writer, context)?;
+                    write!(writer, ")")?;
+                }
+                Sort::Set { element } => {
+                    write!(writer, "(Array ")?;
+                    element.sort_to_smt2(writer, context)?;
+                    write!(writer, " Bool)")?;
+                }
                 Sort::Uninterpreted {
                     name
                 } => context.write_uninterpreted_sort_name(writer, name)?,
+                Sort::Adt { name } => ::rsmt2::print::Sym2Smt::sym_to_smt2(name, writer, context)?,
             }
             Ok(())
         }
@@ -2,6 +2,9 @@ vir_raw_block! { Assert =>
     impl std::fmt::Display for Assert {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             write!(f, "assert ")?;
+            if let Some(position) = &self.position {
+                write!(f, "{} ", position)?;
+            }
             if let Some(label) = &self.label {
                 write!(f, "{} ", label)?;
             }
@@ -14,6 +17,9 @@ vir_raw_block! { Assume =>
     impl std::fmt::Display for Assume {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             write!(f, "assume ")?;
+            if let Some(position) = &self.position {
+                write!(f, "{} ", position)?;
+            }
             if let Some(label) = &self.label {
                 write!(f, "{} ", label)?;
             }
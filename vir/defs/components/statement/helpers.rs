@@ -7,22 +7,22 @@ vir_raw_block! { AssumeAssertHelpers =>
         type LabelSymbol = LabelSymbol;
         fn assume(assertion: Expression) -> Statement {
             Statement::Assume(Assume {
-                assertion, label: None,
+                assertion, label: None, position: None,
             })
         }
         fn assume_with_label(assertion: Expression, label: LabelSymbol) -> Statement {
             Statement::Assume(Assume {
-                assertion, label: Some(label),
+                assertion, label: Some(label), position: None,
             })
         }
         fn assert(assertion: Expression) -> Statement {
             Statement::Assert(Assert {
-                assertion, label: None,
+                assertion, label: None, position: None,
             })
         }
         fn assert_with_label(assertion: Expression, label: LabelSymbol) -> Statement {
             Statement::Assert(Assert {
-                assertion, label: Some(label),
+                assertion, label: Some(label), position: None,
             })
         }
     }
@@ -12,11 +12,16 @@ mod display;
 pub struct Assert {
     pub assertion: Expression,
     pub label: Option<LabelSymbol>,
+    /// The position of the statement in the source of the frontend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<crate::common::position::Position>,
 }
 
 pub struct Assume {
     pub assertion: Expression,
     pub label: Option<LabelSymbol>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<crate::common::position::Position>,
 }
 
 pub enum Havoc {
@@ -26,4 +31,6 @@ pub enum Havoc {
 pub struct Assign {
     pub variable: Variable,
     pub expression: Expression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<crate::common::position::Position>,
 }
\ No newline at end of file
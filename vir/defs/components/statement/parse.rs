@@ -24,7 +24,7 @@ vir_raw_block! { Assert =>
             let label = self.label.to_string();
             let assertion = &self.assertion;
             tokens.extend(quote::quote! {
-                Assert { label: Some(#label.into()), assertion: #assertion }
+                Assert { label: Some(#label.into()), assertion: #assertion, position: None }
             })
         }
     }
@@ -47,7 +47,7 @@ vir_raw_block! { Assume =>
             let label = self.label.to_string();
             let assertion = &self.assertion;
             tokens.extend(quote::quote! {
-                Assume { label: Some(#label.into()), assertion: #assertion }
+                Assume { label: Some(#label.into()), assertion: #assertion, position: None }
             })
         }
     }
@@ -91,7 +91,7 @@ vir_raw_block! { Assign =>
             let variable = &self.variable;
             let expression = &self.expression;
             tokens.extend(quote::quote! {
-                Assign { variable: #variable, expression: #expression }
+                Assign { variable: #variable, expression: #expression, position: None }
             })
         }
     }
@@ -5,7 +5,13 @@ vir_raw_block! { Type =>
                 Type::Bool => write!(f, "Bool"),
                 Type::Int => write!(f, "Int"),
                 Type::Real => write!(f, "Real"),
+                Type::BitVector(bit_vector) => write!(f, "{}", bit_vector),
+                Type::Float(float) => write!(f, "{}", float),
+                Type::Array(array) => write!(f, "{}", array),
+                Type::Seq(seq) => write!(f, "{}", seq),
+                Type::Set(set) => write!(f, "{}", set),
                 Type::Domain(domain) => write!(f, "{}", domain),
+                Type::Reference(reference) => write!(f, "{}", reference),
             }
         }
     }
@@ -17,3 +23,45 @@ vir_raw_block! { DomainType =>
         }
     }
 }
+vir_raw_block! { BitVectorType =>
+    impl std::fmt::Display for BitVectorType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "BV{}", self.width)
+        }
+    }
+}
+vir_raw_block! { FloatType =>
+    impl std::fmt::Display for FloatType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            crate::common::float::write_format(f, self.exponent, self.significand)
+        }
+    }
+}
+vir_raw_block! { ArrayType =>
+    impl std::fmt::Display for ArrayType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Array<{}, {}>", self.index, self.element)
+        }
+    }
+}
+vir_raw_block! { SeqType =>
+    impl std::fmt::Display for SeqType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Seq<{}>", self.element)
+        }
+    }
+}
+vir_raw_block! { SetType =>
+    impl std::fmt::Display for SetType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Set<{}>", self.element)
+        }
+    }
+}
+vir_raw_block! { ReferenceType =>
+    impl std::fmt::Display for ReferenceType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Adt<{}>", self.name)
+        }
+    }
+}
@@ -9,13 +9,133 @@ pub enum Type {
     Int,
     Bool,
     Real,
+    BitVector(BitVectorType),
+    Float(FloatType),
+    Array(ArrayType),
+    Seq(SeqType),
+    Set(SetType),
     Domain(DomainType),
+    Reference(ReferenceType),
+}
+
+pub struct BitVectorType {
+    pub width: u32,
+}
+
+/// An IEEE 754 floating-point number. `significand` includes the hidden bit.
+pub struct FloatType {
+    pub exponent: u32,
+    pub significand: u32,
+}
+
+pub struct ArrayType {
+    pub index: Box<Type>,
+    pub element: Box<Type>,
+}
+
+pub struct SeqType {
+    pub element: Box<Type>,
+}
+
+pub struct SetType {
+    pub element: Box<Type>,
 }
 
 pub struct DomainType {
     pub name: UninterpretedSortSymbol,
 }
 
+/// A reference to an algebraic datatype.
 pub struct ReferenceType {
     pub name: AdtNameSymbol,
 }
+
+/// The same helpers as the ones of `Sort` in the IRs that use sorts, so that
+/// components can be written for both.
+impl Type {
+    pub fn bit_vector(width: u32) -> Self {
+        Type::BitVector(BitVectorType { width })
+    }
+    pub fn float(exponent: u32, significand: u32) -> Self {
+        Type::Float(FloatType {
+            exponent,
+            significand,
+        })
+    }
+    pub fn array(index: Type, element: Type) -> Self {
+        Type::Array(ArrayType {
+            index: Box::new(index),
+            element: Box::new(element),
+        })
+    }
+    pub fn seq(element: Type) -> Self {
+        Type::Seq(SeqType {
+            element: Box::new(element),
+        })
+    }
+    pub fn set(element: Type) -> Self {
+        Type::Set(SetType {
+            element: Box::new(element),
+        })
+    }
+    pub fn adt(name: AdtNameSymbol) -> Self {
+        Type::Reference(ReferenceType { name })
+    }
+    pub fn is_bool(&self) -> bool {
+        std::matches!(self, Type::Bool)
+    }
+    pub fn is_integer(&self) -> bool {
+        std::matches!(self, Type::Int)
+    }
+    pub fn is_real(&self) -> bool {
+        std::matches!(self, Type::Real)
+    }
+    pub fn is_bit_vector(&self) -> bool {
+        std::matches!(self, Type::BitVector(_))
+    }
+    /// The width of a bit-vector type.
+    pub fn bit_vector_width(&self) -> Option<u32> {
+        match self {
+            Type::BitVector(bit_vector) => Some(bit_vector.width),
+            _ => None,
+        }
+    }
+    pub fn is_float(&self) -> bool {
+        std::matches!(self, Type::Float(_))
+    }
+    /// The exponent and significand widths of a floating-point type.
+    pub fn float_format(&self) -> Option<(u32, u32)> {
+        match self {
+            Type::Float(float) => Some((float.exponent, float.significand)),
+            _ => None,
+        }
+    }
+    /// The index and element types of an array type.
+    pub fn array_sorts(&self) -> Option<(&Type, &Type)> {
+        match self {
+            Type::Array(array) => Some((&array.index, &array.element)),
+            _ => None,
+        }
+    }
+    /// The element type of a sequence type.
+    pub fn seq_element_sort(&self) -> Option<&Type> {
+        match self {
+            Type::Seq(seq) => Some(&seq.element),
+            _ => None,
+        }
+    }
+    /// The element type of a set type.
+    pub fn set_element_sort(&self) -> Option<&Type> {
+        match self {
+            Type::Set(set) => Some(&set.element),
+            _ => None,
+        }
+    }
+    /// The name of the algebraic datatype of a reference type.
+    pub fn adt_name(&self) -> Option<&AdtNameSymbol> {
+        match self {
+            Type::Reference(reference) => Some(&reference.name),
+            _ => None,
+        }
+    }
+}
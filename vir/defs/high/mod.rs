@@ -2,17 +2,27 @@ pub mod context;
 pub mod declaration;
 pub mod expression;
 pub mod parse;
+pub mod print;
 pub mod program;
 pub mod statement;
 pub mod typ;
 
 pub use context::*;
-pub use declaration::VariableDeclaration;
+pub use declaration::{
+    AdtConstructorDeclaration, AdtDeclaration, AdtFieldDeclaration, AxiomDeclaration,
+    FunctionDeclaration, UninterpretedSortDeclaration, VariableDeclaration,
+};
 pub use expression::{
-    BinaryOperation, BinaryOperationKind, BoundedVariableDecl, Conditional, Constant,
-    FunctionApplication, Quantifier, QuantifierKind, Trigger, UnaryOperation, UnaryOperationKind,
-    Variable,
+    AdtConstructor, AdtFieldAccess, AdtMatch, AdtMatchCase, AdtTester, ArraySelect, ArrayStore,
+    BinaryOperation, BinaryOperationKind, BitVectorConversion, BitVectorConversionKind,
+    BoundedVariableDecl, Conditional, Constant, ConstantArray, EmptySeq, EmptySet,
+    ExpressionFolder, ExpressionWalker, ExpressionWalkerMut, FloatConversion, FloatConversionKind,
+    FloatOperation, FloatOperationKind, FunctionApplication, InferSort, Let, PositionedExpression,
+    Quantifier, QuantifierKind, SeqOperation, SeqOperationKind, SetOperation, SetOperationKind,
+    SortError, SortScope, SymbolTable, Trigger, UnaryOperation, UnaryOperationKind, Variable,
 };
 pub use program::{BasicBlock, BasicBlockId, ProcedureDeclaration, ProgramFragment};
 pub use statement::{Assert, Assume, Havoc, Assign};
-pub use typ::{DomainType, Type};
+pub use typ::{
+    ArrayType, BitVectorType, DomainType, FloatType, ReferenceType, SeqType, SetType, Type,
+};
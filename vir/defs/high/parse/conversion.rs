@@ -0,0 +1,737 @@
+//! Converts the parsed syntax tree directly into `high` values. This is the
+//! run-time counterpart of the `ToTokens` implementations that `vir_high!`
+//! uses and produces the same values.
+
+use crate::common::float::{F32, F64};
+use crate::common::integer::BigInt;
+use crate::common::real;
+use crate::high;
+use crate::high::parse as ast;
+
+/// An error in the textual representation of a program fragment.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("line {line}, column {column}: {message}")]
+pub struct ParseError {
+    /// The line of the offending token, starting from 1.
+    pub line: usize,
+    /// The column of the offending token, starting from 1.
+    pub column: usize,
+    pub message: String,
+}
+
+impl From<syn::Error> for ParseError {
+    fn from(error: syn::Error) -> Self {
+        let start = error.span().start();
+        Self {
+            line: start.line,
+            column: start.column + 1,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Parses a program fragment written in the syntax accepted by `vir_high!`.
+pub(crate) fn parse_program_fragment(source: &str) -> Result<high::ProgramFragment, ParseError> {
+    let fragment: ast::ProgramFragment = syn::parse_str(source)?;
+    Ok(fragment.into_high()?)
+}
+
+pub(crate) trait IntoHigh {
+    type Target;
+    fn into_high(self) -> syn::Result<Self::Target>;
+}
+
+impl<T: IntoHigh> IntoHigh for Vec<T> {
+    type Target = Vec<T::Target>;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        self.into_iter().map(IntoHigh::into_high).collect()
+    }
+}
+
+impl<T: IntoHigh> IntoHigh for Box<T> {
+    type Target = Box<T::Target>;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(Box::new((*self).into_high()?))
+    }
+}
+
+fn hole_error(ident: &syn::Ident) -> syn::Error {
+    syn::Error::new(
+        ident.span(),
+        format!("`#{}` can only be filled in by `vir_high!`", ident),
+    )
+}
+
+impl IntoHigh for ast::ProgramFragment {
+    type Target = high::ProgramFragment;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::ProgramFragment {
+            sorts: self.sorts.into_high()?,
+            adts: self.adts.into_high()?,
+            axioms: self.axioms.into_high()?,
+            functions: self.functions.into_high()?,
+            procedure: self.procedure.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::program::ProcedureDeclaration {
+    type Target = high::ProcedureDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        let mut basic_blocks = vec![high::BasicBlock {
+            label: "entry".into(),
+            guard: true.into(),
+            statements: Vec::new().into(),
+            successors: vec![1.into()],
+        }];
+        for basic_block in self.basic_blocks {
+            basic_blocks.push(basic_block.into_high()?);
+        }
+        basic_blocks.push(high::BasicBlock {
+            label: "exit".into(),
+            guard: true.into(),
+            statements: Vec::new().into(),
+            successors: Vec::new(),
+        });
+        Ok(high::ProcedureDeclaration {
+            variables: self.variables.into_high()?.into(),
+            basic_blocks: basic_blocks.into(),
+        })
+    }
+}
+
+impl IntoHigh for ast::program::BasicBlock {
+    type Target = high::BasicBlock;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::BasicBlock {
+            label: self.label.to_string().into(),
+            guard: self.guard.into_high()?,
+            statements: self.statements.into_high()?.into(),
+            successors: self.successors.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+impl IntoHigh for ast::declaration::UninterpretedSortDeclaration {
+    type Target = high::declaration::UninterpretedSortDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::declaration::UninterpretedSortDeclaration {
+            name: self.name.to_string().into(),
+        })
+    }
+}
+
+impl IntoHigh for ast::declaration::VariableDeclaration {
+    type Target = high::VariableDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::VariableDeclaration {
+            name: self.name.to_string().into(),
+            sort: self.sort.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::declaration::FunctionDeclaration {
+    type Target = high::FunctionDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        let body = match self.body {
+            Some(body) => Some(body.into_high()?),
+            None => None,
+        };
+        Ok(high::FunctionDeclaration {
+            name: self.name.to_string().into(),
+            parameters: self.parameters.into_high()?,
+            return_sort: self.return_sort.into_high()?,
+            preconditions: self.preconditions.into_high()?,
+            body,
+        })
+    }
+}
+
+impl IntoHigh for ast::declaration::AxiomDeclaration {
+    type Target = high::declaration::AxiomDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::declaration::AxiomDeclaration {
+            name: Some(self.name.to_string().into()),
+            body: self.body.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::declaration::AdtDeclaration {
+    type Target = high::AdtDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtDeclaration {
+            name: self.name.to_string().into(),
+            constructors: self.constructors.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::declaration::AdtConstructorDeclaration {
+    type Target = high::AdtConstructorDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtConstructorDeclaration {
+            name: self.name.to_string().into(),
+            fields: self.fields.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::declaration::AdtFieldDeclaration {
+    type Target = high::AdtFieldDeclaration;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtFieldDeclaration {
+            name: self.name.to_string().into(),
+            sort: self.sort.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::sort::Sort {
+    type Target = high::Type;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        let sort = match self {
+            ast::sort::Sort::Bool => high::Type::Bool,
+            ast::sort::Sort::Int => high::Type::Int,
+            ast::sort::Sort::Real => high::Type::Real,
+            ast::sort::Sort::BitVector { width } => {
+                high::Type::BitVector(high::BitVectorType { width })
+            }
+            ast::sort::Sort::Float {
+                exponent,
+                significand,
+            } => high::Type::Float(high::FloatType {
+                exponent,
+                significand,
+            }),
+            ast::sort::Sort::Array { index, element } => high::Type::Array(high::ArrayType {
+                index: index.into_high()?,
+                element: element.into_high()?,
+            }),
+            ast::sort::Sort::Seq { element } => high::Type::Seq(high::SeqType {
+                element: element.into_high()?,
+            }),
+            ast::sort::Sort::Set { element } => high::Type::Set(high::SetType {
+                element: element.into_high()?,
+            }),
+            ast::sort::Sort::Uninterpreted { name } => high::Type::Domain(high::DomainType {
+                name: name.to_string().into(),
+            }),
+            ast::sort::Sort::Adt { name } => high::Type::Reference(high::ReferenceType {
+                name: name.to_string().into(),
+            }),
+        };
+        Ok(sort)
+    }
+}
+
+impl IntoHigh for ast::statement::Statement {
+    type Target = high::statement::Statement;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        let statement = match self {
+            ast::statement::Statement::Assume(statement) => {
+                high::statement::Statement::Assume(high::Assume {
+                    label: Some(statement.label.to_string().into()),
+                    assertion: statement.assertion.into_high()?,
+                    position: None,
+                })
+            }
+            ast::statement::Statement::Assert(statement) => {
+                high::statement::Statement::Assert(high::Assert {
+                    label: Some(statement.label.to_string().into()),
+                    assertion: statement.assertion.into_high()?,
+                    position: None,
+                })
+            }
+            ast::statement::Statement::Havoc(ast::statement::Havoc::Variable(variable)) => {
+                high::statement::Statement::Havoc(high::Havoc::Variable(variable.into_high()?))
+            }
+            ast::statement::Statement::Assign(statement) => {
+                high::statement::Statement::Assign(high::Assign {
+                    variable: statement.variable.into_high()?,
+                    expression: statement.expression.into_high()?,
+                    position: None,
+                })
+            }
+            ast::statement::Statement::Hole(ident) => return Err(hole_error(&ident)),
+        };
+        Ok(statement)
+    }
+}
+
+impl IntoHigh for ast::expression::Expression {
+    type Target = high::Expression;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::Expression as E;
+        let expression = match self {
+            E::Variable(expr) => high::Expression::Variable(expr.into_high()?),
+            E::Constant(expr) => high::Expression::Constant(expr.into_high()?),
+            E::UnaryOperation(expr) => high::Expression::UnaryOperation(expr.into_high()?),
+            E::BinaryOperation(expr) => high::Expression::BinaryOperation(expr.into_high()?),
+            E::BitVectorConversion(expr) => {
+                high::Expression::BitVectorConversion(expr.into_high()?)
+            }
+            E::ArraySelect(expr) => high::Expression::ArraySelect(expr.into_high()?),
+            E::ArrayStore(expr) => high::Expression::ArrayStore(expr.into_high()?),
+            E::ConstantArray(expr) => high::Expression::ConstantArray(expr.into_high()?),
+            E::AdtConstructor(expr) => high::Expression::AdtConstructor(expr.into_high()?),
+            E::AdtFieldAccess(expr) => high::Expression::AdtFieldAccess(expr.into_high()?),
+            E::AdtTester(expr) => high::Expression::AdtTester(expr.into_high()?),
+            E::AdtMatch(expr) => high::Expression::AdtMatch(expr.into_high()?),
+            E::Conditional(expr) => high::Expression::Conditional(expr.into_high()?),
+            E::Quantifier(expr) => high::Expression::Quantifier(expr.into_high()?),
+            E::FunctionApplication(expr) => {
+                high::Expression::FunctionApplication(expr.into_high()?)
+            }
+            E::FloatOperation(expr) => high::Expression::FloatOperation(expr.into_high()?),
+            E::FloatConversion(expr) => high::Expression::FloatConversion(expr.into_high()?),
+            E::SeqOperation(expr) => high::Expression::SeqOperation(expr.into_high()?),
+            E::EmptySeq(expr) => high::Expression::EmptySeq(expr.into_high()?),
+            E::SetOperation(expr) => high::Expression::SetOperation(expr.into_high()?),
+            E::EmptySet(expr) => high::Expression::EmptySet(expr.into_high()?),
+            E::Let(expr) => high::Expression::Let(expr.into_high()?),
+            E::Hole(ident) => return Err(hole_error(&ident)),
+        };
+        Ok(expression)
+    }
+}
+
+impl IntoHigh for ast::expression::Variable {
+    type Target = high::Variable;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::Variable {
+            name: self.name.to_string().into(),
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::Constant {
+    type Target = high::Constant;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        let constant = match &self.literal {
+            syn::Lit::Bool(literal) => high::Constant::Bool(literal.value),
+            // Bit-vector literals are written with a width suffix: `5bv32`.
+            syn::Lit::Int(int) if int.suffix().starts_with("bv") => {
+                let width = int.suffix()["bv".len()..]
+                    .parse()
+                    .map_err(|_| syn::Error::new(int.span(), "invalid bit-vector width"))?;
+                high::Constant::BitVector {
+                    value: int.base10_parse()?,
+                    width,
+                }
+            }
+            syn::Lit::Int(int) if int.suffix().is_empty() => {
+                let value = int
+                    .base10_digits()
+                    .parse::<BigInt>()
+                    .map_err(|error| syn::Error::new(int.span(), error))?;
+                high::Constant::Int(value)
+            }
+            // Float literals are written with a Rust suffix: `1.5f32`.
+            syn::Lit::Float(float) if float.suffix() == "f32" => {
+                let (exponent, significand) = F32;
+                high::Constant::Float {
+                    bits: u128::from(float.base10_parse::<f32>()?.to_bits()),
+                    exponent,
+                    significand,
+                }
+            }
+            syn::Lit::Float(float) if float.suffix() == "f64" => {
+                let (exponent, significand) = F64;
+                high::Constant::Float {
+                    bits: u128::from(float.base10_parse::<f64>()?.to_bits()),
+                    exponent,
+                    significand,
+                }
+            }
+            // Real literals are written in decimal notation: `1.25`.
+            syn::Lit::Float(float) => {
+                let (numerator, denominator) = real::parse_decimal(float.base10_digits())
+                    .ok_or_else(|| syn::Error::new(float.span(), "invalid real literal"))?;
+                high::Constant::Real {
+                    numerator,
+                    denominator,
+                }
+            }
+            literal => return Err(syn::Error::new(literal.span(), "unsupported literal")),
+        };
+        Ok(constant)
+    }
+}
+
+impl IntoHigh for ast::expression::UnaryOperation {
+    type Target = high::UnaryOperation;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::UnaryOperationKind as K;
+        let kind = match self.kind {
+            K::Not => high::UnaryOperationKind::Not,
+            K::Minus => high::UnaryOperationKind::Minus,
+            K::ToReal => high::UnaryOperationKind::ToReal,
+            K::ToInt => high::UnaryOperationKind::ToInt,
+            K::IsInt => high::UnaryOperationKind::IsInt,
+        };
+        Ok(high::UnaryOperation {
+            kind,
+            arg: self.arg.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::BinaryOperation {
+    type Target = high::BinaryOperation;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::BinaryOperationKind as K;
+        let kind = match self.kind {
+            K::EqCmp => high::BinaryOperationKind::EqCmp,
+            K::NeCmp => high::BinaryOperationKind::NeCmp,
+            K::GtCmp => high::BinaryOperationKind::GtCmp,
+            K::GeCmp => high::BinaryOperationKind::GeCmp,
+            K::LtCmp => high::BinaryOperationKind::LtCmp,
+            K::LeCmp => high::BinaryOperationKind::LeCmp,
+            K::Add => high::BinaryOperationKind::Add,
+            K::Sub => high::BinaryOperationKind::Sub,
+            K::Mul => high::BinaryOperationKind::Mul,
+            K::Div => high::BinaryOperationKind::Div,
+            K::Mod => high::BinaryOperationKind::Mod,
+            K::And => high::BinaryOperationKind::And,
+            K::Or => high::BinaryOperationKind::Or,
+            K::Implies => high::BinaryOperationKind::Implies,
+            K::BitAnd => high::BinaryOperationKind::BitAnd,
+            K::BitOr => high::BinaryOperationKind::BitOr,
+            K::BitXor => high::BinaryOperationKind::BitXor,
+            K::Shl => high::BinaryOperationKind::Shl,
+            K::LShr => high::BinaryOperationKind::LShr,
+            K::AShr => high::BinaryOperationKind::AShr,
+            K::SDiv => high::BinaryOperationKind::SDiv,
+            K::SRem => high::BinaryOperationKind::SRem,
+            K::SGtCmp => high::BinaryOperationKind::SGtCmp,
+            K::SGeCmp => high::BinaryOperationKind::SGeCmp,
+            K::SLtCmp => high::BinaryOperationKind::SLtCmp,
+            K::SLeCmp => high::BinaryOperationKind::SLeCmp,
+            K::Concat => high::BinaryOperationKind::Concat,
+        };
+        Ok(high::BinaryOperation {
+            kind,
+            left: self.left.into_high()?,
+            right: self.right.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::BitVectorConversion {
+    type Target = high::BitVectorConversion;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::BitVectorConversionKind as K;
+        let kind = match self.kind {
+            K::Extract { high: upper, low } => {
+                high::BitVectorConversionKind::Extract { high: upper, low }
+            }
+            K::ZeroExtend { bits } => high::BitVectorConversionKind::ZeroExtend { bits },
+            K::SignExtend { bits } => high::BitVectorConversionKind::SignExtend { bits },
+            K::FromInt { width } => high::BitVectorConversionKind::FromInt { width },
+            K::ToInt => high::BitVectorConversionKind::ToInt,
+        };
+        Ok(high::BitVectorConversion {
+            kind,
+            arg: self.arg.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::ArraySelect {
+    type Target = high::ArraySelect;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::ArraySelect {
+            array: self.array.into_high()?,
+            index: self.index.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::ArrayStore {
+    type Target = high::ArrayStore;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::ArrayStore {
+            array: self.array.into_high()?,
+            index: self.index.into_high()?,
+            value: self.value.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::ConstantArray {
+    type Target = high::ConstantArray;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::ConstantArray {
+            index_sort: self.index_sort.into_high()?,
+            value: self.value.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::FloatOperation {
+    type Target = high::FloatOperation;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::FloatOperationKind as K;
+        let kind = match self.kind {
+            K::Add(rounding_mode) => high::FloatOperationKind::Add(rounding_mode),
+            K::Sub(rounding_mode) => high::FloatOperationKind::Sub(rounding_mode),
+            K::Mul(rounding_mode) => high::FloatOperationKind::Mul(rounding_mode),
+            K::Div(rounding_mode) => high::FloatOperationKind::Div(rounding_mode),
+            K::Fma(rounding_mode) => high::FloatOperationKind::Fma(rounding_mode),
+            K::Sqrt(rounding_mode) => high::FloatOperationKind::Sqrt(rounding_mode),
+            K::RoundToIntegral(rounding_mode) => {
+                high::FloatOperationKind::RoundToIntegral(rounding_mode)
+            }
+            K::Rem => high::FloatOperationKind::Rem,
+            K::Abs => high::FloatOperationKind::Abs,
+            K::Min => high::FloatOperationKind::Min,
+            K::Max => high::FloatOperationKind::Max,
+            K::Eq => high::FloatOperationKind::Eq,
+            K::IsNormal => high::FloatOperationKind::IsNormal,
+            K::IsSubnormal => high::FloatOperationKind::IsSubnormal,
+            K::IsZero => high::FloatOperationKind::IsZero,
+            K::IsInfinite => high::FloatOperationKind::IsInfinite,
+            K::IsNaN => high::FloatOperationKind::IsNaN,
+            K::IsNegative => high::FloatOperationKind::IsNegative,
+            K::IsPositive => high::FloatOperationKind::IsPositive,
+        };
+        Ok(high::FloatOperation {
+            kind,
+            args: self.args.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::FloatConversion {
+    type Target = high::FloatConversion;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::FloatConversionKind as K;
+        let kind = match self.kind {
+            K::ToFloat {
+                rounding_mode,
+                exponent,
+                significand,
+            } => high::FloatConversionKind::ToFloat {
+                rounding_mode,
+                exponent,
+                significand,
+            },
+            K::UnsignedToFloat {
+                rounding_mode,
+                exponent,
+                significand,
+            } => high::FloatConversionKind::UnsignedToFloat {
+                rounding_mode,
+                exponent,
+                significand,
+            },
+            K::FromBits {
+                exponent,
+                significand,
+            } => high::FloatConversionKind::FromBits {
+                exponent,
+                significand,
+            },
+            K::ToReal => high::FloatConversionKind::ToReal,
+            K::ToSigned {
+                rounding_mode,
+                width,
+            } => high::FloatConversionKind::ToSigned {
+                rounding_mode,
+                width,
+            },
+            K::ToUnsigned {
+                rounding_mode,
+                width,
+            } => high::FloatConversionKind::ToUnsigned {
+                rounding_mode,
+                width,
+            },
+        };
+        Ok(high::FloatConversion {
+            kind,
+            arg: self.arg.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::SeqOperation {
+    type Target = high::SeqOperation;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::SeqOperationKind as K;
+        let kind = match self.kind {
+            K::Unit => high::SeqOperationKind::Unit,
+            K::Length => high::SeqOperationKind::Length,
+            K::Index => high::SeqOperationKind::Index,
+            K::Concat => high::SeqOperationKind::Concat,
+            K::Subsequence => high::SeqOperationKind::Subsequence,
+            K::Update => high::SeqOperationKind::Update,
+        };
+        Ok(high::SeqOperation {
+            kind,
+            args: self.args.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::EmptySeq {
+    type Target = high::EmptySeq;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::EmptySeq {
+            element_sort: self.element_sort.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::SetOperation {
+    type Target = high::SetOperation;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        use ast::expression::SetOperationKind as K;
+        let kind = match self.kind {
+            K::Singleton => high::SetOperationKind::Singleton,
+            K::Contains => high::SetOperationKind::Contains,
+            K::Union => high::SetOperationKind::Union,
+            K::Intersection => high::SetOperationKind::Intersection,
+            K::Subset => high::SetOperationKind::Subset,
+        };
+        Ok(high::SetOperation {
+            kind,
+            args: self.args.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::EmptySet {
+    type Target = high::EmptySet;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::EmptySet {
+            element_sort: self.element_sort.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::AdtConstructor {
+    type Target = high::AdtConstructor;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtConstructor {
+            adt: self.adt.to_string().into(),
+            constructor: self.constructor.to_string().into(),
+            args: self.args.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::AdtFieldAccess {
+    type Target = high::AdtFieldAccess;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtFieldAccess {
+            base: self.base.into_high()?,
+            field: self.field.to_string().into(),
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::AdtTester {
+    type Target = high::AdtTester;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtTester {
+            base: self.base.into_high()?,
+            constructor: self.constructor.to_string().into(),
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::AdtMatch {
+    type Target = high::AdtMatch;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtMatch {
+            adt: self.adt.to_string().into(),
+            base: self.base.into_high()?,
+            cases: self.cases.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::AdtMatchCase {
+    type Target = high::AdtMatchCase;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::AdtMatchCase {
+            constructor: self.constructor.to_string().into(),
+            binders: self
+                .binders
+                .into_iter()
+                .map(|binder| binder.to_string().into())
+                .collect(),
+            body: self.body.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::Conditional {
+    type Target = high::Conditional;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::Conditional {
+            guard: self.guard.into_high()?,
+            then_expr: self.then_expr.into_high()?,
+            else_expr: self.else_expr.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::Let {
+    type Target = high::Let;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::Let {
+            variable: self.variable.to_string().into(),
+            value: self.value.into_high()?,
+            body: self.body.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::Quantifier {
+    type Target = high::Quantifier;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        let kind = match self.kind {
+            ast::expression::QuantifierKind::ForAll => high::QuantifierKind::ForAll,
+            ast::expression::QuantifierKind::Exists => high::QuantifierKind::Exists,
+        };
+        Ok(high::Quantifier {
+            kind,
+            variables: self.variables.into_high()?,
+            triggers: self.triggers.into_high()?,
+            body: self.body.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::BoundedVariableDecl {
+    type Target = high::BoundedVariableDecl;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::BoundedVariableDecl {
+            name: self.name.to_string().into(),
+            sort: self.sort.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::Trigger {
+    type Target = high::Trigger;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::Trigger {
+            parts: self.parts.into_high()?,
+        })
+    }
+}
+
+impl IntoHigh for ast::expression::FunctionApplication {
+    type Target = high::FunctionApplication;
+    fn into_high(self) -> syn::Result<Self::Target> {
+        Ok(high::FunctionApplication {
+            function: self.function.to_string().into(),
+            args: self.args.into_high()?,
+        })
+    }
+}
@@ -3,6 +3,9 @@ vir_include! { declaration::parse_ast =>
     use VariableDeclaration;
     use FunctionDeclaration;
     use AxiomDeclaration;
+    use AdtDeclaration;
+    use AdtConstructorDeclaration;
+    use AdtFieldDeclaration;
     derive PartialEq, Eq, Debug, Clone;
 }
 vir_include! { declaration::parse =>
@@ -11,6 +14,9 @@ vir_include! { declaration::parse =>
     use VariableDeclaration;
     use FunctionDeclaration;
     use AxiomDeclaration;
+    use AdtDeclaration;
+    use AdtConstructorDeclaration;
+    use AdtFieldDeclaration;
 }
 
 pub use super::expression::Expression;
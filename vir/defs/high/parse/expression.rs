@@ -5,12 +5,33 @@ vir_include! { expression::parse_ast =>
     use UnaryOperationKind;
     use BinaryOperation;
     use BinaryOperationKind;
+    use BitVectorConversion;
+    use BitVectorConversionKind;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
+    use AdtMatchCase;
     use Conditional;
     use Quantifier;
     use QuantifierKind;
     use Trigger;
     use BoundedVariableDecl;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatOperationKind;
+    use FloatConversion;
+    use FloatConversionKind;
+    use SeqOperation;
+    use SeqOperationKind;
+    use EmptySeq;
+    use SetOperation;
+    use SetOperationKind;
+    use EmptySet;
+    use Let;
     derive PartialEq, Eq, Debug, Clone;
 }
 vir_include! { expression::parse =>
@@ -21,12 +42,33 @@ vir_include! { expression::parse =>
     use UnaryOperationKind;
     use BinaryOperation;
     use BinaryOperationKind;
+    use BitVectorConversion;
+    use BitVectorConversionKind;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
+    use AdtMatchCase;
     use Conditional;
     use Quantifier;
     use QuantifierKind;
     use Trigger;
     use BoundedVariableDecl;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatOperationKind;
+    use FloatConversion;
+    use FloatConversionKind;
+    use SeqOperation;
+    use SeqOperationKind;
+    use EmptySeq;
+    use SetOperation;
+    use SetOperationKind;
+    use EmptySet;
+    use Let;
     use Expression;
 }
 
@@ -38,9 +80,24 @@ pub enum Expression {
     Constant(Constant),
     UnaryOperation(UnaryOperation),
     BinaryOperation(BinaryOperation),
+    BitVectorConversion(BitVectorConversion),
+    ArraySelect(ArraySelect),
+    ArrayStore(ArrayStore),
+    ConstantArray(ConstantArray),
+    AdtConstructor(AdtConstructor),
+    AdtFieldAccess(AdtFieldAccess),
+    AdtTester(AdtTester),
+    AdtMatch(AdtMatch),
     Conditional(Conditional),
     Quantifier(Quantifier),
     FunctionApplication(FunctionApplication),
+    FloatOperation(FloatOperation),
+    FloatConversion(FloatConversion),
+    SeqOperation(SeqOperation),
+    EmptySeq(EmptySeq),
+    SetOperation(SetOperation),
+    EmptySet(EmptySet),
+    Let(Let),
     Hole(syn::Ident),
 }
 
@@ -67,6 +124,46 @@ impl quote::ToTokens for Expression {
                     svirpti_vir::high::expression::Expression::BinaryOperation(#expr)
                 });
             }
+            Expression::BitVectorConversion(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::BitVectorConversion(#expr)
+                });
+            }
+            Expression::ArraySelect(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::ArraySelect(#expr)
+                });
+            }
+            Expression::ArrayStore(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::ArrayStore(#expr)
+                });
+            }
+            Expression::ConstantArray(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::ConstantArray(#expr)
+                });
+            }
+            Expression::AdtConstructor(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::AdtConstructor(#expr)
+                });
+            }
+            Expression::AdtFieldAccess(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::AdtFieldAccess(#expr)
+                });
+            }
+            Expression::AdtTester(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::AdtTester(#expr)
+                });
+            }
+            Expression::AdtMatch(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::AdtMatch(#expr)
+                });
+            }
             Expression::Conditional(expr) => {
                 tokens.extend(quote::quote! {
                     svirpti_vir::high::expression::Expression::Conditional(#expr)
@@ -82,6 +179,41 @@ impl quote::ToTokens for Expression {
                     svirpti_vir::high::expression::Expression::FunctionApplication(#expr)
                 });
             }
+            Expression::FloatOperation(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::FloatOperation(#expr)
+                });
+            }
+            Expression::FloatConversion(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::FloatConversion(#expr)
+                });
+            }
+            Expression::SeqOperation(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::SeqOperation(#expr)
+                });
+            }
+            Expression::EmptySeq(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::EmptySeq(#expr)
+                });
+            }
+            Expression::SetOperation(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::SetOperation(#expr)
+                });
+            }
+            Expression::EmptySet(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::EmptySet(#expr)
+                });
+            }
+            Expression::Let(expr) => {
+                tokens.extend(quote::quote! {
+                    svirpti_vir::high::expression::Expression::Let(#expr)
+                });
+            }
             Expression::Hole(ident) => {
                 tokens.extend(quote::quote! {
                     #ident
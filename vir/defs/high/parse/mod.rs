@@ -1,7 +1,9 @@
+pub mod conversion;
 pub mod declaration;
 pub mod expression;
 pub mod program;
 pub mod sort;
 pub mod statement;
 
+pub use conversion::ParseError;
 pub use program::ProgramFragment;
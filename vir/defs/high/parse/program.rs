@@ -12,6 +12,7 @@ pub mod kw2 {
 
 pub struct ProgramFragment {
     pub sorts: Vec<UninterpretedSortDeclaration>,
+    pub adts: Vec<AdtDeclaration>,
     pub axioms: Vec<AxiomDeclaration>,
     pub functions: Vec<FunctionDeclaration>,
     pub procedure: ProcedureDeclaration,
@@ -25,6 +26,7 @@ pub struct ProcedureDeclaration {
 impl syn::parse::Parse for ProgramFragment {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut sorts = Vec::new();
+        let mut adts = Vec::new();
         let mut axioms = Vec::new();
         let mut functions = Vec::new();
         let mut procedure = None;
@@ -32,6 +34,8 @@ impl syn::parse::Parse for ProgramFragment {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::sort) {
                 sorts.push(input.parse()?);
+            } else if lookahead.peek(kw::adt) {
+                adts.push(input.parse()?);
             } else if lookahead.peek(kw::axiom) {
                 axioms.push(input.parse()?);
             } else if lookahead.peek(syn::Token![fn]) {
@@ -55,6 +59,7 @@ impl syn::parse::Parse for ProgramFragment {
         };
         Ok(Self {
             sorts,
+            adts,
             axioms,
             functions,
             procedure,
@@ -120,6 +125,10 @@ impl quote::ToTokens for ProgramFragment {
         for sort in &self.sorts {
             sort_decls.extend(quote::quote! { #sort, });
         }
+        let mut adt_decls = proc_macro2::TokenStream::new();
+        for adt in &self.adts {
+            adt_decls.extend(quote::quote! { #adt, });
+        }
         let mut axiom_decls = proc_macro2::TokenStream::new();
         for axiom in &self.axioms {
             axiom_decls.extend(quote::quote! { #axiom, });
@@ -132,11 +141,13 @@ impl quote::ToTokens for ProgramFragment {
         tokens.extend(quote::quote! {
             {
                 let sorts = vec![#sort_decls];
+                let adts = vec![#adt_decls];
                 let axioms = vec![#axiom_decls];
                 let functions = vec![#function_decls];
                 let procedure = #procedure;
                 svirpti_vir::high::program::ProgramFragment {
                     sorts,
+                    adts,
                     axioms,
                     functions,
                     procedure,
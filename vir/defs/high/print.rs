@@ -0,0 +1,564 @@
+//! A printer that writes program fragments in the syntax accepted by
+//! `vir_high!` and `ProgramFragment::parse_str`, so that parsing the printed
+//! text gives back the same fragment. Expressions are written on a single
+//! line with as few parentheses as the grammar allows.
+//!
+//! Some values have no syntax of their own and are printed as something
+//! close to them: positions are omitted, statements without a label and
+//! unnamed axioms get a generated name, and constants that are negative or
+//! not finite or have no finite decimal expansion are printed as equivalent
+//! expressions such as `-1` or `(1.0/3.0)`.
+
+use super::declaration::*;
+use super::expression::*;
+use super::parse::expression::Precedence;
+use super::program::*;
+use super::statement::{Havoc, Statement};
+use super::typ::*;
+use crate::common::float::write_format;
+use std::fmt::{Result, Write};
+
+const INDENT: &str = "    ";
+
+impl std::fmt::Display for ProgramFragment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
+        for sort in &self.sorts {
+            writeln!(f, "sort {};", sort.name)?;
+        }
+        let mut separate = !self.sorts.is_empty();
+        let mut separator = |f: &mut std::fmt::Formatter<'_>| -> Result {
+            if separate {
+                writeln!(f)?;
+            }
+            separate = true;
+            Ok(())
+        };
+        for adt in &self.adts {
+            separator(f)?;
+            write_adt(f, adt)?;
+        }
+        for function in &self.functions {
+            separator(f)?;
+            write_function(f, function)?;
+        }
+        for (index, axiom) in self.axioms.iter().enumerate() {
+            separator(f)?;
+            write!(f, "axiom ")?;
+            match &axiom.name {
+                Some(name) => write!(f, "{}", name)?,
+                None => write!(f, "axiom_{}", index)?,
+            }
+            write!(f, " {{\n{}", INDENT)?;
+            write_expression(f, &axiom.body)?;
+            writeln!(f, "\n}}")?;
+        }
+        separator(f)?;
+        write_procedure(f, &self.procedure)
+    }
+}
+
+fn write_adt(f: &mut impl Write, adt: &AdtDeclaration) -> Result {
+    writeln!(f, "adt {} {{", adt.name)?;
+    for constructor in &adt.constructors {
+        write!(f, "{}{}", INDENT, constructor.name)?;
+        if !constructor.fields.is_empty() {
+            write!(f, "(")?;
+            for (i, field) in constructor.fields.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}: ", field.name)?;
+                write_sort(f, &field.sort)?;
+            }
+            write!(f, ")")?;
+        }
+        writeln!(f, ",")?;
+    }
+    writeln!(f, "}}")
+}
+
+fn write_function(f: &mut impl Write, function: &FunctionDeclaration) -> Result {
+    write!(f, "fn {}(", function.name)?;
+    for (i, parameter) in function.parameters.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}: ", parameter.name)?;
+        write_sort(f, &parameter.sort)?;
+    }
+    write!(f, ") -> ")?;
+    write_sort(f, &function.return_sort)?;
+    for precondition in &function.preconditions {
+        write!(f, "\n{}requires ", INDENT)?;
+        write_expression(f, precondition)?;
+    }
+    match &function.body {
+        Some(body) => {
+            if function.preconditions.is_empty() {
+                write!(f, " ")?;
+            } else {
+                writeln!(f)?;
+            }
+            write!(f, "{{\n{}", INDENT)?;
+            write_expression(f, body)?;
+            writeln!(f, "\n}}")
+        }
+        None => writeln!(f, ";"),
+    }
+}
+
+fn write_procedure(f: &mut impl Write, procedure: &ProcedureDeclaration) -> Result {
+    writeln!(f, "procedure {{")?;
+    writeln!(f, "{}locals {{", INDENT)?;
+    for variable in &procedure.variables {
+        write!(f, "{0}{0}{1}: ", INDENT, variable.name)?;
+        write_sort(f, &variable.sort)?;
+        writeln!(f, ",")?;
+    }
+    writeln!(f, "{}}}", INDENT)?;
+    // The sentinel entry and exit blocks are implicit in the syntax.
+    let count = procedure.basic_blocks.len();
+    for (index, basic_block) in procedure.basic_blocks.iter().enumerate() {
+        let label = basic_block.label.as_str();
+        if (index == 0 && label == "entry") || (index + 1 == count && label == "exit") {
+            continue;
+        }
+        writeln!(f, "{}{} {{", INDENT, label)?;
+        write!(f, "{0}{0}guard ", INDENT)?;
+        write_expression(f, &basic_block.guard)?;
+        writeln!(f, ";")?;
+        for (index, statement) in basic_block.statements.iter().enumerate() {
+            write!(f, "{0}{0}", INDENT)?;
+            let generated_label = format!("{}_{}", label, index);
+            write_statement(f, statement, &generated_label)?;
+            writeln!(f)?;
+        }
+        write!(f, "{0}{0}goto {{ ", INDENT)?;
+        for (i, successor) in basic_block.successors.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", procedure.basic_blocks[*successor].label)?;
+        }
+        writeln!(f, " }}")?;
+        writeln!(f, "{}}}", INDENT)?;
+    }
+    writeln!(f, "}}")
+}
+
+fn write_statement(f: &mut impl Write, statement: &Statement, generated_label: &str) -> Result {
+    let (keyword, label, assertion) = match statement {
+        Statement::Assume(statement) => ("assume", &statement.label, &statement.assertion),
+        Statement::Assert(statement) => ("assert", &statement.label, &statement.assertion),
+        Statement::Havoc(Havoc::Variable(variable)) => {
+            return write!(f, "havoc {};", variable.name);
+        }
+        Statement::Assign(statement) => {
+            write!(f, "assign {} = ", statement.variable.name)?;
+            write_expression(f, &statement.expression)?;
+            return write!(f, ";");
+        }
+    };
+    write!(f, "{} ", keyword)?;
+    match label {
+        Some(label) => write!(f, "{} ", label)?,
+        None => write!(f, "{} ", generated_label)?,
+    }
+    write_expression(f, assertion)?;
+    write!(f, ";")
+}
+
+/// Writes a sort. Unlike `Display`, uninterpreted sorts are written by their
+/// name only.
+pub fn write_sort(f: &mut impl Write, sort: &Type) -> Result {
+    match sort {
+        Type::Bool => write!(f, "Bool"),
+        Type::Int => write!(f, "Int"),
+        Type::Real => write!(f, "Real"),
+        Type::BitVector(sort) => write!(f, "BV{}", sort.width),
+        Type::Float(sort) => write_format(f, sort.exponent, sort.significand),
+        Type::Array(sort) => {
+            write!(f, "Array<")?;
+            write_sort(f, &sort.index)?;
+            write!(f, ", ")?;
+            write_sort(f, &sort.element)?;
+            write!(f, ">")
+        }
+        Type::Seq(sort) => {
+            write!(f, "Seq<")?;
+            write_sort(f, &sort.element)?;
+            write!(f, ">")
+        }
+        Type::Set(sort) => {
+            write!(f, "Set<")?;
+            write_sort(f, &sort.element)?;
+            write!(f, ">")
+        }
+        Type::Domain(sort) => write!(f, "{}", sort.name),
+        Type::Reference(sort) => write!(f, "Adt<{}>", sort.name),
+    }
+}
+
+/// How far an expression extends when it is written without parentheses.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    /// A `let` extends as far to the right as possible.
+    Let,
+    Binary(Precedence),
+    /// Prefix operators, conditionals, matches and constants, which may
+    /// start with `-`.
+    Unary,
+    /// Variables, applications and accesses, which may be followed by
+    /// field and array accesses.
+    Primary,
+}
+
+/// The precedence and the operator of the operations that are written with
+/// an infix operator.
+fn infix_operator(kind: &BinaryOperationKind) -> Option<(Precedence, &'static str)> {
+    let operator = match kind {
+        BinaryOperationKind::Implies => (Precedence::Implies, "->"),
+        BinaryOperationKind::And => (Precedence::And, "&&"),
+        BinaryOperationKind::Or => (Precedence::And, "||"),
+        BinaryOperationKind::EqCmp => (Precedence::Compare, "=="),
+        BinaryOperationKind::NeCmp => (Precedence::Compare, "!="),
+        BinaryOperationKind::GtCmp => (Precedence::Compare, ">"),
+        BinaryOperationKind::GeCmp => (Precedence::Compare, ">="),
+        BinaryOperationKind::LtCmp => (Precedence::Compare, "<"),
+        BinaryOperationKind::LeCmp => (Precedence::Compare, "<="),
+        BinaryOperationKind::BitOr => (Precedence::BitOr, "|"),
+        BinaryOperationKind::BitXor => (Precedence::BitXor, "^"),
+        BinaryOperationKind::BitAnd => (Precedence::BitAnd, "&"),
+        BinaryOperationKind::Shl => (Precedence::Shift, "<<"),
+        BinaryOperationKind::LShr => (Precedence::Shift, ">>"),
+        BinaryOperationKind::Add => (Precedence::Add, "+"),
+        BinaryOperationKind::Sub => (Precedence::Add, "-"),
+        BinaryOperationKind::Mul => (Precedence::Mul, "*"),
+        BinaryOperationKind::Div => (Precedence::Mul, "/"),
+        BinaryOperationKind::Mod => (Precedence::Mul, "%"),
+        BinaryOperationKind::AShr
+        | BinaryOperationKind::SDiv
+        | BinaryOperationKind::SRem
+        | BinaryOperationKind::SGtCmp
+        | BinaryOperationKind::SGeCmp
+        | BinaryOperationKind::SLtCmp
+        | BinaryOperationKind::SLeCmp
+        | BinaryOperationKind::Concat => return None,
+    };
+    Some(operator)
+}
+
+fn level(expression: &Expression) -> Level {
+    match expression {
+        Expression::Let(_) => Level::Let,
+        Expression::BinaryOperation(operation) => match infix_operator(&operation.kind) {
+            Some((precedence, _)) => Level::Binary(precedence),
+            None => Level::Primary,
+        },
+        Expression::UnaryOperation(operation) => match operation.kind {
+            UnaryOperationKind::Not | UnaryOperationKind::Minus => Level::Unary,
+            UnaryOperationKind::ToReal | UnaryOperationKind::ToInt | UnaryOperationKind::IsInt => {
+                Level::Primary
+            }
+        },
+        Expression::Constant(_) | Expression::Conditional(_) | Expression::AdtMatch(_) => {
+            Level::Unary
+        }
+        Expression::PositionedExpression(expression) => level(&expression.expression),
+        _ => Level::Primary,
+    }
+}
+
+/// `&&` and `||` have the same precedence, but a mix of them is hard to read
+/// without parentheses.
+fn mixes_connectives(kind: &BinaryOperationKind, operand: &Expression) -> bool {
+    match operand {
+        Expression::BinaryOperation(operation) => matches!(
+            (kind, &operation.kind),
+            (BinaryOperationKind::And, BinaryOperationKind::Or)
+                | (BinaryOperationKind::Or, BinaryOperationKind::And)
+        ),
+        Expression::PositionedExpression(expression) => {
+            mixes_connectives(kind, &expression.expression)
+        }
+        _ => false,
+    }
+}
+
+fn write_parenthesized(f: &mut impl Write, expression: &Expression, parenthesize: bool) -> Result {
+    if parenthesize {
+        write!(f, "(")?;
+        write_expression(f, expression)?;
+        write!(f, ")")
+    } else {
+        write_expression(f, expression)
+    }
+}
+
+/// Writes `expression` in parentheses if it extends less far than `minimum`.
+fn write_operand(f: &mut impl Write, expression: &Expression, minimum: Level) -> Result {
+    write_parenthesized(f, expression, level(expression) < minimum)
+}
+
+fn write_list(f: &mut impl Write, expressions: &[Expression]) -> Result {
+    for (i, expression) in expressions.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write_expression(f, expression)?;
+    }
+    Ok(())
+}
+
+/// Writes an expression in a position where it does not need parentheses.
+pub fn write_expression(f: &mut impl Write, expression: &Expression) -> Result {
+    match expression {
+        Expression::Variable(variable) => write!(f, "{}", variable.name),
+        Expression::Constant(constant) => write!(f, "{}", constant),
+        Expression::UnaryOperation(operation) => match operation.kind {
+            UnaryOperationKind::Not | UnaryOperationKind::Minus => {
+                let operator = if operation.kind == UnaryOperationKind::Not {
+                    "!"
+                } else {
+                    "-"
+                };
+                write!(f, "{}", operator)?;
+                write_operand(f, &operation.arg, Level::Unary)
+            }
+            UnaryOperationKind::ToReal => write_application(f, "to_real", &[&*operation.arg]),
+            UnaryOperationKind::ToInt => write_application(f, "to_int", &[&*operation.arg]),
+            UnaryOperationKind::IsInt => write_application(f, "is_int", &[&*operation.arg]),
+        },
+        Expression::BinaryOperation(operation) => match infix_operator(&operation.kind) {
+            Some((precedence, operator)) => {
+                // Operators of the same precedence associate to the left.
+                let precedence = Level::Binary(precedence);
+                let parenthesize = level(&operation.left) < precedence
+                    || mixes_connectives(&operation.kind, &operation.left);
+                write_parenthesized(f, &operation.left, parenthesize)?;
+                write!(f, " {} ", operator)?;
+                let parenthesize = level(&operation.right) <= precedence;
+                write_parenthesized(f, &operation.right, parenthesize)
+            }
+            None => write_application(
+                f,
+                &operation.kind.to_string(),
+                &[&*operation.left, &*operation.right],
+            ),
+        },
+        Expression::BitVectorConversion(conversion) => {
+            let name = match conversion.kind {
+                BitVectorConversionKind::Extract { .. } => "extract",
+                BitVectorConversionKind::ZeroExtend { .. } => "zero_extend",
+                BitVectorConversionKind::SignExtend { .. } => "sign_extend",
+                BitVectorConversionKind::FromInt { .. } => "int2bv",
+                BitVectorConversionKind::ToInt => "bv2int",
+            };
+            write!(f, "{}(", name)?;
+            write_expression(f, &conversion.arg)?;
+            match conversion.kind {
+                BitVectorConversionKind::Extract { high, low } => write!(f, ", {}, {}", high, low)?,
+                BitVectorConversionKind::ZeroExtend { bits }
+                | BitVectorConversionKind::SignExtend { bits } => write!(f, ", {}", bits)?,
+                BitVectorConversionKind::FromInt { width } => write!(f, ", {}", width)?,
+                BitVectorConversionKind::ToInt => {}
+            }
+            write!(f, ")")
+        }
+        Expression::ArraySelect(select) => {
+            write_operand(f, &select.array, Level::Primary)?;
+            write!(f, "[")?;
+            write_expression(f, &select.index)?;
+            write!(f, "]")
+        }
+        Expression::ArrayStore(store) => {
+            write_operand(f, &store.array, Level::Primary)?;
+            write!(f, "[")?;
+            write_expression(f, &store.index)?;
+            write!(f, " := ")?;
+            write_expression(f, &store.value)?;
+            write!(f, "]")
+        }
+        Expression::ConstantArray(array) => {
+            write!(f, "const_array<")?;
+            write_sort(f, &array.index_sort)?;
+            write!(f, ">(")?;
+            write_expression(f, &array.value)?;
+            write!(f, ")")
+        }
+        Expression::AdtConstructor(constructor) => {
+            write!(f, "{}::{}", constructor.adt, constructor.constructor)?;
+            if !constructor.args.is_empty() {
+                write!(f, "(")?;
+                write_list(f, &constructor.args)?;
+                write!(f, ")")?;
+            }
+            Ok(())
+        }
+        Expression::AdtFieldAccess(access) => {
+            write_operand(f, &access.base, Level::Primary)?;
+            write!(f, ".{}", access.field)
+        }
+        Expression::AdtTester(tester) => {
+            write_application(f, &format!("is_{}", tester.constructor), &[&*tester.base])
+        }
+        Expression::AdtMatch(adt_match) => {
+            write!(f, "match ")?;
+            write_expression(f, &adt_match.base)?;
+            write!(f, " {{ ")?;
+            for (i, case) in adt_match.cases.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}::{}", adt_match.adt, case.constructor)?;
+                if !case.binders.is_empty() {
+                    write!(f, "(")?;
+                    for (i, binder) in case.binders.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", binder)?;
+                    }
+                    write!(f, ")")?;
+                }
+                write!(f, " => ")?;
+                write_expression(f, &case.body)?;
+            }
+            write!(f, " }}")
+        }
+        Expression::Conditional(conditional) => {
+            write!(f, "if ")?;
+            write_expression(f, &conditional.guard)?;
+            write!(f, " {{ ")?;
+            write_expression(f, &conditional.then_expr)?;
+            write!(f, " }} else {{ ")?;
+            write_expression(f, &conditional.else_expr)?;
+            write!(f, " }}")
+        }
+        Expression::Quantifier(quantifier) => {
+            write!(f, "{}(|", quantifier.kind)?;
+            for (i, variable) in quantifier.variables.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}: ", variable.name)?;
+                write_sort(f, &variable.sort)?;
+            }
+            write!(f, "| ")?;
+            write_expression(f, &quantifier.body)?;
+            write!(f, ", [")?;
+            for (i, trigger) in quantifier.triggers.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "(")?;
+                write_list(f, &trigger.parts)?;
+                write!(f, ")")?;
+            }
+            write!(f, "])")
+        }
+        Expression::FunctionApplication(application) => {
+            write!(f, "{}(", application.function)?;
+            write_list(f, &application.args)?;
+            write!(f, ")")
+        }
+        Expression::FloatOperation(operation) => {
+            write!(f, "{}(", operation.kind)?;
+            if let Some(rounding_mode) = operation.kind.rounding_mode() {
+                write!(f, "{}", rounding_mode)?;
+                if !operation.args.is_empty() {
+                    write!(f, ", ")?;
+                }
+            }
+            write_list(f, &operation.args)?;
+            write!(f, ")")
+        }
+        Expression::FloatConversion(conversion) => match &conversion.kind {
+            FloatConversionKind::ToFloat {
+                rounding_mode,
+                exponent,
+                significand,
+            }
+            | FloatConversionKind::UnsignedToFloat {
+                rounding_mode,
+                exponent,
+                significand,
+            } => {
+                let name = match conversion.kind {
+                    FloatConversionKind::ToFloat { .. } => "to_fp",
+                    _ => "to_fp_unsigned",
+                };
+                write!(f, "{}({}, ", name, rounding_mode)?;
+                write_expression(f, &conversion.arg)?;
+                write!(f, ", ")?;
+                write_format(f, *exponent, *significand)?;
+                write!(f, ")")
+            }
+            FloatConversionKind::FromBits {
+                exponent,
+                significand,
+            } => {
+                write!(f, "fp_from_bits(")?;
+                write_expression(f, &conversion.arg)?;
+                write!(f, ", ")?;
+                write_format(f, *exponent, *significand)?;
+                write!(f, ")")
+            }
+            FloatConversionKind::ToReal => write_application(f, "fp_to_real", &[&*conversion.arg]),
+            FloatConversionKind::ToSigned {
+                rounding_mode,
+                width,
+            }
+            | FloatConversionKind::ToUnsigned {
+                rounding_mode,
+                width,
+            } => {
+                let name = match conversion.kind {
+                    FloatConversionKind::ToSigned { .. } => "fp_to_sbv",
+                    _ => "fp_to_ubv",
+                };
+                write!(f, "{}({}, ", name, rounding_mode)?;
+                write_expression(f, &conversion.arg)?;
+                write!(f, ", {})", width)
+            }
+        },
+        Expression::SeqOperation(operation) => {
+            write!(f, "{}(", operation.kind)?;
+            write_list(f, &operation.args)?;
+            write!(f, ")")
+        }
+        Expression::EmptySeq(empty) => {
+            write!(f, "seq_empty<")?;
+            write_sort(f, &empty.element_sort)?;
+            write!(f, ">()")
+        }
+        Expression::SetOperation(operation) => {
+            write!(f, "{}(", operation.kind)?;
+            write_list(f, &operation.args)?;
+            write!(f, ")")
+        }
+        Expression::EmptySet(empty) => {
+            write!(f, "set_empty<")?;
+            write_sort(f, &empty.element_sort)?;
+            write!(f, ">()")
+        }
+        Expression::Let(binding) => {
+            write!(f, "let {} = ", binding.variable)?;
+            write_expression(f, &binding.value)?;
+            write!(f, " in ")?;
+            write_expression(f, &binding.body)
+        }
+        Expression::PositionedExpression(expression) => write_expression(f, &expression.expression),
+    }
+}
+
+fn write_application(f: &mut impl Write, function: &str, args: &[&Expression]) -> Result {
+    write!(f, "{}(", function)?;
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write_expression(f, arg)?;
+    }
+    write!(f, ")")
+}
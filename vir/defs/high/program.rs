@@ -12,6 +12,7 @@ vir_include! { cfg =>
 #[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ProgramFragment {
     pub sorts: Vec<UninterpretedSortDeclaration>,
+    pub adts: Vec<AdtDeclaration>,
     pub axioms: Vec<AxiomDeclaration>,
     pub functions: Vec<FunctionDeclaration>,
     pub procedure: ProcedureDeclaration,
@@ -27,33 +28,33 @@ index_vec::define_index_type! {
     pub struct VariableId = usize;
 }
 
+impl ProgramFragment {
+    /// Parses a program fragment written in the syntax accepted by
+    /// `vir_high!`. Holes such as `#assertion` are rejected because there is
+    /// nothing to fill them in with.
+    pub fn parse_str(source: &str) -> Result<Self, super::parse::ParseError> {
+        super::parse::conversion::parse_program_fragment(source)
+    }
+    /// A symbol table with the datatypes, the functions and the procedure
+    /// variables of this fragment, for inferring the sorts of its expressions.
+    pub fn symbol_table(&self) -> super::expression::SymbolTable {
+        let mut symbols = super::expression::SymbolTable::default();
+        for adt in &self.adts {
+            symbols.declare_adt(adt);
+        }
+        for function in &self.functions {
+            symbols.declare_function(function);
+        }
+        for variable in &self.procedure.variables {
+            symbols.declare_variable(variable);
+        }
+        symbols
+    }
+}
+
 impl crate::common::cfg::Cfg for ProcedureDeclaration {
     type BasicBlock = BasicBlock;
     fn basic_blocks(&self) -> &IndexVec<BasicBlockId, BasicBlock> {
         &self.basic_blocks
     }
 }
-
-impl std::fmt::Display for ProgramFragment {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "program {{")?;
-        writeln!(f, "  sorts: {:?}", self.sorts)?;
-        writeln!(f, "  functions: {:?}", self.functions)?;
-        writeln!(f, "  axioms: {:?}", self.axioms)?;
-        writeln!(f, "  procedure:")?;
-        writeln!(f, "    variables:")?;
-        for variable in &self.procedure.variables {
-            writeln!(f, "      {}: {}", variable.name, variable.sort)?;
-        }
-        writeln!(f, "    blocks:",)?;
-        for (id, basic_block) in self.procedure.basic_blocks.iter_enumerated() {
-            writeln!(f, "      [{:?}] {}:", id, basic_block.label)?;
-            writeln!(f, "        guard: {}", basic_block.guard)?;
-            for statement in &basic_block.statements {
-                writeln!(f, "        {}", statement)?;
-            }
-            writeln!(f, "        successors: {:?}", basic_block.successors)?;
-        }
-        writeln!(f, "}}")
-    }
-}
\ No newline at end of file
@@ -2,10 +2,22 @@ use super::context::*;
 
 vir_include! { typ =>
     use Type;
+    use BitVectorType;
+    use FloatType;
+    use ArrayType;
+    use SeqType;
+    use SetType;
     use DomainType;
+    use ReferenceType;
     derive PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize;
 }
 vir_include! { typ::display =>
     use Type;
+    use BitVectorType;
+    use FloatType;
+    use ArrayType;
+    use SeqType;
+    use SetType;
     use DomainType;
+    use ReferenceType;
 }
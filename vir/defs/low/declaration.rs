@@ -5,5 +5,13 @@ vir_include! { declaration =>
     use VariableDeclaration;
     use FunctionDeclaration;
     use AxiomDeclaration;
+    use AdtDeclaration;
+    use AdtConstructorDeclaration;
+    use AdtFieldDeclaration;
     derive PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize;
 }
+vir_include! { declaration::display =>
+    use AdtDeclaration;
+    use AdtConstructorDeclaration;
+    use AdtFieldDeclaration;
+}
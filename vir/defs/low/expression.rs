@@ -7,12 +7,35 @@ vir_include! { expression =>
     use UnaryOperationKind;
     use BinaryOperation;
     use BinaryOperationKind;
+    use BitVectorConversion;
+    use BitVectorConversionKind;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
+    use AdtMatchCase;
     use Conditional;
     use Quantifier;
     use QuantifierKind;
     use Trigger;
     use BoundedVariableDecl;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatOperationKind;
+    use FloatConversion;
+    use FloatConversionKind;
+    use SeqOperation;
+    use SeqOperationKind;
+    use EmptySeq;
+    use SetOperation;
+    use SetOperationKind;
+    use EmptySet;
+    use Let;
+    use LabelledExpression;
+    use LabelPositivity;
     derive PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize;
 }
 vir_include! { expression::helpers =>
@@ -20,11 +43,28 @@ vir_include! { expression::helpers =>
     use ConstantHelpers;
     use UnaryOperationHelpers;
     use BinaryOperationHelpers;
+    use BitVectorConversionHelpers;
+    use FloatHelpers;
+    use ArrayHelpers;
+    use AdtHelpers;
     use QuantifierHelpers;
     use FunctionApplicationHelpers;
+    use SeqHelpers;
+    use SetHelpers;
+    use LetHelpers;
+    use LabelledExpressionHelpers;
+}
+vir_include! { expression::substitution =>
+    use SubstitutionHelpers;
+}
+vir_include! { expression::simplification =>
+    use Simplification;
+    use LabelledSimplification;
 }
 pub use crate::common::expression::{
-    BinaryOperationHelpers, ConstantHelpers, FunctionApplicationHelpers, QuantifierHelpers,
+    AdtHelpers, ArrayHelpers, BinaryOperationHelpers, BitVectorConversionHelpers, ConstantHelpers,
+    FloatHelpers, FunctionApplicationHelpers, LabelledExpressionHelpers, LetHelpers,
+    QuantifierHelpers, SeqHelpers, SetHelpers, Simplification, SubstitutionHelpers,
     UnaryOperationHelpers, VariableHelpers,
 };
 vir_include! { expression::display =>
@@ -33,12 +73,59 @@ vir_include! { expression::display =>
     use UnaryOperation;
     use BinaryOperation;
     use BinaryOperationKind;
+    use BitVectorConversion;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
     use Conditional;
     use Quantifier;
     use QuantifierKind;
     use BoundedVariableDecl;
     use Trigger;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatOperationKind;
+    use FloatConversion;
+    use SeqOperation;
+    use SeqOperationKind;
+    use EmptySeq;
+    use SetOperation;
+    use SetOperationKind;
+    use EmptySet;
+    use Let;
+    use LabelledExpression;
+}
+vir_include! { expression::sort_inference =>
+    use InferSort;
+    use SymbolTable;
+    use SortError;
+    use Variable;
+    use Constant;
+    use UnaryOperation;
+    use BinaryOperation;
+    use BitVectorConversion;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
+    use Conditional;
+    use Quantifier;
+    use FunctionApplication;
+    use FloatOperation;
+    use FloatConversion;
+    use SeqOperation;
+    use EmptySeq;
+    use SetOperation;
+    use EmptySet;
+    use Let;
+    use LabelledExpression;
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -47,9 +134,25 @@ pub enum Expression {
     Constant(Constant),
     UnaryOperation(UnaryOperation),
     BinaryOperation(BinaryOperation),
+    BitVectorConversion(BitVectorConversion),
+    ArraySelect(ArraySelect),
+    ArrayStore(ArrayStore),
+    ConstantArray(ConstantArray),
+    AdtConstructor(AdtConstructor),
+    AdtFieldAccess(AdtFieldAccess),
+    AdtTester(AdtTester),
+    AdtMatch(AdtMatch),
     Conditional(Conditional),
     Quantifier(Quantifier),
     FunctionApplication(FunctionApplication),
+    FloatOperation(FloatOperation),
+    FloatConversion(FloatConversion),
+    SeqOperation(SeqOperation),
+    EmptySeq(EmptySeq),
+    SetOperation(SetOperation),
+    EmptySet(EmptySet),
+    Let(Let),
+    LabelledExpression(LabelledExpression),
 }
 
 impl std::fmt::Display for Expression {
@@ -59,9 +162,57 @@ impl std::fmt::Display for Expression {
             Expression::Constant(expr) => expr.fmt(f),
             Expression::UnaryOperation(expr) => expr.fmt(f),
             Expression::BinaryOperation(expr) => expr.fmt(f),
+            Expression::BitVectorConversion(expr) => expr.fmt(f),
+            Expression::ArraySelect(expr) => expr.fmt(f),
+            Expression::ArrayStore(expr) => expr.fmt(f),
+            Expression::ConstantArray(expr) => expr.fmt(f),
+            Expression::AdtConstructor(expr) => expr.fmt(f),
+            Expression::AdtFieldAccess(expr) => expr.fmt(f),
+            Expression::AdtTester(expr) => expr.fmt(f),
+            Expression::AdtMatch(expr) => expr.fmt(f),
             Expression::Conditional(expr) => expr.fmt(f),
             Expression::Quantifier(expr) => expr.fmt(f),
             Expression::FunctionApplication(expr) => expr.fmt(f),
+            Expression::FloatOperation(expr) => expr.fmt(f),
+            Expression::FloatConversion(expr) => expr.fmt(f),
+            Expression::SeqOperation(expr) => expr.fmt(f),
+            Expression::EmptySeq(expr) => expr.fmt(f),
+            Expression::SetOperation(expr) => expr.fmt(f),
+            Expression::EmptySet(expr) => expr.fmt(f),
+            Expression::Let(expr) => expr.fmt(f),
+            Expression::LabelledExpression(expr) => expr.fmt(f),
+        }
+    }
+}
+
+impl InferSort for Expression {
+    fn infer_sort_in_scope(&self, scope: &mut SortScope<'_>) -> Result<Sort, SortError> {
+        match self {
+            Expression::Variable(expr) => expr.infer_sort_in_scope(scope),
+            Expression::Constant(expr) => expr.infer_sort_in_scope(scope),
+            Expression::UnaryOperation(expr) => expr.infer_sort_in_scope(scope),
+            Expression::BinaryOperation(expr) => expr.infer_sort_in_scope(scope),
+            Expression::BitVectorConversion(expr) => expr.infer_sort_in_scope(scope),
+            Expression::ArraySelect(expr) => expr.infer_sort_in_scope(scope),
+            Expression::ArrayStore(expr) => expr.infer_sort_in_scope(scope),
+            Expression::ConstantArray(expr) => expr.infer_sort_in_scope(scope),
+            Expression::AdtConstructor(expr) => expr.infer_sort_in_scope(scope),
+            Expression::AdtFieldAccess(expr) => expr.infer_sort_in_scope(scope),
+            Expression::AdtTester(expr) => expr.infer_sort_in_scope(scope),
+            Expression::AdtMatch(expr) => expr.infer_sort_in_scope(scope),
+            Expression::Conditional(expr) => expr.infer_sort_in_scope(scope),
+            Expression::Quantifier(expr) => expr.infer_sort_in_scope(scope),
+            Expression::FunctionApplication(expr) => expr.infer_sort_in_scope(scope),
+            Expression::FloatOperation(expr) => expr.infer_sort_in_scope(scope),
+            Expression::FloatConversion(expr) => expr.infer_sort_in_scope(scope),
+            Expression::SeqOperation(expr) => expr.infer_sort_in_scope(scope),
+            Expression::EmptySeq(expr) => expr.infer_sort_in_scope(scope),
+            Expression::SetOperation(expr) => expr.infer_sort_in_scope(scope),
+            Expression::EmptySet(expr) => expr.infer_sort_in_scope(scope),
+            Expression::Let(expr) => expr.infer_sort_in_scope(scope),
+            Expression::LabelledExpression(expr) => expr.infer_sort_in_scope(scope),
         }
     }
 }
+
+vir_walkers! { Expression }
@@ -0,0 +1,4 @@
+vir_lowering! { high =>
+    use expression::Expression;
+    map VariableSymbol, FunctionSymbol, AdtNameSymbol, AdtConstructorSymbol, FieldNameSymbol, Sort;
+}
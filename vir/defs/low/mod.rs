@@ -1,16 +1,25 @@
 pub mod context;
 pub mod declaration;
 pub mod expression;
+pub mod lowering;
 pub mod program;
 pub mod sort;
 pub mod statement;
 
 pub use context::*;
-pub use declaration::VariableDeclaration;
+pub use declaration::{
+    AdtConstructorDeclaration, AdtDeclaration, AdtFieldDeclaration, FunctionDeclaration,
+    VariableDeclaration,
+};
 pub use expression::{
-    BinaryOperation, BinaryOperationKind, BoundedVariableDecl, Conditional, Constant,
-    FunctionApplication, Quantifier, QuantifierKind, Trigger, UnaryOperation, UnaryOperationKind,
-    Variable,
+    AdtConstructor, AdtFieldAccess, AdtMatch, AdtMatchCase, AdtTester, ArraySelect, ArrayStore,
+    BinaryOperation, BinaryOperationKind, BitVectorConversion, BitVectorConversionKind,
+    BoundedVariableDecl, Conditional, Constant, ConstantArray, EmptySeq, EmptySet,
+    ExpressionFolder, ExpressionWalker, ExpressionWalkerMut, FloatConversion, FloatConversionKind,
+    FloatOperation, FloatOperationKind, FunctionApplication, InferSort, LabelPositivity,
+    LabelledExpression, Let, Quantifier, QuantifierKind, SeqOperation, SeqOperationKind,
+    SetOperation, SetOperationKind, SortError, SortScope, SymbolTable, Trigger, UnaryOperation,
+    UnaryOperationKind, Variable,
 };
 pub use program::{BasicBlock, BasicBlockId, ProgramFragment};
 pub use sort::Sort;
@@ -12,12 +12,31 @@ vir_include! { cfg =>
 #[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ProgramFragment {
     pub uninterpreted_sorts: Vec<UninterpretedSortDeclaration>,
+    pub adts: Vec<AdtDeclaration>,
     pub variables: Vec<VariableDeclaration>,
     pub functions: Vec<FunctionDeclaration>,
     pub axioms: Vec<AxiomDeclaration>,
     pub basic_blocks: IndexVec<BasicBlockId, BasicBlock>,
 }
 
+impl ProgramFragment {
+    /// A symbol table with the datatypes, the functions and the variables of
+    /// this fragment, for inferring the sorts of its expressions.
+    pub fn symbol_table(&self) -> super::expression::SymbolTable {
+        let mut symbols = super::expression::SymbolTable::default();
+        for adt in &self.adts {
+            symbols.declare_adt(adt);
+        }
+        for function in &self.functions {
+            symbols.declare_function(function);
+        }
+        for variable in &self.variables {
+            symbols.declare_variable(variable);
+        }
+        symbols
+    }
+}
+
 impl crate::common::cfg::Cfg for ProgramFragment {
     type BasicBlock = BasicBlock;
     fn basic_blocks(&self) -> &IndexVec<BasicBlockId, BasicBlock> {
@@ -29,6 +48,10 @@ impl std::fmt::Display for ProgramFragment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "program {{")?;
         writeln!(f, "  uninterpreted_sorts: {:?}", self.uninterpreted_sorts)?;
+        writeln!(f, "  adts:")?;
+        for adt in &self.adts {
+            writeln!(f, "    {}", adt)?;
+        }
         writeln!(f, "  variables:")?;
         for variable in &self.variables {
             writeln!(f, "    {}: {}", variable.name, variable.sort)?;
@@ -28,6 +28,12 @@ impl Statement {
             }
         }
     }
+    pub fn get_position(&self) -> Option<crate::common::position::Position> {
+        match self {
+            Statement::Assert(Assert { position, .. })
+            | Statement::Assume(Assume { position, .. }) => *position,
+        }
+    }
 }
 
 impl std::fmt::Display for Statement {
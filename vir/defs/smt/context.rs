@@ -14,16 +14,67 @@ pub trait Context {
         writer: &mut Writer,
         symbol: &LabelSymbol,
     ) -> rsmt2::SmtRes<()>;
+    /// The sort of a declared variable. The variables bound inside
+    /// expressions are looked up in `bound_variables` first.
     fn get_variable_sort<'a>(&'a self, variable: &'a VariableSymbol) -> &'a Sort;
     fn get_function_sort<'a>(&'a self, function: &'a FunctionSymbol) -> &'a Sort;
+    fn get_field_sort<'a>(&'a self, field: &'a FieldNameSymbol) -> &'a Sort;
+    /// The fields of `constructor`, in order.
+    fn get_constructor_fields<'a>(
+        &'a self,
+        constructor: &'a AdtConstructorSymbol,
+    ) -> &'a [AdtFieldDeclaration];
+    /// Whether `name` is a declared algebraic datatype, as opposed to an
+    /// uninterpreted sort.
+    fn is_adt(&self, name: &AdtNameSymbol) -> bool;
     fn resolve_ident(&self, ident: &str) -> rsmt2::SmtRes<IdentSymbol>;
+    fn bound_variables(&self) -> &BoundVariables;
+}
+
+/// The sorts of the variables bound by the quantifiers, let expressions,
+/// match cases and function definitions that enclose the expression being
+/// printed. Inner bindings shadow outer ones, and all of them shadow the
+/// declared variables.
+#[derive(Debug, Default)]
+pub struct BoundVariables {
+    variables: std::cell::RefCell<Vec<(VariableSymbol, Sort)>>,
+}
+
+impl BoundVariables {
+    pub fn get(&self, variable: &VariableSymbol) -> Option<Sort> {
+        self.variables
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(name, _)| name == variable)
+            .map(|(_, sort)| sort.clone())
+    }
+    /// Runs `f` with `variables` bound.
+    pub fn bind<R>(
+        &self,
+        variables: impl IntoIterator<Item = (VariableSymbol, Sort)>,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        // Computing the sorts may look up other bound variables, so they are
+        // collected before the stack is borrowed mutably.
+        let variables: Vec<_> = variables.into_iter().collect();
+        let length = self.variables.borrow().len();
+        self.variables.borrow_mut().extend(variables);
+        let result = f();
+        self.variables.borrow_mut().truncate(length);
+        result
+    }
 }
 
 pub use super::ident::IdentSymbol;
 pub type Sort = super::sort::Sort;
 pub type Expression = super::expression::Expression;
+pub type AdtFieldDeclaration = super::declaration::AdtFieldDeclaration;
 pub type UninterpretedSortSymbol = IdentSymbol;
 pub type UninterpretedSortValue = String;
 pub type VariableSymbol = IdentSymbol;
 pub type LabelSymbol = IdentSymbol;
 pub type FunctionSymbol = IdentSymbol;
+pub type AdtNameSymbol = IdentSymbol;
+pub type AdtConstructorSymbol = IdentSymbol;
+pub type FieldNameSymbol = IdentSymbol;
@@ -5,11 +5,16 @@ vir_include! { declaration =>
     use VariableDeclaration;
     use FunctionDeclaration;
     use LabelDeclaration;
+    use AdtDeclaration;
+    use AdtConstructorDeclaration;
+    use AdtFieldDeclaration;
     derive PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize;
 }
 
 vir_include! { declaration::rsmt =>
     use UninterpretedSortDeclaration;
+    use FunctionDeclaration;
+    use AdtDeclaration;
 }
 
 vir_include! { declaration::display =>
@@ -17,11 +22,16 @@ vir_include! { declaration::display =>
     use VariableDeclaration;
     use FunctionDeclaration;
     use LabelDeclaration;
+    use AdtDeclaration;
+    use AdtConstructorDeclaration;
+    use AdtFieldDeclaration;
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Declarations {
     pub sorts: Vec<UninterpretedSortDeclaration>,
+    /// Algebraic datatypes, which are declared together.
+    pub adts: Vec<AdtDeclaration>,
     pub variables: Vec<VariableDeclaration>,
     pub functions: Vec<FunctionDeclaration>,
     pub labels: Vec<LabelDeclaration>,
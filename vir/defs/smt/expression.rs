@@ -6,29 +6,65 @@ vir_include! { expression =>
     use Constant;
     use UnaryOperation;
     use BinaryOperation;
+    use BitVectorConversion;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
+    use AdtMatchCase;
     use Conditional;
     use Quantifier;
     use QuantifierKind;
     use Trigger;
     use BoundedVariableDecl;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatConversion;
+    use SeqOperation;
+    use EmptySeq;
+    use SetOperation;
+    use EmptySet;
+    use Let;
     use LabelledExpression;
     use LabelPositivity;
-    derive PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize;
+    derive PartialEq, Eq, Hash, Debug, Clone, serde::Serialize, serde::Deserialize;
 }
 vir_include! { expression =>
     use UnaryOperationKind;
     use BinaryOperationKind;
-    derive PartialEq, Eq, Debug, Clone, Copy, serde::Serialize, serde::Deserialize;
+    use BitVectorConversionKind;
+    use SeqOperationKind;
+    use SetOperationKind;
+    use FloatOperationKind;
+    use FloatConversionKind;
+    derive PartialEq, Eq, Hash, Debug, Clone, Copy, serde::Serialize, serde::Deserialize;
 }
 vir_include! { expression::sort =>
     use Variable;
     use Constant;
     use UnaryOperation;
     use BinaryOperation;
+    use BitVectorConversion;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
     use Conditional;
     use Quantifier;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatConversion;
+    use SeqOperation;
+    use EmptySeq;
+    use SetOperation;
+    use EmptySet;
+    use Let;
     use LabelledExpression;
 }
 vir_include! { expression::helpers =>
@@ -36,13 +72,29 @@ vir_include! { expression::helpers =>
     use ConstantHelpers;
     use UnaryOperationHelpers;
     use BinaryOperationHelpers;
+    use BitVectorConversionHelpers;
+    use FloatHelpers;
+    use ArrayHelpers;
+    use AdtHelpers;
     use QuantifierHelpers;
     use FunctionApplicationHelpers;
+    use SeqHelpers;
+    use SetHelpers;
+    use LetHelpers;
     use LabelledExpressionHelpers;
 }
+vir_include! { expression::substitution =>
+    use SubstitutionHelpers;
+}
+vir_include! { expression::simplification =>
+    use Simplification;
+    use LabelledSimplification;
+}
 pub use crate::common::expression::{
-    BinaryOperationHelpers, ConstantHelpers, FunctionApplicationHelpers, LabelledExpressionHelpers,
-    QuantifierHelpers, UnaryOperationHelpers, VariableHelpers,
+    AdtHelpers, ArrayHelpers, BinaryOperationHelpers, BitVectorConversionHelpers, ConstantHelpers,
+    FloatHelpers, FunctionApplicationHelpers, LabelledExpressionHelpers, LetHelpers,
+    QuantifierHelpers, SeqHelpers, SetHelpers, Simplification, SubstitutionHelpers,
+    UnaryOperationHelpers, VariableHelpers,
 };
 vir_include! { expression::display =>
     use Variable;
@@ -50,12 +102,30 @@ vir_include! { expression::display =>
     use UnaryOperation;
     use BinaryOperation;
     use BinaryOperationKind;
+    use BitVectorConversion;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
     use Conditional;
     use Quantifier;
     use QuantifierKind;
     use BoundedVariableDecl;
     use Trigger;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatOperationKind;
+    use FloatConversion;
+    use SeqOperation;
+    use SeqOperationKind;
+    use EmptySeq;
+    use SetOperation;
+    use SetOperationKind;
+    use EmptySet;
+    use Let;
     use LabelledExpression;
 }
 vir_include! { expression::rsmt =>
@@ -63,35 +133,80 @@ vir_include! { expression::rsmt =>
     use Constant;
     use UnaryOperation;
     use BinaryOperation;
+    use BitVectorConversion;
+    use ArraySelect;
+    use ArrayStore;
+    use ConstantArray;
+    use AdtConstructor;
+    use AdtFieldAccess;
+    use AdtTester;
+    use AdtMatch;
     use Conditional;
     use Quantifier;
     use Trigger;
     use FunctionApplication;
+    use FloatOperation;
+    use FloatConversion;
+    use SeqOperation;
+    use EmptySeq;
+    use SetOperation;
+    use EmptySet;
+    use Let;
     use LabelledExpression;
 }
 
-#[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum Expression {
     Variable(Variable),
     Constant(Constant),
     UnaryOperation(UnaryOperation),
     BinaryOperation(BinaryOperation),
+    BitVectorConversion(BitVectorConversion),
+    ArraySelect(ArraySelect),
+    ArrayStore(ArrayStore),
+    ConstantArray(ConstantArray),
+    AdtConstructor(AdtConstructor),
+    AdtFieldAccess(AdtFieldAccess),
+    AdtTester(AdtTester),
+    AdtMatch(AdtMatch),
     Conditional(Conditional),
     Quantifier(Quantifier),
     FunctionApplication(FunctionApplication),
+    FloatOperation(FloatOperation),
+    FloatConversion(FloatConversion),
+    SeqOperation(SeqOperation),
+    EmptySeq(EmptySeq),
+    SetOperation(SetOperation),
+    EmptySet(EmptySet),
+    Let(Let),
     LabelledExpression(LabelledExpression),
 }
 
 impl<C: Context> WithSort<C> for Expression {
-    fn sort<'a>(&'a self, context: &'a C) -> &'a Sort {
+    fn sort(&self, context: &C) -> Sort {
         match self {
             Expression::Variable(expr) => expr.sort(context),
             Expression::Constant(expr) => expr.sort(context),
             Expression::UnaryOperation(expr) => expr.sort(context),
             Expression::BinaryOperation(expr) => expr.sort(context),
+            Expression::BitVectorConversion(expr) => expr.sort(context),
+            Expression::ArraySelect(expr) => expr.sort(context),
+            Expression::ArrayStore(expr) => expr.sort(context),
+            Expression::ConstantArray(expr) => expr.sort(context),
+            Expression::AdtConstructor(expr) => expr.sort(context),
+            Expression::AdtFieldAccess(expr) => expr.sort(context),
+            Expression::AdtTester(expr) => expr.sort(context),
+            Expression::AdtMatch(expr) => expr.sort(context),
             Expression::Conditional(expr) => expr.sort(context),
             Expression::Quantifier(expr) => expr.sort(context),
             Expression::FunctionApplication(expr) => expr.sort(context),
+            Expression::FloatOperation(expr) => expr.sort(context),
+            Expression::FloatConversion(expr) => expr.sort(context),
+            Expression::SeqOperation(expr) => expr.sort(context),
+            Expression::EmptySeq(expr) => expr.sort(context),
+            Expression::SetOperation(expr) => expr.sort(context),
+            Expression::EmptySet(expr) => expr.sort(context),
+            Expression::Let(expr) => expr.sort(context),
             Expression::LabelledExpression(expr) => expr.sort(context),
         }
     }
@@ -108,9 +223,24 @@ impl<'a, C: Context> ::rsmt2::print::Expr2Smt<&'a C> for Expression {
             Expression::Constant(expr) => expr.expr_to_smt2(writer, context),
             Expression::UnaryOperation(expr) => expr.expr_to_smt2(writer, context),
             Expression::BinaryOperation(expr) => expr.expr_to_smt2(writer, context),
+            Expression::BitVectorConversion(expr) => expr.expr_to_smt2(writer, context),
+            Expression::ArraySelect(expr) => expr.expr_to_smt2(writer, context),
+            Expression::ArrayStore(expr) => expr.expr_to_smt2(writer, context),
+            Expression::ConstantArray(expr) => expr.expr_to_smt2(writer, context),
+            Expression::AdtConstructor(expr) => expr.expr_to_smt2(writer, context),
+            Expression::AdtFieldAccess(expr) => expr.expr_to_smt2(writer, context),
+            Expression::AdtTester(expr) => expr.expr_to_smt2(writer, context),
+            Expression::AdtMatch(expr) => expr.expr_to_smt2(writer, context),
             Expression::Conditional(expr) => expr.expr_to_smt2(writer, context),
             Expression::Quantifier(expr) => expr.expr_to_smt2(writer, context),
             Expression::FunctionApplication(expr) => expr.expr_to_smt2(writer, context),
+            Expression::FloatOperation(expr) => expr.expr_to_smt2(writer, context),
+            Expression::FloatConversion(expr) => expr.expr_to_smt2(writer, context),
+            Expression::SeqOperation(expr) => expr.expr_to_smt2(writer, context),
+            Expression::EmptySeq(expr) => expr.expr_to_smt2(writer, context),
+            Expression::SetOperation(expr) => expr.expr_to_smt2(writer, context),
+            Expression::EmptySet(expr) => expr.expr_to_smt2(writer, context),
+            Expression::Let(expr) => expr.expr_to_smt2(writer, context),
             Expression::LabelledExpression(expr) => expr.expr_to_smt2(writer, context),
         }
     }
@@ -123,10 +253,27 @@ impl std::fmt::Display for Expression {
             Expression::Constant(expr) => expr.fmt(f),
             Expression::UnaryOperation(expr) => expr.fmt(f),
             Expression::BinaryOperation(expr) => expr.fmt(f),
+            Expression::BitVectorConversion(expr) => expr.fmt(f),
+            Expression::ArraySelect(expr) => expr.fmt(f),
+            Expression::ArrayStore(expr) => expr.fmt(f),
+            Expression::ConstantArray(expr) => expr.fmt(f),
+            Expression::AdtConstructor(expr) => expr.fmt(f),
+            Expression::AdtFieldAccess(expr) => expr.fmt(f),
+            Expression::AdtTester(expr) => expr.fmt(f),
+            Expression::AdtMatch(expr) => expr.fmt(f),
             Expression::Conditional(expr) => expr.fmt(f),
             Expression::Quantifier(expr) => expr.fmt(f),
             Expression::FunctionApplication(expr) => expr.fmt(f),
+            Expression::FloatOperation(expr) => expr.fmt(f),
+            Expression::FloatConversion(expr) => expr.fmt(f),
+            Expression::SeqOperation(expr) => expr.fmt(f),
+            Expression::EmptySeq(expr) => expr.fmt(f),
+            Expression::SetOperation(expr) => expr.fmt(f),
+            Expression::EmptySet(expr) => expr.fmt(f),
+            Expression::Let(expr) => expr.fmt(f),
             Expression::LabelledExpression(expr) => expr.fmt(f),
         }
     }
 }
+
+vir_walkers! { Expression }
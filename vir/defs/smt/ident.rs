@@ -1,31 +1,4 @@
-#[derive(
-    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
-)]
-pub struct IdentSymbol(String);
-
-impl IdentSymbol {
-    pub fn as_string(&self) -> String {
-        self.0.clone()
-    }
-}
-
-impl From<&str> for IdentSymbol {
-    fn from(value: &str) -> Self {
-        Self(value.into())
-    }
-}
-
-impl From<String> for IdentSymbol {
-    fn from(value: String) -> Self {
-        Self(value)
-    }
-}
-
-impl std::fmt::Display for IdentSymbol {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
-    }
-}
+crate::derive_string_symbol!(IdentSymbol);
 
 impl<'a, C: super::context::Context> rsmt2::print::Sym2Smt<&'a C> for IdentSymbol {
     fn sym_to_smt2<Writer: std::io::Write>(
@@ -33,7 +6,7 @@ impl<'a, C: super::context::Context> rsmt2::print::Sym2Smt<&'a C> for IdentSymbo
         writer: &mut Writer,
         _context: &'a C,
     ) -> rsmt2::SmtRes<()> {
-        write!(writer, "{}", self.0)?;
+        writer.write_all(self.as_str().as_bytes())?;
         Ok(())
     }
 }
@@ -44,7 +17,7 @@ impl<'a, C: super::context::Context> ::rsmt2::print::Expr2Smt<&'a C> for IdentSy
         writer: &mut Writer,
         _context: &'a C,
     ) -> ::rsmt2::SmtRes<()> {
-        write!(writer, "{}", self.0)?;
+        writer.write_all(self.as_str().as_bytes())?;
         Ok(())
     }
 }
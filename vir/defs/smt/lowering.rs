@@ -0,0 +1,6 @@
+vir_lowering! { low =>
+    use expression::Expression;
+    use sort::Sort;
+    map VariableSymbol, FunctionSymbol, UninterpretedSortSymbol, AdtNameSymbol,
+        AdtConstructorSymbol, FieldNameSymbol, LabelSymbol;
+}
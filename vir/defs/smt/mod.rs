@@ -2,20 +2,28 @@ pub mod context;
 pub mod declaration;
 pub mod expression;
 pub mod ident;
+pub mod lowering;
 pub mod model;
 pub mod query;
 pub mod sort;
 
 pub use context::*;
 pub use declaration::{
-    Declarations, FunctionDeclaration, LabelDeclaration, UninterpretedSortDeclaration,
-    VariableDeclaration,
+    AdtConstructorDeclaration, AdtDeclaration, AdtFieldDeclaration, Declarations,
+    FunctionDeclaration, LabelDeclaration, UninterpretedSortDeclaration, VariableDeclaration,
 };
 pub use expression::{
-    BinaryOperation, BinaryOperationHelpers, BinaryOperationKind, BoundedVariableDecl, Conditional,
-    Constant, Expression, FunctionApplication, FunctionApplicationHelpers,
-    LabelledExpressionHelpers, Quantifier, QuantifierHelpers, QuantifierKind, Trigger,
-    UnaryOperation, UnaryOperationHelpers, UnaryOperationKind, Variable, VariableHelpers,
+    AdtConstructor, AdtFieldAccess, AdtHelpers, AdtMatch, AdtMatchCase, AdtTester, ArrayHelpers,
+    ArraySelect, ArrayStore, BinaryOperation, BinaryOperationHelpers, BinaryOperationKind,
+    BitVectorConversion, BitVectorConversionHelpers, BitVectorConversionKind, BoundedVariableDecl,
+    Conditional, Constant, ConstantArray, ConstantHelpers, EmptySeq, EmptySet, Expression,
+    ExpressionFolder, ExpressionWalker, ExpressionWalkerMut, FloatConversion, FloatConversionKind,
+    FloatHelpers, FloatOperation, FloatOperationKind, FunctionApplication,
+    FunctionApplicationHelpers, LabelPositivity, LabelledExpression, LabelledExpressionHelpers,
+    Let, LetHelpers, Quantifier, QuantifierHelpers, QuantifierKind, SeqHelpers, SeqOperation,
+    SeqOperationKind, SetHelpers, SetOperation, SetOperationKind, Simplification,
+    SubstitutionHelpers, Trigger, UnaryOperation, UnaryOperationHelpers, UnaryOperationKind,
+    Variable, VariableHelpers,
 };
 pub use model::{Model, ModelItem, ModelItemArg, Value};
 pub use query::{Assertions, Query};
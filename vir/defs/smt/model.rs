@@ -7,3 +7,6 @@ vir_include! { model =>
     use Model;
     derive PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize;
 }
+vir_include! { model::display =>
+    use Value;
+}
@@ -3,7 +3,7 @@ use super::context::*;
 vir_include! { sort =>
     use Sort;
     use WithSort;
-    derive PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize;
+    derive PartialEq, Eq, Hash, Debug, Clone, serde::Serialize, serde::Deserialize;
 }
 vir_include! { sort::rsmt =>
     use Sort;
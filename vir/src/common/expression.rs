@@ -1,3 +1,7 @@
+use super::float::RoundingMode;
+use super::position::Position;
+use std::collections::{BTreeSet, HashMap};
+
 pub trait VariableHelpers {
     type VariableSymbol;
     fn variable(name: Self::VariableSymbol) -> Self;
@@ -8,6 +12,12 @@ pub trait ConstantHelpers {
     fn constant(value: Self::Constant) -> Self;
     fn bool(value: bool) -> Self;
     fn int(value: i64) -> Self;
+    fn bit_vector(value: u128, width: u32) -> Self;
+    /// The rational `numerator / denominator`, which must not have a zero
+    /// denominator.
+    fn real(numerator: i64, denominator: i64) -> Self;
+    fn f32(value: f32) -> Self;
+    fn f64(value: f64) -> Self;
 }
 
 pub trait UnaryOperationHelpers {
@@ -15,6 +25,9 @@ pub trait UnaryOperationHelpers {
     fn unary_operation(kind: Self::UnaryOperationKind, arg: Self) -> Self;
     fn not(arg: Self) -> Self;
     fn minus(arg: Self) -> Self;
+    fn to_real(arg: Self) -> Self;
+    fn to_int(arg: Self) -> Self;
+    fn is_int(arg: Self) -> Self;
 }
 
 pub trait BinaryOperationHelpers {
@@ -36,6 +49,79 @@ pub trait BinaryOperationHelpers {
     fn implies(left: Self, right: Self) -> Self;
 }
 
+pub trait BitVectorConversionHelpers {
+    type BitVectorConversionKind;
+    fn bit_vector_conversion(kind: Self::BitVectorConversionKind, arg: Self) -> Self;
+    fn extract(arg: Self, high: u32, low: u32) -> Self;
+    fn zero_extend(arg: Self, bits: u32) -> Self;
+    fn sign_extend(arg: Self, bits: u32) -> Self;
+    fn int_to_bit_vector(arg: Self, width: u32) -> Self;
+    fn bit_vector_to_int(arg: Self) -> Self;
+}
+
+pub trait FloatHelpers: Sized {
+    type FloatOperationKind;
+    type FloatConversionKind;
+    fn float_operation(kind: Self::FloatOperationKind, args: Vec<Self>) -> Self;
+    fn float_conversion(kind: Self::FloatConversionKind, arg: Self) -> Self;
+    fn fp_add(rounding_mode: RoundingMode, left: Self, right: Self) -> Self;
+    fn fp_sub(rounding_mode: RoundingMode, left: Self, right: Self) -> Self;
+    fn fp_mul(rounding_mode: RoundingMode, left: Self, right: Self) -> Self;
+    fn fp_div(rounding_mode: RoundingMode, left: Self, right: Self) -> Self;
+    fn fp_sqrt(rounding_mode: RoundingMode, arg: Self) -> Self;
+    fn fp_eq(left: Self, right: Self) -> Self;
+    fn fp_is_nan(arg: Self) -> Self;
+    fn fp_is_infinite(arg: Self) -> Self;
+    fn to_float(rounding_mode: RoundingMode, arg: Self, exponent: u32, significand: u32) -> Self;
+    fn float_to_real(arg: Self) -> Self;
+    fn float_to_signed(rounding_mode: RoundingMode, arg: Self, width: u32) -> Self;
+}
+
+pub trait ArrayHelpers {
+    type Sort;
+    fn select(array: Self, index: Self) -> Self;
+    fn store(array: Self, index: Self, value: Self) -> Self;
+    fn constant_array(index_sort: Self::Sort, value: Self) -> Self;
+}
+
+pub trait SeqHelpers: Sized {
+    type Sort;
+    type SeqOperationKind;
+    fn seq_operation(kind: Self::SeqOperationKind, args: Vec<Self>) -> Self;
+    fn empty_seq(element_sort: Self::Sort) -> Self;
+    fn seq_unit(value: Self) -> Self;
+    fn seq_length(seq: Self) -> Self;
+    fn seq_index(seq: Self, index: Self) -> Self;
+    fn seq_concat(left: Self, right: Self) -> Self;
+    fn seq_subsequence(seq: Self, offset: Self, length: Self) -> Self;
+    fn seq_update(seq: Self, index: Self, value: Self) -> Self;
+}
+
+pub trait SetHelpers: Sized {
+    type Sort;
+    type SetOperationKind;
+    fn set_operation(kind: Self::SetOperationKind, args: Vec<Self>) -> Self;
+    fn empty_set(element_sort: Self::Sort) -> Self;
+    fn set_singleton(value: Self) -> Self;
+    fn set_contains(set: Self, value: Self) -> Self;
+    fn set_union(left: Self, right: Self) -> Self;
+    fn set_intersection(left: Self, right: Self) -> Self;
+    fn set_subset(left: Self, right: Self) -> Self;
+}
+
+pub trait AdtHelpers: Sized {
+    type AdtNameSymbol;
+    type AdtConstructorSymbol;
+    type FieldNameSymbol;
+    fn construct(
+        adt: Self::AdtNameSymbol,
+        constructor: Self::AdtConstructorSymbol,
+        args: Vec<Self>,
+    ) -> Self;
+    fn field(base: Self, field: Self::FieldNameSymbol) -> Self;
+    fn is_constructor(base: Self, constructor: Self::AdtConstructorSymbol) -> Self;
+}
+
 pub trait QuantifierHelpers {
     type QuantifierKind;
     type BoundedVariableDecl;
@@ -58,6 +144,11 @@ pub trait QuantifierHelpers {
     ) -> Self;
 }
 
+pub trait LetHelpers: VariableHelpers + Sized {
+    /// `body` with `variable` bound to `value`.
+    fn let_in(variable: Self::VariableSymbol, value: Self, body: Self) -> Self;
+}
+
 pub trait FunctionApplicationHelpers: Sized {
     type FunctionSymbol;
     fn call(function: Self::FunctionSymbol, args: Vec<Self>) -> Self;
@@ -75,6 +166,43 @@ pub trait LabelledExpressionHelpers {
     fn label_positive(name: Self::LabelSymbol, expression: Self) -> Self;
 }
 
+pub trait PositionedExpressionHelpers {
+    /// Attaches the position of the frontend to `expression`.
+    fn positioned(position: Position, expression: Self) -> Self;
+}
+
+/// Substitution and renaming that respect the variables bound by quantifiers,
+/// let expressions and match cases.
+pub trait SubstitutionHelpers: VariableHelpers + Sized {
+    /// The variables that occur free in the expression.
+    fn free_variables(&self) -> BTreeSet<Self::VariableSymbol>;
+    /// Replaces the free occurrences of the variables in `substitution` with
+    /// their values. A bound variable that would capture a free variable of a
+    /// value is renamed to a fresh name.
+    fn substitute(self, substitution: &HashMap<Self::VariableSymbol, Self>) -> Self;
+    /// Renames the free occurrences of the variables in `renaming`, avoiding
+    /// capture like `substitute`.
+    fn rename_free_variables(
+        self,
+        renaming: &HashMap<Self::VariableSymbol, Self::VariableSymbol>,
+    ) -> Self;
+    /// Renames every bound variable to the name returned by `fresh_name`,
+    /// which must not occur in the expression.
+    fn rename_bound_variables(
+        self,
+        fresh_name: &mut dyn FnMut(&Self::VariableSymbol) -> Self::VariableSymbol,
+    ) -> Self;
+}
+
+/// Constant folding and simplification of Boolean expressions. The result is
+/// equivalent to the original expression.
+pub trait Simplification {
+    /// Folds operations on constants, removes double negations, compares an
+    /// expression with itself, and flattens nested conjunctions and
+    /// disjunctions, dropping duplicate, absorbed and unit operands.
+    fn simplify(self) -> Self;
+}
+
 pub trait SyntacticEvaluation {
     /// Check whether the expression is syntactically known to be true.
     fn is_true(&self) -> bool;
@@ -88,7 +216,7 @@ pub trait ExpressionIterator<E> {
     fn conjoin(&mut self) -> E;
 
     /// Disjoin a sequence of expressions into a single expression.
-    /// Returns true if the sequence has no elements.
+    /// Returns false if the sequence has no elements.
     fn disjoin(&mut self) -> E;
 }
 
@@ -111,7 +239,7 @@ where
     fn disjoin(&mut self) -> E {
         if let Some(mut disjunction) = self.next() {
             for disjunct in self {
-                disjunction = E::and(disjunction, disjunct);
+                disjunction = E::or(disjunction, disjunct);
             }
             disjunction
         } else {
@@ -0,0 +1,189 @@
+//! IEEE 754 floating-point numbers. A format is given by the number of
+//! exponent bits and the number of significand bits, which includes the
+//! hidden bit as in SMT-LIB. Values are represented by their bits.
+
+/// The rounding mode of a floating-point operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum RoundingMode {
+    NearestTiesToEven,
+    NearestTiesToAway,
+    TowardPositive,
+    TowardNegative,
+    TowardZero,
+}
+
+impl RoundingMode {
+    /// Parses the SMT-LIB abbreviation of a rounding mode, for example `RNE`.
+    pub fn from_abbreviation(abbreviation: &str) -> Option<Self> {
+        match abbreviation {
+            "RNE" => Some(Self::NearestTiesToEven),
+            "RNA" => Some(Self::NearestTiesToAway),
+            "RTP" => Some(Self::TowardPositive),
+            "RTN" => Some(Self::TowardNegative),
+            "RTZ" => Some(Self::TowardZero),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NearestTiesToEven => write!(f, "RNE"),
+            Self::NearestTiesToAway => write!(f, "RNA"),
+            Self::TowardPositive => write!(f, "RTP"),
+            Self::TowardNegative => write!(f, "RTN"),
+            Self::TowardZero => write!(f, "RTZ"),
+        }
+    }
+}
+
+pub const F16: (u32, u32) = (5, 11);
+pub const F32: (u32, u32) = (8, 24);
+pub const F64: (u32, u32) = (11, 53);
+pub const F128: (u32, u32) = (15, 113);
+
+/// Whether a format with the given numbers of exponent and significand bits
+/// is supported. The bits of a value must fit into a `u128`.
+pub fn is_supported_format(exponent: u32, significand: u32) -> bool {
+    exponent > 1 && significand > 1 && exponent + significand <= 128
+}
+
+/// The format called `name`, for example `F32`.
+pub fn format_from_name(name: &str) -> Option<(u32, u32)> {
+    match name {
+        "F16" => Some(F16),
+        "F32" => Some(F32),
+        "F64" => Some(F64),
+        "F128" => Some(F128),
+        _ => None,
+    }
+}
+
+/// Writes the name of a standard format, for example `F32`, and
+/// `Float<exponent, significand>` for other formats.
+pub fn write_format(
+    f: &mut impl std::fmt::Write,
+    exponent: u32,
+    significand: u32,
+) -> std::fmt::Result {
+    match (exponent, significand) {
+        F16 => write!(f, "F16"),
+        F32 => write!(f, "F32"),
+        F64 => write!(f, "F64"),
+        F128 => write!(f, "F128"),
+        _ => write!(f, "Float<{}, {}>", exponent, significand),
+    }
+}
+
+/// Assembles the bits of a value from its sign, its biased exponent, and
+/// its significand without the hidden bit.
+pub fn from_parts(
+    sign: bool,
+    biased_exponent: u128,
+    fraction: u128,
+    exponent: u32,
+    significand: u32,
+) -> u128 {
+    (u128::from(sign) << (exponent + significand - 1))
+        | (biased_exponent << (significand - 1))
+        | fraction
+}
+
+/// Splits the bits of a value into its sign, its biased exponent, and its
+/// significand without the hidden bit.
+pub fn to_parts(bits: u128, exponent: u32, significand: u32) -> (bool, u128, u128) {
+    let fraction_mask = (1 << (significand - 1)) - 1;
+    let exponent_mask = (1 << exponent) - 1;
+    (
+        (bits >> (exponent + significand - 1)) & 1 == 1,
+        (bits >> (significand - 1)) & exponent_mask,
+        bits & fraction_mask,
+    )
+}
+
+/// The bits of the special value written `(_ <name> exponent significand)`
+/// in SMT-LIB, for example `+oo`. NaN is represented by a quiet NaN.
+pub fn special_value(name: &str, exponent: u32, significand: u32) -> Option<u128> {
+    let infinite = (1 << exponent) - 1;
+    let (sign, biased_exponent, fraction) = match name {
+        "+zero" => (false, 0, 0),
+        "-zero" => (true, 0, 0),
+        "+oo" => (false, infinite, 0),
+        "-oo" => (true, infinite, 0),
+        "NaN" => (false, infinite, 1 << (significand - 2)),
+        _ => return None,
+    };
+    Some(from_parts(
+        sign,
+        biased_exponent,
+        fraction,
+        exponent,
+        significand,
+    ))
+}
+
+/// Writes a value as it is printed in models: `f32` and `f64` values are
+/// written as Rust literals, for example `1.5f32` or `NaNf64`, and other
+/// values by their bits.
+pub fn write_value(
+    f: &mut impl std::fmt::Write,
+    bits: u128,
+    exponent: u32,
+    significand: u32,
+) -> std::fmt::Result {
+    match (exponent, significand) {
+        F32 => write!(f, "{:?}f32", f32::from_bits(bits as u32)),
+        F64 => write!(f, "{:?}f64", f64::from_bits(bits as u64)),
+        _ => write_from_bits(f, bits, exponent, significand),
+    }
+}
+
+/// Writes a value as it is written in `vir_high!`: finite `f32` and `f64`
+/// values as literals and all other values as `fp_from_bits(bits, format)`.
+pub fn write_literal(
+    f: &mut impl std::fmt::Write,
+    bits: u128,
+    exponent: u32,
+    significand: u32,
+) -> std::fmt::Result {
+    match (exponent, significand) {
+        F32 if f32::from_bits(bits as u32).is_finite() => {
+            write_value(f, bits, exponent, significand)
+        }
+        F64 if f64::from_bits(bits as u64).is_finite() => {
+            write_value(f, bits, exponent, significand)
+        }
+        _ => write_from_bits(f, bits, exponent, significand),
+    }
+}
+
+fn write_from_bits(
+    f: &mut impl std::fmt::Write,
+    bits: u128,
+    exponent: u32,
+    significand: u32,
+) -> std::fmt::Result {
+    write!(f, "fp_from_bits({}bv{}, ", bits, exponent + significand)?;
+    write_format(f, exponent, significand)?;
+    write!(f, ")")
+}
+
+/// Writes a value as the SMT-LIB term `(fp <sign> <exponent> <significand>)`.
+pub fn write_smt(
+    f: &mut impl std::io::Write,
+    bits: u128,
+    exponent: u32,
+    significand: u32,
+) -> std::io::Result<()> {
+    let (sign, biased_exponent, fraction) = to_parts(bits, exponent, significand);
+    write!(
+        f,
+        "(fp #b{} #b{:0exponent$b} #b{:0fraction$b})",
+        u8::from(sign),
+        biased_exponent,
+        fraction,
+        exponent = exponent as usize,
+        fraction = significand as usize - 1
+    )
+}
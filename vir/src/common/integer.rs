@@ -0,0 +1,33 @@
+//! Arbitrary-precision integers. They are serialized as numbers if they fit
+//! into an `i64` and as decimal strings otherwise.
+
+pub use num_bigint::BigInt;
+
+use std::convert::TryFrom;
+
+pub fn serialize<S: serde::Serializer>(value: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+    match i64::try_from(value) {
+        Ok(value) => serializer.serialize_i64(value),
+        Err(_) => serializer.collect_str(value),
+    }
+}
+
+pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+    struct Visitor;
+    impl<'de> serde::de::Visitor<'de> for Visitor {
+        type Value = BigInt;
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "an integer or a string of decimal digits")
+        }
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<BigInt, E> {
+            Ok(value.into())
+        }
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<BigInt, E> {
+            Ok(value.into())
+        }
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<BigInt, E> {
+            value.parse().map_err(E::custom)
+        }
+    }
+    deserializer.deserialize_any(Visitor)
+}
@@ -1,3 +1,8 @@
 pub mod cfg;
 pub mod expression;
+pub mod float;
+pub mod integer;
+pub mod position;
+pub mod real;
 pub mod statement;
+pub mod symbol;
@@ -0,0 +1,24 @@
+//! Positions of the high IR nodes in the source of the frontend.
+
+/// An opaque position provided by the frontend, typically an index into its
+/// own table of source spans. Svirpti only carries positions through lowering
+/// and reports them back in verification errors.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Position(u64);
+
+impl Position {
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+    pub fn id(&self) -> u64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{}", self.0)
+    }
+}
@@ -0,0 +1,77 @@
+//! Rational numbers, which are represented by a numerator and a positive
+//! denominator in lowest terms.
+
+use super::integer::BigInt;
+
+fn gcd(mut a: BigInt, mut b: BigInt) -> BigInt {
+    let zero = BigInt::from(0);
+    while b != zero {
+        let t = &a % &b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// Brings `numerator / denominator` into lowest terms with a positive
+/// denominator. Returns `None` if `denominator` is zero.
+pub fn normalize(numerator: BigInt, denominator: BigInt) -> Option<(BigInt, BigInt)> {
+    let zero = BigInt::from(0);
+    if denominator == zero {
+        return None;
+    }
+    let mut divisor = gcd(numerator.clone(), denominator.clone());
+    if denominator < zero {
+        divisor = -divisor;
+    }
+    Some((numerator / &divisor, denominator / &divisor))
+}
+
+/// Parses an unsigned decimal literal such as `2`, `2.0` or `0.125`.
+pub fn parse_decimal(literal: &str) -> Option<(BigInt, BigInt)> {
+    let (integer, fraction) = literal.split_once('.').unwrap_or((literal, ""));
+    let all_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if integer.is_empty() || !all_digits(integer) || !all_digits(fraction) {
+        return None;
+    }
+    let numerator: BigInt = format!("{}{}", integer, fraction).parse().ok()?;
+    let denominator = BigInt::from(10).pow(fraction.len() as u32);
+    normalize(numerator, denominator)
+}
+
+/// Writes the rational as a decimal if that is exact, for example `-1.5`,
+/// and as a division `(1.0/3.0)` otherwise.
+pub fn write_rational(
+    f: &mut impl std::fmt::Write,
+    numerator: &BigInt,
+    denominator: &BigInt,
+) -> std::fmt::Result {
+    // A denominator in lowest terms divides a power of ten only if it is of
+    // the form 2^a * 5^b, and then it divides 10^max(a, b). The exponents are
+    // bounded by the number of bits of the denominator.
+    let zero = BigInt::from(0);
+    let mut scale = BigInt::from(1);
+    let mut digits = 0;
+    while &scale % denominator != zero && digits < denominator.bits() {
+        scale *= 10;
+        digits += 1;
+    }
+    if &scale % denominator != zero {
+        return write!(f, "({}.0/{}.0)", numerator, denominator);
+    }
+    let scaled = numerator.magnitude() * (&scale / denominator).magnitude();
+    let scale = scale.magnitude();
+    let sign = if numerator < &zero { "-" } else { "" };
+    if digits == 0 {
+        write!(f, "{}{}.0", sign, scaled)
+    } else {
+        write!(
+            f,
+            "{}{}.{:0width$}",
+            sign,
+            &scaled / scale,
+            &scaled % scale,
+            width = digits as usize
+        )
+    }
+}
@@ -0,0 +1,117 @@
+//! Interned strings used as the names of declarations in all IRs.
+
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+
+static INTERNER: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+/// A string interned in a global interner.
+///
+/// Every distinct string is stored once for the lifetime of the program, and
+/// its address serves as the id of the symbol. Therefore, copying, comparing
+/// for equality, hashing and reading back the string are cheap; only creating
+/// a symbol takes a lock. Symbols are ordered by their strings and serialized
+/// as their strings, so both are independent of the interning order.
+///
+/// The interner leaks every string it stores with `Box::leak`, which is what
+/// makes the `&'static str` sound. Its memory is therefore bounded only by the
+/// number of distinct names the process ever creates; a long-running process
+/// that keeps verifying programs with fresh names keeps growing.
+#[derive(Clone, Copy)]
+pub struct Symbol(&'static str);
+
+impl Symbol {
+    pub fn intern(string: &str) -> Self {
+        let mut strings = interner().lock().unwrap();
+        if let Some(interned) = strings.get(string) {
+            return Self(interned);
+        }
+        let interned: &'static str = Box::leak(string.into());
+        strings.insert(interned);
+        Self(interned)
+    }
+    /// Same as `intern`, but reuses the allocation of `string` if it was not
+    /// interned yet.
+    pub fn intern_string(string: String) -> Self {
+        let mut strings = interner().lock().unwrap();
+        if let Some(interned) = strings.get(string.as_str()) {
+            return Self(interned);
+        }
+        let interned: &'static str = Box::leak(string.into_boxed_str());
+        strings.insert(interned);
+        Self(interned)
+    }
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+fn interner() -> &'static Mutex<HashSet<&'static str>> {
+    INTERNER.get_or_init(Default::default)
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl std::hash::Hash for Symbol {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.as_ptr().hash(state)
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if self == other {
+            std::cmp::Ordering::Equal
+        } else {
+            self.0.cmp(other.0)
+        }
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Self::intern(value)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Self::intern_string(value)
+    }
+}
+
+impl std::fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::fmt::Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl serde::Serialize for Symbol {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Symbol {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::intern_string)
+    }
+}
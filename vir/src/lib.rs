@@ -1,15 +1,30 @@
 // TODO: Figure out how to move this to a submodule.
+/// Defines a symbol type that wraps an interned `common::symbol::Symbol`.
 #[macro_export]
 macro_rules! derive_string_symbol {
     ($name: ident) => {
         #[derive(
             Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
         )]
-        pub struct $name(String);
+        pub struct $name($crate::common::symbol::Symbol);
 
         impl $name {
+            pub fn as_str(&self) -> &'static str {
+                self.0.as_str()
+            }
             pub fn as_string(&self) -> String {
-                self.0.clone()
+                self.0.as_str().to_string()
+            }
+            /// The interned string, which converts to any other symbol type
+            /// without interning again.
+            pub fn symbol(&self) -> $crate::common::symbol::Symbol {
+                self.0
+            }
+        }
+
+        impl From<$crate::common::symbol::Symbol> for $name {
+            fn from(value: $crate::common::symbol::Symbol) -> Self {
+                Self(value)
             }
         }
 
@@ -21,7 +36,7 @@ macro_rules! derive_string_symbol {
 
         impl From<String> for $name {
             fn from(value: String) -> Self {
-                Self(value)
+                Self(value.into())
             }
         }
 